@@ -1,10 +1,13 @@
+use alloy_eips::eip2930::AccessList;
 use alloy_primitives::*;
+use alloy_rlp::{Decodable, Encodable};
 
 use crate::{
     transaction::{SignableTransaction, Transaction},
-    Signed, TxDawnDecrypted, TxDawnEncrypted,
+    Signed, TxDawnDecrypted, TxDawnEncrypted, TxDawnUndecrypted,
 };
 use dawn_crypto::{Ciphertext, DecryptionKey, MasterPublicKey};
+pub use dawn_crypto::{Padding, MAX_PADDING};
 
 #[derive(Debug)]
 pub enum Error {
@@ -12,10 +15,17 @@ pub enum Error {
     AuthenticationError,
     InvalidDecryptionKey,
     InvalidSender,
-    ShortPayloadError,
+    MalformedPayloadError,
+    DecryptionSucceeded,
+    MissingChainId,
+    InvalidHiddenFields,
 }
 
-fn label(chain_id: ChainId, sender: &Address, nonce: u64) -> [u8; 96] {
+/// Returns the identity label of the encrypted transaction sent by `sender` with `nonce`.
+///
+/// The label is the concatenation of the chain id, the sender address and the nonce, each left
+/// padded to 32 bytes. It is also used as the associated data of the symmetric encryption.
+pub fn label(chain_id: ChainId, sender: &Address, nonce: u64) -> [u8; 96] {
     let mut label = [0; 96];
     label[24..32].copy_from_slice(&chain_id.to_be_bytes());
     label[44..64].copy_from_slice(sender.as_slice());
@@ -23,6 +33,248 @@ fn label(chain_id: ChainId, sender: &Address, nonce: u64) -> [u8; 96] {
     label
 }
 
+/// Returns the label of the block `number`, which the ciphertexts of the encrypted transactions of
+/// its shadow block are encrypted for on a chain that labels them by block.
+///
+/// The label is the chain id and the block number, each left padded to 32 bytes, followed by 32
+/// bytes of `0xff`. The label of a transaction never ends like this, since its nonce fills at most
+/// 8 bytes of the last 32. The label of the transaction is still the associated data of the
+/// symmetric encryption.
+pub fn block_label(chain_id: ChainId, number: BlockNumber) -> [u8; 96] {
+    let mut label = [0; 96];
+    label[24..32].copy_from_slice(&chain_id.to_be_bytes());
+    label[56..64].copy_from_slice(&number.to_be_bytes());
+    label[64..96].fill(0xff);
+    label
+}
+
+/// The identity that the ciphertext of an encrypted transaction is encrypted for, whose decryption
+/// key the SMC reveals.
+///
+/// Either way, the ciphertext is authenticated with the [`label`] of the transaction as associated
+/// data, so that it only decrypts for its own sender and nonce.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Identity {
+    /// The transaction itself, see [`label`]: the SMC reveals a key per transaction.
+    #[default]
+    Transaction,
+    /// The block whose shadow block includes the transaction, see [`block_label`]: the SMC reveals
+    /// a single key for all the transactions of the block. A transaction included in another
+    /// block fails to decrypt.
+    Block(BlockNumber),
+}
+
+impl Identity {
+    /// Returns the label of the identity for the transaction sent by `sender` with `nonce`.
+    pub fn label(&self, chain_id: ChainId, sender: &Address, nonce: u64) -> [u8; 96] {
+        match *self {
+            Self::Transaction => label(chain_id, sender, nonce),
+            Self::Block(number) => block_label(chain_id, number),
+        }
+    }
+}
+
+/// Version of the [`Plaintext`] encoding produced by this implementation.
+pub const PLAINTEXT_VERSION: u8 = 1;
+
+/// The plaintext of an encrypted transaction, the fields of the transaction that only become
+/// public once it is decrypted.
+///
+/// A plaintext is encoded as the RLP list `[version, to, input]`, with `to` the empty string for
+/// a contract creation, optionally extended with the hidden fields `value` and `access_list`:
+/// `[version, to, input, value, access_list]`. Future versions may append more fields, so a
+/// plaintext with an unknown version fails to decode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Plaintext {
+    /// The `to` field of the transaction.
+    pub to: TxKind,
+    /// The input of the transaction.
+    pub input: Bytes,
+    /// The fields of the transaction that are hidden in the plaintext instead of being public.
+    pub hidden: Option<HiddenFields>,
+}
+
+/// Fields of an encrypted transaction that may be carried in its [`Plaintext`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HiddenFields {
+    /// The value transferred by the transaction.
+    pub value: U256,
+    /// The access list of the transaction.
+    pub access_list: AccessList,
+}
+
+impl Plaintext {
+    /// Returns the plaintext of a transaction to `to` with `input` and no hidden fields.
+    pub fn new(to: TxKind, input: Bytes) -> Self {
+        Self { to, input, hidden: None }
+    }
+
+    /// Returns `true` if the hidden fields, if any, are allowed by the public `value` and
+    /// `access_list` of the encrypted transaction: the public value bounds the hidden value, and
+    /// the public access list must be empty.
+    pub fn fits(&self, value: U256, access_list: &AccessList) -> bool {
+        self.hidden
+            .as_ref()
+            .map_or(true, |hidden| hidden.value <= value && access_list.0.is_empty())
+    }
+
+    /// Decodes a plaintext, rejecting unknown versions and trailing bytes.
+    pub fn decode_exact(plaintext: &[u8]) -> Result<Self, Error> {
+        alloy_rlp::decode_exact(plaintext).map_err(|_| Error::MalformedPayloadError)
+    }
+
+    /// Decodes a plaintext followed by the zero bytes padding it, and returns it with the length
+    /// of its padding.
+    ///
+    /// The RLP header of the plaintext delimits it, so the padding is stripped unambiguously. Any
+    /// other trailing byte, or more than [`MAX_PADDING`] bytes of padding, is rejected.
+    pub fn decode_padded(mut payload: &[u8]) -> Result<(Self, usize), Error> {
+        let plaintext = Self::decode(&mut payload).map_err(|_| Error::MalformedPayloadError)?;
+        if payload.len() > MAX_PADDING || payload.iter().any(|&byte| byte != 0) {
+            return Err(Error::MalformedPayloadError);
+        }
+        Ok((plaintext, payload.len()))
+    }
+
+    fn fields_len(&self) -> usize {
+        let mut len = PLAINTEXT_VERSION.length() + self.to.length() + self.input.length();
+        if let Some(hidden) = &self.hidden {
+            len += hidden.value.length() + hidden.access_list.length();
+        }
+        len
+    }
+}
+
+impl Encodable for Plaintext {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.fields_len() }.encode(out);
+        PLAINTEXT_VERSION.encode(out);
+        self.to.encode(out);
+        self.input.encode(out);
+        if let Some(hidden) = &self.hidden {
+            hidden.value.encode(out);
+            hidden.access_list.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        alloy_rlp::length_of_length(payload_length) + payload_length
+    }
+}
+
+impl Decodable for Plaintext {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = alloy_rlp::Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let (mut fields, rest) = buf.split_at(header.payload_length);
+        if u8::decode(&mut fields)? != PLAINTEXT_VERSION {
+            return Err(alloy_rlp::Error::Custom("unsupported plaintext version"));
+        }
+        let to = Decodable::decode(&mut fields)?;
+        let input = Decodable::decode(&mut fields)?;
+        let hidden = if fields.is_empty() {
+            None
+        } else {
+            Some(HiddenFields {
+                value: Decodable::decode(&mut fields)?,
+                access_list: Decodable::decode(&mut fields)?,
+            })
+        };
+        if !fields.is_empty() {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: header.payload_length - fields.len(),
+            });
+        }
+        *buf = rest;
+        Ok(Self { to, input, hidden })
+    }
+}
+
+/// Gas charged for checking that the ephemeral public key of the ciphertext lies in G2, priced
+/// as a G2 multiplication.
+pub const EPHEMERAL_KEY_GAS: u64 = 45_000;
+
+/// Gas charged for hashing the label of the transaction to G1.
+pub const HASH_TO_G1_GAS: u64 = 5_500;
+
+/// Base gas of a pairing.
+pub const PAIRING_BASE_GAS: u64 = 65_000;
+
+/// Gas charged per pair of a pairing.
+pub const PAIRING_PER_PAIR_GAS: u64 = 43_000;
+
+/// Gas charged on top of the calldata and access list gas of a Dawn transaction for the work of
+/// decrypting it:
+///
+/// - checking the ephemeral public key of the ciphertext,
+/// - verifying the decryption key against the master public key, which hashes the label to G1 and
+///   runs a pairing check with two pairs,
+/// - re-encrypting the decrypted transaction, which recovers the shared secret with a pairing.
+pub const DECRYPTION_GAS: u64 =
+    EPHEMERAL_KEY_GAS + HASH_TO_G1_GAS + 2 * PAIRING_BASE_GAS + 3 * PAIRING_PER_PAIR_GAS;
+
+const TX_BASE_GAS: u64 = 21_000;
+const TX_DATA_ZERO_GAS: u64 = 4;
+const TX_DATA_NON_ZERO_GAS_FRONTIER: u64 = 68;
+const TX_DATA_NON_ZERO_GAS: u64 = 16;
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+
+/// The hardforks that change the intrinsic gas of an encrypted transaction, see [`intrinsic_gas`].
+///
+/// Clients sizing the `gas_limit` of a transaction for a current chain use [`GasSpec::Berlin`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GasSpec {
+    /// Before Istanbul, a non-zero byte of calldata costs 68 gas.
+    Frontier,
+    /// From Istanbul, a non-zero byte of calldata costs 16 gas, see EIP-2028.
+    Istanbul,
+    /// From Berlin, the access list is charged for, see EIP-2930.
+    #[default]
+    Berlin,
+}
+
+/// Returns the intrinsic gas of the encrypted transaction under `spec`, the lowest `gas_limit` it
+/// is accepted with.
+///
+/// This is the intrinsic gas of a call carrying the ciphertext, ephemeral public key, proof and
+/// padding included, as calldata together with the access list of the transaction, plus
+/// [`DECRYPTION_GAS`]. Nodes charge exactly this when they include the transaction.
+pub fn intrinsic_gas(tx: &TxDawnEncrypted, spec: GasSpec) -> u128 {
+    let non_zero_gas = if spec >= GasSpec::Istanbul {
+        TX_DATA_NON_ZERO_GAS
+    } else {
+        TX_DATA_NON_ZERO_GAS_FRONTIER
+    };
+    let proof = tx.ciphertext.proof.as_ref().map_or(&[][..], |proof| &proof.0[..]);
+    let ciphertext = [&tx.ciphertext.u.0[..], proof, &tx.ciphertext.payload, &tx.ciphertext.tag];
+    let data_gas: u64 = ciphertext
+        .iter()
+        .flat_map(|part| part.iter())
+        .map(|&byte| if byte == 0 { TX_DATA_ZERO_GAS } else { non_zero_gas })
+        .sum();
+    let access_list_gas: u64 = if spec >= GasSpec::Berlin {
+        tx.access_list
+            .0
+            .iter()
+            .map(|item| {
+                ACCESS_LIST_ADDRESS_GAS
+                    + item.storage_keys.len() as u64 * ACCESS_LIST_STORAGE_KEY_GAS
+            })
+            .sum()
+    } else {
+        0
+    };
+    (TX_BASE_GAS + data_gas + access_list_gas + DECRYPTION_GAS) as u128
+}
+
 #[cfg(feature = "k256")]
 pub fn decrypt(
     signed: &Signed<TxDawnEncrypted>,
@@ -43,6 +295,16 @@ pub fn reencrypt(signed: &Signed<TxDawnDecrypted>) -> Result<Signed<TxDawnEncryp
     Ok(tx)
 }
 
+#[cfg(feature = "k256")]
+pub fn undecrypt(
+    signed: &Signed<TxDawnEncrypted>,
+    decryption_key: &DecryptionKey,
+) -> Result<Signed<TxDawnUndecrypted>, Error> {
+    let sender = signed.recover_signer().map_err(Error::SignatureError)?;
+    let tx = undecrypt_unsigned(signed.tx(), decryption_key, &sender)?;
+    Ok(Signed::new_unchecked(tx, *signed.signature(), *signed.hash()))
+}
+
 pub fn decrypt_unsigned(
     tx: &TxDawnEncrypted,
     decryption_key: &DecryptionKey,
@@ -52,65 +314,179 @@ pub fn decrypt_unsigned(
     let Some(payload) = tx.ciphertext.decrypt(decryption_key, &label) else {
         return Err(Error::AuthenticationError);
     };
-    if payload.len() < 20 {
-        return Err(Error::ShortPayloadError);
+    let (plaintext, padding) = Plaintext::decode_padded(&payload)?;
+    if !plaintext.fits(tx.value, &tx.access_list) {
+        return Err(Error::InvalidHiddenFields);
     }
-    let to = &payload[..20];
-    let input = &payload[20..];
+    let value_bound = plaintext.hidden.is_some().then_some(tx.value);
+    let (value, access_list) = match plaintext.hidden {
+        Some(hidden) => (hidden.value, hidden.access_list),
+        None => (tx.value, tx.access_list.clone()),
+    };
     Ok(TxDawnDecrypted {
         chain_id: tx.chain_id,
         nonce: tx.nonce,
         gas_limit: tx.gas_limit,
         max_fee_per_gas: tx.max_fee_per_gas,
         max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
-        to: TxKind::Call(Address::from_slice(to)),
+        to: plaintext.to,
+        value,
+        access_list,
+        input: plaintext.input,
+        ephemeral_public_key: tx.ciphertext.u.clone(),
+        ciphertext_proof: tx.ciphertext.proof,
+        decryption_key: decryption_key.clone(),
+        sender: *sender,
+        value_bound,
+        padding: padding as u64,
+    })
+}
+
+pub fn undecrypt_unsigned(
+    tx: &TxDawnEncrypted,
+    decryption_key: &DecryptionKey,
+    sender: &Address,
+) -> Result<TxDawnUndecrypted, Error> {
+    match decrypt_unsigned(tx, decryption_key, sender) {
+        Ok(_) => return Err(Error::DecryptionSucceeded),
+        Err(
+            Error::AuthenticationError | Error::MalformedPayloadError | Error::InvalidHiddenFields,
+        ) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(TxDawnUndecrypted {
+        chain_id: tx.chain_id,
+        nonce: tx.nonce,
+        gas_limit: tx.gas_limit,
+        max_fee_per_gas: tx.max_fee_per_gas,
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
         value: tx.value,
         access_list: tx.access_list.clone(),
-        input: Bytes::copy_from_slice(input),
-        ephemeral_public_key: tx.ciphertext.u.clone(),
+        ciphertext: tx.ciphertext.clone(),
         decryption_key: decryption_key.clone(),
         sender: *sender,
     })
 }
 
+/// Checks that the decryption key of `tx` was produced by the SMC for `identity` and that
+/// decryption fails.
+pub fn verify_undecrypted(
+    mpk: &MasterPublicKey,
+    tx: &TxDawnUndecrypted,
+    identity: Identity,
+) -> Result<(), Error> {
+    let label = identity.label(tx.chain_id, &tx.sender, tx.nonce);
+    if !dawn_crypto::verify(&label, mpk, &tx.decryption_key) {
+        return Err(Error::InvalidDecryptionKey);
+    }
+    undecrypt_unsigned(&tx.encrypted(), &tx.decryption_key, &tx.sender).map(|_| ())
+}
+
+/// Re-encrypts a decrypted transaction into the encrypted transaction its sender signed.
+///
+/// Panics if the ephemeral public key or the decryption key is not a valid point, which decoding
+/// the transaction rules out.
 pub fn reencrypt_unsigned(tx: &TxDawnDecrypted) -> TxDawnEncrypted {
     let label = label(tx.chain_id, &tx.sender, tx.nonce);
-    let TxKind::Call(to) = tx.to() else {
-        panic!("only Call transactions are supported");
+    let hidden = tx
+        .value_bound
+        .map(|_| HiddenFields { value: tx.value, access_list: tx.access_list.clone() });
+    let (value, access_list) = match tx.value_bound {
+        Some(value_bound) => (value_bound, AccessList::default()),
+        None => (tx.value, tx.access_list.clone()),
     };
-    let payload = [to.as_slice(), &tx.input[..]].concat();
-    let ciphertext =
-        Ciphertext::reencrypt(&tx.ephemeral_public_key, &tx.decryption_key, &payload, &label);
+    let mut payload = alloy_rlp::encode(Plaintext { to: tx.to, input: tx.input.clone(), hidden });
+    payload.resize(payload.len() + tx.padding as usize, 0);
+    let ciphertext = Ciphertext::reencrypt(
+        &tx.ephemeral_public_key,
+        tx.ciphertext_proof,
+        &tx.decryption_key,
+        &payload,
+        &label,
+    )
+    .expect("the keys of a decrypted transaction are valid points");
     TxDawnEncrypted {
         chain_id: tx.chain_id,
         nonce: tx.nonce,
         gas_limit: tx.gas_limit,
         max_fee_per_gas: tx.max_fee_per_gas,
         max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
-        value: tx.value,
-        access_list: tx.access_list.clone(),
+        value,
+        access_list,
         ciphertext,
     }
 }
 
-pub fn encrypt<T: Transaction>(mpk: &MasterPublicKey, tx: &T, sender: &Address) -> TxDawnEncrypted {
-    let TxKind::Call(to) = tx.to() else {
-        panic!("only Call transactions are supported");
+/// Encrypts the `to` and `input` of `tx` sent by `sender` for `identity`, which must match how
+/// the chain labels ciphertexts.
+///
+/// The plaintext is padded with zero bytes according to `padding`, so that the length of the
+/// ciphertext hides the length of `input`. The padding is paid for as calldata, see
+/// [`intrinsic_gas`].
+pub fn encrypt<T: Transaction>(
+    mpk: &MasterPublicKey,
+    tx: &T,
+    sender: &Address,
+    identity: Identity,
+    padding: Padding,
+) -> Result<TxDawnEncrypted, Error> {
+    encrypt_plaintext(mpk, tx, sender, identity, None, padding)
+}
+
+/// Encrypts `tx` like [`encrypt`], but also hides its value and access list in the ciphertext.
+///
+/// The encrypted transaction carries `value_bound` as its public value, which the sender must be
+/// able to afford, and an empty access list.
+pub fn encrypt_hidden<T: Transaction>(
+    mpk: &MasterPublicKey,
+    tx: &T,
+    sender: &Address,
+    identity: Identity,
+    value_bound: U256,
+    padding: Padding,
+) -> Result<TxDawnEncrypted, Error> {
+    if tx.value() > value_bound {
+        return Err(Error::InvalidHiddenFields);
+    }
+    encrypt_plaintext(mpk, tx, sender, identity, Some(value_bound), padding)
+}
+
+fn encrypt_plaintext<T: Transaction>(
+    mpk: &MasterPublicKey,
+    tx: &T,
+    sender: &Address,
+    identity: Identity,
+    value_bound: Option<U256>,
+    padding: Padding,
+) -> Result<TxDawnEncrypted, Error> {
+    let access_list = tx.access_list().cloned().unwrap_or_default();
+    let mut plaintext = Plaintext::new(tx.to(), Bytes::copy_from_slice(tx.input()));
+    let (value, access_list) = match value_bound {
+        Some(value_bound) => {
+            plaintext.hidden = Some(HiddenFields { value: tx.value(), access_list });
+            (value_bound, AccessList::default())
+        }
+        None => (tx.value(), access_list),
     };
-    let payload = [to.as_slice(), tx.input()].concat();
-    let chain_id = tx.chain_id().unwrap();
+    let chain_id = tx.chain_id().ok_or(Error::MissingChainId)?;
     let label = label(chain_id, sender, tx.nonce());
-    let ciphertext = Ciphertext::encrypt(mpk, &label, &payload, &label);
-    TxDawnEncrypted {
+    let ciphertext = Ciphertext::encrypt(
+        mpk,
+        &identity.label(chain_id, sender, tx.nonce()),
+        &alloy_rlp::encode(plaintext),
+        &label,
+        padding,
+    );
+    Ok(TxDawnEncrypted {
         chain_id,
         nonce: tx.nonce(),
         gas_limit: tx.gas_limit(),
         max_fee_per_gas: tx.max_fee_per_gas(),
         max_priority_fee_per_gas: tx.max_priority_fee_per_gas().unwrap_or(0),
-        value: tx.value(),
-        access_list: tx.access_list().unwrap_or(&Default::default()).clone(),
+        value,
+        access_list,
         ciphertext,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -120,7 +496,7 @@ mod tests {
     use alloy_eips::eip2930::AccessList;
     use alloy_network::TxSignerSync;
     use alloy_signer_local::PrivateKeySigner;
-    use dawn_crypto::*;
+    use dawn_crypto::{generate, reveal};
 
     #[cfg(feature = "k256")]
     #[test]
@@ -139,7 +515,16 @@ mod tests {
             max_priority_fee_per_gas: 10_000_000,
             value,
             access_list: Default::default(),
-            ciphertext: Ciphertext::encrypt(&mpk, &label, &[0x22; 20], &label),
+            ciphertext: Ciphertext::encrypt(
+                &mpk,
+                &label,
+                &alloy_rlp::encode(Plaintext::new(
+                    address!("2222222222222222222222222222222222222222").into(),
+                    Bytes::new(),
+                )),
+                &label,
+                Padding::None,
+            ),
         };
         let signature = signer.sign_transaction_sync(&mut tx).unwrap();
         let signed = tx.into_signed(signature);
@@ -169,7 +554,16 @@ mod tests {
         let label = label(chain_id, &sender, nonce);
         let access_list = AccessList::default();
         let input = Bytes::copy_from_slice(&[]);
-        let ciphertext = Ciphertext::encrypt(&mpk, &label, &[0x22; 20], &label);
+        let ciphertext = Ciphertext::encrypt(
+            &mpk,
+            &label,
+            &alloy_rlp::encode(Plaintext::new(
+                address!("2222222222222222222222222222222222222222").into(),
+                Bytes::new(),
+            )),
+            &label,
+            Padding::None,
+        );
         let tx = TxDawnEncrypted {
             chain_id,
             nonce,
@@ -196,11 +590,120 @@ mod tests {
                 input,
                 sender,
                 ephemeral_public_key: tx.ciphertext.u.clone(),
+                ciphertext_proof: tx.ciphertext.proof,
                 decryption_key,
+                value_bound: None,
+                padding: 0,
             }
         );
     }
 
+    #[test]
+    fn test_undecrypt_unsigned() {
+        let (mpk, msk) = generate();
+        let chain_id = 1;
+        let nonce = 0;
+        let sender = address!("3333333333333333333333333333333333333333");
+        let label = label(chain_id, &sender, nonce);
+        let decryption_key = reveal(&label, &msk);
+        let mut tx = TxDawnEncrypted {
+            chain_id,
+            nonce,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 10_000_000,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(&mpk, &label, &[0x22; 19], &label, Padding::None),
+        };
+
+        // malformed payload
+        let undecrypted = undecrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(undecrypted.encrypted(), tx);
+        assert!(verify_undecrypted(&mpk, &undecrypted, Identity::Transaction).is_ok());
+
+        // unknown plaintext version
+        tx.ciphertext = Ciphertext::encrypt(&mpk, &label, &hex!("c3028080"), &label, Padding::None);
+        let undecrypted = undecrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert!(verify_undecrypted(&mpk, &undecrypted, Identity::Transaction).is_ok());
+
+        // tampered ciphertext
+        tx.ciphertext.tag[0] ^= 1;
+        let undecrypted = undecrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert!(verify_undecrypted(&mpk, &undecrypted, Identity::Transaction).is_ok());
+
+        // well-formed ciphertext
+        tx.ciphertext = Ciphertext::encrypt(
+            &mpk,
+            &label,
+            &alloy_rlp::encode(Plaintext::new(
+                address!("2222222222222222222222222222222222222222").into(),
+                Bytes::new(),
+            )),
+            &label,
+            Padding::None,
+        );
+        assert!(matches!(
+            undecrypt_unsigned(&tx, &decryption_key, &sender),
+            Err(Error::DecryptionSucceeded)
+        ));
+
+        // key not issued by the SMC
+        let (_, other_msk) = generate();
+        let undecrypted = TxDawnUndecrypted {
+            decryption_key: reveal(&label, &other_msk),
+            ..undecrypt_unsigned(&tx, &reveal(&label, &other_msk), &sender).unwrap()
+        };
+        assert!(matches!(
+            verify_undecrypted(&mpk, &undecrypted, Identity::Transaction),
+            Err(Error::InvalidDecryptionKey)
+        ));
+    }
+
+    #[test]
+    fn test_intrinsic_gas() {
+        let (mpk, _) = generate();
+        let mut tx = TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 0,
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(&mpk, &[0; 96], &[0x22; 20], &[], Padding::None),
+        };
+        let proof = tx.ciphertext.proof.unwrap();
+        let data =
+            [&tx.ciphertext.u.0[..], &proof.0, &tx.ciphertext.payload, &tx.ciphertext.tag].concat();
+        let data_gas: u64 = data.iter().map(|&byte| if byte == 0 { 4 } else { 16 }).sum();
+        assert_eq!(
+            intrinsic_gas(&tx, GasSpec::Berlin),
+            (21_000 + data_gas + DECRYPTION_GAS) as u128
+        );
+
+        tx.access_list = AccessList(vec![alloy_eips::eip2930::AccessListItem {
+            address: Address::ZERO,
+            storage_keys: vec![B256::ZERO, B256::ZERO],
+        }]);
+        assert_eq!(
+            intrinsic_gas(&tx, GasSpec::Berlin),
+            (21_000 + data_gas + DECRYPTION_GAS + 2_400 + 3_800) as u128
+        );
+
+        // the access list is only charged for from Berlin, and non-zero bytes cost more before
+        // Istanbul
+        assert_eq!(
+            intrinsic_gas(&tx, GasSpec::Istanbul),
+            (21_000 + data_gas + DECRYPTION_GAS) as u128
+        );
+        let frontier_data_gas: u64 = data.iter().map(|&byte| if byte == 0 { 4 } else { 68 }).sum();
+        assert_eq!(
+            intrinsic_gas(&tx, GasSpec::Frontier),
+            (21_000 + frontier_data_gas + DECRYPTION_GAS) as u128
+        );
+    }
+
     #[test]
     fn test_encrypt() {
         let (mpk, msk) = generate();
@@ -228,7 +731,10 @@ mod tests {
                 input: input.clone(),
             },
             &sender,
-        );
+            Identity::Transaction,
+            Padding::None,
+        )
+        .unwrap();
         let decryption_key = reveal(&label, &msk);
         let decrypted = decrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
         assert_eq!(
@@ -245,8 +751,391 @@ mod tests {
                 input,
                 sender,
                 ephemeral_public_key: tx.ciphertext.u.clone(),
+                ciphertext_proof: tx.ciphertext.proof,
                 decryption_key,
+                value_bound: None,
+                padding: 0,
             }
         );
     }
+
+    #[test]
+    fn test_encrypt_create() {
+        let (mpk, msk) = generate();
+        let sender = address!("3333333333333333333333333333333333333333");
+        let input = Bytes::copy_from_slice(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let create = TxEip1559 {
+            chain_id: 1,
+            nonce: 7,
+            gas_limit: 1_000_000,
+            to: TxKind::Create,
+            input: input.clone(),
+            ..Default::default()
+        };
+        let tx = encrypt(&mpk, &create, &sender, Identity::Transaction, Padding::None).unwrap();
+        let decryption_key = reveal(&label(1, &sender, 7), &msk);
+        let decrypted = decrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(decrypted.to, TxKind::Create);
+        assert_eq!(decrypted.input, input);
+        assert_eq!(reencrypt_unsigned(&decrypted), tx);
+
+        let legacy = crate::TxLegacy { chain_id: None, ..Default::default() };
+        assert!(matches!(
+            encrypt(&mpk, &legacy, &sender, Identity::Transaction, Padding::None),
+            Err(Error::MissingChainId)
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_hidden() {
+        let (mpk, msk) = generate();
+        let sender = address!("3333333333333333333333333333333333333333");
+        let value_bound = U256::from(10);
+        let access_list = AccessList(vec![alloy_eips::eip2930::AccessListItem {
+            address: address!("2222222222222222222222222222222222222222"),
+            storage_keys: vec![B256::with_last_byte(1)],
+        }]);
+        let bid = TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            to: address!("2222222222222222222222222222222222222222").into(),
+            value: U256::from(7),
+            access_list: access_list.clone(),
+            input: bytes!("c0ffee"),
+            ..Default::default()
+        };
+        let decryption_key = reveal(&label(1, &sender, 0), &msk);
+
+        let tx =
+            encrypt_hidden(&mpk, &bid, &sender, Identity::Transaction, value_bound, Padding::None)
+                .unwrap();
+        assert_eq!(tx.value, value_bound);
+        assert_eq!(tx.access_list, AccessList::default());
+        let decrypted = decrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(decrypted.value, U256::from(7));
+        assert_eq!(decrypted.access_list, access_list);
+        assert_eq!(decrypted.value_bound, Some(value_bound));
+        assert_eq!(reencrypt_unsigned(&decrypted), tx);
+        let encoded = alloy_rlp::encode(&decrypted);
+        assert_eq!(TxDawnDecrypted::decode(&mut &encoded[..]).unwrap(), decrypted);
+
+        assert!(matches!(
+            encrypt_hidden(
+                &mpk,
+                &bid,
+                &sender,
+                Identity::Transaction,
+                U256::from(6),
+                Padding::None
+            ),
+            Err(Error::InvalidHiddenFields)
+        ));
+
+        // hidden value above the public bound
+        let mut tx =
+            encrypt_hidden(&mpk, &bid, &sender, Identity::Transaction, value_bound, Padding::None)
+                .unwrap();
+        tx.value = U256::from(6);
+        tx.ciphertext = Ciphertext::reencrypt(
+            &decrypted.ephemeral_public_key,
+            decrypted.ciphertext_proof,
+            &decryption_key,
+            &tx.ciphertext.decrypt(&decryption_key, &label(1, &sender, 0)).unwrap(),
+            &label(1, &sender, 0),
+        )
+        .unwrap();
+        assert!(matches!(
+            decrypt_unsigned(&tx, &decryption_key, &sender),
+            Err(Error::InvalidHiddenFields)
+        ));
+        let undecrypted = undecrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert!(verify_undecrypted(&mpk, &undecrypted, Identity::Transaction).is_ok());
+
+        // hidden access list next to a public one
+        tx.value = value_bound;
+        tx.access_list = access_list;
+        assert!(matches!(
+            decrypt_unsigned(&tx, &decryption_key, &sender),
+            Err(Error::InvalidHiddenFields)
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_padded() {
+        let (mpk, msk) = generate();
+        let sender = address!("3333333333333333333333333333333333333333");
+        let bid = TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            to: address!("2222222222222222222222222222222222222222").into(),
+            input: bytes!("c0ffee"),
+            ..Default::default()
+        };
+        let decryption_key = reveal(&label(1, &sender, 0), &msk);
+
+        let tx =
+            encrypt(&mpk, &bid, &sender, Identity::Transaction, Padding::Multiple(256)).unwrap();
+        assert_eq!(tx.ciphertext.payload.len(), 256);
+        let decrypted = decrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(decrypted.input, bid.input);
+        let plaintext = Plaintext::new(bid.to, bid.input.clone());
+        assert_eq!(decrypted.padding, 256 - plaintext.length() as u64);
+        assert_eq!(reencrypt_unsigned(&decrypted), tx);
+        assert_eq!(
+            TxDawnDecrypted::decode(&mut &alloy_rlp::encode(&decrypted)[..]).unwrap(),
+            decrypted
+        );
+
+        // the padding is paid for
+        let unpadded = encrypt(&mpk, &bid, &sender, Identity::Transaction, Padding::None).unwrap();
+        assert!(
+            intrinsic_gas(&tx, GasSpec::Berlin)
+                > intrinsic_gas(&unpadded, GasSpec::Berlin) + 4 * decrypted.padding as u128
+        );
+
+        // padding with anything but zero bytes does not decrypt
+        let mut payload = tx.ciphertext.decrypt(&decryption_key, &label(1, &sender, 0)).unwrap();
+        payload[255] = 1;
+        let mut malformed = tx.clone();
+        malformed.ciphertext = Ciphertext::reencrypt(
+            &tx.ciphertext.u,
+            tx.ciphertext.proof,
+            &decryption_key,
+            &payload,
+            &label(1, &sender, 0),
+        )
+        .unwrap();
+        assert!(matches!(
+            decrypt_unsigned(&malformed, &decryption_key, &sender),
+            Err(Error::MalformedPayloadError)
+        ));
+
+        // re-encrypting needs the padding to stay bounded
+        let too_long = TxDawnDecrypted { padding: MAX_PADDING as u64 + 1, ..decrypted };
+        assert_eq!(
+            TxDawnDecrypted::decode(&mut &alloy_rlp::encode(&too_long)[..]),
+            Err(alloy_rlp::Error::Custom("padding too long"))
+        );
+    }
+
+    #[test]
+    fn test_encrypt_for_block() {
+        let (mpk, msk) = generate();
+        let senders = [
+            address!("3333333333333333333333333333333333333333"),
+            address!("4444444444444444444444444444444444444444"),
+        ];
+        let bid = TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            to: address!("2222222222222222222222222222222222222222").into(),
+            input: bytes!("c0ffee"),
+            ..Default::default()
+        };
+        let block_key = reveal(&block_label(1, 5), &msk);
+
+        // a single key decrypts the transactions of all senders for the block
+        for sender in &senders {
+            let tx = encrypt(&mpk, &bid, sender, Identity::Block(5), Padding::None).unwrap();
+            assert!(tx.ciphertext.verify_proof(&block_label(1, 5), &label(1, sender, 0)));
+            let decrypted = decrypt_unsigned(&tx, &block_key, sender).unwrap();
+            assert_eq!(decrypted.input, bid.input);
+            assert_eq!(reencrypt_unsigned(&decrypted), tx);
+
+            // but not the key of the transaction, nor the ciphertext copied by someone else
+            let tx_key = reveal(&label(1, sender, 0), &msk);
+            assert!(matches!(
+                decrypt_unsigned(&tx, &tx_key, sender),
+                Err(Error::AuthenticationError)
+            ));
+            assert!(matches!(
+                decrypt_unsigned(
+                    &tx,
+                    &block_key,
+                    &address!("5555555555555555555555555555555555555555")
+                ),
+                Err(Error::AuthenticationError)
+            ));
+        }
+
+        // a transaction encrypted for another block is undecrypted with the key of its block
+        let tx = encrypt(&mpk, &bid, &senders[0], Identity::Block(6), Padding::None).unwrap();
+        let undecrypted = undecrypt_unsigned(&tx, &block_key, &senders[0]).unwrap();
+        assert!(verify_undecrypted(&mpk, &undecrypted, Identity::Block(5)).is_ok());
+        assert!(matches!(
+            verify_undecrypted(&mpk, &undecrypted, Identity::Transaction),
+            Err(Error::InvalidDecryptionKey)
+        ));
+
+        assert_eq!(Identity::Block(5).label(1, &senders[0], 0), block_label(1, 5));
+        assert_ne!(block_label(1, 5), block_label(2, 5));
+        assert_ne!(block_label(1, 5), label(1, &Address::with_last_byte(5), 0));
+    }
+
+    #[test]
+    fn test_decode_invalid_points() {
+        let (mpk, msk) = generate();
+        let sender = address!("1111111111111111111111111111111111111111");
+        let label = label(1, &sender, 0);
+        let tx = TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(&mpk, &label, &[], &label, Padding::None),
+        };
+        let decryption_key = reveal(&label, &msk);
+        let undecrypted = undecrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(
+            TxDawnUndecrypted::decode(&mut &alloy_rlp::encode(&undecrypted)[..]).unwrap(),
+            undecrypted
+        );
+
+        // the ephemeral public key is not on the curve
+        let mut invalid = tx.clone();
+        invalid.ciphertext.u = dawn_crypto::EphemeralPublicKey([0xff; 96]);
+        assert_eq!(
+            TxDawnEncrypted::decode(&mut &alloy_rlp::encode(&invalid)[..]),
+            Err(alloy_rlp::Error::Custom("invalid ephemeral public key"))
+        );
+
+        // an invalid decryption key fails to decrypt instead of panicking
+        let invalid_key = DecryptionKey([0xff; 48]);
+        let undecrypted = undecrypt_unsigned(&tx, &invalid_key, &sender).unwrap();
+        assert_eq!(
+            TxDawnUndecrypted::decode(&mut &alloy_rlp::encode(&undecrypted)[..]),
+            Err(alloy_rlp::Error::Custom("invalid decryption key"))
+        );
+    }
+
+    #[test]
+    fn test_decode_ciphertext_versions() {
+        let (mpk, msk) = generate();
+        let sender = address!("1111111111111111111111111111111111111111");
+        let label = label(1, &sender, 0);
+        let plaintext = Plaintext {
+            to: address!("2222222222222222222222222222222222222222").into(),
+            input: Bytes::new(),
+            hidden: None,
+        };
+        let tx = TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(
+                &mpk,
+                &label,
+                &alloy_rlp::encode(&plaintext),
+                &label,
+                Padding::None,
+            ),
+        };
+        assert_eq!(tx.ciphertext.version(), 2);
+        let encoded = alloy_rlp::encode(&tx);
+        assert_eq!(TxDawnEncrypted::decode(&mut &encoded[..]).unwrap(), tx);
+
+        // the proof of a decrypted transaction survives re-encryption
+        let decryption_key = reveal(&label, &msk);
+        let decrypted = decrypt_unsigned(&tx, &decryption_key, &sender).unwrap();
+        assert_eq!(decrypted.ciphertext_proof, tx.ciphertext.proof);
+        assert_eq!(
+            TxDawnDecrypted::decode(&mut &alloy_rlp::encode(&decrypted)[..]).unwrap(),
+            decrypted
+        );
+        assert_eq!(reencrypt_unsigned(&decrypted), tx);
+
+        // version 1 ciphertexts are encoded without the proof
+        let mut v1 = tx.clone();
+        v1.ciphertext.proof = None;
+        let v1_encoded = alloy_rlp::encode(&v1);
+        assert_eq!(v1_encoded.len(), encoded.len() - 64);
+        assert_eq!(TxDawnEncrypted::decode(&mut &v1_encoded[..]).unwrap(), v1);
+        let decrypted = decrypt_unsigned(&v1, &decryption_key, &sender).unwrap();
+        assert_eq!(decrypted.ciphertext_proof, None);
+        assert_eq!(reencrypt_unsigned(&decrypted), v1);
+
+        // a proof of the wrong length
+        let mut fields = Vec::new();
+        tx.chain_id.encode(&mut fields);
+        tx.nonce.encode(&mut fields);
+        tx.max_priority_fee_per_gas.encode(&mut fields);
+        tx.max_fee_per_gas.encode(&mut fields);
+        tx.gas_limit.encode(&mut fields);
+        tx.value.encode(&mut fields);
+        tx.access_list.encode(&mut fields);
+        [&tx.ciphertext.u.0[..], &[0; 32]].concat().as_slice().encode(&mut fields);
+        tx.ciphertext.payload.encode(&mut fields);
+        tx.ciphertext.tag.encode(&mut fields);
+        let mut encoded = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: fields.len() }.encode(&mut encoded);
+        encoded.extend_from_slice(&fields);
+        assert_eq!(
+            TxDawnEncrypted::decode(&mut &encoded[..]),
+            Err(alloy_rlp::Error::UnexpectedLength)
+        );
+    }
+
+    /// Test vectors of the plaintext encoding, shared with other client implementations.
+    #[test]
+    fn test_plaintext_vectors() {
+        let to = address!("2222222222222222222222222222222222222222");
+        let vectors = [
+            (
+                Plaintext::new(TxKind::Call(to), bytes!("c0ffee")),
+                hex!("da01942222222222222222222222222222222222222222""83c0ffee").to_vec(),
+            ),
+            (Plaintext::new(TxKind::Create, Bytes::new()), hex!("c3018080").to_vec()),
+            (
+                Plaintext {
+                    to: TxKind::Call(to),
+                    input: Bytes::new(),
+                    hidden: Some(HiddenFields {
+                        value: U256::from(1_000_000_000_000_000_000u128),
+                        access_list: AccessList::default(),
+                    }),
+                },
+                hex!("e1019422222222222222222222222222222222222222228088""0de0b6b3a7640000c0")
+                    .to_vec(),
+            ),
+        ];
+        for (plaintext, encoded) in vectors {
+            assert_eq!(alloy_rlp::encode(&plaintext), encoded);
+            assert_eq!(plaintext.length(), encoded.len());
+            assert_eq!(Plaintext::decode_exact(&encoded).unwrap(), plaintext);
+            let padded = [&encoded[..], &[0; 5]].concat();
+            assert_eq!(Plaintext::decode_padded(&padded).unwrap(), (plaintext, 5));
+        }
+
+        for malformed in [
+            // empty
+            &hex!("")[..],
+            // not a list
+            &hex!("80"),
+            // unknown version
+            &hex!("c3028080"),
+            // trailing bytes
+            &hex!("c301808000"),
+            // value without access list
+            &hex!("c401808001"),
+            // unknown field after the hidden fields
+            &hex!("c601808001c080"),
+            // unversioned `to || input`
+            &hex!("942222222222222222222222222222222222222222c0ffee"),
+        ] {
+            assert!(matches!(
+                Plaintext::decode_exact(malformed),
+                Err(Error::MalformedPayloadError)
+            ));
+        }
+    }
 }
@@ -128,6 +128,9 @@ pub struct Header {
     /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
     #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub requests_root: Option<B256>,
+    /// The Keccak 256-bit hash of the RLP encoding of the shadow block carried in the block body.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub shadow_root: Option<B256>,
     /// An arbitrary byte array containing data relevant to this block. This must be 32 bytes or
     /// fewer; formally Hx.
     pub extra_data: Bytes,
@@ -157,6 +160,7 @@ impl Default for Header {
             excess_blob_gas: None,
             parent_beacon_block_root: None,
             requests_root: None,
+            shadow_root: None,
         }
     }
 }
@@ -340,6 +344,12 @@ impl Header {
         //    generate a block like this.
         if let Some(requests_root) = self.requests_root {
             length += requests_root.length();
+        } else if self.shadow_root.is_some() {
+            length += 1; // EMPTY STRING CODE
+        }
+
+        if let Some(shadow_root) = self.shadow_root {
+            length += shadow_root.length();
         }
 
         length
@@ -422,6 +432,13 @@ impl Encodable for Header {
         //    generate a block like this.
         if let Some(ref requests_root) = self.requests_root {
             requests_root.encode(out);
+        } else if self.shadow_root.is_some() {
+            out.put_u8(EMPTY_STRING_CODE);
+        }
+
+        // Encode shadow root.
+        if let Some(ref shadow_root) = self.shadow_root {
+            shadow_root.encode(out);
         }
     }
 
@@ -462,6 +479,7 @@ impl Decodable for Header {
             excess_blob_gas: None,
             parent_beacon_block_root: None,
             requests_root: None,
+            shadow_root: None,
         };
 
         if started_len - buf.len() < rlp_head.payload_length {
@@ -513,7 +531,16 @@ impl Decodable for Header {
         //    post-London, so this is technically not valid. However, a tool like proptest would
         //    generate a block like this.
         if started_len - buf.len() < rlp_head.payload_length {
-            this.parent_beacon_block_root = Some(B256::decode(buf)?);
+            if buf.first().map(|b| *b == EMPTY_STRING_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.requests_root = Some(B256::decode(buf)?);
+            }
+        }
+
+        // Decode shadow root.
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.shadow_root = Some(B256::decode(buf)?);
         }
 
         let consumed = started_len - buf.len();
@@ -33,7 +33,7 @@ pub mod transaction;
 #[cfg(feature = "kzg")]
 pub use transaction::BlobTransactionValidationError;
 pub use transaction::{
-    SignableTransaction, Transaction, TxDawnDecrypted, TxDawnEncrypted, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
+    SignableTransaction, Transaction, TxDawnDecrypted, TxDawnEncrypted, TxDawnUndecrypted, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
     TxEip4844WithSidecar, TxEnvelope, TxLegacy, TxType, TypedTransaction,
 };
 
@@ -38,8 +38,11 @@ pub enum ReceiptEnvelope<T = Log> {
     #[cfg_attr(feature = "serde", serde(rename = "0x3", alias = "0x03"))]
     Eip4844(ReceiptWithBloom<T>),
     // no Encrypted variant because encrypted transactions cannot be executed
+    // undecrypted transactions are executed and only charge the sender for inclusion
     #[cfg_attr(feature = "serde", serde(rename = "0x6", alias = "0x06"))]
     DawnDecrypted(ReceiptWithBloom<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "0x7", alias = "0x07"))]
+    DawnUndecrypted(ReceiptWithBloom<T>),
 }
 
 impl<T> ReceiptEnvelope<T> {
@@ -52,6 +55,7 @@ impl<T> ReceiptEnvelope<T> {
             Self::Eip1559(_) => TxType::Eip1559,
             Self::Eip4844(_) => TxType::Eip4844,
             Self::DawnDecrypted(_) => TxType::DawnDecrypted,
+            Self::DawnUndecrypted(_) => TxType::DawnUndecrypted,
         }
     }
 
@@ -89,6 +93,7 @@ impl<T> ReceiptEnvelope<T> {
             | Self::Eip1559(t)
             | Self::Eip4844(t)
             | Self::DawnDecrypted(t) => Some(t),
+            | Self::DawnUndecrypted(t) => Some(t),
         }
     }
 
@@ -101,6 +106,7 @@ impl<T> ReceiptEnvelope<T> {
             | Self::Eip1559(t)
             | Self::Eip4844(t)
             | Self::DawnDecrypted(t) => Some(&t.receipt),
+            | Self::DawnUndecrypted(t) => Some(&t.receipt),
         }
     }
 }
@@ -179,6 +185,7 @@ impl Encodable2718 for ReceiptEnvelope {
             Self::Eip1559(_) => Some(TxType::Eip1559 as u8),
             Self::Eip4844(_) => Some(TxType::Eip4844 as u8),
             Self::DawnDecrypted(_) => Some(TxType::DawnDecrypted as u8),
+            Self::DawnUndecrypted(_) => Some(TxType::DawnUndecrypted as u8),
         }
     }
 
@@ -203,6 +210,7 @@ impl Decodable2718 for ReceiptEnvelope {
             TxType::Eip1559 => Ok(Self::Eip1559(receipt)),
             TxType::Eip4844 => Ok(Self::Eip4844(receipt)),
             TxType::DawnDecrypted => Ok(Self::DawnDecrypted(receipt)),
+            TxType::DawnUndecrypted => Ok(Self::DawnUndecrypted(receipt)),
             ty => Err(Eip2718Error::UnexpectedType(ty.into())),
         }
     }
@@ -220,12 +228,13 @@ where
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let receipt = ReceiptWithBloom::<T>::arbitrary(u)?;
 
-        match u.int_in_range(0..=6)? {
+        match u.int_in_range(0..=7)? {
             0 => Ok(Self::Legacy(receipt)),
             1 => Ok(Self::Eip2930(receipt)),
             2 => Ok(Self::Eip1559(receipt)),
             3 => Ok(Self::Eip4844(receipt)),
             6 => Ok(Self::DawnDecrypted(receipt)),
+            7 => Ok(Self::DawnUndecrypted(receipt)),
             _ => unreachable!(),
         }
     }
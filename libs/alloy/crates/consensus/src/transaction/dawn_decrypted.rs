@@ -1,3 +1,7 @@
+use super::dawn_encrypted::{
+    decode_decryption_key, decode_ephemeral_public_key, encode_ephemeral_public_key,
+    ephemeral_public_key_length,
+};
 use crate::{EncodableSignature, SignableTransaction, Signed, Transaction, TxType};
 use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
 use alloy_primitives::{keccak256, Address, Bytes, ChainId, Signature, TxKind, B256, U256};
@@ -7,7 +11,7 @@ use core::mem;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use dawn_crypto::{DecryptionKey, EphemeralPublicKey};
+use dawn_crypto::{CiphertextProof, DecryptionKey, EphemeralPublicKey, MAX_PADDING};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -75,8 +79,20 @@ pub struct TxDawnDecrypted {
     /// input data of the message call, formally Td.
     pub input: Bytes,
     pub ephemeral_public_key: EphemeralPublicKey,
+    /// The proof of the ciphertext if it is of version 2, which re-encrypting cannot recompute.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub ciphertext_proof: Option<CiphertextProof>,
     pub decryption_key: DecryptionKey,
     pub sender: Address,
+    /// The public value of the encrypted transaction if its plaintext hides `value` and
+    /// `access_list`, in which case it bounds `value` and the public access list is empty. `None`
+    /// if both are public.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub value_bound: Option<U256>,
+    /// The number of zero bytes padding the plaintext in the ciphertext, at most
+    /// [`MAX_PADDING`], which re-encrypting needs to rebuild the ciphertext.
+    #[cfg_attr(feature = "serde", serde(default, with = "alloy_serde::quantity"))]
+    pub padding: u64,
 }
 
 impl TxDawnDecrypted {
@@ -112,20 +128,52 @@ impl TxDawnDecrypted {
     /// - `value`
     /// - `data` (`input`)
     /// - `access_list`
+    /// - `ephemeral_public_key`, followed by `ciphertext_proof` if any
+    /// - `decryption_key`
+    /// - `sender`
+    /// - `value_bound`, as a list holding the bound, empty if the value is public
+    /// - `padding`
+    ///
+    /// The ephemeral public key and the decryption key must be valid points, and the padding must
+    /// not exceed [`MAX_PADDING`].
     pub fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let chain_id = Decodable::decode(buf)?;
+        let nonce = Decodable::decode(buf)?;
+        let max_priority_fee_per_gas = Decodable::decode(buf)?;
+        let max_fee_per_gas = Decodable::decode(buf)?;
+        let gas_limit = Decodable::decode(buf)?;
+        let to = Decodable::decode(buf)?;
+        let value = Decodable::decode(buf)?;
+        let input = Decodable::decode(buf)?;
+        let access_list = Decodable::decode(buf)?;
+        let (ephemeral_public_key, ciphertext_proof) = decode_ephemeral_public_key(buf)?;
+        let decryption_key = decode_decryption_key(buf)?;
+        let sender = Decodable::decode(buf)?;
+        let value_bound = match Vec::<U256>::decode(buf)?[..] {
+            [] => None,
+            [value_bound] => Some(value_bound),
+            _ => return Err(alloy_rlp::Error::Custom("more than one value bound")),
+        };
+        let padding = Decodable::decode(buf)?;
+        if padding > MAX_PADDING as u64 {
+            return Err(alloy_rlp::Error::Custom("padding too long"));
+        }
         Ok(Self {
-            chain_id: Decodable::decode(buf)?,
-            nonce: Decodable::decode(buf)?,
-            max_priority_fee_per_gas: Decodable::decode(buf)?,
-            max_fee_per_gas: Decodable::decode(buf)?,
-            gas_limit: Decodable::decode(buf)?,
-            to: Decodable::decode(buf)?,
-            value: Decodable::decode(buf)?,
-            input: Decodable::decode(buf)?,
-            access_list: Decodable::decode(buf)?,
-            ephemeral_public_key: EphemeralPublicKey(Decodable::decode(buf)?),
-            decryption_key: DecryptionKey(Decodable::decode(buf)?),
-            sender: Decodable::decode(buf)?,
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to,
+            value,
+            input,
+            access_list,
+            ephemeral_public_key,
+            ciphertext_proof,
+            decryption_key,
+            sender,
+            value_bound,
+            padding,
         })
     }
 
@@ -142,9 +190,12 @@ impl TxDawnDecrypted {
         len += self.value.length();
         len += self.input.0.length();
         len += self.access_list.length();
-        len += self.ephemeral_public_key.0.length();
+        len +=
+            ephemeral_public_key_length(&self.ephemeral_public_key, self.ciphertext_proof.as_ref());
         len += self.decryption_key.0.length();
         len += self.sender.length();
+        len += alloy_rlp::list_length::<_, U256>(self.value_bound.as_slice());
+        len += self.padding.length();
         len
     }
 
@@ -159,9 +210,15 @@ impl TxDawnDecrypted {
         self.value.encode(out);
         self.input.0.encode(out);
         self.access_list.encode(out);
-        out.put_slice(&self.ephemeral_public_key.0);
-        out.put_slice(&self.decryption_key.0);
+        encode_ephemeral_public_key(
+            &self.ephemeral_public_key,
+            self.ciphertext_proof.as_ref(),
+            out,
+        );
+        self.decryption_key.0.encode(out);
         self.sender.encode(out);
+        alloy_rlp::encode_list::<_, U256>(self.value_bound.as_slice(), out);
+        self.padding.encode(out);
     }
 
     /// Returns what the encoded length should be, if the transaction were RLP encoded with the
@@ -275,7 +332,10 @@ impl TxDawnDecrypted {
         self.access_list.size() + // access_list
         self.input.len() + // input
         mem::size_of::<EphemeralPublicKey>() + // value
-        mem::size_of::<DecryptionKey>() // value
+        mem::size_of::<Option<CiphertextProof>>() + // ciphertext_proof
+        mem::size_of::<DecryptionKey>() + // value
+        mem::size_of::<Option<U256>>() + // value_bound
+        mem::size_of::<u64>() // padding
     }
 }
 
@@ -326,14 +386,14 @@ impl SignableTransaction<Signature> for TxDawnDecrypted {
         self.chain_id = chain_id;
     }
 
+    /// The sender signed the encrypted transaction, so this is the signing encoding of the
+    /// re-encrypted transaction.
     fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
-        // TODO: reencrypt
-        out.put_u8(self.tx_type() as u8);
-        self.encode(out)
+        crate::dawn::reencrypt_unsigned(self).encode_for_signing(out)
     }
 
     fn payload_len_for_signature(&self) -> usize {
-        self.length() + 1
+        crate::dawn::reencrypt_unsigned(self).payload_len_for_signature()
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
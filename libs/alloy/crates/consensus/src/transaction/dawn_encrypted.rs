@@ -7,7 +7,55 @@ use core::mem;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use dawn_crypto::{Ciphertext, EphemeralPublicKey};
+use dawn_crypto::{Ciphertext, CiphertextProof, DecryptionKey, EphemeralPublicKey};
+
+/// Decodes an ephemeral public key, rejecting it unless it is a valid point, and the proof of a
+/// version 2 ciphertext, which follows the key in the same string so that version 1 ciphertexts
+/// keep their encoding.
+pub(crate) fn decode_ephemeral_public_key(
+    buf: &mut &[u8],
+) -> alloy_rlp::Result<(EphemeralPublicKey, Option<CiphertextProof>)> {
+    let bytes = Header::decode_bytes(buf, false)?;
+    let (u, proof) = match bytes.len() {
+        96 => (bytes, None),
+        160 => (&bytes[..96], Some(CiphertextProof(bytes[96..].try_into().unwrap()))),
+        _ => return Err(alloy_rlp::Error::UnexpectedLength),
+    };
+    let u = EphemeralPublicKey::try_from(<[u8; 96]>::try_from(u).unwrap())
+        .map_err(|_| alloy_rlp::Error::Custom("invalid ephemeral public key"))?;
+    Ok((u, proof))
+}
+
+/// Returns the length of an ephemeral public key and proof encoded by
+/// [`encode_ephemeral_public_key`].
+pub(crate) fn ephemeral_public_key_length(
+    u: &EphemeralPublicKey,
+    proof: Option<&CiphertextProof>,
+) -> usize {
+    let payload_length = u.0.len() + proof.map_or(0, |proof| proof.0.len());
+    Header { list: false, payload_length }.length() + payload_length
+}
+
+/// Encodes an ephemeral public key followed by the proof of a version 2 ciphertext, if any, see
+/// [`decode_ephemeral_public_key`].
+pub(crate) fn encode_ephemeral_public_key(
+    u: &EphemeralPublicKey,
+    proof: Option<&CiphertextProof>,
+    out: &mut dyn BufMut,
+) {
+    let payload_length = u.0.len() + proof.map_or(0, |proof| proof.0.len());
+    Header { list: false, payload_length }.encode(out);
+    out.put_slice(&u.0);
+    if let Some(proof) = proof {
+        out.put_slice(&proof.0);
+    }
+}
+
+/// Decodes a decryption key, rejecting it unless it is a valid point.
+pub(crate) fn decode_decryption_key(buf: &mut &[u8]) -> alloy_rlp::Result<DecryptionKey> {
+    DecryptionKey::try_from(<[u8; 48]>::decode(buf)?)
+        .map_err(|_| alloy_rlp::Error::Custom("invalid decryption key"))
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -94,7 +142,8 @@ impl TxDawnEncrypted {
     /// - `gas_limit`
     /// - `value`
     /// - `access_list`
-    /// - `ciphertext`
+    /// - `ciphertext`, whose ephemeral public key must be a valid point, with the proof of a
+    ///   version 2 ciphertext appended to it
     pub fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
         Ok(Self {
             chain_id: Decodable::decode(buf)?,
@@ -104,10 +153,14 @@ impl TxDawnEncrypted {
             gas_limit: Decodable::decode(buf)?,
             value: Decodable::decode(buf)?,
             access_list: Decodable::decode(buf)?,
-            ciphertext: Ciphertext {
-                u: EphemeralPublicKey(Decodable::decode(buf)?),
-                payload: Decodable::decode(buf)?,
-                tag: Decodable::decode(buf)?,
+            ciphertext: {
+                let (u, proof) = decode_ephemeral_public_key(buf)?;
+                Ciphertext {
+                    u,
+                    payload: Decodable::decode(buf)?,
+                    tag: Decodable::decode(buf)?,
+                    proof,
+                }
             },
         })
     }
@@ -123,7 +176,7 @@ impl TxDawnEncrypted {
         len += self.gas_limit.length();
         len += self.value.length();
         len += self.access_list.length();
-        len += self.ciphertext.u.0.length();
+        len += ephemeral_public_key_length(&self.ciphertext.u, self.ciphertext.proof.as_ref());
         len += self.ciphertext.payload.length();
         len += self.ciphertext.tag.length();
         len
@@ -138,7 +191,7 @@ impl TxDawnEncrypted {
         self.gas_limit.encode(out);
         self.value.encode(out);
         self.access_list.encode(out);
-        self.ciphertext.u.0.encode(out);
+        encode_ephemeral_public_key(&self.ciphertext.u, self.ciphertext.proof.as_ref(), out);
         self.ciphertext.payload.encode(out);
         self.ciphertext.tag.encode(out);
     }
@@ -252,6 +305,7 @@ impl TxDawnEncrypted {
         mem::size_of::<U256>() + // value
         self.access_list.size() + // access_list
         self.ciphertext.u.0.len() + // ciphertext
+        self.ciphertext.proof.map_or(0, |proof| proof.0.len()) +
         self.ciphertext.payload.len() +
         self.ciphertext.tag.len()
     }
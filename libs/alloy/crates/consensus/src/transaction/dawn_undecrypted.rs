@@ -0,0 +1,403 @@
+use super::dawn_encrypted::{
+    decode_decryption_key, decode_ephemeral_public_key, encode_ephemeral_public_key,
+    ephemeral_public_key_length,
+};
+use crate::{
+    EncodableSignature, SignableTransaction, Signed, Transaction, TxDawnEncrypted, TxType,
+};
+use alloy_eips::eip2930::AccessList;
+use alloy_primitives::{keccak256, Address, ChainId, Signature, TxKind, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use core::mem;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use dawn_crypto::{Ciphertext, DecryptionKey};
+
+/// An encrypted transaction whose ciphertext failed to decrypt.
+///
+/// It carries the original ciphertext and the decryption key revealed by the SMC, so that anyone
+/// can check that decryption indeed fails. Executing it does not transfer value or run code; the
+/// sender only pays for the ciphertext as calldata.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[doc(
+    alias = "DawnUndecryptedTransaction",
+    alias = "TransactionDawnUndecrypted",
+    alias = "DawnUndecryptedTx"
+)]
+pub struct TxDawnUndecrypted {
+    /// EIP-155: Simple replay attack protection
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub chain_id: ChainId,
+    /// A scalar value equal to the number of transactions sent by the sender; formally Tn.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub nonce: u64,
+    /// A scalar value equal to the maximum
+    /// amount of gas that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub gas_limit: u128,
+    /// A scalar value equal to the maximum
+    /// amount of gas that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    ///
+    /// This is also known as `GasFeeCap`
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub max_fee_per_gas: u128,
+    /// Max Priority fee that transaction is paying
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    ///
+    /// This is also known as `GasTipCap`
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub max_priority_fee_per_gas: u128,
+    /// The value of the original encrypted transaction. It is never transferred.
+    pub value: U256,
+    /// The access list of the original encrypted transaction.
+    pub access_list: AccessList,
+    /// The ciphertext of the original encrypted transaction.
+    pub ciphertext: Ciphertext,
+    /// The decryption key for the label of the original encrypted transaction.
+    pub decryption_key: DecryptionKey,
+    /// The sender of the original encrypted transaction.
+    pub sender: Address,
+}
+
+impl TxDawnUndecrypted {
+    /// Returns the effective gas price for the given `base_fee`.
+    pub const fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        match base_fee {
+            None => self.max_fee_per_gas,
+            Some(base_fee) => {
+                // if the tip is greater than the max priority fee per gas, set it to the max
+                // priority fee per gas + base fee
+                let tip = self.max_fee_per_gas.saturating_sub(base_fee as u128);
+                if tip > self.max_priority_fee_per_gas {
+                    self.max_priority_fee_per_gas + base_fee as u128
+                } else {
+                    // otherwise return the max fee per gas
+                    self.max_fee_per_gas
+                }
+            }
+        }
+    }
+
+    /// Returns the original encrypted transaction, which is what the sender signed.
+    pub fn encrypted(&self) -> TxDawnEncrypted {
+        TxDawnEncrypted {
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            gas_limit: self.gas_limit,
+            max_fee_per_gas: self.max_fee_per_gas,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            value: self.value,
+            access_list: self.access_list.clone(),
+            ciphertext: self.ciphertext.clone(),
+        }
+    }
+
+    /// Returns the number of bytes of ciphertext the sender is charged for.
+    pub fn ciphertext_len(&self) -> usize {
+        self.ciphertext.u.0.len()
+            + self.ciphertext.proof.map_or(0, |proof| proof.0.len())
+            + self.ciphertext.payload.len()
+            + self.ciphertext.tag.len()
+    }
+
+    /// Decodes the inner [TxDawnUndecrypted] fields from RLP bytes.
+    ///
+    /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
+    /// RLP fields in the following order:
+    ///
+    /// - `chain_id`
+    /// - `nonce`
+    /// - `max_priority_fee_per_gas`
+    /// - `max_fee_per_gas`
+    /// - `gas_limit`
+    /// - `value`
+    /// - `access_list`
+    /// - `ciphertext`, with the proof of a version 2 ciphertext appended to its ephemeral public
+    ///   key
+    /// - `decryption_key`
+    /// - `sender`
+    ///
+    /// The ephemeral public key and the decryption key must be valid points.
+    pub fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            chain_id: Decodable::decode(buf)?,
+            nonce: Decodable::decode(buf)?,
+            max_priority_fee_per_gas: Decodable::decode(buf)?,
+            max_fee_per_gas: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            access_list: Decodable::decode(buf)?,
+            ciphertext: {
+                let (u, proof) = decode_ephemeral_public_key(buf)?;
+                Ciphertext {
+                    u,
+                    payload: Decodable::decode(buf)?,
+                    tag: Decodable::decode(buf)?,
+                    proof,
+                }
+            },
+            decryption_key: decode_decryption_key(buf)?,
+            sender: Decodable::decode(buf)?,
+        })
+    }
+
+    /// Outputs the length of the transaction's fields, without a RLP header.
+    #[doc(hidden)]
+    pub fn fields_len(&self) -> usize {
+        let mut len = 0;
+        len += self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.value.length();
+        len += self.access_list.length();
+        len += ephemeral_public_key_length(&self.ciphertext.u, self.ciphertext.proof.as_ref());
+        len += self.ciphertext.payload.length();
+        len += self.ciphertext.tag.length();
+        len += self.decryption_key.0.length();
+        len += self.sender.length();
+        len
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.max_priority_fee_per_gas.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.gas_limit.encode(out);
+        self.value.encode(out);
+        self.access_list.encode(out);
+        encode_ephemeral_public_key(&self.ciphertext.u, self.ciphertext.proof.as_ref(), out);
+        self.ciphertext.payload.encode(out);
+        self.ciphertext.tag.encode(out);
+        self.decryption_key.0.encode(out);
+        self.sender.encode(out);
+    }
+
+    /// Returns what the encoded length should be, if the transaction were RLP encoded with the
+    /// given signature, depending on the value of `with_header`.
+    ///
+    /// If `with_header` is `true`, the payload length will include the RLP header length.
+    /// If `with_header` is `false`, the payload length will not include the RLP header length.
+    pub fn encoded_len_with_signature<S>(&self, signature: &S, with_header: bool) -> usize
+    where
+        S: EncodableSignature,
+    {
+        // this counts the tx fields and signature fields
+        let payload_length = self.fields_len() + signature.rlp_vrs_len();
+
+        // this counts:
+        // * tx type byte
+        // * inner header length
+        // * inner payload length
+        let inner_payload_length =
+            1 + Header { list: true, payload_length }.length() + payload_length;
+
+        if with_header {
+            // header length plus length of the above, wrapped with a string header
+            Header { list: false, payload_length: inner_payload_length }.length()
+                + inner_payload_length
+        } else {
+            inner_payload_length
+        }
+    }
+
+    /// Inner encoding function that is used for both rlp [`Encodable`] trait and for calculating
+    /// hash that for eip2718 does not require a rlp header.
+    #[doc(hidden)]
+    pub fn encode_with_signature<S>(&self, signature: &S, out: &mut dyn BufMut, with_header: bool)
+    where
+        S: EncodableSignature,
+    {
+        let payload_length = self.fields_len() + signature.rlp_vrs_len();
+        if with_header {
+            Header {
+                list: false,
+                payload_length: 1 + Header { list: true, payload_length }.length() + payload_length,
+            }
+            .encode(out);
+        }
+        out.put_u8(self.tx_type() as u8);
+        self.encode_with_signature_fields(signature, out);
+    }
+
+    /// Decodes the transaction from RLP bytes, including the signature.
+    ///
+    /// This __does not__ expect the bytes to start with a transaction type byte or string
+    /// header.
+    ///
+    /// This __does__ expect the bytes to start with a list header and include a signature.
+    #[doc(hidden)]
+    pub fn decode_signed_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Signed<Self>> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+
+        // record original length so we can check encoding
+        let original_len = buf.len();
+
+        let tx = Self::decode_fields(buf)?;
+        let signature = Signature::decode_rlp_vrs(buf)?;
+
+        let signed = tx.into_signed(signature);
+        if buf.len() + header.payload_length != original_len {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: original_len - buf.len(),
+            });
+        }
+
+        Ok(signed)
+    }
+
+    /// Encodes the transaction from RLP bytes, including the signature. This __does not__ encode a
+    /// tx type byte or string header.
+    ///
+    /// This __does__ encode a list header and include a signature.
+    pub fn encode_with_signature_fields<S>(&self, signature: &S, out: &mut dyn BufMut)
+    where
+        S: EncodableSignature,
+    {
+        let payload_length = self.fields_len() + signature.rlp_vrs_len();
+        let header = Header { list: true, payload_length };
+        header.encode(out);
+        self.encode_fields(out);
+        signature.write_rlp_vrs(out);
+    }
+
+    /// Get transaction type
+    #[doc(alias = "transaction_type")]
+    pub(crate) const fn tx_type(&self) -> TxType {
+        TxType::DawnUndecrypted
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [TxDawnUndecrypted] transaction.
+    #[inline]
+    pub fn size(&self) -> usize {
+        mem::size_of::<ChainId>() + // chain_id
+        mem::size_of::<u64>() + // nonce
+        mem::size_of::<u128>() + // gas_limit
+        mem::size_of::<u128>() + // max_fee_per_gas
+        mem::size_of::<u128>() + // max_priority_fee_per_gas
+        mem::size_of::<U256>() + // value
+        self.access_list.size() + // access_list
+        self.ciphertext_len() + // ciphertext
+        mem::size_of::<DecryptionKey>() + // decryption_key
+        mem::size_of::<Address>() // sender
+    }
+}
+
+impl Transaction for TxDawnUndecrypted {
+    fn chain_id(&self) -> Option<ChainId> {
+        Some(self.chain_id)
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn gas_limit(&self) -> u128 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.max_fee_per_gas
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        Some(self.max_priority_fee_per_gas)
+    }
+
+    fn to(&self) -> TxKind {
+        TxKind::Call(self.sender)
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &[u8] {
+        &[]
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        Some(&self.access_list)
+    }
+}
+
+impl SignableTransaction<Signature> for TxDawnUndecrypted {
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.chain_id = chain_id;
+    }
+
+    fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
+        // the sender signed the encrypted transaction
+        self.encrypted().encode_for_signing(out)
+    }
+
+    fn payload_len_for_signature(&self) -> usize {
+        self.encrypted().payload_len_for_signature()
+    }
+
+    fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-1559 transaction. V should indicate the y-parity of the
+        // signature.
+        let signature = signature.with_parity_bool();
+
+        let mut buf = Vec::with_capacity(self.encoded_len_with_signature(&signature, false));
+        self.encode_with_signature(&signature, &mut buf, false);
+        let hash = keccak256(&buf);
+
+        Signed::new_unchecked(self, signature, hash)
+    }
+}
+
+impl Encodable for TxDawnUndecrypted {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for TxDawnUndecrypted {
+    fn decode(data: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(data)?;
+        let remaining_len = data.len();
+
+        if header.payload_length > remaining_len {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+
+        Self::decode_fields(data)
+    }
+}
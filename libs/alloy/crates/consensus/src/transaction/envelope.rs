@@ -1,6 +1,9 @@
 use core::fmt;
 
-use crate::{Signed, Transaction, TxDawnDecrypted, TxDawnEncrypted, TxEip1559, TxEip2930, TxLegacy};
+use crate::{
+    Signed, Transaction, TxDawnDecrypted, TxDawnEncrypted, TxDawnUndecrypted, TxEip1559, TxEip2930,
+    TxLegacy,
+};
 use alloy_eips::{
     eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718},
     eip2930::AccessList,
@@ -33,6 +36,8 @@ pub enum TxType {
     DawnEncrypted = 5,
     /// Dawn Decrypted transaction type.
     DawnDecrypted = 6,
+    /// Dawn Undecrypted transaction type.
+    DawnUndecrypted = 7,
 }
 
 impl From<TxType> for u8 {
@@ -50,6 +55,7 @@ impl fmt::Display for TxType {
             Self::Eip4844 => write!(f, "EIP-4844"),
             Self::DawnEncrypted => write!(f, "Dawn Encrypted"),
             Self::DawnDecrypted => write!(f, "Dawn Decrypted"),
+            Self::DawnUndecrypted => write!(f, "Dawn Undecrypted"),
         }
     }
 }
@@ -72,6 +78,7 @@ impl TryFrom<u8> for TxType {
             3 => Self::Eip4844,
             5 => Self::DawnEncrypted,
             6 => Self::DawnDecrypted,
+            7 => Self::DawnUndecrypted,
             _ => return Err(Eip2718Error::UnexpectedType(value)),
         })
     }
@@ -116,6 +123,8 @@ pub enum TxEnvelope {
     DawnEncrypted(Signed<TxDawnEncrypted>),
     #[cfg_attr(feature = "serde", serde(rename = "0x6", alias = "0x06"))]
     DawnDecrypted(Signed<TxDawnDecrypted>),
+    #[cfg_attr(feature = "serde", serde(rename = "0x7", alias = "0x07"))]
+    DawnUndecrypted(Signed<TxDawnUndecrypted>),
 }
 
 impl From<Signed<TxLegacy>> for TxEnvelope {
@@ -168,6 +177,12 @@ impl From<Signed<TxDawnDecrypted>> for TxEnvelope {
     }
 }
 
+impl From<Signed<TxDawnUndecrypted>> for TxEnvelope {
+    fn from(v: Signed<TxDawnUndecrypted>) -> Self {
+        Self::DawnUndecrypted(v)
+    }
+}
+
 impl TxEnvelope {
     /// Returns true if the transaction is a legacy transaction.
     #[inline]
@@ -237,6 +252,7 @@ impl TxEnvelope {
             Self::Eip4844(tx) => tx.recover_signer(),
             Self::DawnEncrypted(tx) => tx.recover_signer(),
             Self::DawnDecrypted(tx) => tx.recover_signer(),
+            Self::DawnUndecrypted(tx) => tx.recover_signer(),
         }
     }
 
@@ -249,6 +265,7 @@ impl TxEnvelope {
             Self::Eip4844(tx) => tx.signature_hash(),
             Self::DawnEncrypted(tx) => tx.signature_hash(),
             Self::DawnDecrypted(tx) => tx.signature_hash(),
+            Self::DawnUndecrypted(tx) => tx.signature_hash(),
         }
     }
 
@@ -262,6 +279,7 @@ impl TxEnvelope {
             Self::Eip4844(tx) => tx.hash(),
             Self::DawnEncrypted(tx) => tx.hash(),
             Self::DawnDecrypted(tx) => tx.hash(),
+            Self::DawnUndecrypted(tx) => tx.hash(),
         }
     }
 
@@ -275,6 +293,7 @@ impl TxEnvelope {
             Self::Eip4844(_) => TxType::Eip4844,
             Self::DawnEncrypted(_) => TxType::DawnEncrypted,
             Self::DawnDecrypted(_) => TxType::DawnDecrypted,
+            Self::DawnUndecrypted(_) => TxType::DawnUndecrypted,
         }
     }
 
@@ -314,6 +333,10 @@ impl TxEnvelope {
                 let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
                 Header { list: true, payload_length }.length() + payload_length
             }
+            Self::DawnUndecrypted(t) => {
+                let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length
+            }
         }
     }
 
@@ -366,6 +389,7 @@ impl Decodable2718 for TxEnvelope {
             TxType::Eip4844 => Ok(TxEip4844Variant::decode_signed_fields(buf)?.into()),
             TxType::DawnEncrypted => Ok(TxDawnEncrypted::decode_signed_fields(buf)?.into()),
             TxType::DawnDecrypted => Ok(TxDawnDecrypted::decode_signed_fields(buf)?.into()),
+            TxType::DawnUndecrypted => Ok(TxDawnUndecrypted::decode_signed_fields(buf)?.into()),
             TxType::Legacy => Err(Eip2718Error::UnexpectedType(0)),
         }
     }
@@ -384,6 +408,7 @@ impl Encodable2718 for TxEnvelope {
             Self::Eip4844(_) => Some(TxType::Eip4844.into()),
             Self::DawnEncrypted(_) => Some(TxType::DawnEncrypted.into()),
             Self::DawnDecrypted(_) => Some(TxType::DawnDecrypted.into()),
+            Self::DawnUndecrypted(_) => Some(TxType::DawnUndecrypted.into()),
         }
     }
 
@@ -410,6 +435,9 @@ impl Encodable2718 for TxEnvelope {
             Self::DawnDecrypted(tx) => {
                 tx.tx().encode_with_signature(tx.signature(), out, false);
             }
+            Self::DawnUndecrypted(tx) => {
+                tx.tx().encode_with_signature(tx.signature(), out, false);
+            }
         }
     }
 }
@@ -423,6 +451,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().chain_id(),
             Self::DawnEncrypted(tx) => tx.tx().chain_id(),
             Self::DawnDecrypted(tx) => tx.tx().chain_id(),
+            Self::DawnUndecrypted(tx) => tx.tx().chain_id(),
         }
     }
 
@@ -434,6 +463,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().gas_limit(),
             Self::DawnEncrypted(tx) => tx.tx().gas_limit(),
             Self::DawnDecrypted(tx) => tx.tx().gas_limit(),
+            Self::DawnUndecrypted(tx) => tx.tx().gas_limit(),
         }
     }
 
@@ -445,6 +475,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().gas_price(),
             Self::DawnEncrypted(tx) => tx.tx().gas_price(),
             Self::DawnDecrypted(tx) => tx.tx().gas_price(),
+            Self::DawnUndecrypted(tx) => tx.tx().gas_price(),
         }
     }
     
@@ -456,6 +487,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().max_fee_per_gas(),
             Self::DawnEncrypted(tx) => tx.tx().max_fee_per_gas(),
             Self::DawnDecrypted(tx) => tx.tx().max_fee_per_gas(),
+            Self::DawnUndecrypted(tx) => tx.tx().max_fee_per_gas(),
         }
     }
     
@@ -467,6 +499,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().max_priority_fee_per_gas(),
             Self::DawnEncrypted(tx) => tx.tx().max_priority_fee_per_gas(),
             Self::DawnDecrypted(tx) => tx.tx().max_priority_fee_per_gas(),
+            Self::DawnUndecrypted(tx) => tx.tx().max_priority_fee_per_gas(),
         }
     }
 
@@ -478,6 +511,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().input(),
             Self::DawnEncrypted(tx) => tx.tx().input(),
             Self::DawnDecrypted(tx) => tx.tx().input(),
+            Self::DawnUndecrypted(tx) => tx.tx().input(),
         }
     }
 
@@ -489,6 +523,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().nonce(),
             Self::DawnEncrypted(tx) => tx.tx().nonce(),
             Self::DawnDecrypted(tx) => tx.tx().nonce(),
+            Self::DawnUndecrypted(tx) => tx.tx().nonce(),
         }
     }
 
@@ -500,6 +535,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().to(),
             Self::DawnEncrypted(tx) => tx.tx().to(),
             Self::DawnDecrypted(tx) => tx.tx().to(),
+            Self::DawnUndecrypted(tx) => tx.tx().to(),
         }
     }
 
@@ -511,6 +547,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().value(),
             Self::DawnEncrypted(tx) => tx.tx().value(),
             Self::DawnDecrypted(tx) => tx.tx().value(),
+            Self::DawnUndecrypted(tx) => tx.tx().value(),
         }
     }
 
@@ -522,6 +559,7 @@ impl Transaction for TxEnvelope {
             Self::Eip4844(tx) => tx.tx().access_list(),
             Self::DawnEncrypted(tx) => tx.tx().access_list(),
             Self::DawnDecrypted(tx) => tx.tx().access_list(),
+            Self::DawnUndecrypted(tx) => tx.tx().access_list(),
         }
     }
 }
@@ -22,6 +22,9 @@ pub use dawn_encrypted::TxDawnEncrypted;
 mod dawn_decrypted;
 pub use dawn_decrypted::TxDawnDecrypted;
 
+mod dawn_undecrypted;
+pub use dawn_undecrypted::TxDawnUndecrypted;
+
 pub use alloy_eips::eip4844::{
     builder::{SidecarBuilder, SidecarCoder, SimpleCoder},
     utils as eip4844_utils, Blob, BlobTransactionSidecar, Bytes48,
@@ -1,6 +1,7 @@
 use crate::{
     transaction::eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar},
-    Transaction, TxDawnDecrypted, TxDawnEncrypted, TxEip1559, TxEip2930, TxEnvelope, TxLegacy, TxType,
+    Transaction, TxDawnDecrypted, TxDawnEncrypted, TxDawnUndecrypted, TxEip1559, TxEip2930, TxEnvelope,
+    TxLegacy, TxType,
 };
 use alloy_primitives::{ChainId, TxKind};
 use alloy_eips::eip2930::AccessList;
@@ -31,6 +32,7 @@ pub enum TypedTransaction {
     Eip4844(TxEip4844Variant),
     DawnEncrypted(TxDawnEncrypted),
     DawnDecrypted(TxDawnDecrypted),
+    DawnUndecrypted(TxDawnUndecrypted),
 }
 
 impl From<TxLegacy> for TypedTransaction {
@@ -82,6 +84,12 @@ impl From<TxDawnDecrypted> for TypedTransaction {
     }
 }
 
+impl From<TxDawnUndecrypted> for TypedTransaction {
+    fn from(tx: TxDawnUndecrypted) -> Self {
+        Self::DawnUndecrypted(tx)
+    }
+}
+
 impl From<TxEnvelope> for TypedTransaction {
     fn from(envelope: TxEnvelope) -> Self {
         match envelope {
@@ -91,6 +99,7 @@ impl From<TxEnvelope> for TypedTransaction {
             TxEnvelope::Eip4844(tx) => Self::Eip4844(tx.strip_signature()),
             TxEnvelope::DawnEncrypted(tx) => Self::DawnEncrypted(tx.strip_signature()),
             TxEnvelope::DawnDecrypted(tx) => Self::DawnDecrypted(tx.strip_signature()),
+            TxEnvelope::DawnUndecrypted(tx) => Self::DawnUndecrypted(tx.strip_signature()),
         }
     }
 }
@@ -106,6 +115,7 @@ impl TypedTransaction {
             Self::Eip4844(_) => TxType::Eip4844,
             Self::DawnEncrypted(_) => TxType::DawnEncrypted,
             Self::DawnDecrypted(_) => TxType::DawnDecrypted,
+            Self::DawnUndecrypted(_) => TxType::DawnUndecrypted,
         }
     }
 
@@ -143,6 +153,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.chain_id(),
             Self::DawnEncrypted(tx) => tx.chain_id(),
             Self::DawnDecrypted(tx) => tx.chain_id(),
+            Self::DawnUndecrypted(tx) => tx.chain_id(),
         }
     }
 
@@ -154,6 +165,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.nonce(),
             Self::DawnEncrypted(tx) => tx.nonce(),
             Self::DawnDecrypted(tx) => tx.nonce(),
+            Self::DawnUndecrypted(tx) => tx.nonce(),
         }
     }
 
@@ -165,6 +177,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.gas_limit(),
             Self::DawnEncrypted(tx) => tx.gas_limit(),
             Self::DawnDecrypted(tx) => tx.gas_limit(),
+            Self::DawnUndecrypted(tx) => tx.gas_limit(),
         }
     }
 
@@ -176,6 +189,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.gas_price(),
             Self::DawnEncrypted(tx) => tx.gas_price(),
             Self::DawnDecrypted(tx) => tx.gas_price(),
+            Self::DawnUndecrypted(tx) => tx.gas_price(),
         }
     }
 
@@ -187,6 +201,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.max_fee_per_gas(),
             Self::DawnEncrypted(tx) => tx.max_fee_per_gas(),
             Self::DawnDecrypted(tx) => tx.max_fee_per_gas(),
+            Self::DawnUndecrypted(tx) => tx.max_fee_per_gas(),
         }
     }
     
@@ -198,6 +213,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.max_priority_fee_per_gas(),
             Self::DawnEncrypted(tx) => tx.max_priority_fee_per_gas(),
             Self::DawnDecrypted(tx) => tx.max_priority_fee_per_gas(),
+            Self::DawnUndecrypted(tx) => tx.max_priority_fee_per_gas(),
         }
     }
 
@@ -209,6 +225,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.to(),
             Self::DawnEncrypted(tx) => tx.to(),
             Self::DawnDecrypted(tx) => tx.to(),
+            Self::DawnUndecrypted(tx) => tx.to(),
         }
     }
 
@@ -220,6 +237,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.value(),
             Self::DawnEncrypted(tx) => tx.value(),
             Self::DawnDecrypted(tx) => tx.value(),
+            Self::DawnUndecrypted(tx) => tx.value(),
         }
     }
 
@@ -231,6 +249,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.input(),
             Self::DawnEncrypted(tx) => tx.input(),
             Self::DawnDecrypted(tx) => tx.input(),
+            Self::DawnUndecrypted(tx) => tx.input(),
         }
     }
 
@@ -242,6 +261,7 @@ impl Transaction for TypedTransaction {
             Self::Eip4844(tx) => tx.access_list(),
             Self::DawnEncrypted(tx) => tx.access_list(),
             Self::DawnDecrypted(tx) => tx.access_list(),
+            Self::DawnUndecrypted(tx) => tx.access_list(),
         }
     }
 }
@@ -121,8 +121,11 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
             TxType::Eip2930 => self.complete_2930(),
             TxType::Eip1559 => self.complete_1559(),
             TxType::Eip4844 => self.complete_4844(),
-            TxType::DawnEncrypted => todo!(),
-            TxType::DawnDecrypted => todo!(),
+            // Dawn transactions are encrypted by their sender, and decrypted by the SMC, which a
+            // request can not do
+            TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted => {
+                Err(vec!["ciphertext"])
+            }
         }
     }
 
@@ -816,6 +816,9 @@ pub enum PayloadError {
     /// blob transactions present in pre-prague payload.
     #[error("eip 7702 transactions present in pre-prague payload")]
     PrePragueBlockWithEip7702Transactions,
+    /// Dawn transactions present in pre-dawn payload.
+    #[error("dawn transactions present in pre-dawn payload")]
+    PreDawnBlockWithDawnTransactions,
 
     /// Invalid payload block hash.
     #[error("block hash mismatch: want {consensus}, got {execution}")]
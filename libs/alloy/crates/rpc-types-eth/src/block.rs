@@ -122,6 +122,9 @@ pub struct Header {
     /// EIP-7685 requests root.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub requests_root: Option<B256>,
+    /// Dawn shadow block root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_root: Option<B256>,
 }
 
 impl Header {
@@ -175,6 +178,7 @@ impl TryFrom<Header> for alloy_consensus::Header {
             excess_blob_gas,
             parent_beacon_block_root,
             requests_root,
+            shadow_root,
             // not included in the consensus header
             hash: _hash,
             total_difficulty: _total_difficulty,
@@ -200,6 +204,7 @@ impl TryFrom<Header> for alloy_consensus::Header {
             excess_blob_gas,
             parent_beacon_block_root,
             requests_root,
+            shadow_root,
             extra_data,
         })
     }
@@ -369,6 +374,7 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                shadow_root: None,
             },
             uncles: vec![B256::with_last_byte(17)],
             transactions: vec![B256::with_last_byte(18)].into(),
@@ -412,6 +418,7 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                shadow_root: None,
             },
             uncles: vec![],
             transactions: BlockTransactions::Uncle,
@@ -455,6 +462,7 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                shadow_root: None,
             },
             uncles: vec![B256::with_last_byte(17)],
             transactions: vec![B256::with_last_byte(18)].into(),
@@ -64,6 +64,10 @@ pub enum ConversionError {
     /// Excess blob gas integer conversion error
     #[error("excess blob gas integer conversion error: {0}")]
     ExcessBlobGasConversion(TryFromIntError),
+    /// The transaction type can not be converted from its RPC representation, which lacks some of
+    /// its fields.
+    #[error("{0} transactions can not be converted from their RPC representation")]
+    UnsupportedTransactionType(alloy_consensus::TxType),
     /// A custom Conversion Error that doesn't fit other categories.
     #[error("conversion error: {0}")]
     Custom(String),
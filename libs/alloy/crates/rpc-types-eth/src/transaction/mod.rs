@@ -273,8 +273,11 @@ impl TryFrom<Transaction> for TxEnvelope {
             TxType::Eip1559 => Ok(Self::Eip1559(tx.try_into()?)),
             TxType::Eip2930 => Ok(Self::Eip2930(tx.try_into()?)),
             TxType::Eip4844 => Ok(Self::Eip4844(tx.try_into()?)),
-            TxType::DawnEncrypted => todo!(),
-            TxType::DawnDecrypted => todo!(),
+            // the ciphertext and decryption key of Dawn transactions are not part of their RPC
+            // representation
+            ty @ (TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted) => {
+                Err(ConversionError::UnsupportedTransactionType(ty))
+            }
         }
     }
 }
@@ -451,4 +454,13 @@ mod tests {
         assert!(request.gas_price.is_none());
         assert!(request.max_fee_per_gas.is_some());
     }
+
+    #[test]
+    fn dawn_transactions_into_envelope() {
+        let tx = Transaction { transaction_type: Some(5), ..Default::default() };
+        assert!(matches!(
+            TxEnvelope::try_from(tx),
+            Err(ConversionError::UnsupportedTransactionType(TxType::DawnEncrypted))
+        ));
+    }
 }
@@ -2,8 +2,8 @@
 
 use crate::{transaction::AccessList, BlobTransactionSidecar, Transaction};
 use alloy_consensus::{
-    TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEip4844WithSidecar, TxEnvelope, TxLegacy,
-    TxType, TypedTransaction,
+    TxDawnDecrypted, TxDawnEncrypted, TxDawnUndecrypted, TxEip1559, TxEip2930, TxEip4844,
+    TxEip4844Variant, TxEip4844WithSidecar, TxEnvelope, TxLegacy, TxType, TypedTransaction,
 };
 use alloy_primitives::{Address, Bytes, ChainId, TxKind, B256, U256};
 use serde::{Deserialize, Serialize};
@@ -340,7 +340,7 @@ impl TransactionRequest {
             TxType::Eip4844 => {
                 self.gas_price = None;
             }
-            TxType::DawnEncrypted | TxType::DawnDecrypted => {
+            TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted => {
                 unreachable!("Dawn transactions cannot be preferred types");
             }
         }
@@ -378,7 +378,7 @@ impl TransactionRequest {
             TxType::Eip2930 => self.complete_2930(),
             TxType::Eip1559 => self.complete_1559(),
             TxType::Eip4844 => self.complete_4844(),
-            TxType::DawnEncrypted | TxType::DawnDecrypted => {
+            TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted => {
                 unreachable!("Dawn transactions cannot be preferred types")
             }
         } {
@@ -464,7 +464,7 @@ impl TransactionRequest {
             TxType::Eip2930 => self.complete_2930().ok(),
             TxType::Eip1559 => self.complete_1559().ok(),
             TxType::Eip4844 => self.complete_4844().ok(),
-            TxType::DawnEncrypted | TxType::DawnDecrypted => {
+            TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted => {
                 unreachable!("Dawn transactions cannot be preferred types")
             }
         }?;
@@ -484,7 +484,7 @@ impl TransactionRequest {
             TxType::Eip2930 => self.build_2930().into(),
             TxType::Eip1559 => self.build_1559().into(),
             TxType::Eip4844 => self.build_4844().into(),
-            TxType::DawnEncrypted | TxType::DawnDecrypted => {
+            TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted => {
                 unreachable!("Dawn transactions cannot be preferred types")
             }
         })
@@ -700,6 +700,60 @@ impl From<TxEip4844Variant> for TransactionRequest {
     }
 }
 
+/// Only the public fields of the encrypted transaction are carried over.
+impl From<TxDawnEncrypted> for TransactionRequest {
+    fn from(tx: TxDawnEncrypted) -> Self {
+        Self {
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+            gas: Some(tx.gas_limit),
+            value: Some(tx.value),
+            nonce: Some(tx.nonce),
+            chain_id: Some(tx.chain_id),
+            access_list: Some(tx.access_list),
+            transaction_type: Some(5),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<TxDawnDecrypted> for TransactionRequest {
+    fn from(tx: TxDawnDecrypted) -> Self {
+        Self {
+            from: Some(tx.sender),
+            to: if let TxKind::Call(to) = tx.to { Some(to.into()) } else { None },
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+            gas: Some(tx.gas_limit),
+            value: Some(tx.value),
+            input: tx.input.into(),
+            nonce: Some(tx.nonce),
+            chain_id: Some(tx.chain_id),
+            access_list: Some(tx.access_list),
+            transaction_type: Some(6),
+            ..Default::default()
+        }
+    }
+}
+
+/// Only the public fields of the encrypted transaction are carried over.
+impl From<TxDawnUndecrypted> for TransactionRequest {
+    fn from(tx: TxDawnUndecrypted) -> Self {
+        Self {
+            from: Some(tx.sender),
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+            gas: Some(tx.gas_limit),
+            value: Some(tx.value),
+            nonce: Some(tx.nonce),
+            chain_id: Some(tx.chain_id),
+            access_list: Some(tx.access_list),
+            transaction_type: Some(7),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<TypedTransaction> for TransactionRequest {
     fn from(tx: TypedTransaction) -> Self {
         match tx {
@@ -707,8 +761,9 @@ impl From<TypedTransaction> for TransactionRequest {
             TypedTransaction::Eip2930(tx) => tx.into(),
             TypedTransaction::Eip1559(tx) => tx.into(),
             TypedTransaction::Eip4844(tx) => tx.into(),
-            TypedTransaction::DawnEncrypted(tx) => todo!(),
-            TypedTransaction::DawnDecrypted(tx) => todo!(),
+            TypedTransaction::DawnEncrypted(tx) => tx.into(),
+            TypedTransaction::DawnDecrypted(tx) => tx.into(),
+            TypedTransaction::DawnUndecrypted(tx) => tx.into(),
         }
     }
 }
@@ -0,0 +1,189 @@
+//! Confidential channels between the enclaves of the SMC.
+//!
+//! Each enclave generates a [`ChannelPrivateKey`] and has its [`ChannelPublicKey`] attested to the
+//! other members. Two enclaves share the static Diffie-Hellman secret of their keys in G1, from
+//! which each message derives its own key with a random salt, so that secrets travel through the
+//! hosts encrypted and authenticated. The sender and the recipient derive the same key, so the
+//! sender can also decrypt what it sent.
+
+use crate::encapsulate::{random_scalar, unpack_g1};
+use crate::{symmetric, Error};
+use group::ff::Field;
+use ic_bls12_381::{G1Affine, Scalar};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const DOMAIN: &[u8] = b"dawn channel";
+
+/// The public key of the channels of an enclave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "[u8; 48]", into = "[u8; 48]")]
+pub struct ChannelPublicKey(G1Affine);
+
+impl ChannelPublicKey {
+    pub fn to_bytes(&self) -> [u8; 48] {
+        self.0.to_compressed()
+    }
+}
+
+impl TryFrom<[u8; 48]> for ChannelPublicKey {
+    type Error = Error;
+
+    /// Decodes a channel public key, failing unless it is a point of G1 other than the identity.
+    fn try_from(bytes: [u8; 48]) -> Result<Self, Error> {
+        unpack_g1(&bytes).map(Self)
+    }
+}
+
+impl From<ChannelPublicKey> for [u8; 48] {
+    fn from(pk: ChannelPublicKey) -> Self {
+        pk.to_bytes()
+    }
+}
+
+/// The private key of the channels of an enclave, which must only leave it sealed.
+#[derive(Debug)]
+pub struct ChannelPrivateKey(Scalar);
+
+impl ChannelPrivateKey {
+    /// Generates a random channel key.
+    pub fn generate() -> Self {
+        Self(random_scalar())
+    }
+
+    pub fn public_key(&self) -> ChannelPublicKey {
+        ChannelPublicKey((G1Affine::generator() * self.0).into())
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Decodes a channel private key, failing if `bytes` is not a canonical non-zero scalar.
+    pub fn try_from_bytes(bytes: [u8; 32]) -> Result<Self, Error> {
+        Option::from(Scalar::from_bytes(&bytes))
+            .filter(|sk: &Scalar| !bool::from(sk.is_zero()))
+            .map(Self)
+            .ok_or(Error::InvalidScalar)
+    }
+
+    /// Encrypts `secret` for the enclave of `peer`, binding `context` as associated data.
+    pub fn encrypt(
+        &self,
+        peer: &ChannelPublicKey,
+        secret: &[u8; 32],
+        context: &[u8],
+    ) -> ChannelCiphertext {
+        let salt = random_scalar().to_bytes();
+        let mut payload = *secret;
+        let tag = symmetric::encrypt(&self.derive_key(peer, &salt), &mut payload, context);
+        ChannelCiphertext { salt, payload, tag }
+    }
+
+    /// Decrypts a secret sent by the enclave of `peer` with the same `context`, or sent to it.
+    /// Returns `None` if it was not, or if it was tampered with.
+    pub fn decrypt(
+        &self,
+        peer: &ChannelPublicKey,
+        ciphertext: &ChannelCiphertext,
+        context: &[u8],
+    ) -> Option<[u8; 32]> {
+        let mut payload = ciphertext.payload;
+        symmetric::decrypt(
+            &self.derive_key(peer, &ciphertext.salt),
+            &mut payload,
+            context,
+            &ciphertext.tag,
+        )
+        .ok()?;
+        Some(payload)
+    }
+
+    fn derive_key(&self, peer: &ChannelPublicKey, salt: &[u8; 32]) -> [u8; symmetric::KEY_SIZE] {
+        let shared = G1Affine::from(peer.0 * self.0);
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        hasher.update(shared.to_compressed());
+        hasher.update(salt);
+        hasher.finalize().into()
+    }
+}
+
+/// A secret encrypted over a channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelCiphertext {
+    pub salt: [u8; 32],
+    pub payload: [u8; 32],
+    pub tag: [u8; symmetric::TAG_SIZE],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel() {
+        let (alice, bob, eve) = (
+            ChannelPrivateKey::generate(),
+            ChannelPrivateKey::generate(),
+            ChannelPrivateKey::generate(),
+        );
+        let secret = [7; 32];
+        let ciphertext = alice.encrypt(&bob.public_key(), &secret, b"context");
+        assert_ne!(ciphertext.payload, secret);
+        assert_eq!(
+            bob.decrypt(&alice.public_key(), &ciphertext, b"context"),
+            Some(secret)
+        );
+        // the sender can decrypt what it sent
+        assert_eq!(
+            alice.decrypt(&bob.public_key(), &ciphertext, b"context"),
+            Some(secret)
+        );
+
+        assert_eq!(
+            bob.decrypt(&alice.public_key(), &ciphertext, b"other"),
+            None
+        );
+        assert_eq!(
+            eve.decrypt(&alice.public_key(), &ciphertext, b"context"),
+            None
+        );
+        assert_eq!(
+            bob.decrypt(&eve.public_key(), &ciphertext, b"context"),
+            None
+        );
+        let mut tampered = ciphertext.clone();
+        tampered.payload[0] ^= 1;
+        assert_eq!(
+            bob.decrypt(&alice.public_key(), &tampered, b"context"),
+            None
+        );
+
+        // every message has its own key
+        let again = alice.encrypt(&bob.public_key(), &secret, b"context");
+        assert_ne!(again.payload, ciphertext.payload);
+    }
+
+    #[test]
+    fn test_decode_keys() {
+        let sk = ChannelPrivateKey::generate();
+        let pk = sk.public_key();
+        assert_eq!(ChannelPublicKey::try_from(pk.to_bytes()), Ok(pk));
+        assert_eq!(
+            ChannelPrivateKey::try_from_bytes(sk.to_bytes())
+                .unwrap()
+                .public_key(),
+            pk
+        );
+        assert_eq!(
+            ChannelPrivateKey::try_from_bytes([0; 32]).unwrap_err(),
+            Error::InvalidScalar
+        );
+        assert_eq!(
+            ChannelPublicKey::try_from(G1Affine::identity().to_compressed()),
+            Err(Error::InvalidPoint)
+        );
+    }
+}
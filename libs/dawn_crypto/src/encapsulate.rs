@@ -1,48 +1,89 @@
 use crate::hash_to_g1;
+use crate::Error;
 use group::Group;
 use ic_bls12_381::multi_miller_loop;
 use ic_bls12_381::pairing;
-use ic_bls12_381::{G1Affine, G2Affine, G2Prepared, Gt, Scalar};
+use ic_bls12_381::{G1Affine, G1Projective, G2Affine, G2Prepared, Gt, Scalar};
 
 use serde::{Deserialize, Serialize};
+use std::vec::Vec;
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-pub struct MasterPublicKey([u8; 96]);
+/// The master public key.
+///
+/// The key is kept decoded: deserializing and [`MasterPublicKey::try_from`] check that its bytes
+/// are a valid point, so that unpacking it does not fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "[u8; 96]", into = "[u8; 96]")]
+pub struct MasterPublicKey(pub(crate) G2Affine);
 
 impl MasterPublicKey {
     pub fn unpack(&self) -> G2Affine {
-        G2Affine::from_compressed(&self.0).unwrap()
+        self.0
     }
-    pub fn pack(e: &G2Affine) -> Self {
-        Self(e.to_compressed())
+    pub fn to_bytes(&self) -> [u8; 96] {
+        self.0.to_compressed()
+    }
+}
+
+impl TryFrom<[u8; 96]> for MasterPublicKey {
+    type Error = Error;
+
+    /// Decodes a master public key, failing unless it is a point of G2 other than the identity.
+    fn try_from(bytes: [u8; 96]) -> Result<Self, Error> {
+        unpack_g2(&bytes).map(Self)
+    }
+}
+
+impl From<MasterPublicKey> for [u8; 96] {
+    fn from(mpk: MasterPublicKey) -> Self {
+        mpk.to_bytes()
     }
 }
 
 #[derive(Debug)]
-pub struct MasterPrivateKey(Scalar);
+pub struct MasterPrivateKey(pub(crate) Scalar);
 
 impl MasterPrivateKey {
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0.to_bytes()
     }
 
-    pub fn from_bytes(bytes: [u8; 32]) -> Self {
-        Self(Scalar::from_bytes(&bytes).unwrap())
+    /// Decodes a master private key, failing if `bytes` is not a canonical scalar.
+    pub fn try_from_bytes(bytes: [u8; 32]) -> Result<Self, Error> {
+        Option::from(Scalar::from_bytes(&bytes))
+            .map(Self)
+            .ok_or(Error::InvalidScalar)
     }
 }
 
+/// The ephemeral public key of a ciphertext.
+///
+/// The bytes are public, so they may not be a valid point: deserializing and
+/// [`EphemeralPublicKey::try_from`] check them, and [`EphemeralPublicKey::try_unpack`] checks
+/// them again.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "[u8; 96]")]
 pub struct EphemeralPublicKey(pub [u8; 96]);
 
 impl EphemeralPublicKey {
-    pub fn unpack(&self) -> G2Affine {
-        G2Affine::from_compressed(&self.0).unwrap()
+    /// Decodes the key, failing unless it is a point of G2 other than the identity.
+    pub fn try_unpack(&self) -> Result<G2Affine, Error> {
+        unpack_g2(&self.0)
     }
     pub fn pack(e: &G2Affine) -> Self {
         Self(e.to_compressed())
     }
 }
 
+impl TryFrom<[u8; 96]> for EphemeralPublicKey {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 96]) -> Result<Self, Error> {
+        unpack_g2(&bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct SharedSecret(Gt);
 
@@ -52,26 +93,62 @@ impl SharedSecret {
     }
 }
 
+/// The decryption key of a label.
+///
+/// Like [`EphemeralPublicKey`], its bytes may not be a valid point.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "[u8; 48]")]
 pub struct DecryptionKey(pub [u8; 48]);
 
 impl DecryptionKey {
-    pub fn unpack(&self) -> G1Affine {
-        G1Affine::from_compressed(&self.0).unwrap()
+    /// Decodes the key, failing unless it is a point of G1 other than the identity.
+    pub fn try_unpack(&self) -> Result<G1Affine, Error> {
+        unpack_g1(&self.0)
     }
     pub fn pack(e: &G1Affine) -> Self {
         Self(e.to_compressed())
     }
 }
 
+impl TryFrom<[u8; 48]> for DecryptionKey {
+    type Error = Error;
+
+    fn try_from(bytes: [u8; 48]) -> Result<Self, Error> {
+        unpack_g1(&bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+// decompressing checks that the point is on the curve and in the prime-order subgroup, and the
+// identity is rejected since it would make the keys and shared secrets public
+pub(crate) fn unpack_g1(bytes: &[u8; 48]) -> Result<G1Affine, Error> {
+    Option::from(G1Affine::from_compressed(bytes))
+        .filter(|point: &G1Affine| !bool::from(point.is_identity()))
+        .ok_or(Error::InvalidPoint)
+}
+
+pub(crate) fn unpack_g2(bytes: &[u8; 96]) -> Result<G2Affine, Error> {
+    Option::from(G2Affine::from_compressed(bytes))
+        .filter(|point: &G2Affine| !bool::from(point.is_identity()))
+        .ok_or(Error::InvalidPoint)
+}
+
 pub fn generate() -> (MasterPublicKey, MasterPrivateKey) {
     let sk = random_scalar();
     let pk = G2Affine::generator() * sk;
-    (MasterPublicKey::pack(&pk.into()), MasterPrivateKey(sk))
+    (MasterPublicKey(pk.into()), MasterPrivateKey(sk))
 }
 
 pub fn share(label: &[u8], mpk: &MasterPublicKey) -> (EphemeralPublicKey, SharedSecret) {
-    let r = random_scalar();
+    share_with(label, mpk, &random_scalar())
+}
+
+/// Like [`share`], with the randomness `r` of the ephemeral public key chosen by the caller.
+pub(crate) fn share_with(
+    label: &[u8],
+    mpk: &MasterPublicKey,
+    r: &Scalar,
+) -> (EphemeralPublicKey, SharedSecret) {
     let u: G2Affine = (r * G2Affine::generator()).into();
     let s = pairing(&hash_to_g1::hash_to_g1(label), &mpk.unpack()) * r;
     (EphemeralPublicKey::pack(&u.into()), SharedSecret(s))
@@ -82,19 +159,69 @@ pub fn reveal(label: &[u8], sk: &MasterPrivateKey) -> DecryptionKey {
     DecryptionKey::pack(&dk.into())
 }
 
-pub fn recover(u: &EphemeralPublicKey, dk: &DecryptionKey) -> SharedSecret {
-    SharedSecret(pairing(&dk.unpack(), &u.unpack()))
+/// Recovers the shared secret of a ciphertext with ephemeral public key `u`, failing if `u` or
+/// `dk` is not a valid point.
+pub fn recover(u: &EphemeralPublicKey, dk: &DecryptionKey) -> Result<SharedSecret, Error> {
+    Ok(SharedSecret(pairing(&dk.try_unpack()?, &u.try_unpack()?)))
 }
 
+/// Returns `true` if `dk` is the decryption key of `label` under `mpk`, and `false` if it is
+/// another key or not a valid point.
 pub fn verify(label: &[u8], mpk: &MasterPublicKey, dk: &DecryptionKey) -> bool {
+    let Ok(dk) = dk.try_unpack() else {
+        return false;
+    };
     fast_pairing_equality(
         &hash_to_g1::hash_to_g1(label),
         &mpk.unpack(),
-        &dk.unpack(),
+        &dk,
         &G2Affine::generator(),
     )
 }
 
+/// Checks many decryption keys under `mpk` at once, and returns the indices of the `items` whose
+/// key is not the decryption key of their label, like [`verify`] would, if there are any.
+///
+/// The keys are checked together with a random linear combination, `e(Σ ρᵢ·H(labelᵢ), mpk) ==
+/// e(Σ ρᵢ·dkᵢ, g2)`, which costs one pairing check for the whole batch instead of one per key.
+/// An invalid key makes the check fail except with negligible probability, in which case every
+/// key is checked on its own to locate the invalid ones.
+pub fn verify_batch(
+    mpk: &MasterPublicKey,
+    items: &[(&[u8], &DecryptionKey)],
+) -> Result<(), Vec<usize>> {
+    let mut hashes = G1Projective::identity();
+    let mut keys = G1Projective::identity();
+    for (label, dk) in items {
+        let Ok(dk) = dk.try_unpack() else {
+            return Err(verify_each(mpk, items));
+        };
+        // the weights must be unknown to whoever chose the keys, or invalid keys could cancel
+        let rho = random_scalar();
+        hashes += hash_to_g1::hash_to_g1(label) * rho;
+        keys += dk * rho;
+    }
+    if fast_pairing_equality(
+        &hashes.into(),
+        &mpk.unpack(),
+        &keys.into(),
+        &G2Affine::generator(),
+    ) {
+        Ok(())
+    } else {
+        Err(verify_each(mpk, items))
+    }
+}
+
+fn verify_each(mpk: &MasterPublicKey, items: &[(&[u8], &DecryptionKey)]) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, (label, dk))| !verify(label, mpk, dk))
+        .map(|(index, _)| index)
+        .collect()
+}
+
 // yoinked from https://github.com/noislabs/drand-verify
 /// Checks if e(p, q) == e(r, s)
 ///
@@ -105,7 +232,12 @@ pub fn verify(label: &[u8], mpk: &MasterPublicKey, dk: &DecryptionKey) -> bool {
 ///   (which is costly is to multiply in G2 because these are very big numbers)
 ///   we can do FinalExponentiation(MillerLoop( [a,b], [-c,d] )) which is the same
 ///   in an optimized way.
-fn fast_pairing_equality(p: &G1Affine, q: &G2Affine, r: &G1Affine, s: &G2Affine) -> bool {
+pub(crate) fn fast_pairing_equality(
+    p: &G1Affine,
+    q: &G2Affine,
+    r: &G1Affine,
+    s: &G2Affine,
+) -> bool {
     let minus_p = -p;
     // "some number of (G1, G2) pairs" are the inputs of the miller loop
     let pair1 = (&minus_p, &G2Prepared::from(*q));
@@ -116,12 +248,12 @@ fn fast_pairing_equality(p: &G1Affine, q: &G2Affine, r: &G1Affine, s: &G2Affine)
 }
 
 #[cfg(feature = "mesalock_sgx")]
-fn random_scalar() -> Scalar {
+pub(crate) fn random_scalar() -> Scalar {
     let rng = sgx_rand::os::SgxRng::new().unwrap();
     Scalar::random(rng)
 }
 #[cfg(feature = "no_mesalock_sgx")]
-fn random_scalar() -> Scalar {
+pub(crate) fn random_scalar() -> Scalar {
     use group::ff::Field;
     let rng = rand::rngs::OsRng;
     Scalar::random(rng)
@@ -137,7 +269,7 @@ mod tests {
         let (mpk, msk) = generate();
         let (u, s) = share(label, &mpk);
         let dk = reveal(label, &msk);
-        let s_prime = recover(&u, &dk);
+        let s_prime = recover(&u, &dk).unwrap();
         assert_eq!(s, s_prime);
     }
 
@@ -147,5 +279,104 @@ mod tests {
         let (mpk, msk) = generate();
         let dk = reveal(label, &msk);
         assert!(verify(label, &mpk, &dk));
+        assert!(!verify(label, &mpk, &DecryptionKey([0xff; 48])));
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let (mpk, msk) = generate();
+        let labels: Vec<[u8; 1]> = (0..8).map(|i| [i]).collect();
+        let verify_keys = |keys: &[DecryptionKey]| {
+            let items: Vec<(&[u8], &DecryptionKey)> =
+                labels.iter().map(|label| &label[..]).zip(keys).collect();
+            verify_batch(&mpk, &items)
+        };
+        let valid: Vec<DecryptionKey> = labels.iter().map(|label| reveal(label, &msk)).collect();
+        assert_eq!(verify_batch(&mpk, &[]), Ok(()));
+        assert_eq!(verify_keys(&valid), Ok(()));
+
+        // keys of other labels, of another master key, and invalid points
+        let mut keys = valid.clone();
+        keys.swap(1, 2);
+        let (_, other_msk) = generate();
+        keys[5] = reveal(&labels[5], &other_msk);
+        keys[7] = DecryptionKey([0xff; 48]);
+        assert_eq!(verify_keys(&keys), Err(vec![1, 2, 5, 7]));
+
+        // keys whose errors cancel out in an unweighted sum
+        let mut keys = valid.clone();
+        let offset = G1Projective::generator();
+        let shift = |dk: &DecryptionKey, offset: G1Projective| {
+            DecryptionKey::pack(&(dk.try_unpack().unwrap() + offset).into())
+        };
+        keys[0] = shift(&valid[0], offset);
+        keys[1] = shift(&valid[1], -offset);
+        assert_eq!(verify_keys(&keys), Err(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_unpack() {
+        let (mpk, msk) = generate();
+        let (u, _) = share(b"test", &mpk);
+        let dk = reveal(b"test", &msk);
+        assert_eq!(MasterPublicKey::try_from(mpk.to_bytes()), Ok(mpk));
+        assert_eq!(EphemeralPublicKey::try_from(u.0), Ok(u.clone()));
+        assert_eq!(DecryptionKey::try_from(dk.0), Ok(dk.clone()));
+        assert_eq!(
+            MasterPrivateKey::try_from_bytes(msk.to_bytes()).map(|msk| msk.0),
+            Ok(msk.0)
+        );
+        assert_eq!(
+            MasterPrivateKey::try_from_bytes([0xff; 32]).map(|msk| msk.0),
+            Err(Error::InvalidScalar)
+        );
+
+        // not on the curve
+        let mut invalid = u.0;
+        invalid[95] ^= 1;
+        assert_eq!(
+            EphemeralPublicKey::try_from(invalid),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            EphemeralPublicKey(invalid).try_unpack(),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            DecryptionKey::try_from([0xff; 48]),
+            Err(Error::InvalidPoint)
+        );
+        // the identity
+        let mut identity = [0; 96];
+        identity[0] = 0xc0;
+        assert_eq!(
+            MasterPublicKey::try_from(identity),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            DecryptionKey::try_from(G1Affine::identity().to_compressed()),
+            Err(Error::InvalidPoint)
+        );
+        // on the curve but outside the prime-order subgroup
+        assert_eq!(
+            DecryptionKey::try_from(torsion_point()),
+            Err(Error::InvalidPoint)
+        );
+    }
+
+    /// Returns a point of G1 that is on the curve, but not in the prime-order subgroup.
+    fn torsion_point() -> [u8; 48] {
+        (0u8..)
+            .map(|x| {
+                let mut bytes = [0; 48];
+                bytes[0] = 0x80;
+                bytes[47] = x;
+                bytes
+            })
+            .find(|bytes| {
+                bool::from(G1Affine::from_compressed_unchecked(bytes).is_some())
+                    && bool::from(G1Affine::from_compressed(bytes).is_none())
+            })
+            .unwrap()
     }
 }
@@ -17,47 +17,127 @@ compile_error!(
     "feature \"no_mesalock_sgx\" and feature \"mesalock_sgx\" cannot be enabled at the same time"
 );
 
+pub mod channel;
 mod encapsulate;
 mod hash_to_g1;
+mod padding;
+mod proof;
 mod symmetric;
+pub mod threshold;
 
 use sha2::{Digest, Sha256};
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::vec::Vec;
 
 pub use encapsulate::*;
+pub use padding::{Padding, MAX_PADDING};
+pub use proof::CiphertextProof;
 
+use proof::Statement;
+
+/// Errors of decoding keys from bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The bytes are not the compressed encoding of a point of the prime-order subgroup, or they
+    /// encode its identity.
+    InvalidPoint,
+    /// The bytes are not the canonical encoding of a scalar.
+    InvalidScalar,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPoint => write!(f, "invalid curve point"),
+            Self::InvalidScalar => write!(f, "invalid scalar"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A ciphertext.
+///
+/// Version 1 ciphertexts are malleable: anyone can pair their ephemeral public key with another
+/// payload, label or associated data. Version 2 ciphertexts carry a [`CiphertextProof`] that binds
+/// the ephemeral public key to the rest of the ciphertext, its label and its associated data, see
+/// [`Ciphertext::verify_proof`]. Both versions decrypt the same way.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Ciphertext {
     pub u: EphemeralPublicKey,
     pub payload: Vec<u8>,
     pub tag: [u8; symmetric::TAG_SIZE],
+    /// The proof of a version 2 ciphertext, `None` for version 1.
+    pub proof: Option<CiphertextProof>,
 }
 
 impl Ciphertext {
+    /// Encrypts `payload` for `label` as a version 2 ciphertext, after padding it with zero bytes
+    /// according to `padding`.
     pub fn encrypt(
         mpk: &MasterPublicKey,
         label: &[u8],
         payload: &[u8],
         associated_data: &[u8],
+        padding: Padding,
     ) -> Self {
         let mut buf = payload.to_vec();
-        let (u, s) = encapsulate::share(label, mpk);
+        buf.resize(payload.len() + padding.padding(payload.len()), 0);
+        let r = encapsulate::random_scalar();
+        let (u, s) = encapsulate::share_with(label, mpk, &r);
         let k = derive_key(&s);
         let tag = symmetric::encrypt(&k, &mut buf, associated_data);
+        let proof = CiphertextProof::prove(
+            &r,
+            &Statement {
+                label,
+                associated_data,
+                u: &u,
+                payload: &buf,
+                tag: &tag,
+            },
+        );
         Self {
             u,
             payload: buf,
             tag,
+            proof: Some(proof),
+        }
+    }
+
+    /// Returns 2 if the ciphertext carries a proof, and 1 otherwise.
+    pub fn version(&self) -> u8 {
+        match self.proof {
+            Some(_) => 2,
+            None => 1,
         }
     }
 
+    /// Returns `true` if the ciphertext is a version 2 ciphertext whose proof holds for `label`
+    /// and `associated_data`, which means that it was encrypted for them as is by someone who
+    /// knows its randomness. Returns `false` for version 1 ciphertexts.
+    ///
+    /// The check is much cheaper than decrypting, and needs no key.
+    pub fn verify_proof(&self, label: &[u8], associated_data: &[u8]) -> bool {
+        self.proof.map_or(false, |proof| {
+            proof.verify(&Statement {
+                label,
+                associated_data,
+                u: &self.u,
+                payload: &self.payload,
+                tag: &self.tag,
+            })
+        })
+    }
+
     // decrypt the ciphertext with the given decryption key and associated data.
-    // will return None if the ciphertext fails authentication.
+    // will return None if the ciphertext fails authentication, or if `u` or `dk` is not a valid
+    // point.
     pub fn decrypt(&self, dk: &DecryptionKey, associated_data: &[u8]) -> Option<Vec<u8>> {
         let mut buf = self.payload.to_vec();
-        let s = encapsulate::recover(&self.u, dk);
+        let s = encapsulate::recover(&self.u, dk).ok()?;
         let k = derive_key(&s);
         match symmetric::decrypt(&k, &mut buf, associated_data, &self.tag) {
             Ok(()) => Some(buf),
@@ -65,21 +145,27 @@ impl Ciphertext {
         }
     }
 
+    /// Rebuilds the ciphertext with ephemeral public key `u` and proof `proof`, if any, that
+    /// decrypts to `payload` with `dk` and `associated_data`.
+    ///
+    /// The proof cannot be recomputed without the randomness of `u`, so it is taken as is.
     pub fn reencrypt(
         u: &EphemeralPublicKey,
+        proof: Option<CiphertextProof>,
         dk: &DecryptionKey,
         payload: &[u8],
         associated_data: &[u8],
-    ) -> Self {
+    ) -> Result<Self, Error> {
         let mut buf = payload.to_vec();
-        let s = encapsulate::recover(u, dk);
+        let s = encapsulate::recover(u, dk)?;
         let k = derive_key(&s);
         let tag = symmetric::encrypt(&k, &mut buf, associated_data);
-        Self {
+        Ok(Self {
             u: u.clone(),
             payload: buf,
             tag,
-        }
+            proof,
+        })
     }
 }
 
@@ -101,9 +187,113 @@ mod tests {
         let (mpk, msk) = generate();
         let payload = b"hello world";
         let ad = b"associated data";
-        let c = Ciphertext::encrypt(&mpk, label, payload, ad);
+        let c = Ciphertext::encrypt(&mpk, label, payload, ad, Padding::None);
         let dk = encapsulate::reveal(label, &msk);
         let decrypted = c.decrypt(&dk, ad).unwrap();
         assert_eq!(decrypted, payload);
     }
+
+    #[test]
+    fn test_encrypt_padded() {
+        let label = b"test";
+        let (mpk, msk) = generate();
+        let ad = b"associated data";
+        let c = Ciphertext::encrypt(&mpk, label, b"hello world", ad, Padding::Multiple(32));
+        assert_eq!(c.payload.len(), 32);
+        assert!(c.verify_proof(label, ad));
+
+        let dk = encapsulate::reveal(label, &msk);
+        let mut padded = b"hello world".to_vec();
+        padded.resize(32, 0);
+        assert_eq!(c.decrypt(&dk, ad).unwrap(), padded);
+        assert_eq!(
+            Ciphertext::reencrypt(&c.u, c.proof, &dk, &padded, ad),
+            Ok(c)
+        );
+    }
+
+    #[test]
+    fn test_decrypt_invalid_points() {
+        let label = b"test";
+        let (mpk, msk) = generate();
+        let c = Ciphertext::encrypt(&mpk, label, b"hello world", label, Padding::None);
+        let dk = encapsulate::reveal(label, &msk);
+        let invalid = Ciphertext {
+            u: EphemeralPublicKey([0xff; 96]),
+            ..c.clone()
+        };
+        assert_eq!(invalid.decrypt(&dk, label), None);
+        assert_eq!(c.decrypt(&DecryptionKey([0xff; 48]), label), None);
+        assert_eq!(
+            Ciphertext::reencrypt(&invalid.u, c.proof, &dk, b"hello world", label),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            Ciphertext::reencrypt(&c.u, c.proof, &dk, b"hello world", label),
+            Ok(c)
+        );
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let label = b"test";
+        let (mpk, msk) = generate();
+        let c = Ciphertext::encrypt(
+            &mpk,
+            label,
+            b"hello world",
+            b"associated data",
+            Padding::None,
+        );
+        assert_eq!(c.version(), 2);
+        assert!(c.verify_proof(label, b"associated data"));
+
+        // another label or associated data
+        assert!(!c.verify_proof(b"other", b"associated data"));
+        assert!(!c.verify_proof(label, b"other"));
+
+        // the ephemeral public key of the victim with another payload
+        let other = Ciphertext::encrypt(
+            &mpk,
+            b"other",
+            b"hello world",
+            b"associated data",
+            Padding::None,
+        );
+        let mauled = Ciphertext {
+            u: c.u.clone(),
+            ..other.clone()
+        };
+        assert!(!mauled.verify_proof(b"other", b"associated data"));
+        let mut mauled = c.clone();
+        mauled.payload[0] ^= 1;
+        assert!(!mauled.verify_proof(label, b"associated data"));
+        let mut mauled = c.clone();
+        mauled.tag[0] ^= 1;
+        assert!(!mauled.verify_proof(label, b"associated data"));
+
+        // malformed proofs
+        let mut proof = c.proof.unwrap();
+        proof.0[40] ^= 1;
+        let mauled = Ciphertext {
+            proof: Some(proof),
+            ..c.clone()
+        };
+        assert!(!mauled.verify_proof(label, b"associated data"));
+        let mauled = Ciphertext {
+            proof: Some(CiphertextProof([0xff; 64])),
+            ..c.clone()
+        };
+        assert!(!mauled.verify_proof(label, b"associated data"));
+
+        // version 1 ciphertexts carry no proof, but still decrypt
+        let v1 = Ciphertext {
+            proof: None,
+            ..c.clone()
+        };
+        assert_eq!(v1.version(), 1);
+        assert!(!v1.verify_proof(label, b"associated data"));
+        let dk = encapsulate::reveal(label, &msk);
+        assert_eq!(v1.decrypt(&dk, b"associated data").unwrap(), b"hello world");
+    }
 }
@@ -0,0 +1,56 @@
+//! Padding of payloads, which hides their exact length in ciphertexts.
+
+/// The most zero bytes a [`Padding`] appends to a payload.
+pub const MAX_PADDING: usize = 1 << 16;
+
+/// How [`Ciphertext::encrypt`](crate::Ciphertext::encrypt) pads a payload with zero bytes before
+/// encrypting it, so that the length of the ciphertext only reveals a bucket of payload lengths.
+///
+/// The padding is encrypted with the payload and kept by decryption. It can only be stripped
+/// unambiguously from a payload whose encoding delimits itself, like an RLP item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Padding {
+    /// No padding: the ciphertext has the length of the payload.
+    #[default]
+    None,
+    /// Pads to the next multiple of the given number of bytes.
+    Multiple(usize),
+    /// Pads to the next power of two, and to at least the given number of bytes.
+    PowerOfTwo(usize),
+}
+
+impl Padding {
+    /// Returns the number of zero bytes appended to a payload of `len` bytes.
+    ///
+    /// Payloads so long that reaching their bucket would take more than [`MAX_PADDING`] bytes
+    /// are only padded with [`MAX_PADDING`] bytes.
+    pub fn padding(&self, len: usize) -> usize {
+        let padded = match *self {
+            Self::None => None,
+            Self::Multiple(size) => len
+                .checked_add(size.saturating_sub(1))
+                .and_then(|len| len.checked_div(size))
+                .map(|blocks| blocks * size),
+            Self::PowerOfTwo(min) => len.max(min).checked_next_power_of_two(),
+        };
+        padded.map_or(0, |padded| (padded - len).min(MAX_PADDING))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padding() {
+        assert_eq!(Padding::None.padding(100), 0);
+        assert_eq!(Padding::Multiple(0).padding(100), 0);
+        assert_eq!(Padding::Multiple(1).padding(100), 0);
+        assert_eq!(Padding::Multiple(32).padding(96), 0);
+        assert_eq!(Padding::Multiple(32).padding(100), 28);
+        assert_eq!(Padding::PowerOfTwo(256).padding(100), 156);
+        assert_eq!(Padding::PowerOfTwo(64).padding(100), 28);
+        assert_eq!(Padding::PowerOfTwo(0).padding(1 << 20 | 1), MAX_PADDING);
+        assert_eq!(Padding::Multiple(usize::MAX).padding(usize::MAX - 1), 0);
+    }
+}
@@ -0,0 +1,585 @@
+//! t-of-n threshold sharing of the master key.
+//!
+//! The master private key is split into Shamir shares, one per member of the network. Each member
+//! reveals a decryption key share for a label with its private share, anyone can check that share
+//! against the member's public share, and any `threshold` valid shares recombine into the
+//! [`DecryptionKey`] that [`reveal`](crate::reveal) would have produced with the master key.
+
+use crate::encapsulate::{fast_pairing_equality, random_scalar, unpack_g2};
+use crate::hash_to_g1;
+use crate::{DecryptionKey, Error, MasterPrivateKey, MasterPublicKey};
+use ic_bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+use serde::{Deserialize, Serialize};
+use std::vec::Vec;
+
+/// The share of the master private key held by member `index`.
+#[derive(Debug)]
+pub struct PrivateKeyShare {
+    pub index: u32,
+    share: Scalar,
+}
+
+impl PrivateKeyShare {
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.share.to_bytes()
+    }
+
+    /// Decodes the share of member `index`, failing if `bytes` is not a canonical scalar, as when
+    /// it was received from another member.
+    pub fn try_from_bytes(index: u32, bytes: [u8; 32]) -> Result<Self, Error> {
+        Option::from(Scalar::from_bytes(&bytes))
+            .map(|share| Self { index, share })
+            .ok_or(Error::InvalidScalar)
+    }
+
+    /// Sums shares of the same member from several sharings into its share of the sum of their
+    /// secrets. Returns `None` if there are no shares or their indices differ.
+    pub fn sum(shares: &[PrivateKeyShare]) -> Option<Self> {
+        let weights: Vec<_> = shares.iter().map(|_| Scalar::one()).collect();
+        Self::linear_combination(shares.iter(), &weights)
+    }
+
+    /// Recombines the shares of the same member from the sharings of [`reshare`], by index of
+    /// their dealer, into its share of the secret the dealers shared. Returns `None` if there are
+    /// no shares, their indices differ, or a dealer index is 0 or repeated.
+    ///
+    /// The result is only a share of that secret if at least as many dealers as its threshold
+    /// dealt valid sharings.
+    pub fn interpolate(shares: &[(u32, PrivateKeyShare)]) -> Option<Self> {
+        let dealers: Vec<_> = shares.iter().map(|(dealer, _)| *dealer).collect();
+        Self::linear_combination(
+            shares.iter().map(|(_, share)| share),
+            &lagrange_coefficients(&dealers)?,
+        )
+    }
+
+    fn linear_combination<'a>(
+        shares: impl Iterator<Item = &'a PrivateKeyShare> + Clone,
+        weights: &[Scalar],
+    ) -> Option<Self> {
+        let index = shares.clone().next()?.index;
+        if shares.clone().any(|share| share.index != index) {
+            return None;
+        }
+        let share = shares
+            .zip(weights)
+            .fold(Scalar::zero(), |acc, (share, weight)| {
+                acc + share.share * weight
+            });
+        Some(Self { index, share })
+    }
+
+    /// Returns the public share matching this private share.
+    pub fn public_share(&self) -> PublicKeyShare {
+        PublicKeyShare::pack(self.index, &(G2Affine::generator() * self.share).into())
+    }
+}
+
+/// The share of the master public key of member `index`, which verifies its decryption key
+/// shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKeyShare {
+    pub index: u32,
+    key: [u8; 96],
+}
+
+impl PublicKeyShare {
+    /// Decodes the share, failing unless it is a point of G2 other than the identity.
+    pub fn try_unpack(&self) -> Result<G2Affine, Error> {
+        unpack_g2(&self.key)
+    }
+    pub fn pack(index: u32, e: &G2Affine) -> Self {
+        Self {
+            index,
+            key: e.to_compressed(),
+        }
+    }
+}
+
+/// A Feldman commitment to a sharing polynomial f, that is g2^a_k for each coefficient a_k of f.
+///
+/// It lets each member check its private share f(index) without learning anything about f(0),
+/// and derives the public share of every member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(pub Vec<[u8; 96]>);
+
+impl Commitment {
+    /// Returns the number of shares needed to recombine the committed secret.
+    pub fn threshold(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the commitment has `threshold` coefficients that are all valid points.
+    pub fn is_valid(&self, threshold: usize) -> bool {
+        self.threshold() == threshold && self.unpack().is_some()
+    }
+
+    /// Returns the public key of the committed secret, g2^f(0), or `None` if the commitment is
+    /// invalid or the secret is 0.
+    pub fn public_key(&self) -> Option<MasterPublicKey> {
+        self.unpack()?;
+        MasterPublicKey::try_from(*self.0.first()?).ok()
+    }
+
+    /// Returns the public share of member `index`, g2^f(index).
+    pub fn public_share(&self, index: u32) -> Option<PublicKeyShare> {
+        let x = Scalar::from(index as u64);
+        let pk = self
+            .unpack()?
+            .iter()
+            .rev()
+            .fold(G2Projective::identity(), |acc, point| acc * x + point);
+        Some(PublicKeyShare::pack(index, &pk.into()))
+    }
+
+    /// Returns `true` if `sk` is the share of member `sk.index` committed to.
+    pub fn verify_share(&self, sk: &PrivateKeyShare) -> bool {
+        self.public_share(sk.index) == Some(sk.public_share())
+    }
+
+    /// Returns `true` if the committed secret is the private share behind `pk`, as required of
+    /// the sharings of [`reshare`].
+    pub fn commits_to(&self, pk: &PublicKeyShare) -> bool {
+        self.public_share(0).map(|committed| committed.key) == Some(pk.key)
+    }
+
+    /// Sums commitments into the commitment to the sum of their polynomials, whose shares are the
+    /// [`PrivateKeyShare::sum`] of the shares of each. Returns `None` if there are no
+    /// commitments, their thresholds differ or one of them is invalid.
+    pub fn sum(commitments: &[Commitment]) -> Option<Self> {
+        let weights: Vec<_> = commitments.iter().map(|_| Scalar::one()).collect();
+        Self::linear_combination(commitments.iter(), &weights)
+    }
+
+    /// Recombines the commitments of the sharings of [`reshare`], by index of their dealer, into
+    /// the commitment to the new sharing, whose shares are the [`PrivateKeyShare::interpolate`]
+    /// of the shares of each. Returns `None` if there are no commitments, their thresholds
+    /// differ, one of them is invalid, or a dealer index is 0 or repeated.
+    pub fn interpolate(commitments: &[(u32, Commitment)]) -> Option<Self> {
+        let dealers: Vec<_> = commitments.iter().map(|(dealer, _)| *dealer).collect();
+        Self::linear_combination(
+            commitments.iter().map(|(_, commitment)| commitment),
+            &lagrange_coefficients(&dealers)?,
+        )
+    }
+
+    fn linear_combination<'a>(
+        commitments: impl Iterator<Item = &'a Commitment> + Clone,
+        weights: &[Scalar],
+    ) -> Option<Self> {
+        let threshold = commitments.clone().next()?.threshold();
+        let mut sum: Vec<_> = (0..threshold).map(|_| G2Projective::identity()).collect();
+        for (commitment, weight) in commitments.zip(weights) {
+            if commitment.threshold() != threshold {
+                return None;
+            }
+            for (acc, point) in sum.iter_mut().zip(commitment.unpack()?) {
+                *acc += point * weight;
+            }
+        }
+        Some(Self(
+            sum.into_iter()
+                .map(|point| G2Affine::from(point).to_compressed())
+                .collect(),
+        ))
+    }
+
+    fn unpack(&self) -> Option<Vec<G2Affine>> {
+        self.0
+            .iter()
+            .map(|point| Option::from(G2Affine::from_compressed(point)))
+            .collect()
+    }
+}
+
+/// A partial decryption key revealed by member `index`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DecryptionKeyShare {
+    pub index: u32,
+    pub key: DecryptionKey,
+}
+
+/// Splits `msk` into `members` shares such that any `threshold` of them recombine, returning the
+/// public and private share of each member. Members are indexed from 1 to `members`.
+///
+/// Panics unless `0 < threshold <= members`.
+pub fn split(
+    msk: &MasterPrivateKey,
+    threshold: usize,
+    members: u32,
+) -> (Vec<PublicKeyShare>, Vec<PrivateKeyShare>) {
+    let (_, private_shares) = share_secret(msk.0, threshold, members);
+    let public_shares = private_shares
+        .iter()
+        .map(PrivateKeyShare::public_share)
+        .collect();
+    (public_shares, private_shares)
+}
+
+/// Shares a fresh random secret among `members` such that any `threshold` of them recombine,
+/// returning the commitment to the sharing and the private share of each member. This is the
+/// contribution of a single dealer to a distributed key generation.
+///
+/// Panics unless `0 < threshold <= members`.
+pub fn deal(threshold: usize, members: u32) -> (Commitment, Vec<PrivateKeyShare>) {
+    share_secret(random_scalar(), threshold, members)
+}
+
+/// Shares the private share `sk` of an old committee among the `members` of a new committee such
+/// that any `threshold` of them recombine, returning the commitment to the sharing and the
+/// private share of each new member.
+///
+/// Once at least as many old members as the old threshold reshared, each new member recombines its
+/// share of the same master private key with [`PrivateKeyShare::interpolate`], and the shares of
+/// the old committee must be erased: they still recombine the master private key, but are
+/// unrelated to the new shares, so mixing old and new shares recombines nothing.
+///
+/// Panics unless `0 < threshold <= members`.
+pub fn reshare(
+    sk: &PrivateKeyShare,
+    threshold: usize,
+    members: u32,
+) -> (Commitment, Vec<PrivateKeyShare>) {
+    share_secret(sk.share, threshold, members)
+}
+
+// shares `secret` with a random polynomial f of degree threshold - 1 such that f(0) = secret
+fn share_secret(
+    secret: Scalar,
+    threshold: usize,
+    members: u32,
+) -> (Commitment, Vec<PrivateKeyShare>) {
+    assert!(
+        threshold > 0 && threshold <= members as usize,
+        "threshold must be between 1 and the number of members"
+    );
+    // f(x) = secret + a_1 x + ... + a_{t-1} x^{t-1}
+    let mut coefficients = Vec::with_capacity(threshold);
+    coefficients.push(secret);
+    coefficients.extend((1..threshold).map(|_| random_scalar()));
+
+    let commitment = Commitment(
+        coefficients
+            .iter()
+            .map(|coefficient| G2Affine::from(G2Affine::generator() * coefficient).to_compressed())
+            .collect(),
+    );
+    let private_shares = (1..=members)
+        .map(|index| PrivateKeyShare {
+            index,
+            share: evaluate(&coefficients, Scalar::from(index as u64)),
+        })
+        .collect();
+    (commitment, private_shares)
+}
+
+/// Reveals the decryption key share of member `sk.index` for `label`.
+pub fn reveal_share(label: &[u8], sk: &PrivateKeyShare) -> DecryptionKeyShare {
+    let dk = hash_to_g1::hash_to_g1(label) * sk.share;
+    DecryptionKeyShare {
+        index: sk.index,
+        key: DecryptionKey::pack(&dk.into()),
+    }
+}
+
+/// Checks that `dk` was revealed for `label` by the member owning `pk`, that is
+/// e(H(label), pk) == e(dk, g2). Invalid points never verify.
+pub fn verify_share(label: &[u8], pk: &PublicKeyShare, dk: &DecryptionKeyShare) -> bool {
+    let (Ok(pk_point), Ok(dk_point)) = (pk.try_unpack(), dk.key.try_unpack()) else {
+        return false;
+    };
+    pk.index == dk.index
+        && fast_pairing_equality(
+            &hash_to_g1::hash_to_g1(label),
+            &pk_point,
+            &dk_point,
+            &G2Affine::generator(),
+        )
+}
+
+/// Recombines decryption key shares into the full decryption key by Lagrange interpolation at 0.
+///
+/// The result is only the decryption key of the master key if at least `threshold` of the shares
+/// are valid, see [`verify_share`]. Returns `None` if there are no shares, if an index is 0 or
+/// repeated, or if a share is not a valid point.
+pub fn combine(shares: &[DecryptionKeyShare]) -> Option<DecryptionKey> {
+    let indices: Vec<_> = shares.iter().map(|share| share.index).collect();
+    let mut dk = G1Projective::identity();
+    for (share, lambda) in shares.iter().zip(lagrange_coefficients(&indices)?) {
+        dk += share.key.try_unpack().ok()? * lambda;
+    }
+    Some(DecryptionKey::pack(&G1Affine::from(dk)))
+}
+
+/// Recombines public key shares into the master public key, see [`combine`].
+pub fn combine_public(shares: &[PublicKeyShare]) -> Option<MasterPublicKey> {
+    let indices: Vec<_> = shares.iter().map(|share| share.index).collect();
+    let mut pk = G2Projective::identity();
+    for (share, lambda) in shares.iter().zip(lagrange_coefficients(&indices)?) {
+        pk += share.try_unpack().ok()? * lambda;
+    }
+    Some(G2Affine::from(pk))
+        .filter(|pk| !bool::from(pk.is_identity()))
+        .map(MasterPublicKey)
+}
+
+// evaluates the polynomial with the given coefficients, lowest degree first, at x
+fn evaluate(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, coefficient| acc * x + coefficient)
+}
+
+// the Lagrange coefficients at 0 of the given distinct, non-zero indices:
+// lambda_i = prod_{j != i} x_j / (x_j - x_i)
+fn lagrange_coefficients(indices: &[u32]) -> Option<Vec<Scalar>> {
+    if indices.is_empty() || indices.contains(&0) {
+        return None;
+    }
+    indices
+        .iter()
+        .enumerate()
+        .map(|(i, &xi)| {
+            let xi = Scalar::from(xi as u64);
+            let (numerator, denominator) = indices
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &xj)| Scalar::from(xj as u64))
+                .fold((Scalar::one(), Scalar::one()), |(num, den), xj| {
+                    (num * xj, den * (xj - xi))
+                });
+            // a zero denominator means a repeated index
+            Option::from(denominator.invert()).map(|inverse: Scalar| numerator * inverse)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, reveal, verify};
+
+    #[test]
+    fn test_combine() {
+        let label = b"test";
+        let (mpk, msk) = generate();
+        let (public_shares, private_shares) = split(&msk, 3, 5);
+        let shares: Vec<_> = private_shares
+            .iter()
+            .map(|sk| reveal_share(label, sk))
+            .collect();
+        for (pk, dk) in public_shares.iter().zip(&shares) {
+            assert!(verify_share(label, pk, dk));
+        }
+
+        let expected = reveal(label, &msk);
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset: Vec<_> = subset.iter().map(|&i| shares[i].clone()).collect();
+            let dk = combine(&subset).unwrap();
+            assert_eq!(dk, expected);
+            assert!(verify(label, &mpk, &dk));
+        }
+        assert_eq!(combine_public(&public_shares[1..4]), Some(mpk));
+
+        // fewer than threshold shares do not recombine
+        let dk = combine(&shares[..2]).unwrap();
+        assert!(!verify(label, &mpk, &dk));
+    }
+
+    #[test]
+    fn test_verify_share() {
+        let label = b"test";
+        let (_, msk) = generate();
+        let (public_shares, private_shares) = split(&msk, 2, 3);
+        let dk = reveal_share(label, &private_shares[0]);
+        assert!(verify_share(label, &public_shares[0], &dk));
+        assert!(!verify_share(b"other", &public_shares[0], &dk));
+        assert!(!verify_share(label, &public_shares[1], &dk));
+
+        // a share revealed by another member under a forged index
+        let forged = DecryptionKeyShare {
+            index: 1,
+            ..reveal_share(label, &private_shares[1])
+        };
+        assert!(!verify_share(label, &public_shares[0], &forged));
+    }
+
+    #[test]
+    fn test_combine_invalid_indices() {
+        let label = b"test";
+        let (_, msk) = generate();
+        let (_, private_shares) = split(&msk, 2, 3);
+        let dk = reveal_share(label, &private_shares[0]);
+        assert_eq!(combine(&[]), None);
+        assert_eq!(combine(&[dk.clone(), dk.clone()]), None);
+        assert_eq!(
+            combine(&[DecryptionKeyShare {
+                index: 0,
+                ..dk.clone()
+            }]),
+            None
+        );
+        let invalid = DecryptionKeyShare {
+            index: 2,
+            key: DecryptionKey([0xff; 48]),
+        };
+        assert_eq!(combine(&[dk, invalid]), None);
+    }
+
+    #[test]
+    fn test_commitment() {
+        let label = b"test";
+        let dealings: Vec<_> = (0..3).map(|_| deal(2, 3)).collect();
+        for (commitment, shares) in &dealings {
+            assert!(commitment.is_valid(2));
+            for sk in shares {
+                assert!(commitment.verify_share(sk));
+            }
+        }
+        let (commitment, shares) = &dealings[0];
+        let forged = PrivateKeyShare::try_from_bytes(2, shares[0].to_bytes()).unwrap();
+        assert!(!commitment.verify_share(&forged));
+
+        // the sum of the dealings is a sharing of the sum of their secrets
+        let commitments: Vec<_> = dealings.iter().map(|(c, _)| c.clone()).collect();
+        let commitment = Commitment::sum(&commitments).unwrap();
+        let mpk = commitment.public_key().unwrap();
+        let shares: Vec<_> = (0..3)
+            .map(|i| {
+                let shares: Vec<_> = dealings
+                    .iter()
+                    .map(|(_, shares)| {
+                        PrivateKeyShare::try_from_bytes(shares[i].index, shares[i].to_bytes())
+                            .unwrap()
+                    })
+                    .collect();
+                PrivateKeyShare::sum(&shares).unwrap()
+            })
+            .collect();
+        for sk in &shares {
+            assert!(commitment.verify_share(sk));
+            assert_eq!(commitment.public_share(sk.index), Some(sk.public_share()));
+        }
+        let dk = combine(&[
+            reveal_share(label, &shares[0]),
+            reveal_share(label, &shares[2]),
+        ])
+        .unwrap();
+        assert!(verify(label, &mpk, &dk));
+
+        assert_eq!(Commitment::sum(&[]), None);
+        assert_eq!(
+            Commitment::sum(&[commitments[0].clone(), deal(1, 3).0]),
+            None
+        );
+        assert!(!Commitment(vec![[0xff; 96]]).is_valid(1));
+    }
+
+    /// Reshares the `shares` of the old committee dealt by `dealers` into `threshold`-of-`members`
+    /// shares, returning the new commitment and shares.
+    fn reshare_epoch(
+        old: &Commitment,
+        shares: &[PrivateKeyShare],
+        dealers: &[usize],
+        threshold: usize,
+        members: u32,
+    ) -> (Commitment, Vec<PrivateKeyShare>) {
+        let dealings: Vec<_> = dealers
+            .iter()
+            .map(|&i| {
+                let (commitment, subshares) = reshare(&shares[i], threshold, members);
+                assert!(commitment.commits_to(&old.public_share(shares[i].index).unwrap()));
+                (shares[i].index, commitment, subshares)
+            })
+            .collect();
+        let commitments: Vec<_> = dealings
+            .iter()
+            .map(|(dealer, commitment, _)| (*dealer, commitment.clone()))
+            .collect();
+        let commitment = Commitment::interpolate(&commitments).unwrap();
+        let shares = (0..members as usize)
+            .map(|j| {
+                let subshares: Vec<_> = dealings
+                    .iter()
+                    .map(|(dealer, commitment, subshares)| {
+                        assert!(commitment.verify_share(&subshares[j]));
+                        let subshare = &subshares[j];
+                        (
+                            *dealer,
+                            PrivateKeyShare::try_from_bytes(subshare.index, subshare.to_bytes())
+                                .unwrap(),
+                        )
+                    })
+                    .collect();
+                PrivateKeyShare::interpolate(&subshares).unwrap()
+            })
+            .collect();
+        (commitment, shares)
+    }
+
+    #[test]
+    fn test_reshare() {
+        let label = b"test";
+        let (mpk, msk) = generate();
+        let (commitment0, shares0) = share_secret(msk.0, 2, 3);
+
+        // 2-of-3 to 3-of-4, by old members 1 and 3
+        let (commitment1, shares1) = reshare_epoch(&commitment0, &shares0, &[0, 2], 3, 4);
+        // 3-of-4 to 2-of-2, by new members 2, 3 and 4
+        let (commitment2, shares2) = reshare_epoch(&commitment1, &shares1, &[1, 2, 3], 2, 2);
+
+        for (commitment, shares, threshold) in [
+            (&commitment0, &shares0, 2),
+            (&commitment1, &shares1, 3),
+            (&commitment2, &shares2, 2),
+        ] {
+            assert_eq!(commitment.public_key(), Some(mpk));
+            let dks: Vec<_> = shares.iter().map(|sk| reveal_share(label, sk)).collect();
+            for (sk, dk) in shares.iter().zip(&dks) {
+                assert!(commitment.verify_share(sk));
+                assert!(verify_share(
+                    label,
+                    &commitment.public_share(sk.index).unwrap(),
+                    dk
+                ));
+            }
+            for window in dks.windows(threshold) {
+                assert!(verify(label, &mpk, &combine(window).unwrap()));
+            }
+        }
+
+        // old shares do not mix with new ones
+        let mixed = [
+            reveal_share(label, &shares0[0]),
+            reveal_share(label, &shares1[1]),
+            reveal_share(label, &shares1[2]),
+        ];
+        assert!(!verify(label, &mpk, &combine(&mixed).unwrap()));
+        assert!(!commitment1.verify_share(&shares0[0]));
+
+        // a resharing must commit to the share of its dealer
+        let (commitment, _) = reshare(&shares0[0], 3, 4);
+        assert!(!commitment.commits_to(&shares0[1].public_share()));
+        assert!(PrivateKeyShare::interpolate(&[]).is_none());
+        assert_eq!(
+            Commitment::interpolate(&[(1, commitment.clone()), (1, commitment)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_private_share_roundtrip() {
+        let (_, msk) = generate();
+        let (public_shares, private_shares) = split(&msk, 1, 2);
+        let sk = &private_shares[1];
+        let sk = PrivateKeyShare::try_from_bytes(sk.index, sk.to_bytes()).unwrap();
+        assert_eq!(sk.public_share(), public_shares[1]);
+        assert_eq!(
+            PrivateKeyShare::try_from_bytes(1, [0xff; 32]).map(|sk| sk.index),
+            Err(Error::InvalidScalar)
+        );
+    }
+}
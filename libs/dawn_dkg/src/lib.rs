@@ -0,0 +1,1072 @@
+//! Dealerless distributed key generation for the secret management committee (SMC).
+//!
+//! This is the Joint-Feldman protocol of Pedersen with complaints: every member deals a random
+//! secret with [`dawn_crypto::threshold::deal`], and the master private key is the sum of the
+//! secrets of the qualified dealers, which no member ever learns. It runs in three synchronous
+//! rounds:
+//!
+//! 1. every member broadcasts the commitment to its polynomial and sends each other member its
+//!    share;
+//! 2. every member broadcasts a complaint against each dealer whose share does not match its
+//!    commitment;
+//! 3. every dealer answers each complaint against it by broadcasting the disputed share.
+//!
+//! A dealer is disqualified if its commitment is missing or malformed, or if it fails to answer
+//! a complaint with a valid share. Since broadcasts reach all members identically, honest members
+//! agree on the qualified dealers, and thus on the master public key.
+//!
+//! The same rounds reshare the key to a new committee without changing the master public key
+//! (Desmedt-Jajodia): the dealers are the members of the old committee, each sharing its own share
+//! with a commitment to the matching public share, and each new member interpolates its share from
+//! those of at least the old threshold of qualified dealers.
+//!
+//! Secret shares only leave the state machine encrypted for the enclaves of their recipients, with
+//! the channel keys of the participants that the caller attested before starting, see
+//! [`dawn_crypto::channel`]. Justifications are encrypted for every participant, so that all of
+//! them can check the disclosed shares.
+//!
+//! [`Dkg`] is a pure state machine: the caller delivers the messages of each round with
+//! [`Dkg::handle`], and ends it with [`Dkg::end_round`] once its deadline has passed.
+
+#![cfg_attr(feature = "mesalock_sgx", no_std)]
+#![cfg_attr(
+    all(target_env = "sgx", target_vendor = "mesalock"),
+    feature(rustc_private)
+)]
+
+#[cfg(feature = "mesalock_sgx")]
+extern crate sgx_tstd as std;
+
+use dawn_crypto::channel::{ChannelPrivateKey, ChannelPublicKey};
+use dawn_crypto::threshold::{deal, Commitment, PrivateKeyShare};
+use dawn_enclave_protocol::{Committee, DkgMessage, DkgPayload, MasterPublicKey};
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DkgError {
+    /// The threshold is not between 1 and the number of members, the member is not one of them,
+    /// or the channel keys do not match the participants.
+    InvalidParameters,
+    /// The message was sent by, or to, a member outside the committee.
+    UnknownMember(u32),
+    /// The sender already sent a message of this kind.
+    DuplicateMessage(u32),
+    /// The sender sent a message that does not belong to the current round.
+    UnexpectedMessage(u32),
+    /// The sender sent a share that does not decrypt with the channel keys.
+    UndecryptableShare(u32),
+    /// Fewer than `threshold` dealers are qualified, so the key may be known to a coalition.
+    NotEnoughQualifiedDealers(usize),
+    /// The key generation already completed.
+    Finished,
+}
+
+impl fmt::Display for DkgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParameters => write!(f, "invalid committee parameters"),
+            Self::UnknownMember(index) => write!(f, "unknown member {index}"),
+            Self::DuplicateMessage(index) => write!(f, "duplicate message from member {index}"),
+            Self::UnexpectedMessage(index) => write!(f, "unexpected message from member {index}"),
+            Self::UndecryptableShare(index) => {
+                write!(f, "undecryptable share from member {index}")
+            }
+            Self::NotEnoughQualifiedDealers(count) => {
+                write!(f, "only {count} dealers are qualified")
+            }
+            Self::Finished => write!(f, "key generation already finished"),
+        }
+    }
+}
+
+impl std::error::Error for DkgError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum Round {
+    Deal,
+    Complain,
+    Justify,
+    Done,
+}
+
+/// The result of a successful key generation or resharing for one member.
+#[derive(Debug)]
+pub struct DkgOutput {
+    pub mpk: MasterPublicKey,
+    /// The committee that was formed.
+    pub committee: Committee,
+    /// The share of the master private key of this member.
+    pub share: PrivateKeyShare,
+    /// The dealers whose sharings make up the shares of the committee.
+    pub qualified: Vec<u32>,
+}
+
+/// What to do after a round ended.
+#[derive(Debug)]
+pub enum Step {
+    /// Send these messages, and deliver the messages of the next round.
+    Continue(Vec<DkgMessage>),
+    /// The key generation or resharing completed.
+    Done(DkgOutput),
+    /// The resharing completed, and this dealer is not a member of the new committee.
+    Retired,
+}
+
+/// The key generation or resharing state of one participant, see the
+/// [crate documentation](crate).
+///
+/// It holds secret shares, so it must only leave the enclave sealed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dkg {
+    /// The index of this participant as a dealer, if it deals.
+    dealer: Option<u32>,
+    /// The index of this participant in the committee being formed, if it is a member.
+    me: Option<u32>,
+    threshold: u32,
+    members: u32,
+    /// The committee whose shares are reshared, or `None` to generate a fresh key.
+    previous: Option<Committee>,
+    /// The private channel key of this participant.
+    channel: [u8; 32],
+    /// The public channel keys of the dealers followed by those of the members when resharing,
+    /// or of the members otherwise.
+    keys: Vec<ChannelPublicKey>,
+    round: Round,
+    /// The shares this participant dealt, by index of the recipient, to answer complaints.
+    dealt: Vec<[u8; 32]>,
+    /// The commitments of the dealers, including this participant.
+    commitments: BTreeMap<u32, Commitment>,
+    /// The shares of this member from each dealer.
+    shares: BTreeMap<u32, [u8; 32]>,
+    /// The members complaining against each dealer.
+    complaints: BTreeMap<u32, BTreeSet<u32>>,
+    /// The shares disclosed by each dealer, by complainer.
+    justifications: BTreeMap<u32, BTreeMap<u32, [u8; 32]>>,
+    disqualified: BTreeSet<u32>,
+}
+
+impl Dkg {
+    /// Starts the key generation as member `me` of a `threshold`-of-`members` committee, whose
+    /// members have the public channel keys `keys` by index, returning the messages of the first
+    /// round.
+    pub fn new(
+        me: u32,
+        threshold: u32,
+        members: u32,
+        channel: &ChannelPrivateKey,
+        keys: Vec<ChannelPublicKey>,
+    ) -> Result<(Self, Vec<DkgMessage>), DkgError> {
+        if threshold == 0
+            || threshold > members
+            || me == 0
+            || me > members
+            || keys.len() != members as usize
+            || keys[me as usize - 1] != channel.public_key()
+        {
+            return Err(DkgError::InvalidParameters);
+        }
+        let dealing = deal(threshold as usize, members);
+        Ok(Self::start(
+            Some((me, dealing)),
+            Some(me),
+            threshold,
+            members,
+            None,
+            channel,
+            keys,
+        ))
+    }
+
+    /// Starts handing the shares of the `previous` committee over to a new `threshold`-of-`members`
+    /// committee, as the holder of `share` in the previous committee, if any, and member `me` of
+    /// the new one, if any. `keys` are the public channel keys of the previous members followed by
+    /// those of the new members. Returns the messages of the first round.
+    ///
+    /// The master public key stays the same. The dealers are the members of the previous
+    /// committee, at least `previous.threshold` of which must deal honestly.
+    pub fn reshare(
+        previous: Committee,
+        share: Option<&PrivateKeyShare>,
+        me: Option<u32>,
+        threshold: u32,
+        members: u32,
+        channel: &ChannelPrivateKey,
+        keys: Vec<ChannelPublicKey>,
+    ) -> Result<(Self, Vec<DkgMessage>), DkgError> {
+        let dealers = previous.public_shares.len() as u32;
+        let own_key =
+            |position: u32| keys.get(position as usize - 1) == Some(&channel.public_key());
+        let valid = threshold > 0
+            && threshold <= members
+            && previous.threshold > 0
+            && previous.threshold <= dealers
+            && (1..=dealers)
+                .zip(&previous.public_shares)
+                .all(|(index, pk)| pk.index == index)
+            && (share.is_some() || me.is_some())
+            && share.map_or(true, |share| {
+                (1..=dealers).contains(&share.index)
+                    && previous.public_shares[share.index as usize - 1] == share.public_share()
+            })
+            && me.map_or(true, |me| (1..=members).contains(&me))
+            && keys.len() == (dealers + members) as usize
+            && share.map_or(true, |share| own_key(share.index))
+            && me.map_or(true, |me| own_key(dealers + me));
+        if !valid {
+            return Err(DkgError::InvalidParameters);
+        }
+        let dealing = share.map(|share| {
+            (
+                share.index,
+                dawn_crypto::threshold::reshare(share, threshold as usize, members),
+            )
+        });
+        Ok(Self::start(
+            dealing,
+            me,
+            threshold,
+            members,
+            Some(previous),
+            channel,
+            keys,
+        ))
+    }
+
+    fn start(
+        dealing: Option<(u32, (Commitment, Vec<PrivateKeyShare>))>,
+        me: Option<u32>,
+        threshold: u32,
+        members: u32,
+        previous: Option<Committee>,
+        channel: &ChannelPrivateKey,
+        keys: Vec<ChannelPublicKey>,
+    ) -> (Self, Vec<DkgMessage>) {
+        let mut dkg = Self {
+            dealer: None,
+            me,
+            threshold,
+            members,
+            previous,
+            channel: channel.to_bytes(),
+            keys,
+            round: Round::Deal,
+            dealt: Vec::new(),
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            complaints: BTreeMap::new(),
+            justifications: BTreeMap::new(),
+            disqualified: BTreeSet::new(),
+        };
+        let mut messages = Vec::new();
+        if let Some((dealer, (commitment, shares))) = dealing {
+            messages.push(DkgMessage {
+                from: dealer,
+                payload: DkgPayload::Deal(commitment.clone()),
+            });
+            for share in &shares {
+                if Some(share.index) == me {
+                    dkg.shares.insert(dealer, share.to_bytes());
+                } else {
+                    let context = share_context(dkg.epoch(), dealer, share.index);
+                    messages.push(DkgMessage {
+                        from: dealer,
+                        payload: DkgPayload::Share {
+                            to: share.index,
+                            share: channel.encrypt(
+                                dkg.member_key(share.index),
+                                &share.to_bytes(),
+                                &context,
+                            ),
+                        },
+                    });
+                }
+            }
+            dkg.dealer = Some(dealer);
+            dkg.dealt = shares.iter().map(PrivateKeyShare::to_bytes).collect();
+            dkg.commitments.insert(dealer, commitment);
+        }
+        (dkg, messages)
+    }
+
+    /// Delivers a message of the current round sent by another participant.
+    ///
+    /// An error means the message is invalid and was dropped; the key generation can go on.
+    pub fn handle(&mut self, message: DkgMessage) -> Result<(), DkgError> {
+        let from = message.from;
+        let (senders, own) = match message.payload {
+            DkgPayload::Complaint { .. } => (self.members, self.me),
+            _ => (self.dealers(), self.dealer),
+        };
+        if from == 0 || from > senders || Some(from) == own {
+            return Err(DkgError::UnknownMember(from));
+        }
+        match (self.round, message.payload) {
+            (Round::Deal, DkgPayload::Deal(commitment)) => {
+                if self.commitments.contains_key(&from) {
+                    return Err(DkgError::DuplicateMessage(from));
+                }
+                self.commitments.insert(from, commitment);
+            }
+            (Round::Deal, DkgPayload::Share { to, share }) => {
+                if Some(to) != self.me {
+                    return Err(DkgError::UnknownMember(to));
+                }
+                if self.shares.contains_key(&from) {
+                    return Err(DkgError::DuplicateMessage(from));
+                }
+                let share = self
+                    .channel()
+                    .decrypt(
+                        self.dealer_key(from),
+                        &share,
+                        &share_context(self.epoch(), from, to),
+                    )
+                    .ok_or(DkgError::UndecryptableShare(from))?;
+                self.shares.insert(from, share);
+            }
+            (Round::Complain, DkgPayload::Complaint { dealer }) => {
+                if dealer == 0 || dealer > self.dealers() {
+                    return Err(DkgError::UnknownMember(dealer));
+                }
+                if !self.complaints.entry(dealer).or_default().insert(from) {
+                    return Err(DkgError::DuplicateMessage(from));
+                }
+            }
+            (Round::Justify, DkgPayload::Justification { complainer, shares }) => {
+                let complained = self
+                    .complaints
+                    .get(&from)
+                    .map_or(false, |complainers| complainers.contains(&complainer));
+                if !complained || shares.len() != self.keys.len() {
+                    return Err(DkgError::UnexpectedMessage(from));
+                }
+                let position = self.own_position();
+                let share = self
+                    .channel()
+                    .decrypt(
+                        self.dealer_key(from),
+                        &shares[position],
+                        &justification_context(self.epoch(), from, complainer, position),
+                    )
+                    .ok_or(DkgError::UndecryptableShare(from))?;
+                let justifications = self.justifications.entry(from).or_default();
+                if justifications.insert(complainer, share).is_some() {
+                    return Err(DkgError::DuplicateMessage(from));
+                }
+            }
+            (Round::Done, _) => return Err(DkgError::Finished),
+            _ => return Err(DkgError::UnexpectedMessage(from)),
+        }
+        Ok(())
+    }
+
+    /// Ends the current round, returning the messages of the next one, or the output of the key
+    /// generation after the last round.
+    pub fn end_round(&mut self) -> Result<Step, DkgError> {
+        let messages = match self.round {
+            Round::Deal => {
+                self.round = Round::Complain;
+                self.complain()
+            }
+            Round::Complain => {
+                self.round = Round::Justify;
+                self.justify()
+            }
+            Round::Justify => {
+                self.round = Round::Done;
+                return self.finish();
+            }
+            Round::Done => return Err(DkgError::Finished),
+        };
+        Ok(Step::Continue(messages))
+    }
+
+    // the number of dealers: the previous committee when resharing, everyone otherwise
+    fn dealers(&self) -> u32 {
+        self.previous
+            .as_ref()
+            .map_or(self.members, |previous| previous.public_shares.len() as u32)
+    }
+
+    fn complain(&mut self) -> Vec<DkgMessage> {
+        let mut messages = Vec::new();
+        for dealer in 1..=self.dealers() {
+            let Some(commitment) = self.commitments.get(&dealer).filter(|commitment| {
+                // a resharing must commit to the previous share of its dealer
+                commitment.is_valid(self.threshold as usize)
+                    && self.previous.as_ref().map_or(true, |previous| {
+                        commitment.commits_to(&previous.public_shares[dealer as usize - 1])
+                    })
+            }) else {
+                // everyone saw the same broadcast, so there is nothing to complain about
+                self.disqualified.insert(dealer);
+                continue;
+            };
+            let Some(me) = self.me else {
+                continue;
+            };
+            let valid = self
+                .share_of(me, dealer)
+                .map_or(false, |share| commitment.verify_share(&share));
+            if !valid {
+                self.complaints.entry(dealer).or_default().insert(me);
+                messages.push(DkgMessage {
+                    from: me,
+                    payload: DkgPayload::Complaint { dealer },
+                });
+            }
+        }
+        messages
+    }
+
+    fn justify(&mut self) -> Vec<DkgMessage> {
+        let Some(dealer) = self.dealer else {
+            return Vec::new();
+        };
+        let complainers = self.complaints.get(&dealer).cloned().unwrap_or_default();
+        let channel = self.channel();
+        complainers
+            .into_iter()
+            .map(|complainer| {
+                let share = self.dealt[complainer as usize - 1];
+                self.justifications
+                    .entry(dealer)
+                    .or_default()
+                    .insert(complainer, share);
+                let shares = self
+                    .keys
+                    .iter()
+                    .enumerate()
+                    .map(|(position, key)| {
+                        let context =
+                            justification_context(self.epoch(), dealer, complainer, position);
+                        channel.encrypt(key, &share, &context)
+                    })
+                    .collect();
+                DkgMessage {
+                    from: dealer,
+                    payload: DkgPayload::Justification { complainer, shares },
+                }
+            })
+            .collect()
+    }
+
+    fn finish(&mut self) -> Result<Step, DkgError> {
+        for (&dealer, complainers) in &self.complaints {
+            let Some(commitment) = self.commitments.get(&dealer) else {
+                continue;
+            };
+            let justifications = self.justifications.get(&dealer);
+            for &complainer in complainers {
+                let justified = justifications
+                    .and_then(|justifications| justifications.get(&complainer))
+                    .filter(|&&share| {
+                        PrivateKeyShare::try_from_bytes(complainer, share)
+                            .map_or(false, |share| commitment.verify_share(&share))
+                    });
+                match justified {
+                    // the disclosed share replaces the one that did not match
+                    Some(&share) if Some(complainer) == self.me => {
+                        self.shares.insert(dealer, share);
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.disqualified.insert(dealer);
+                    }
+                }
+            }
+        }
+
+        let qualified: Vec<_> = (1..=self.dealers())
+            .filter(|dealer| !self.disqualified.contains(dealer))
+            .collect();
+        // with fewer honest dealers than the threshold, a coalition may know the secret
+        let needed = self
+            .previous
+            .as_ref()
+            .map_or(self.threshold, |previous| previous.threshold);
+        if qualified.len() < needed as usize {
+            return Err(DkgError::NotEnoughQualifiedDealers(qualified.len()));
+        }
+        let Some(me) = self.me else {
+            return Ok(Step::Retired);
+        };
+
+        let dealings: Vec<_> = qualified
+            .iter()
+            .map(|&dealer| {
+                let share = self
+                    .share_of(me, dealer)
+                    .expect("shares of qualified dealers are valid");
+                (dealer, self.commitments[&dealer].clone(), share)
+            })
+            .collect();
+        let (commitment, share) = if self.previous.is_some() {
+            let commitments: Vec<_> = dealings
+                .iter()
+                .map(|(dealer, c, _)| (*dealer, c.clone()))
+                .collect();
+            let shares: Vec<_> = dealings
+                .into_iter()
+                .map(|(dealer, _, share)| (dealer, share))
+                .collect();
+            (
+                Commitment::interpolate(&commitments),
+                PrivateKeyShare::interpolate(&shares),
+            )
+        } else {
+            let commitments: Vec<_> = dealings.iter().map(|(_, c, _)| c.clone()).collect();
+            let shares: Vec<_> = dealings.into_iter().map(|(_, _, share)| share).collect();
+            (Commitment::sum(&commitments), PrivateKeyShare::sum(&shares))
+        };
+        let commitment = commitment.expect("commitments are valid");
+        let share = share.expect("shares are of this member");
+
+        Ok(Step::Done(DkgOutput {
+            mpk: commitment.public_key().expect("commitment is valid"),
+            committee: Committee {
+                epoch: self.epoch(),
+                threshold: self.threshold,
+                public_shares: (1..=self.members)
+                    .map(|index| commitment.public_share(index).expect("commitment is valid"))
+                    .collect(),
+            },
+            share,
+            qualified,
+        }))
+    }
+
+    /// Returns the epoch of the committee being formed, the one after the epoch of the previous
+    /// committee when resharing.
+    pub fn epoch(&self) -> u64 {
+        self.previous
+            .as_ref()
+            .map_or(0, |previous| previous.epoch + 1)
+    }
+
+    fn channel(&self) -> ChannelPrivateKey {
+        ChannelPrivateKey::try_from_bytes(self.channel).expect("channel key is valid")
+    }
+
+    fn dealer_key(&self, dealer: u32) -> &ChannelPublicKey {
+        &self.keys[dealer as usize - 1]
+    }
+
+    fn member_key(&self, member: u32) -> &ChannelPublicKey {
+        &self.keys[self.member_position(member)]
+    }
+
+    // the dealers come first in the channel keys when resharing, and are the members otherwise
+    fn member_position(&self, member: u32) -> usize {
+        let dealers = match self.previous {
+            Some(_) => self.dealers(),
+            None => 0,
+        };
+        (dealers + member) as usize - 1
+    }
+
+    // the position of one of the channel keys of this participant, which all participants
+    // encrypt justifications for
+    fn own_position(&self) -> usize {
+        match (self.me, self.dealer) {
+            (Some(me), _) => self.member_position(me),
+            (None, Some(dealer)) => dealer as usize - 1,
+            (None, None) => unreachable!("participants deal or are members"),
+        }
+    }
+
+    // the share of member `me` dealt by `dealer`, if it was received and is a scalar
+    fn share_of(&self, me: u32, dealer: u32) -> Option<PrivateKeyShare> {
+        PrivateKeyShare::try_from_bytes(me, *self.shares.get(&dealer)?).ok()
+    }
+}
+
+// binds an encrypted share to the committee being formed, its dealer and its recipient
+fn share_context(epoch: u64, dealer: u32, to: u32) -> Vec<u8> {
+    let mut context = b"share".to_vec();
+    context.extend_from_slice(&epoch.to_le_bytes());
+    context.extend_from_slice(&dealer.to_le_bytes());
+    context.extend_from_slice(&to.to_le_bytes());
+    context
+}
+
+// binds a disclosed share to the committee being formed, its dealer, its complainer and the
+// position of the channel key it is encrypted for
+fn justification_context(epoch: u64, dealer: u32, complainer: u32, position: usize) -> Vec<u8> {
+    let mut context = b"justification".to_vec();
+    context.extend_from_slice(&epoch.to_le_bytes());
+    context.extend_from_slice(&dealer.to_le_bytes());
+    context.extend_from_slice(&complainer.to_le_bytes());
+    context.extend_from_slice(&(position as u32).to_le_bytes());
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dawn_crypto::channel::ChannelCiphertext;
+    use dawn_crypto::threshold::{combine, reveal_share, verify_share};
+    use dawn_crypto::verify;
+
+    /// Tampers with the messages a participant sends in a round: `(round, participant,
+    /// messages)`, where participants are numbered from 1 in the order they were started.
+    type Adversary = dyn Fn(usize, u32, &mut Vec<DkgMessage>);
+
+    /// Runs the rounds among simulated participants over a reliable broadcast network, returning
+    /// the final step of each.
+    fn run_rounds(
+        mut nodes: Vec<Dkg>,
+        mut outgoing: Vec<Vec<DkgMessage>>,
+        adversary: &Adversary,
+    ) -> Vec<Result<Step, DkgError>> {
+        for round in 0.. {
+            for (participant, messages) in (1..).zip(&mut outgoing) {
+                adversary(round, participant, messages);
+            }
+            for (sender, messages) in outgoing.drain(..).enumerate() {
+                for message in messages {
+                    for (receiver, node) in nodes.iter_mut().enumerate() {
+                        let delivered = match message.recipient() {
+                            Some(to) => node.me == Some(to),
+                            None => receiver != sender,
+                        };
+                        if delivered {
+                            node.handle(message.clone()).unwrap();
+                        }
+                    }
+                }
+            }
+            let steps: Vec<_> = nodes.iter_mut().map(Dkg::end_round).collect();
+            if round == 2 {
+                return steps;
+            }
+            outgoing = steps
+                .into_iter()
+                .map(|step| match step.unwrap() {
+                    Step::Continue(messages) => messages,
+                    _ => panic!("key generation finished early"),
+                })
+                .collect();
+        }
+        unreachable!()
+    }
+
+    /// The channel key of a simulated participant, numbered from 1.
+    fn channel_key(participant: u32) -> ChannelPrivateKey {
+        let mut bytes = [0; 32];
+        bytes[..4].copy_from_slice(&participant.to_le_bytes());
+        ChannelPrivateKey::try_from_bytes(bytes).unwrap()
+    }
+
+    /// Makes the sender of a secret encrypt another one in its place, as a malicious dealer
+    /// would.
+    fn tamper(
+        sender: u32,
+        recipient: &ChannelPublicKey,
+        ciphertext: &mut ChannelCiphertext,
+        context: &[u8],
+    ) {
+        let sender = channel_key(sender);
+        let mut secret = sender.decrypt(recipient, ciphertext, context).unwrap();
+        secret[0] ^= 1;
+        *ciphertext = sender.encrypt(recipient, &secret, context);
+    }
+
+    fn done(step: Result<Step, DkgError>) -> Result<DkgOutput, DkgError> {
+        match step? {
+            Step::Done(output) => Ok(output),
+            step => panic!("unexpected step {step:?}"),
+        }
+    }
+
+    /// Runs the key generation among `members` simulated members, returning the result of each.
+    fn run(
+        threshold: u32,
+        members: u32,
+        adversary: &Adversary,
+    ) -> Vec<Result<DkgOutput, DkgError>> {
+        let keys: Vec<_> = (1..=members)
+            .map(|me| channel_key(me).public_key())
+            .collect();
+        let (nodes, outgoing) = (1..=members)
+            .map(|me| Dkg::new(me, threshold, members, &channel_key(me), keys.clone()).unwrap())
+            .unzip();
+        run_rounds(nodes, outgoing, adversary)
+            .into_iter()
+            .map(done)
+            .collect()
+    }
+
+    /// The channel keys of a resharing among `participants`: those of the dealers by share index,
+    /// followed by those of the members.
+    fn reshare_keys(
+        participants: &[(Option<PrivateKeyShare>, Option<u32>)],
+        dealers: u32,
+        members: u32,
+    ) -> Vec<ChannelPublicKey> {
+        let key_of = |matches: &dyn Fn(&(Option<PrivateKeyShare>, Option<u32>)) -> bool| {
+            let participant = participants.iter().position(matches).unwrap() as u32 + 1;
+            channel_key(participant).public_key()
+        };
+        let dealer_keys = (1..=dealers).map(|dealer| {
+            key_of(&|(share, _)| share.as_ref().map(|share| share.index) == Some(dealer))
+        });
+        let member_keys = (1..=members).map(|member| key_of(&|(_, me)| *me == Some(member)));
+        dealer_keys.chain(member_keys).collect()
+    }
+
+    /// Reshares the key of the `previous` committee to a `threshold`-of-`members` committee.
+    /// Each participant holds the share of `previous`, if any, and is the given member of the
+    /// new committee, if any.
+    fn run_reshare(
+        previous: &Committee,
+        participants: Vec<(Option<PrivateKeyShare>, Option<u32>)>,
+        threshold: u32,
+        members: u32,
+        adversary: &Adversary,
+    ) -> Vec<Result<Step, DkgError>> {
+        let keys = reshare_keys(&participants, previous.public_shares.len() as u32, members);
+        let (nodes, outgoing) = (1..)
+            .zip(participants)
+            .map(|(participant, (share, me))| {
+                let channel = channel_key(participant);
+                Dkg::reshare(
+                    previous.clone(),
+                    share.as_ref(),
+                    me,
+                    threshold,
+                    members,
+                    &channel,
+                    keys.clone(),
+                )
+                .unwrap()
+            })
+            .unzip();
+        run_rounds(nodes, outgoing, adversary)
+    }
+
+    fn honest(_: usize, _: u32, _: &mut Vec<DkgMessage>) {}
+
+    /// Checks that the members agree on the key, and that any `threshold` of their reveals
+    /// recombine into a valid decryption key.
+    fn check(threshold: u32, outputs: &[DkgOutput], qualified: &[u32]) {
+        let label = b"test";
+        let mpk = outputs[0].mpk;
+        for output in outputs {
+            assert_eq!(output.mpk, mpk);
+            assert_eq!(output.committee, outputs[0].committee);
+            assert_eq!(output.committee.threshold, threshold);
+            assert_eq!(output.qualified, qualified);
+            assert_eq!(
+                output.committee.public_shares[output.share.index as usize - 1],
+                output.share.public_share()
+            );
+        }
+        let shares: Vec<_> = outputs
+            .iter()
+            .map(|output| reveal_share(label, &output.share))
+            .collect();
+        for (dk, output) in shares.iter().zip(outputs) {
+            assert!(verify_share(
+                label,
+                &output.committee.public_shares[dk.index as usize - 1],
+                dk
+            ));
+        }
+        for window in shares.windows(threshold as usize) {
+            let dk = combine(window).unwrap();
+            assert!(verify(label, &mpk, &dk));
+        }
+    }
+
+    #[test]
+    fn test_honest() {
+        let outputs: Vec<_> = run(3, 5, &honest).into_iter().map(Result::unwrap).collect();
+        check(3, &outputs, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_justified_complaint() {
+        // member 2 sends member 4 a wrong share, then discloses the right one
+        let outputs: Vec<_> = run(2, 4, &|round, me, messages| {
+            if round == 0 && me == 2 {
+                for message in messages {
+                    if let DkgPayload::Share { to: 4, share } = &mut message.payload {
+                        let recipient = channel_key(4).public_key();
+                        tamper(2, &recipient, share, &share_context(0, 2, 4));
+                    }
+                }
+            }
+        })
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+        check(2, &outputs, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_malicious_dealers() {
+        let outputs: Vec<_> = run(2, 5, &|round, me, messages| match (round, me) {
+            // member 1 sends member 3 a wrong share, and does not justify it
+            (0, 1) => {
+                for message in messages.iter_mut() {
+                    if let DkgPayload::Share { to: 3, share } = &mut message.payload {
+                        let recipient = channel_key(3).public_key();
+                        tamper(1, &recipient, share, &share_context(0, 1, 3));
+                    }
+                }
+            }
+            (2, 1) => messages.clear(),
+            // member 2 justifies a complaint with another wrong share
+            (0, 2) => {
+                for message in messages.iter_mut() {
+                    if let DkgPayload::Share { to: 5, share } = &mut message.payload {
+                        let recipient = channel_key(5).public_key();
+                        tamper(2, &recipient, share, &share_context(0, 2, 5));
+                    }
+                }
+            }
+            (2, 2) => {
+                for message in messages.iter_mut() {
+                    if let DkgPayload::Justification { complainer, shares } = &mut message.payload {
+                        for (position, share) in shares.iter_mut().enumerate() {
+                            let recipient = channel_key(position as u32 + 1).public_key();
+                            let context = justification_context(0, 2, *complainer, position);
+                            tamper(2, &recipient, share, &context);
+                        }
+                    }
+                }
+            }
+            // member 3 does not deal
+            (0, 3) => messages.clear(),
+            _ => {}
+        })
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+        // the malicious members 1 to 3 may disagree
+        check(2, &outputs[3..], &[4, 5]);
+    }
+
+    #[test]
+    fn test_malformed_commitment() {
+        // member 1 commits to a polynomial of the wrong degree
+        let outputs: Vec<_> = run(3, 4, &|round, me, messages| {
+            if round == 0 && me == 1 {
+                let (commitment, _) = deal(2, 4);
+                messages[0].payload = DkgPayload::Deal(commitment);
+            }
+        })
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+        check(3, &outputs[1..], &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_not_enough_qualified_dealers() {
+        // members 1 and 2 do not deal, leaving fewer than 3 qualified dealers
+        let results = run(3, 4, &|round, me, messages| {
+            if round == 0 && me <= 2 {
+                messages.clear();
+            }
+        });
+        for result in &results[2..] {
+            assert_eq!(
+                result.as_ref().unwrap_err(),
+                &DkgError::NotEnoughQualifiedDealers(2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reshare() {
+        let label = b"test";
+        let epoch0: Vec<_> = run(2, 3, &honest).into_iter().map(Result::unwrap).collect();
+        let mpk = epoch0[0].mpk;
+        let committee0 = epoch0[0].committee.clone();
+        assert_eq!(committee0.epoch, 0);
+        let old_dk = reveal_share(label, &epoch0[0].share);
+
+        // 2-of-3 to 3-of-4: old member 1 retires, old members 2 and 3 become new members 1 and 2
+        let mut participants: Vec<_> = epoch0
+            .into_iter()
+            .zip([None, Some(1), Some(2)])
+            .map(|(output, me)| (Some(output.share), me))
+            .collect();
+        participants.extend([(None, Some(3)), (None, Some(4))]);
+        let mut steps = run_reshare(&committee0, participants, 3, 4, &honest).into_iter();
+        assert!(matches!(steps.next(), Some(Ok(Step::Retired))));
+        let epoch1: Vec<_> = steps.map(done).map(Result::unwrap).collect();
+        check(3, &epoch1, &[1, 2, 3]);
+        assert_eq!(epoch1[0].mpk, mpk);
+        assert_eq!(epoch1[0].committee.epoch, 1);
+
+        // old shares do not mix with new ones
+        let mixed = [
+            old_dk,
+            reveal_share(label, &epoch1[1].share),
+            reveal_share(label, &epoch1[2].share),
+        ];
+        assert!(!verify(label, &mpk, &combine(&mixed).unwrap()));
+
+        // 3-of-4 to 2-of-2 by the same members, while dealer 4 sends member 1 a wrong share and
+        // does not justify it
+        let committee1 = epoch1[0].committee.clone();
+        let participants = epoch1
+            .into_iter()
+            .zip([Some(1), Some(2), None, None])
+            .map(|(output, me)| (Some(output.share), me))
+            .collect();
+        let steps = run_reshare(
+            &committee1,
+            participants,
+            2,
+            2,
+            &|round, participant, messages| {
+                if participant == 4 {
+                    match round {
+                        0 => {
+                            for message in messages.iter_mut() {
+                                if let DkgPayload::Share { to: 1, share } = &mut message.payload {
+                                    let recipient = channel_key(1).public_key();
+                                    tamper(4, &recipient, share, &share_context(2, 4, 1));
+                                }
+                            }
+                        }
+                        2 => messages.clear(),
+                        _ => {}
+                    }
+                }
+            },
+        );
+        let mut steps = steps.into_iter();
+        let epoch2: Vec<_> = steps
+            .by_ref()
+            .take(2)
+            .map(done)
+            .map(Result::unwrap)
+            .collect();
+        assert!(steps.all(|step| matches!(step, Ok(Step::Retired))));
+        check(2, &epoch2, &[1, 2, 3]);
+        assert_eq!(epoch2[0].mpk, mpk);
+        assert_eq!(epoch2[0].committee.epoch, 2);
+    }
+
+    #[test]
+    fn test_reshare_wrong_share() {
+        // dealer 1 reshares a secret other than its share
+        let epoch0: Vec<_> = run(2, 2, &honest).into_iter().map(Result::unwrap).collect();
+        let committee0 = epoch0[0].committee.clone();
+        let keys: Vec<_> = (1..=4).map(|key| channel_key(key).public_key()).collect();
+
+        // a channel key that is not the one of the share
+        assert_eq!(
+            Dkg::reshare(
+                committee0.clone(),
+                Some(&epoch0[0].share),
+                None,
+                2,
+                2,
+                &channel_key(2),
+                keys.clone()
+            )
+            .unwrap_err(),
+            DkgError::InvalidParameters
+        );
+
+        let participants = epoch0
+            .into_iter()
+            .zip([Some(1), Some(2)])
+            .map(|(output, me)| (Some(output.share), me))
+            .collect();
+        let results = run_reshare(
+            &committee0,
+            participants,
+            2,
+            2,
+            &|round, participant, messages| {
+                if round == 0 && participant == 1 {
+                    let (commitment, _) = deal(2, 2);
+                    messages[0].payload = DkgPayload::Deal(commitment);
+                }
+            },
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap_err(),
+            &DkgError::NotEnoughQualifiedDealers(1)
+        );
+
+        // a share that is not in the previous committee
+        let (_, shares) = deal(2, 2);
+        assert_eq!(
+            Dkg::reshare(
+                committee0,
+                Some(&shares[0]),
+                None,
+                2,
+                2,
+                &channel_key(1),
+                keys
+            )
+            .unwrap_err(),
+            DkgError::InvalidParameters
+        );
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        let keys: Vec<_> = (1..=3).map(|me| channel_key(me).public_key()).collect();
+        let (mut dkg, _) = Dkg::new(1, 2, 3, &channel_key(1), keys.clone()).unwrap();
+        let (_, messages) = Dkg::new(2, 2, 3, &channel_key(2), keys.clone()).unwrap();
+        let deal = messages[0].clone();
+        assert_eq!(
+            Dkg::new(4, 2, 3, &channel_key(4), keys.clone()).unwrap_err(),
+            DkgError::InvalidParameters
+        );
+        // the channel key of member 1 is not the attested one
+        assert_eq!(
+            Dkg::new(1, 2, 3, &channel_key(2), keys.clone()).unwrap_err(),
+            DkgError::InvalidParameters
+        );
+        assert_eq!(
+            Dkg::new(1, 2, 3, &channel_key(1), keys[..2].to_vec()).unwrap_err(),
+            DkgError::InvalidParameters
+        );
+        assert_eq!(
+            dkg.handle(DkgMessage {
+                from: 4,
+                ..deal.clone()
+            }),
+            Err(DkgError::UnknownMember(4))
+        );
+        assert_eq!(dkg.handle(deal.clone()), Ok(()));
+        assert_eq!(dkg.handle(deal), Err(DkgError::DuplicateMessage(2)));
+        // the share of member 3 is not for member 1
+        assert_eq!(
+            dkg.handle(messages[2].clone()),
+            Err(DkgError::UnknownMember(3))
+        );
+        // the share of member 1 is not encrypted for its channel key
+        let mut share = messages[1].clone();
+        if let DkgPayload::Share { share, .. } = &mut share.payload {
+            *share = channel_key(2).encrypt(
+                &channel_key(4).public_key(),
+                &[1; 32],
+                &share_context(0, 2, 1),
+            );
+        }
+        assert_eq!(dkg.handle(share), Err(DkgError::UndecryptableShare(2)));
+        assert_eq!(
+            dkg.handle(DkgMessage {
+                from: 2,
+                payload: DkgPayload::Complaint { dealer: 3 }
+            }),
+            Err(DkgError::UnexpectedMessage(2))
+        );
+    }
+}
@@ -9,8 +9,11 @@ extern crate sgx_tstd as std;
 
 use serde::{Deserialize, Serialize};
 
+use std::string::String;
 use std::vec::Vec;
 
+pub use dawn_crypto::channel::{ChannelCiphertext, ChannelPublicKey};
+pub use dawn_crypto::threshold::{Commitment, DecryptionKeyShare, PublicKeyShare};
 pub use dawn_crypto::{DecryptionKey, EphemeralPublicKey, MasterPublicKey};
 
 pub type Label = Vec<u8>;
@@ -18,14 +21,339 @@ pub type Label = Vec<u8>;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SealedMasterPrivateKey(pub Vec<u8>);
 
+/// The share of the master private key of a member of the SMC, sealed by its enclave along with
+/// the epoch of its committee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SealedKeyShare(pub Vec<u8>);
+
+/// The trusted starting point of the light client of an enclave: a canonical block of the chain,
+/// and the Dawn delay of the chain.
+///
+/// The operator sets it up along with the authorities of the chain when a key is created, and the
+/// enclave seals both next to the key, so that the host can not move them afterwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Anchor {
+    pub number: u64,
+    pub hash: [u8; 32],
+    pub delay: u64,
+}
+
+impl Anchor {
+    /// The length of [`Anchor::to_bytes`].
+    pub const LEN: usize = 48;
+
+    /// Encodes the anchor as its number, hash and delay, the numbers in little-endian.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..8].copy_from_slice(&self.number.to_le_bytes());
+        bytes[8..40].copy_from_slice(&self.hash);
+        bytes[40..].copy_from_slice(&self.delay.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes an anchor encoded by [`Anchor::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            number: u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            hash: bytes[8..40].try_into().unwrap(),
+            delay: u64::from_le_bytes(bytes[40..].try_into().unwrap()),
+        })
+    }
+
+    /// Encodes the anchor followed by the authorities of its chain, which the enclave seals next
+    /// to the keys it creates.
+    pub fn to_bytes_with_authorities(&self, authorities: &[[u8; 20]]) -> Vec<u8> {
+        let mut bytes = self.to_bytes().to_vec();
+        for authority in authorities {
+            bytes.extend_from_slice(authority);
+        }
+        bytes
+    }
+
+    /// Decodes an anchor and authorities encoded by [`Anchor::to_bytes_with_authorities`].
+    pub fn from_bytes_with_authorities(bytes: &[u8]) -> Option<(Self, Vec<[u8; 20]>)> {
+        if bytes.len() < Self::LEN || (bytes.len() - Self::LEN) % 20 != 0 {
+            return None;
+        }
+        let (anchor, authorities) = bytes.split_at(Self::LEN);
+        let authorities = authorities
+            .chunks(20)
+            .map(|authority| authority.try_into().unwrap())
+            .collect();
+        Some((Self::from_bytes(anchor)?, authorities))
+    }
+}
+
+/// A block of the chain trusted by the light client of an enclave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub number: u64,
+    pub hash: [u8; 32],
+}
+
+/// The light client of an enclave, sealed by it, along with its checkpoint for the host to build
+/// proofs from.
+///
+/// The enclave only trusts the sealed copy of the checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SealedLightClient {
+    pub checkpoint: Checkpoint,
+    pub sealed: Vec<u8>,
+}
+
+/// Evidence that the shadow block of a block is canonical and `delay` blocks deep.
+///
+/// `headers` are the RLP encoded headers of the blocks following the checkpoint of the light
+/// client, in order, and `shadow` is the RLP encoded shadow block committed to by
+/// `headers[block]`. The shadow block is deep enough if `headers` go on to block
+/// `block + delay - 1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShadowProof {
+    pub headers: Vec<Vec<u8>>,
+    pub block: u32,
+    pub shadow: Vec<u8>,
+}
+
+/// The private channel key of an enclave, sealed by it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SealedChannelKey(pub Vec<u8>);
+
+/// The state of an ongoing distributed key generation, sealed by the enclave running it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SealedDkgState(pub Vec<u8>);
+
+/// A committee of the SMC: its epoch, the number of members needed to reveal a decryption key, and
+/// the public shares of all members by increasing index, starting from 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Committee {
+    /// The number of resharings since the master key was generated, so 0 for the committee that
+    /// generated it. Enclaves refuse the key shares of committees older than the latest they saw.
+    pub epoch: u64,
+    pub threshold: u32,
+    pub public_shares: Vec<PublicKeyShare>,
+}
+
+/// A message of the distributed key generation or resharing, sent by dealer `from`, or for
+/// complaints by member `from` of the committee being formed.
+///
+/// Dealers and members are indexed from 1. The transport must authenticate `from`, and deliver
+/// broadcast messages identically to all participants.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgMessage {
+    pub from: u32,
+    pub payload: DkgPayload,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DkgPayload {
+    /// Broadcast: the commitment to the polynomial dealt by the sender.
+    Deal(Commitment),
+    /// Direct: the share of member `to` of the polynomial dealt by the sender, encrypted for the
+    /// enclave of `to`.
+    Share { to: u32, share: ChannelCiphertext },
+    /// Broadcast: the sender did not receive a share from `dealer` matching its commitment.
+    Complaint { dealer: u32 },
+    /// Broadcast: the dealer's answer to a complaint, disclosing the share of `complainer` to the
+    /// enclaves of all participants, encrypted for each channel key of the run in order.
+    Justification {
+        complainer: u32,
+        shares: Vec<ChannelCiphertext>,
+    },
+}
+
+impl DkgMessage {
+    /// Returns the recipient of a direct message, or `None` for a broadcast.
+    pub fn recipient(&self) -> Option<u32> {
+        match self.payload {
+            DkgPayload::Share { to, .. } => Some(to),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Request {
-    Generate(),
-    Reveal(Label, SealedMasterPrivateKey),
+    /// Generates a master private key bound to the anchor of its chain, and starts a light client
+    /// at the anchor that only accepts headers sealed by one of the authorities, of which there
+    /// must be at least one. The key only reveals decryption keys with that light client, and can
+    /// not be bound to another anchor or other authorities afterwards.
+    Generate(Anchor, Vec<[u8; 20]>),
+    /// Moves the checkpoint of a light client along the headers following it, up to the parent of
+    /// the deepest block whose shadow block they prove.
+    AdvanceHeaders(SealedLightClient, Vec<Vec<u8>>),
+    /// Reveals the decryption key of the encrypted transaction at index `u32` of a shadow block,
+    /// if the proof shows that it is canonical on the chain of the light client of the key.
+    Reveal(ShadowProof, u32, SealedLightClient, SealedMasterPrivateKey),
+    /// Reveals the decryption keys of many transactions of a shadow block, unsealing the master
+    /// private key and verifying the proof once.
+    RevealBatch(
+        ShadowProof,
+        Vec<u32>,
+        SealedLightClient,
+        SealedMasterPrivateKey,
+    ),
+    /// Generates the channel key of the enclave. Its public key must be attested to the enclaves of
+    /// the other participants of a distributed key generation before it starts.
+    ChannelKey(),
+    /// Starts a distributed key generation as member `me` of a `threshold`-of-`members` SMC, with
+    /// the given channel key. `keys` are the attested public channel keys of the members, by
+    /// index: the secret shares they exchange are only encrypted for them. The resulting key share
+    /// is bound to `anchor` and `authorities`, like a key from `Generate`.
+    DkgStart {
+        me: u32,
+        threshold: u32,
+        members: u32,
+        channel: SealedChannelKey,
+        keys: Vec<ChannelPublicKey>,
+        anchor: Anchor,
+        authorities: Vec<[u8; 20]>,
+    },
+    /// Starts handing the shares of the `old` committee over to a new `threshold`-of-`members`
+    /// committee without changing the master public key, as the holder of `share` in the old
+    /// committee, if any, and member `me` of the new one, if any. `keys` are the attested public
+    /// channel keys of the old members followed by those of the new members, and the new share is
+    /// bound to `anchor` and `authorities`, like `DkgStart`.
+    ReshareStart {
+        old: Committee,
+        share: Option<SealedKeyShare>,
+        me: Option<u32>,
+        threshold: u32,
+        members: u32,
+        channel: SealedChannelKey,
+        keys: Vec<ChannelPublicKey>,
+        anchor: Anchor,
+        authorities: Vec<[u8; 20]>,
+    },
+    /// Delivers the messages received during the current round of a distributed key generation
+    /// or resharing, and ends the round.
+    DkgRound(SealedDkgState, Vec<DkgMessage>),
+    /// Reveals the decryption key share of a transaction of a shadow block with the given key
+    /// share, like `Reveal`.
+    RevealShare(ShadowProof, u32, SealedLightClient, SealedKeyShare),
+    /// Reveals the decryption key of the block committing to a shadow block, for the chain of id
+    /// `u64` whose ciphertexts are encrypted for the block including them, if the proof shows that
+    /// the shadow block is canonical on the chain of the light client of the key.
+    RevealBlock(ShadowProof, u64, SealedLightClient, SealedMasterPrivateKey),
+    /// Reveals the decryption key share of the block committing to a shadow block with the given
+    /// key share, like `RevealBlock`.
+    RevealBlockShare(ShadowProof, u64, SealedLightClient, SealedKeyShare),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Response {
-    Generate(MasterPublicKey, SealedMasterPrivateKey),
+    /// The master public key, the master private key sealed along with its anchor and
+    /// authorities, and the light client starting at the anchor.
+    Generate(MasterPublicKey, SealedMasterPrivateKey, SealedLightClient),
+    /// The light client with its new checkpoint.
+    AdvanceHeaders(SealedLightClient),
     Reveal(DecryptionKey),
+    /// The decryption keys of the transactions of a batch, in order, or why each could not be
+    /// revealed.
+    RevealBatch(Vec<Result<DecryptionKey, String>>),
+    /// The public channel key of the enclave, and its sealed private key.
+    ChannelKey(ChannelPublicKey, SealedChannelKey),
+    /// The state of the distributed key generation, with the messages to send in the next round.
+    DkgRound(SealedDkgState, Vec<DkgMessage>),
+    /// The distributed key generation or resharing completed: the master public key, the new
+    /// committee, the share of this member sealed along with its anchor and authorities, and the
+    /// light client starting at the anchor.
+    DkgDone(
+        MasterPublicKey,
+        Committee,
+        SealedKeyShare,
+        SealedLightClient,
+    ),
+    /// The resharing completed, and this member is not part of the new committee. Its old share
+    /// must be erased.
+    DkgRetired,
+    /// The distributed key generation or resharing failed.
+    DkgFailed(String),
+    RevealShare(DecryptionKeyShare),
+    /// The enclave refused the request, for example because the light client is not the one of
+    /// the key or the proof is invalid.
+    Refused(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_bytes_round_trip() {
+        let anchor = Anchor {
+            number: 7,
+            hash: [0xab; 32],
+            delay: 2,
+        };
+        assert_eq!(Anchor::from_bytes(&anchor.to_bytes()), Some(anchor));
+        assert_eq!(Anchor::from_bytes(&anchor.to_bytes()[1..]), None);
+
+        let authorities = vec![[1; 20], [2; 20]];
+        let bytes = anchor.to_bytes_with_authorities(&authorities);
+        assert_eq!(
+            Anchor::from_bytes_with_authorities(&bytes),
+            Some((anchor, authorities))
+        );
+        assert_eq!(Anchor::from_bytes_with_authorities(&bytes[1..]), None);
+        assert_eq!(
+            Anchor::from_bytes_with_authorities(&anchor.to_bytes()),
+            Some((anchor, vec![]))
+        );
+    }
+
+    #[test]
+    fn test_reveal_batch_cbor_round_trip() {
+        let (mpk, msk) = dawn_crypto::generate();
+        let proof = ShadowProof {
+            headers: vec![vec![0xc0], vec![0xc1, 0x80]],
+            block: 0,
+            shadow: vec![0xc2, 0x80, 0xc0],
+        };
+        let indices = vec![0, 2, 5];
+        let light_client = SealedLightClient {
+            checkpoint: Checkpoint {
+                number: 7,
+                hash: [0xab; 32],
+            },
+            sealed: vec![7; 96],
+        };
+        let sealed = vec![42; 64];
+
+        let request = Request::RevealBatch(
+            proof.clone(),
+            indices.clone(),
+            light_client.clone(),
+            SealedMasterPrivateKey(sealed.clone()),
+        );
+        let bytes = serde_cbor::to_vec(&request).unwrap();
+        let Request::RevealBatch(decoded, decoded_indices, decoded_client, key) =
+            serde_cbor::from_slice(&bytes).unwrap()
+        else {
+            panic!("Expected RevealBatch request")
+        };
+        assert_eq!(decoded, proof);
+        assert_eq!(decoded_indices, indices);
+        assert_eq!(decoded_client.checkpoint, light_client.checkpoint);
+        assert_eq!(decoded_client.sealed, light_client.sealed);
+        assert_eq!(key.0, sealed);
+
+        let labels: Vec<Label> = vec![b"first".to_vec(), b"third".to_vec()];
+        let keys = vec![
+            Ok(dawn_crypto::reveal(&labels[0], &msk)),
+            Err("not an encrypted transaction".to_string()),
+            Ok(dawn_crypto::reveal(&labels[1], &msk)),
+        ];
+        let response = Response::RevealBatch(keys.clone());
+        let bytes = serde_cbor::to_vec(&response).unwrap();
+        let Response::RevealBatch(decoded) = serde_cbor::from_slice(&bytes).unwrap() else {
+            panic!("Expected RevealBatch response")
+        };
+        assert_eq!(decoded, keys);
+        assert!(dawn_crypto::verify(
+            &labels[1],
+            &mpk,
+            decoded[2].as_ref().unwrap()
+        ));
+    }
 }
@@ -0,0 +1,52 @@
+//! Proof-of-authority seals of headers, in the style of Clique: the extra data of a sealed header
+//! ends with the signature of its authority over the header without the signature.
+
+use alloy_rlp::{Encodable, Header};
+
+use std::vec::Vec;
+
+use crate::transaction::recover;
+use crate::{keccak256, Error};
+
+const EXTRA_DATA_FIELD: usize = 12;
+/// The length of a seal: `r || s || y_parity`.
+pub(crate) const SEAL_LEN: usize = 65;
+
+/// Returns the authority that sealed a header given its fields, or `None` if it is not sealed.
+pub(crate) fn sealer(fields: &[&[u8]]) -> Result<Option<[u8; 20]>, Error> {
+    let Some(extra_data) = fields.get(EXTRA_DATA_FIELD) else {
+        return Ok(None);
+    };
+    let extra_data = Header::decode_bytes(&mut &extra_data[..], false)?;
+    let Some(len) = extra_data.len().checked_sub(SEAL_LEN) else {
+        return Ok(None);
+    };
+    let (extra_data, seal) = extra_data.split_at(len);
+    let y_parity = match seal[64] {
+        0 => false,
+        1 => true,
+        _ => return Ok(None),
+    };
+
+    let mut payload = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        if index == EXTRA_DATA_FIELD {
+            extra_data.encode(&mut payload);
+        } else {
+            payload.extend_from_slice(field);
+        }
+    }
+    let mut unsealed = Vec::with_capacity(payload.len() + 9);
+    Header {
+        list: true,
+        payload_length: payload.len(),
+    }
+    .encode(&mut unsealed);
+    unsealed.extend_from_slice(&payload);
+
+    let (r, s) = (
+        seal[..32].try_into().unwrap(),
+        seal[32..64].try_into().unwrap(),
+    );
+    Ok(recover(&keccak256(&unsealed), y_parity, r, s))
+}
@@ -0,0 +1,536 @@
+//! The light client run by the enclaves of the secret management committee (SMC), which only
+//! reveal the decryption keys of encrypted transactions proven canonical.
+//!
+//! An enclave trusts an [`Anchor`]: a canonical block of the chain set up by the operator along
+//! with the master key, and sealed next to it. Its [`LightClient`] starts there, and moves its
+//! checkpoint along the headers that the host hands over. A [`ShadowProof`] carries the headers of
+//! the blocks that follow the checkpoint and a shadow block committed to by one of them, and
+//! [`LightClient::verify`] checks that the headers link up to the checkpoint and that the shadow
+//! block is `delay` blocks deep on them. The encrypted transactions of the shadow block are then
+//! decrypted as part of the body of the block `delay` blocks after its own.
+//!
+//! Headers must be sealed by one of the authorities of the chain, which the operator sets up along
+//! with the anchor: a light client without authorities would accept any chain extending the
+//! anchor far enough, whoever produced it. Headers are not checked against the other consensus
+//! rules of the chain.
+
+#![cfg_attr(feature = "mesalock_sgx", no_std)]
+#![cfg_attr(
+    all(target_env = "sgx", target_vendor = "mesalock"),
+    feature(rustc_private)
+)]
+
+#[cfg(feature = "mesalock_sgx")]
+extern crate sgx_tstd as std;
+
+use alloy_rlp::{Decodable, Header};
+use dawn_enclave_protocol::{Anchor, Checkpoint, ShadowProof};
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::vec::Vec;
+
+mod authority;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod transaction;
+
+pub use transaction::{block_label, label, DAWN_ENCRYPTED_TX_TYPE};
+
+/// The number of fields of a header that commits to a shadow block, the shadow root being the
+/// last one. Dawn blocks are post-Cancun, and carry the placeholder of the requests root.
+const SHADOW_HEADER_FIELDS: usize = 22;
+const PARENT_HASH_FIELD: usize = 0;
+const NUMBER_FIELD: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A header, the shadow block or one of its transactions is malformed.
+    Rlp(alloy_rlp::Error),
+    /// The header at this index does not extend the checkpoint, or the header before it.
+    Unlinked(usize),
+    /// The header at this index is not sealed by an authority of the chain.
+    Unsealed(usize),
+    /// The headers do not go on for `delay` blocks after the block of the shadow block.
+    NotDeep {
+        block: u32,
+        headers: usize,
+        delay: u64,
+    },
+    /// The header of the block does not commit to a shadow block.
+    NoShadowRoot,
+    /// The shadow block does not match the shadow root of its header.
+    ShadowMismatch,
+    /// The shadow block has no transaction at this index.
+    NoTransaction(u32),
+    /// The transaction at this index is not an encrypted transaction.
+    NotEncrypted(u32),
+    /// The sender of the transaction at this index can not be recovered from its signature.
+    InvalidSignature(u32),
+    /// The light client is older than one the enclave has already seen.
+    RolledBack { version: u64, latest: u64 },
+    /// The light client would be anchored without any authority sealing the headers.
+    NoAuthorities,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rlp(err) => write!(f, "malformed RLP: {err}"),
+            Self::Unlinked(index) => write!(f, "header {index} does not extend the chain"),
+            Self::Unsealed(index) => write!(f, "header {index} is not sealed by an authority"),
+            Self::NotDeep {
+                block,
+                headers,
+                delay,
+            } => write!(
+                f,
+                "block {block} is not {delay} blocks deep on {headers} headers"
+            ),
+            Self::NoShadowRoot => write!(f, "the block has no shadow block"),
+            Self::ShadowMismatch => write!(f, "the shadow block does not match its header"),
+            Self::NoTransaction(index) => write!(f, "no transaction {index} in the shadow block"),
+            Self::NotEncrypted(index) => write!(f, "transaction {index} is not encrypted"),
+            Self::InvalidSignature(index) => write!(f, "invalid signature of transaction {index}"),
+            Self::RolledBack { version, latest } => write!(
+                f,
+                "light client version {version} is older than version {latest}"
+            ),
+            Self::NoAuthorities => write!(f, "no authorities seal the headers of the chain"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<alloy_rlp::Error> for Error {
+    fn from(err: alloy_rlp::Error) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+/// A shadow block proven canonical and deep enough by [`verify`].
+#[derive(Debug)]
+pub struct Shadow<'a> {
+    /// The number of the block committing to the shadow block.
+    number: u64,
+    /// The RLP encodings of the transactions of the shadow block.
+    transactions: Vec<&'a [u8]>,
+}
+
+impl Shadow<'_> {
+    /// Returns the number of the block committing to the shadow block, whose label
+    /// [`block_label`] identifies the ciphertexts of a chain labelling them by block.
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Returns the number of transactions of the shadow block.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns whether the shadow block has no transaction.
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns the identity label of the encrypted transaction at `index`, recovering its sender
+    /// from its signature.
+    pub fn label(&self, index: u32) -> Result<[u8; 96], Error> {
+        let tx = self
+            .transactions
+            .get(index as usize)
+            .ok_or(Error::NoTransaction(index))?;
+        transaction::encrypted_label(tx, index)
+    }
+}
+
+/// The light client of an enclave: the checkpoint it trusts on the chain of its anchor, and the
+/// authorities sealing the blocks of the chain.
+///
+/// The enclave seals it between requests. Its version counts the moves of its checkpoint, so that
+/// [`Versions`] can tell an outdated light client replayed by the host.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClient {
+    anchor: Anchor,
+    checkpoint: Checkpoint,
+    authorities: Vec<[u8; 20]>,
+    version: u64,
+}
+
+impl LightClient {
+    /// Starts a light client at `anchor`, accepting headers sealed by any of `authorities`, of
+    /// which there must be at least one.
+    pub fn new(anchor: Anchor, authorities: Vec<[u8; 20]>) -> Result<Self, Error> {
+        if authorities.is_empty() {
+            return Err(Error::NoAuthorities);
+        }
+        Ok(Self {
+            anchor,
+            checkpoint: Checkpoint {
+                number: anchor.number,
+                hash: anchor.hash,
+            },
+            authorities,
+            version: 0,
+        })
+    }
+
+    pub fn anchor(&self) -> &Anchor {
+        &self.anchor
+    }
+
+    /// Returns the authorities whose seals the light client accepts.
+    pub fn authorities(&self) -> &[[u8; 20]] {
+        &self.authorities
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.checkpoint
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Moves the checkpoint along `headers`, which must follow it, to the parent of the deepest
+    /// block whose shadow block they prove.
+    ///
+    /// The checkpoint stops short of that block so that its shadow block can still be proven,
+    /// for example again after the host restarts. Headers that prove no shadow block past the
+    /// one of the block after the checkpoint leave it where it is.
+    pub fn advance(&mut self, headers: &[Vec<u8>]) -> Result<(), Error> {
+        self.follow(headers)?;
+        let deepest = (headers.len() as u64).saturating_sub(self.anchor.delay.max(1));
+        if deepest == 0 {
+            return Ok(());
+        }
+        let parent = deepest as usize - 1;
+        self.checkpoint = Checkpoint {
+            number: self.checkpoint.number + deepest,
+            hash: keccak256(&headers[parent]),
+        };
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Verifies that `proof` proves the shadow block of a block that is `delay` blocks deep on
+    /// a chain extending the checkpoint, and returns that shadow block.
+    pub fn verify<'a>(&self, proof: &'a ShadowProof) -> Result<Shadow<'a>, Error> {
+        let headers = proof.headers.len();
+        let delay = self.anchor.delay;
+        if proof.block as usize >= headers || ((headers - proof.block as usize) as u64) < delay {
+            return Err(Error::NotDeep {
+                block: proof.block,
+                headers,
+                delay,
+            });
+        }
+
+        let fields = self
+            .follow(&proof.headers)?
+            .swap_remove(proof.block as usize);
+        if fields.len() != SHADOW_HEADER_FIELDS {
+            return Err(Error::NoShadowRoot);
+        }
+        let shadow_root = <[u8; 32]>::decode(&mut &fields[SHADOW_HEADER_FIELDS - 1][..])?;
+        if shadow_root != keccak256(&proof.shadow) {
+            return Err(Error::ShadowMismatch);
+        }
+        // a shadow block is the list of its beneficiary and its list of transactions
+        let shadow = list_items(&proof.shadow)?;
+        let [_beneficiary, transactions] = &shadow[..] else {
+            return Err(alloy_rlp::Error::UnexpectedLength.into());
+        };
+        Ok(Shadow {
+            number: self.checkpoint.number + 1 + proof.block as u64,
+            transactions: list_items(transactions)?,
+        })
+    }
+
+    /// Checks that `headers` extend the checkpoint one after the other, sealed by authorities, and
+    /// returns the fields of each.
+    fn follow<'a>(&self, headers: &'a [Vec<u8>]) -> Result<Vec<Vec<&'a [u8]>>, Error> {
+        let Checkpoint {
+            mut number,
+            mut hash,
+        } = self.checkpoint;
+        let mut followed = Vec::with_capacity(headers.len());
+        for (index, header) in headers.iter().enumerate() {
+            let fields = list_items(header)?;
+            let (Some(parent_hash), Some(child_number)) =
+                (fields.get(PARENT_HASH_FIELD), fields.get(NUMBER_FIELD))
+            else {
+                return Err(alloy_rlp::Error::InputTooShort.into());
+            };
+            let parent_hash = <[u8; 32]>::decode(&mut &parent_hash[..])?;
+            let child_number = u64::decode(&mut &child_number[..])?;
+            if parent_hash != hash || Some(child_number) != number.checked_add(1) {
+                return Err(Error::Unlinked(index));
+            }
+            match authority::sealer(&fields)? {
+                Some(sealer) if self.authorities.contains(&sealer) => {}
+                _ => return Err(Error::Unsealed(index)),
+            }
+            (number, hash) = (child_number, keccak256(header));
+            followed.push(fields);
+        }
+        Ok(followed)
+    }
+}
+
+/// The latest version of the light client of each anchor that an enclave has seen since it was
+/// loaded, to refuse the outdated light clients that a host replays.
+///
+/// This only protects against rollbacks while the enclave stays loaded: after a restart, the host
+/// is trusted to hand over the latest light client it persisted.
+#[derive(Debug, Default)]
+pub struct Versions {
+    latest: BTreeMap<[u8; 32], u64>,
+}
+
+impl Versions {
+    /// Records the version of `client`, failing if a later version of the light client of its
+    /// anchor was seen.
+    pub fn check(&mut self, client: &LightClient) -> Result<(), Error> {
+        let latest = self.latest.entry(client.anchor.hash).or_default();
+        if client.version < *latest {
+            return Err(Error::RolledBack {
+                version: client.version,
+                latest: *latest,
+            });
+        }
+        *latest = client.version;
+        Ok(())
+    }
+}
+
+/// Splits the RLP list `buf` into the RLP encodings of its items.
+fn list_items(mut buf: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    let mut payload = Header::decode_bytes(&mut buf, true)?;
+    if !buf.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let mut rest = payload;
+        let header = Header::decode(&mut rest)?;
+        // a single byte below 0x80 is its own encoding, and `rest` then still starts with it
+        let len = payload.len() - rest.len() + header.payload_length;
+        items.push(&payload[..len]);
+        payload = &payload[len..];
+    }
+    Ok(items)
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{encrypted_transaction, Chain};
+    use super::*;
+
+    const AUTHORITY: [u8; 32] = [9; 32];
+
+    fn chain() -> (Chain, Vec<[u8; 96]>) {
+        let mut chain = Chain::sealed(2, &AUTHORITY);
+        let (first, first_label) = encrypted_transaction(&[1; 32], 1, 0);
+        let (second, second_label) = encrypted_transaction(&[2; 32], 1, 7);
+        chain.push(vec![first, testing::legacy_transaction(), second]);
+        chain.push(vec![]);
+        (chain, vec![first_label, second_label])
+    }
+
+    #[test]
+    fn test_labels_of_deep_shadow_block() {
+        let (chain, labels) = chain();
+        let client = chain.light_client();
+        let proof = chain.proof(0, 1);
+        let shadow = client.verify(&proof).unwrap();
+        assert_eq!(shadow.number(), chain.anchor.number + 1);
+        assert_eq!(shadow.len(), 3);
+        assert_eq!(shadow.label(0), Ok(labels[0]));
+        assert_eq!(shadow.label(1), Err(Error::NotEncrypted(1)));
+        assert_eq!(shadow.label(2), Ok(labels[1]));
+        assert_eq!(shadow.label(3), Err(Error::NoTransaction(3)));
+    }
+
+    #[test]
+    fn test_rejects_shallow_shadow_block() {
+        let (chain, _) = chain();
+        let client = chain.light_client();
+        let mut proof = chain.proof(0, 1);
+        proof.headers.pop();
+        assert!(matches!(
+            client.verify(&proof),
+            Err(Error::NotDeep { block: 0, .. })
+        ));
+
+        // the shadow block of the tip is not deep enough either
+        let proof = chain.proof(0, 2);
+        assert!(matches!(
+            client.verify(&proof),
+            Err(Error::NotDeep { block: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unlinked_headers() {
+        let (chain, _) = chain();
+        let client = chain.light_client();
+
+        let mut proof = chain.proof(0, 1);
+        proof.headers.swap(0, 1);
+        assert_eq!(client.verify(&proof).unwrap_err(), Error::Unlinked(0));
+
+        let mut other = Chain::sealed(2, &AUTHORITY);
+        other.push(vec![]);
+        other.push(vec![]);
+        let mut proof = chain.proof(0, 1);
+        proof.headers[1] = other.proof(0, 1).headers[1].clone();
+        assert_eq!(client.verify(&proof).unwrap_err(), Error::Unlinked(1));
+
+        // the headers must extend the anchor of the key
+        let anchor = Anchor {
+            hash: [0xff; 32],
+            ..chain.anchor
+        };
+        let client = LightClient::new(anchor, chain.authority().into_iter().collect()).unwrap();
+        assert_eq!(
+            client.verify(&chain.proof(0, 1)).unwrap_err(),
+            Error::Unlinked(0)
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_shadow_block() {
+        let (chain, _) = chain();
+        let client = chain.light_client();
+        let mut proof = chain.proof(0, 1);
+        proof.shadow = chain.proof(0, 2).shadow;
+        assert_eq!(client.verify(&proof).unwrap_err(), Error::ShadowMismatch);
+    }
+
+    #[test]
+    fn test_rejects_malleable_signature() {
+        let (tx, _) = encrypted_transaction(&[1; 32], 1, 0);
+        let malleable = testing::malleable_transaction(&[1; 32], 1, 0);
+        let mut chain = Chain::sealed(1, &AUTHORITY);
+        chain.push(vec![tx, malleable]);
+        let client = chain.light_client();
+        let proof = chain.proof(0, 1);
+        let shadow = client.verify(&proof).unwrap();
+        assert!(shadow.label(0).is_ok());
+        assert_eq!(shadow.label(1), Err(Error::InvalidSignature(1)));
+    }
+
+    #[test]
+    fn test_advance_checkpoint() {
+        let mut chain = Chain::sealed(2, &AUTHORITY);
+        for _ in 0..5 {
+            chain.push(vec![]);
+        }
+        let mut client = chain.light_client();
+
+        // blocks 1 and 2 are only proven deep enough by the headers up to block 3
+        client.advance(&chain.headers(0, 2)).unwrap();
+        assert_eq!(client.checkpoint(), chain.checkpoint(0));
+        assert_eq!(client.version(), 0);
+        client.advance(&chain.headers(0, 3)).unwrap();
+        assert_eq!(client.checkpoint(), chain.checkpoint(1));
+        assert_eq!(client.version(), 1);
+
+        // proofs now follow the checkpoint, which the shadow block of block 2 still does
+        let proof = chain.proof(1, 2);
+        assert_eq!(
+            client.verify(&proof).unwrap().number(),
+            chain.anchor.number + 2
+        );
+        assert_eq!(
+            client.verify(&chain.proof(0, 2)).unwrap_err(),
+            Error::Unlinked(0)
+        );
+
+        let mut unlinked = chain.headers(1, 5);
+        unlinked.remove(1);
+        assert_eq!(client.advance(&unlinked).unwrap_err(), Error::Unlinked(1));
+        assert_eq!(client.checkpoint(), chain.checkpoint(1));
+
+        client.advance(&chain.headers(1, 5)).unwrap();
+        assert_eq!(client.checkpoint(), chain.checkpoint(3));
+        assert_eq!(client.version(), 2);
+    }
+
+    #[test]
+    fn test_only_accept_headers_sealed_by_authorities() {
+        let mut chain = Chain::sealed(2, &AUTHORITY);
+        let mut impostor = Chain::sealed(2, &[8; 32]);
+        let mut unsealed = Chain::new(2);
+        for chain in [&mut chain, &mut impostor, &mut unsealed] {
+            chain.push(vec![]);
+            chain.push(vec![]);
+        }
+        let authority = chain.authority().unwrap();
+
+        let mut client = LightClient::new(chain.anchor, vec![[1; 20], authority]).unwrap();
+        client.verify(&chain.proof(0, 1)).unwrap();
+        assert_eq!(
+            client.verify(&impostor.proof(0, 1)).unwrap_err(),
+            Error::Unsealed(0)
+        );
+        assert_eq!(
+            client.advance(&unsealed.headers(0, 2)).unwrap_err(),
+            Error::Unsealed(0)
+        );
+
+        // a light client can not be anchored without authorities
+        assert_eq!(
+            LightClient::new(chain.anchor, vec![]).unwrap_err(),
+            Error::NoAuthorities
+        );
+    }
+
+    #[test]
+    fn test_refuse_rolled_back_light_client() {
+        let mut chain = Chain::sealed(1, &AUTHORITY);
+        for _ in 0..3 {
+            chain.push(vec![]);
+        }
+        let old = chain.light_client();
+        let mut new = old.clone();
+        new.advance(&chain.headers(0, 3)).unwrap();
+
+        let mut versions = Versions::default();
+        versions.check(&old).unwrap();
+        versions.check(&new).unwrap();
+        versions.check(&new).unwrap();
+        assert_eq!(
+            versions.check(&old).unwrap_err(),
+            Error::RolledBack {
+                version: 0,
+                latest: 1
+            }
+        );
+
+        // light clients of other anchors have their own versions
+        let other = LightClient::new(
+            Anchor {
+                hash: [0xff; 32],
+                ..chain.anchor
+            },
+            chain.authority().into_iter().collect(),
+        )
+        .unwrap();
+        versions.check(&other).unwrap();
+    }
+}
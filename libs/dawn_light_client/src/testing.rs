@@ -0,0 +1,270 @@
+//! Chains of headers and shadow blocks encoded like Reth does, to test the light client and the
+//! enclave without a node.
+
+use alloy_rlp::{Encodable, Header};
+use dawn_enclave_protocol::{Anchor, Checkpoint, ShadowProof};
+use k256::ecdsa::SigningKey;
+use k256::FieldBytes;
+
+use std::vec::Vec;
+
+use crate::authority::SEAL_LEN;
+use crate::transaction::address;
+use crate::{keccak256, label, LightClient, DAWN_ENCRYPTED_TX_TYPE};
+
+/// A chain of blocks following an anchor, each with a shadow block.
+#[derive(Debug)]
+pub struct Chain {
+    pub anchor: Anchor,
+    /// The authority sealing the headers, if any.
+    authority: Option<SigningKey>,
+    /// The RLP encoded headers and shadow blocks of the blocks after the anchor.
+    blocks: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Chain {
+    /// Starts a chain at block 0 of a chain with the given Dawn delay.
+    pub fn new(delay: u64) -> Self {
+        Self {
+            anchor: Anchor {
+                number: 0,
+                hash: keccak256(b"genesis"),
+                delay,
+            },
+            authority: None,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Starts a chain like [`Chain::new`], whose headers are sealed by the secp256k1 private key
+    /// `authority`.
+    pub fn sealed(delay: u64, authority: &[u8; 32]) -> Self {
+        Self {
+            authority: Some(SigningKey::from_bytes(authority.into()).unwrap()),
+            ..Self::new(delay)
+        }
+    }
+
+    /// Returns the address of the authority sealing the headers, if any.
+    pub fn authority(&self) -> Option<[u8; 20]> {
+        self.authority
+            .as_ref()
+            .map(|key| address(key.verifying_key()))
+    }
+
+    /// Returns a light client at the anchor of the chain, accepting the headers of its authority.
+    ///
+    /// # Panics
+    ///
+    /// If the headers of the chain are not sealed.
+    pub fn light_client(&self) -> LightClient {
+        LightClient::new(self.anchor, self.authority().into_iter().collect())
+            .expect("the chain is sealed by an authority")
+    }
+
+    /// Appends a block whose shadow block holds `transactions`, as encoded in a block body.
+    pub fn push(&mut self, transactions: Vec<Vec<u8>>) {
+        let mut shadow = Vec::new();
+        list(&[&[0x22; 20], &RawList(transactions)], &mut shadow);
+
+        let parent_hash = match self.blocks.last() {
+            Some((header, _)) => keccak256(header),
+            None => self.anchor.hash,
+        };
+        let number = self.anchor.number + self.blocks.len() as u64 + 1;
+        let shadow_root = keccak256(&shadow);
+        let mut header = encode_header(&parent_hash, number, &[], &shadow_root);
+        if let Some(authority) = &self.authority {
+            let (signature, recovery_id) = authority
+                .sign_prehash_recoverable(&keccak256(&header))
+                .unwrap();
+            let mut seal = [0; SEAL_LEN];
+            seal[..64].copy_from_slice(&signature.to_bytes());
+            seal[64] = recovery_id.is_y_odd() as u8;
+            header = encode_header(&parent_hash, number, &seal, &shadow_root);
+        }
+        self.blocks.push((header, shadow));
+    }
+
+    /// Returns the checkpoint at block `number`.
+    pub fn checkpoint(&self, number: u64) -> Checkpoint {
+        let hash = match number - self.anchor.number {
+            0 => self.anchor.hash,
+            index => keccak256(&self.blocks[index as usize - 1].0),
+        };
+        Checkpoint { number, hash }
+    }
+
+    /// Returns the headers of the blocks after block `after`, up to block `to`.
+    pub fn headers(&self, after: u64, to: u64) -> Vec<Vec<u8>> {
+        let first = (after - self.anchor.number) as usize;
+        let last = (to - self.anchor.number) as usize;
+        self.blocks[first..last]
+            .iter()
+            .map(|(header, _)| header.clone())
+            .collect()
+    }
+
+    /// Returns the proof of the shadow block of block `number` from the checkpoint at block
+    /// `after`, with the headers up to the tip.
+    pub fn proof(&self, after: u64, number: u64) -> ShadowProof {
+        let tip = self.anchor.number + self.blocks.len() as u64;
+        ShadowProof {
+            headers: self.headers(after, tip),
+            block: (number - after - 1) as u32,
+            shadow: self.blocks[(number - self.anchor.number - 1) as usize]
+                .1
+                .clone(),
+        }
+    }
+}
+
+/// Encodes a post-Cancun header committing to a shadow block.
+fn encode_header(
+    parent_hash: &[u8; 32],
+    number: u64,
+    extra_data: &[u8],
+    shadow_root: &[u8; 32],
+) -> Vec<u8> {
+    let mut header = Vec::new();
+    list(
+        &[
+            parent_hash,
+            &[0x1d; 32],
+            &[0; 20],
+            &[0x5a; 32],
+            &[0x7a; 32],
+            &[0x2e; 32],
+            &[0; 256],
+            &0u64,
+            &number,
+            &30_000_000u64,
+            &0u64,
+            &number,
+            &extra_data,
+            &[0; 32],
+            &[0; 8],
+            &7u64,
+            &[0x3d; 32],
+            &0u64,
+            &0u64,
+            &[0; 32],
+            // the placeholder of the missing requests root
+            &[0; 0],
+            shadow_root,
+        ],
+        &mut header,
+    );
+    header
+}
+
+/// Returns an encrypted transaction with the given chain id and nonce signed by the secp256k1
+/// private key `key`, as encoded in a shadow block, and its identity label.
+pub fn encrypted_transaction(key: &[u8; 32], chain_id: u64, nonce: u64) -> (Vec<u8>, [u8; 96]) {
+    sign(key, chain_id, nonce, false)
+}
+
+/// Returns the same transaction as [`encrypted_transaction`], with the malleable form of its
+/// signature, whose `s` is high.
+pub fn malleable_transaction(key: &[u8; 32], chain_id: u64, nonce: u64) -> Vec<u8> {
+    sign(key, chain_id, nonce, true).0
+}
+
+/// Returns a legacy transaction, which is encoded as a list in a shadow block.
+pub fn legacy_transaction() -> Vec<u8> {
+    let mut tx = Vec::new();
+    list(
+        &[&0u64, &1u64, &21_000u64, &[0x33; 20], &0u64, &[0; 0]],
+        &mut tx,
+    );
+    tx
+}
+
+fn sign(key: &[u8; 32], chain_id: u64, nonce: u64, high_s: bool) -> (Vec<u8>, [u8; 96]) {
+    let key = SigningKey::from_bytes(key.into()).unwrap();
+    let mut fields = Vec::new();
+    chain_id.encode(&mut fields);
+    nonce.encode(&mut fields);
+    0u64.encode(&mut fields);
+    1_000_000_000u64.encode(&mut fields);
+    1_000_000u64.encode(&mut fields);
+    0u64.encode(&mut fields);
+    RawList(Vec::new()).encode(&mut fields);
+    [0x99; 48].encode(&mut fields);
+    [0xc0, 0xff, 0xee].encode(&mut fields);
+    [0x7a; 16].encode(&mut fields);
+
+    let mut tx = vec![DAWN_ENCRYPTED_TX_TYPE];
+    let mut body = fields.clone();
+    Header {
+        list: true,
+        payload_length: fields.len(),
+    }
+    .encode(&mut tx);
+    tx.extend_from_slice(&fields);
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&keccak256(&tx)).unwrap();
+
+    let (r, mut s) = (
+        FieldBytes::from(signature.r()),
+        FieldBytes::from(signature.s()),
+    );
+    let mut y_parity = recovery_id.is_y_odd();
+    if high_s {
+        // negating `s` also negates the point recovered from `r`
+        s = FieldBytes::from(-*signature.s());
+        y_parity = !y_parity;
+    }
+    y_parity.encode(&mut body);
+    strip(&r).encode(&mut body);
+    strip(&s).encode(&mut body);
+
+    tx.truncate(1);
+    Header {
+        list: true,
+        payload_length: body.len(),
+    }
+    .encode(&mut tx);
+    tx.extend_from_slice(&body);
+    // typed transactions are strings in a block body
+    let mut encoded = Vec::new();
+    tx[..].encode(&mut encoded);
+
+    let sender = address(key.verifying_key());
+    (encoded, label(chain_id, &sender, nonce))
+}
+
+/// Encodes `items` as a list.
+fn list(items: &[&dyn Encodable], out: &mut Vec<u8>) {
+    let mut payload = Vec::new();
+    for item in items {
+        item.encode(&mut payload);
+    }
+    Header {
+        list: true,
+        payload_length: payload.len(),
+    }
+    .encode(out);
+    out.extend_from_slice(&payload);
+}
+
+/// Strips the leading zeros of a big-endian integer.
+fn strip(bytes: &[u8]) -> &[u8] {
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    &bytes[zeros..]
+}
+
+/// A list of items that are already RLP encoded.
+struct RawList(Vec<Vec<u8>>);
+
+impl Encodable for RawList {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        Header {
+            list: true,
+            payload_length: self.0.iter().map(Vec::len).sum(),
+        }
+        .encode(out);
+        for item in &self.0 {
+            out.put_slice(item);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Identity labels of the encrypted transactions of a shadow block.
+
+use alloy_rlp::{Decodable, Header};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use std::vec::Vec;
+
+use crate::{keccak256, list_items, Error};
+
+/// The EIP-2718 type of Dawn encrypted transactions.
+pub const DAWN_ENCRYPTED_TX_TYPE: u8 = 5;
+
+/// The fields of an encrypted transaction before its signature: chain id, nonce, priority fee,
+/// max fee, gas limit, value, access list, and the three parts of the ciphertext.
+const ENCRYPTED_TX_FIELDS: usize = 10;
+const CHAIN_ID_FIELD: usize = 0;
+const NONCE_FIELD: usize = 1;
+
+/// Returns the identity label of an encrypted transaction: its chain id, sender and nonce, each
+/// left-padded to 32 bytes.
+pub fn label(chain_id: u64, sender: &[u8; 20], nonce: u64) -> [u8; 96] {
+    let mut label = [0; 96];
+    label[24..32].copy_from_slice(&chain_id.to_be_bytes());
+    label[44..64].copy_from_slice(sender);
+    label[88..96].copy_from_slice(&nonce.to_be_bytes());
+    label
+}
+
+/// Returns the identity label of a block, for chains whose ciphertexts are encrypted for the block
+/// including them: its chain id and number, each left-padded to 32 bytes, followed by 32 bytes of
+/// `0xff` so that it never collides with the label of a transaction.
+pub fn block_label(chain_id: u64, number: u64) -> [u8; 96] {
+    let mut label = [0; 96];
+    label[24..32].copy_from_slice(&chain_id.to_be_bytes());
+    label[56..64].copy_from_slice(&number.to_be_bytes());
+    label[64..96].fill(0xff);
+    label
+}
+
+/// Returns the identity label of the encrypted transaction at `index` of a shadow block, given
+/// its RLP encoding there: a string wrapping `type || rlp([fields..., y_parity, r, s])`.
+pub(crate) fn encrypted_label(mut tx: &[u8], index: u32) -> Result<[u8; 96], Error> {
+    // legacy transactions are lists instead
+    let Ok(envelope) = Header::decode_bytes(&mut tx, false) else {
+        return Err(Error::NotEncrypted(index));
+    };
+    if !tx.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+    let Some((&DAWN_ENCRYPTED_TX_TYPE, body)) = envelope.split_first() else {
+        return Err(Error::NotEncrypted(index));
+    };
+    let items = list_items(body)?;
+    let [fields @ .., y_parity, r, s] = &items[..] else {
+        return Err(alloy_rlp::Error::InputTooShort.into());
+    };
+    if fields.len() != ENCRYPTED_TX_FIELDS {
+        return Err(alloy_rlp::Error::UnexpectedLength.into());
+    }
+
+    let chain_id = u64::decode(&mut &fields[CHAIN_ID_FIELD][..])?;
+    let nonce = u64::decode(&mut &fields[NONCE_FIELD][..])?;
+    let y_parity = bool::decode(&mut &y_parity[..])?;
+    let (r, s) = (scalar(r)?, scalar(s)?);
+
+    // the signature covers `type || rlp([fields...])`
+    let fields = fields.concat();
+    let mut signed = Vec::with_capacity(fields.len() + 10);
+    signed.push(DAWN_ENCRYPTED_TX_TYPE);
+    Header {
+        list: true,
+        payload_length: fields.len(),
+    }
+    .encode(&mut signed);
+    signed.extend_from_slice(&fields);
+
+    let sender =
+        recover(&keccak256(&signed), y_parity, r, s).ok_or(Error::InvalidSignature(index))?;
+    Ok(label(chain_id, &sender, nonce))
+}
+
+/// Decodes a 256-bit big-endian integer, left-padded to 32 bytes.
+fn scalar(mut item: &[u8]) -> Result<[u8; 32], Error> {
+    let bytes = Header::decode_bytes(&mut item, false)?;
+    if bytes.len() > 32 {
+        return Err(alloy_rlp::Error::Overflow.into());
+    }
+    if bytes.first() == Some(&0) {
+        return Err(alloy_rlp::Error::LeadingZero.into());
+    }
+    let mut scalar = [0; 32];
+    scalar[32 - bytes.len()..].copy_from_slice(bytes);
+    Ok(scalar)
+}
+
+/// Recovers the address that signed `hash`, rejecting malleable signatures with a high `s` like
+/// Ethereum does since EIP-2.
+pub(crate) fn recover(
+    hash: &[u8; 32],
+    y_parity: bool,
+    r: [u8; 32],
+    s: [u8; 32],
+) -> Option<[u8; 20]> {
+    let signature = Signature::from_scalars(r, s).ok()?;
+    if signature.normalize_s().is_some() {
+        return None;
+    }
+    let key =
+        VerifyingKey::recover_from_prehash(hash, &signature, RecoveryId::new(y_parity, false))
+            .ok()?;
+    Some(address(&key))
+}
+
+/// Returns the Ethereum address of a public key.
+pub(crate) fn address(key: &VerifyingKey) -> [u8; 20] {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    hash[12..].try_into().unwrap()
+}
@@ -167,7 +167,7 @@ array_impls! {
     01 02 03 04 05 06 07 08 09 10
     11 12 13 14 15 16 17 18 19 20
     21 22 23 24 25 26 27 28 29 30
-    31 32 48 96
+    31 32 48 64 96
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -1,6 +1,7 @@
 {
   mkShell,
   just,
+  jq,
   pkg-config,
   openssl,
   rustup,
@@ -18,6 +19,7 @@ mkShell {
   name = "dawn2";
   packages = [
     just
+    jq
     rustup
 
     # to build rusttls
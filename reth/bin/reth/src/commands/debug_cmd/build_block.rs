@@ -143,7 +143,7 @@ impl Command {
 
         let validator =
             TransactionValidationTaskExecutor::eth_builder(provider_factory.chain_spec())
-                .with_head_timestamp(best_block.timestamp)
+                .with_head_block(best_block.number, best_block.timestamp)
                 .kzg_settings(self.kzg_settings()?)
                 .with_additional_tasks(1)
                 .build_with_tasks(
@@ -9,7 +9,9 @@ static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 compile_error!("Cannot build the `reth` binary with the `optimism` feature flag enabled. Did you mean to build `op-reth`?");
 
 /// clap [Args] for Engine related arguments.
-use clap::Args;
+use clap::{ArgGroup, Args};
+use reth_dawn_decryptor::{DaemonAddress, EnclaveError, SgxEnclave};
+use std::path::PathBuf;
 
 /// Parameters for configuring the engine
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -20,12 +22,62 @@ pub struct EngineArgs {
     pub experimental: bool,
 }
 
+/// Parameters for decrypting Dawn transactions
+#[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Dawn")]
+#[command(group(ArgGroup::new("dawn_enclave").args(["enclave", "enclave_socket"])))]
+pub struct DawnArgs {
+    /// Path to the SGX app running the enclave of the Dawn SMC. Enables the decryption of the
+    /// encrypted transactions of the canonical chain.
+    #[arg(long = "dawn.enclave", requires = "sealed_key")]
+    pub enclave: Option<PathBuf>,
+
+    /// Address of an `app serve` daemon keeping the enclave of the Dawn SMC loaded, either a TCP
+    /// socket address or the path of a Unix socket. Enables the decryption of the encrypted
+    /// transactions of the canonical chain, without loading the enclave for every request.
+    #[arg(long = "dawn.enclave-socket", value_name = "ADDRESS", requires = "sealed_key")]
+    pub enclave_socket: Option<DaemonAddress>,
+
+    /// Path to the master private key sealed by the enclave along with its anchor and light
+    /// client, as written by `app anchor`. The file is rewritten as the light client advances.
+    #[arg(long = "dawn.sealed-key", requires = "dawn_enclave")]
+    pub sealed_key: Option<PathBuf>,
+}
+
+impl DawnArgs {
+    /// Returns the configured enclave, if any.
+    pub fn enclave(&self) -> Result<Option<SgxEnclave>, EnclaveError> {
+        let Some(sealed_key) = &self.sealed_key else { return Ok(None) };
+        match (&self.enclave, &self.enclave_socket) {
+            (Some(app), _) => SgxEnclave::load(app.clone(), sealed_key).map(Some),
+            (_, Some(address)) => SgxEnclave::load_daemon(address.clone(), sealed_key).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// The arguments of the reth binary on top of the node arguments.
+#[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
+pub struct RethArgs {
+    /// Engine arguments
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
+    /// Dawn arguments
+    #[command(flatten)]
+    pub dawn: DawnArgs,
+}
+
 #[cfg(not(feature = "optimism"))]
 fn main() {
     use clap::Parser;
     use reth::cli::Cli;
-    use reth_node_builder::EngineNodeLauncher;
-    use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
+    use reth_dawn_decryptor::{decryptor_exex, DecryptedTransactions};
+    use reth_node_builder::{DefaultNodeLauncher, EngineNodeLauncher};
+    use reth_node_ethereum::{
+        node::{EthereumAddOns, EthereumPayloadBuilder},
+        EthEvmConfig, EthereumNode,
+    };
     use reth_provider::providers::BlockchainProvider2;
 
     reth_cli_util::sigsegv_handler::install();
@@ -35,14 +87,26 @@ fn main() {
         std::env::set_var("RUST_BACKTRACE", "1");
     }
 
-    if let Err(err) = Cli::<EngineArgs>::parse().run(|builder, engine_args| async move {
-        let enable_engine2 = engine_args.experimental;
+    if let Err(err) = Cli::<RethArgs>::parse().run(|builder, args| async move {
+        let enable_engine2 = args.engine.experimental;
+        let enclave = args.dawn.enclave()?;
+        let decrypted = DecryptedTransactions::default();
+        // blocks must start with the decrypted transactions, if this node decrypts them
+        let mandatory_transactions = enclave.is_some().then(|| decrypted.clone());
+        let payload_builder = EthereumPayloadBuilder::new(EthEvmConfig::default())
+            .with_mandatory_transactions(mandatory_transactions.clone());
         match enable_engine2 {
             true => {
-                let handle = builder
+                let mut builder = builder
                     .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
-                    .with_components(EthereumNode::components())
-                    .with_add_ons::<EthereumAddOns>()
+                    .with_components(EthereumNode::components().payload(payload_builder))
+                    .with_add_ons::<EthereumAddOns>();
+                if let Some(enclave) = enclave {
+                    builder = builder.install_exex("dawn-decryptor", move |ctx| async move {
+                        decryptor_exex(ctx, enclave, decrypted)
+                    });
+                }
+                let handle = builder
                     .launch_with_fn(|builder| {
                         let launcher = EngineNodeLauncher::new(
                             builder.task_executor().clone(),
@@ -54,7 +118,25 @@ fn main() {
                 handle.node_exit_future.await
             }
             false => {
-                let handle = builder.launch_node(EthereumNode::default()).await?;
+                let mut builder = builder
+                    .with_types::<EthereumNode>()
+                    .with_components(EthereumNode::components().payload(payload_builder))
+                    .with_add_ons::<EthereumAddOns>();
+                if let Some(enclave) = enclave {
+                    builder = builder.install_exex("dawn-decryptor", move |ctx| async move {
+                        decryptor_exex(ctx, enclave, decrypted)
+                    });
+                }
+                let handle = builder
+                    .launch_with_fn(|builder| {
+                        let launcher = DefaultNodeLauncher::new(
+                            builder.task_executor().clone(),
+                            builder.config().datadir(),
+                        )
+                        .with_mandatory_transactions(mandatory_transactions);
+                        builder.launch_with(launcher)
+                    })
+                    .await?;
                 handle.node_exit_future.await
             }
         }
@@ -82,4 +164,55 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_dawn_args() {
+        let default_args = DawnArgs::default();
+        let args = CommandParser::<DawnArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<DawnArgs>::parse_from([
+            "reth",
+            "--dawn.enclave",
+            "sgx/bin/app",
+            "--dawn.sealed-key",
+            "sealed_key.json",
+        ])
+        .args;
+        assert_eq!(args.enclave, Some(PathBuf::from("sgx/bin/app")));
+        assert_eq!(args.sealed_key, Some(PathBuf::from("sealed_key.json")));
+
+        // the enclave is useless without the sealed key
+        assert!(
+            CommandParser::<DawnArgs>::try_parse_from(["reth", "--dawn.enclave", "app"]).is_err()
+        );
+
+        let args = CommandParser::<DawnArgs>::parse_from([
+            "reth",
+            "--dawn.enclave-socket",
+            "127.0.0.1:7878",
+            "--dawn.sealed-key",
+            "sealed_key.json",
+        ])
+        .args;
+        assert_eq!(args.enclave_socket, Some(DaemonAddress::Tcp(([127, 0, 0, 1], 7878).into())));
+
+        // the enclave is either run by the node or by a daemon
+        assert!(CommandParser::<DawnArgs>::try_parse_from([
+            "reth",
+            "--dawn.enclave",
+            "app",
+            "--dawn.enclave-socket",
+            "enclave.sock",
+            "--dawn.sealed-key",
+            "sealed_key.json",
+        ])
+        .is_err());
+        assert!(CommandParser::<DawnArgs>::try_parse_from([
+            "reth",
+            "--dawn.sealed-key",
+            "sealed_key.json",
+        ])
+        .is_err());
+    }
 }
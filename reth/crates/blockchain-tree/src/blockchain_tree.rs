@@ -1634,6 +1634,7 @@ mod tests {
                     ommers: Vec::new(),
                     withdrawals: Some(Withdrawals::default()),
                     requests: None,
+                    shadow: None,
                 },
                 body.iter().map(|tx| tx.signer()).collect(),
             )
@@ -166,6 +166,7 @@ impl TestBlockBuilder {
             ommers: Vec::new(),
             withdrawals: None,
             requests: None,
+            shadow: None,
         };
 
         SealedBlockWithSenders::new(block, vec![self.signer; num_txs as usize]).unwrap()
@@ -0,0 +1,103 @@
+use alloy_genesis::Genesis;
+use alloy_primitives::BlockNumber;
+use dawn_crypto::MasterPublicKey;
+use reth_ethereum_forks::ForkCondition;
+use serde::{de::Error as _, Deserialize, Serialize};
+
+/// The Dawn parameters of a chain, read from the `dawn` section of the genesis config.
+///
+/// The activation block or timestamp, at most one of which may be set, determines the
+/// [`EthereumHardfork::Dawn`](reth_ethereum_forks::EthereumHardfork::Dawn) fork condition of the
+/// chain. Dawn is active from genesis if neither is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DawnConfig {
+    /// The master public key of the Dawn secret management committee, which issues the decryption
+    /// keys of encrypted transactions.
+    pub master_public_key: MasterPublicKey,
+    /// The number of blocks between the inclusion of a transaction in a shadow block and its
+    /// execution. It must not be zero.
+    #[serde(default = "DawnConfig::default_delay")]
+    pub delay: u64,
+    /// The block at which Dawn activates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockNumber>,
+    /// The timestamp at which Dawn activates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// What the ciphertexts of encrypted transactions are encrypted for, and thus how many
+    /// decryption keys the SMC reveals.
+    #[serde(default)]
+    pub labels: DawnLabels,
+}
+
+/// What the ciphertexts of the encrypted transactions of a chain are encrypted for.
+///
+/// Either way, a ciphertext is authenticated with the label of its transaction as associated
+/// data, so that it only decrypts for its own sender and nonce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DawnLabels {
+    /// Each ciphertext is encrypted for the label of its transaction, made of its chain id, sender
+    /// and nonce, and the SMC reveals a decryption key per transaction, once the shadow block
+    /// including it is deep enough.
+    #[default]
+    Transaction,
+    /// Each ciphertext is encrypted for the label of the block whose shadow block includes it,
+    /// made of the chain id and the block number, and the SMC reveals a single decryption key per
+    /// block, carried by all the decrypted and undecrypted transactions of the shadow block.
+    ///
+    /// Senders must target the block that will include their transaction. One that is included
+    /// in another block is executed undecrypted, and its contents are exposed as soon as the key
+    /// of the block it targeted is revealed.
+    Block,
+}
+
+impl DawnConfig {
+    /// The default number of blocks between the inclusion and the execution of a transaction.
+    pub const DEFAULT_DELAY: u64 = 2;
+
+    /// Creates a new [`DawnConfig`] with the default delay, active from genesis, whose ciphertexts
+    /// are encrypted for the labels of their transactions.
+    pub const fn new(master_public_key: MasterPublicKey) -> Self {
+        Self {
+            master_public_key,
+            delay: Self::DEFAULT_DELAY,
+            block: None,
+            timestamp: None,
+            labels: DawnLabels::Transaction,
+        }
+    }
+
+    /// Extracts the Dawn parameters from the `dawn` field of the genesis config.
+    ///
+    /// Returns `None` if the field is missing, and an error if it is malformed.
+    pub fn extract_from(genesis: &Genesis) -> Option<Result<Self, serde_json::Error>> {
+        genesis.config.extra_fields.get_deserialized::<Self>("dawn").map(|config| {
+            config.and_then(|config| {
+                if config.delay == 0 {
+                    return Err(serde_json::Error::custom("dawn delay must not be zero"))
+                }
+                if config.block.is_some() && config.timestamp.is_some() {
+                    return Err(serde_json::Error::custom(
+                        "dawn must activate at either a block or a timestamp",
+                    ))
+                }
+                Ok(config)
+            })
+        })
+    }
+
+    /// Returns the activation condition of the Dawn hardfork.
+    pub const fn fork_condition(&self) -> ForkCondition {
+        match (self.block, self.timestamp) {
+            (_, Some(timestamp)) => ForkCondition::Timestamp(timestamp),
+            (Some(block), None) => ForkCondition::Block(block),
+            (None, None) => ForkCondition::Block(0),
+        }
+    }
+
+    const fn default_delay() -> u64 {
+        Self::DEFAULT_DELAY
+    }
+}
@@ -10,6 +10,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
+pub use dawn::{DawnConfig, DawnLabels};
 pub use info::ChainInfo;
 pub use spec::{
     BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecProvider,
@@ -26,6 +27,9 @@ extern crate alloc;
 /// The chain info module.
 mod info;
 
+/// The Dawn parameters module.
+mod dawn;
+
 /// The chain spec module.
 mod spec;
 
@@ -1,4 +1,4 @@
-use crate::constants::MAINNET_DEPOSIT_CONTRACT;
+use crate::{constants::MAINNET_DEPOSIT_CONTRACT, DawnConfig};
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use alloy_chains::{Chain, ChainKind, NamedChain};
@@ -53,6 +53,7 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 20000,
+        dawn: None,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -77,6 +78,7 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
+        dawn: None,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -99,6 +101,7 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
+        dawn: None,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -109,15 +112,23 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
 /// Includes 20 prefunded accounts with `10_000` ETH each derived from mnemonic "test test test test
 /// test test test test test test test junk".
 pub static DEV: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
+    let genesis: Genesis = serde_json::from_str(include_str!("../res/genesis/dev.json"))
+        .expect("Can't deserialize Dev testnet genesis json");
+    let dawn = DawnConfig::extract_from(&genesis)
+        .map(|dawn| dawn.expect("Can't deserialize Dev testnet dawn config"));
+    let mut hardforks = DEV_HARDFORKS.clone();
+    if let Some(dawn) = dawn {
+        hardforks.insert(EthereumHardfork::Dawn, dawn.fork_condition());
+    }
     ChainSpec {
         chain: Chain::dev(),
-        genesis: serde_json::from_str(include_str!("../res/genesis/dev.json"))
-            .expect("Can't deserialize Dev testnet genesis json"),
+        genesis,
         genesis_hash: Some(DEV_GENESIS_HASH),
         paris_block_and_final_difficulty: Some((0, U256::from(0))),
-        hardforks: DEV_HARDFORKS.clone(),
+        hardforks,
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         deposit_contract: None, // TODO: do we even have?
+        dawn,
         ..Default::default()
     }
     .into()
@@ -310,6 +321,9 @@ pub struct ChainSpec {
 
     /// The delete limit for pruner, per run.
     pub prune_delete_limit: usize,
+
+    /// The Dawn parameters of the chain, if it supports encrypted transactions.
+    pub dawn: Option<DawnConfig>,
 }
 
 impl Default for ChainSpec {
@@ -324,6 +338,7 @@ impl Default for ChainSpec {
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             prune_delete_limit: MAINNET.prune_delete_limit,
+            dawn: None,
         }
     }
 }
@@ -372,6 +387,15 @@ impl ChainSpec {
         self.chain == Chain::optimism_mainnet()
     }
 
+    /// Returns the Dawn parameters of the chain if [`EthereumHardfork::Dawn`] is active at the
+    /// given block and timestamp.
+    #[inline]
+    pub fn dawn_at(&self, block_number: BlockNumber, timestamp: u64) -> Option<&DawnConfig> {
+        self.dawn
+            .as_ref()
+            .filter(|_| self.is_dawn_active_at_block_and_timestamp(block_number, timestamp))
+    }
+
     /// Get the genesis block specification.
     ///
     /// To get the header for the genesis block, use [`Self::genesis_header`] instead.
@@ -426,6 +450,7 @@ impl ChainSpec {
             blob_gas_used,
             excess_blob_gas,
             requests_root,
+            shadow_root: None,
             ..Default::default()
         }
     }
@@ -706,6 +731,9 @@ impl From<Genesis> for ChainSpec {
         let genesis_info =
             optimism_genesis_info.optimism_chain_info.genesis_info.unwrap_or_default();
 
+        // A malformed dawn section is ignored here, it is rejected when parsing a custom chain
+        let dawn = DawnConfig::extract_from(&genesis).and_then(Result::ok);
+
         // Block-based hardforks
         let hardfork_opts = [
             (EthereumHardfork::Homestead.boxed(), genesis.config.homestead_block),
@@ -721,6 +749,10 @@ impl From<Genesis> for ChainSpec {
             (EthereumHardfork::London.boxed(), genesis.config.london_block),
             (EthereumHardfork::ArrowGlacier.boxed(), genesis.config.arrow_glacier_block),
             (EthereumHardfork::GrayGlacier.boxed(), genesis.config.gray_glacier_block),
+            (
+                EthereumHardfork::Dawn.boxed(),
+                dawn.filter(|dawn| dawn.timestamp.is_none()).map(|dawn| dawn.block.unwrap_or(0)),
+            ),
             #[cfg(feature = "optimism")]
             (OptimismHardfork::Bedrock.boxed(), genesis_info.bedrock_block),
         ];
@@ -750,6 +782,7 @@ impl From<Genesis> for ChainSpec {
             (EthereumHardfork::Shanghai.boxed(), genesis.config.shanghai_time),
             (EthereumHardfork::Cancun.boxed(), genesis.config.cancun_time),
             (EthereumHardfork::Prague.boxed(), genesis.config.prague_time),
+            (EthereumHardfork::Dawn.boxed(), dawn.and_then(|dawn| dawn.timestamp)),
             #[cfg(feature = "optimism")]
             (OptimismHardfork::Regolith.boxed(), genesis_info.regolith_time),
             #[cfg(feature = "optimism")]
@@ -805,6 +838,7 @@ impl From<Genesis> for ChainSpec {
             deposit_contract,
             #[cfg(feature = "optimism")]
             base_fee_params: optimism_genesis_info.base_fee_params,
+            dawn,
             ..Default::default()
         }
     }
@@ -823,6 +857,7 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    dawn: Option<DawnConfig>,
 }
 
 impl ChainSpecBuilder {
@@ -832,6 +867,7 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            dawn: None,
         }
     }
 }
@@ -849,6 +885,19 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Set the Dawn parameters and activate [`EthereumHardfork::Dawn`] at their activation block or
+    /// timestamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Dawn delay is zero.
+    pub fn dawn(mut self, dawn: DawnConfig) -> Self {
+        assert_ne!(dawn.delay, 0, "dawn delay must not be zero");
+        self.hardforks.insert(EthereumHardfork::Dawn, dawn.fork_condition());
+        self.dawn = Some(dawn);
+        self
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork(mut self, fork: EthereumHardfork, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -1045,6 +1094,7 @@ impl ChainSpecBuilder {
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
             deposit_contract: None,
+            dawn: self.dawn,
             ..Default::default()
         }
     }
@@ -1056,6 +1106,7 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            dawn: value.dawn,
         }
     }
 }
@@ -1134,6 +1185,7 @@ impl OptimismGenesisInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::DawnLabels;
     use alloy_chains::Chain;
     use alloy_genesis::{ChainConfig, GenesisAccount};
     use alloy_primitives::{b256, hex};
@@ -2426,6 +2478,74 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.cancun_time, Some(4661));
     }
 
+    #[test]
+    fn test_parse_dawn_genesis() {
+        let mut genesis: Genesis =
+            serde_json::from_str(include_str!("../res/genesis/dev.json")).unwrap();
+        let dawn = DawnConfig::extract_from(&genesis).unwrap().unwrap();
+        assert_eq!(dawn.delay, DawnConfig::DEFAULT_DELAY);
+        assert_eq!(ChainSpec::from(genesis.clone()).dawn, Some(dawn));
+        assert_eq!(DEV.dawn, Some(dawn));
+
+        // activation block and default delay
+        let mpk = serde_json::to_value(dawn.master_public_key).unwrap();
+        genesis
+            .config
+            .extra_fields
+            .insert("dawn".to_string(), serde_json::json!({ "masterPublicKey": mpk, "block": 10 }));
+        let chain_spec = ChainSpec::from(genesis.clone());
+        assert_eq!(chain_spec.dawn, Some(DawnConfig { block: Some(10), ..dawn }));
+        assert_eq!(chain_spec.fork(EthereumHardfork::Dawn), ForkCondition::Block(10));
+        assert!(chain_spec.dawn_at(9, u64::MAX).is_none());
+        assert!(chain_spec.dawn_at(10, 0).is_some());
+
+        // the activation block is part of the fork id
+        let head = |number| Head { number, ..Default::default() };
+        assert_eq!(chain_spec.fork_id(&head(9)).next, 10);
+        assert_ne!(chain_spec.fork_id(&head(9)).hash, chain_spec.fork_id(&head(10)).hash);
+
+        // activation timestamp
+        genesis.config.extra_fields.insert(
+            "dawn".to_string(),
+            serde_json::json!({ "masterPublicKey": mpk, "timestamp": 20 }),
+        );
+        let chain_spec = ChainSpec::from(genesis.clone());
+        assert_eq!(chain_spec.fork(EthereumHardfork::Dawn), ForkCondition::Timestamp(20));
+        assert!(chain_spec.dawn_at(u64::MAX, 19).is_none());
+        assert!(chain_spec.dawn_at(0, 20).is_some());
+
+        // ciphertexts labelled by block
+        genesis.config.extra_fields.insert(
+            "dawn".to_string(),
+            serde_json::json!({ "masterPublicKey": mpk, "labels": "block" }),
+        );
+        let chain_spec = ChainSpec::from(genesis.clone());
+        assert_eq!(chain_spec.dawn, Some(DawnConfig { labels: DawnLabels::Block, ..dawn }));
+        assert_eq!(dawn.labels, DawnLabels::Transaction);
+
+        // an activation block and timestamp are rejected
+        genesis.config.extra_fields.insert(
+            "dawn".to_string(),
+            serde_json::json!({ "masterPublicKey": mpk, "block": 10, "timestamp": 20 }),
+        );
+        assert!(DawnConfig::extract_from(&genesis).unwrap().is_err());
+
+        // a zero delay is rejected
+        genesis
+            .config
+            .extra_fields
+            .insert("dawn".to_string(), serde_json::json!({ "masterPublicKey": mpk, "delay": 0 }));
+        assert!(DawnConfig::extract_from(&genesis).unwrap().is_err());
+        assert_eq!(ChainSpec::from(genesis).dawn, None);
+    }
+
+    #[test]
+    #[should_panic = "dawn delay must not be zero"]
+    fn test_builder_rejects_zero_dawn_delay() {
+        let dawn = DawnConfig { delay: 0, ..DEV.dawn.unwrap() };
+        let _ = ChainSpecBuilder::mainnet().dawn(dawn);
+    }
+
     #[test]
     fn test_parse_prague_genesis_all_formats() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661, "pragueTime": 4662},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
@@ -19,14 +19,16 @@ use reth_beacon_consensus::BeaconEngineMessage;
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_engine_primitives::EngineTypes;
+use reth_ethereum_consensus::validate_dawn_transactions;
 use reth_execution_errors::{
     BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
 };
 use reth_execution_types::ExecutionOutcome;
+use reth_payload_primitives::MandatoryTransactions;
 use reth_primitives::{
-    eip4844::calculate_excess_blob_gas, proofs, Block, BlockBody, BlockHash, BlockHashOrNumber,
-    BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock, SealedHeader,
-    TransactionSigned, Withdrawals, B256, U256,
+    eip4844::calculate_excess_blob_gas, proofs, sign_message, Address, Block, BlockBody, BlockHash,
+    BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock,
+    SealedHeader, ShadowBlock, TransactionSigned, Withdrawals, B256, U256,
 };
 use reth_provider::{BlockReaderIdExt, StateProviderFactory, StateRootProvider};
 use reth_revm::database::StateProviderDatabase;
@@ -45,7 +47,10 @@ mod task;
 
 pub use crate::client::AutoSealClient;
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
-use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_evm::{
+    dawn,
+    execute::{BlockExecutionOutput, BlockExecutorProvider, Executor},
+};
 pub use task::MiningTask;
 
 /// A consensus implementation intended for local development and testing purposes.
@@ -84,8 +89,8 @@ impl Consensus for AutoSealConsensus {
         Ok(())
     }
 
-    fn validate_block_pre_execution(&self, _block: &SealedBlock) -> Result<(), ConsensusError> {
-        Ok(())
+    fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
+        validate_dawn_transactions(block, &self.chain_spec)
     }
 
     fn validate_block_post_execution(
@@ -99,7 +104,7 @@ impl Consensus for AutoSealConsensus {
 
 /// Builder type for configuring the setup
 #[derive(Debug)]
-pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
+pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig, Mandatory = ()> {
     client: Client,
     consensus: AutoSealConsensus,
     pool: Pool,
@@ -107,6 +112,7 @@ pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
     storage: Storage,
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     evm_config: EvmConfig,
+    mandatory_transactions: Mandatory,
 }
 
 // === impl AutoSealBuilder ===
@@ -132,29 +138,94 @@ where
             .flatten()
             .unwrap_or_else(|| chain_spec.sealed_genesis_header());
 
+        // load the shadow blocks whose transactions have not been executed yet
+        let delay = chain_spec.dawn.map_or(0, |dawn| dawn.delay);
+        let shadows = ((latest_header.number + 1).saturating_sub(delay)..=latest_header.number)
+            .filter_map(|number| {
+                let shadow = client.block(number.into()).ok().flatten()?.shadow?;
+                Some((number, shadow))
+            })
+            .collect();
+
         Self {
-            storage: Storage::new(latest_header),
+            storage: Storage::new(latest_header, shadows),
             client,
             consensus: AutoSealConsensus::new(chain_spec),
             pool,
             mode,
             to_engine,
             evm_config,
+            mandatory_transactions: (),
         }
     }
+}
 
+impl<Client, Pool, Engine, EvmConfig, Mandatory>
+    AutoSealBuilder<Client, Pool, Engine, EvmConfig, Mandatory>
+where
+    Client: BlockReaderIdExt,
+    Pool: TransactionPool,
+    Engine: EngineTypes,
+{
     /// Sets the [`MiningMode`] it operates in, default is [`MiningMode::Auto`]
     pub fn mode(mut self, mode: MiningMode) -> Self {
         self.mode = mode;
         self
     }
 
+    /// Sets the beneficiary of mined blocks and of their shadow blocks, default is the zero
+    /// address.
+    pub fn beneficiary(self, beneficiary: Address) -> Self {
+        self.storage.inner.try_write().expect("storage is not shared before build").beneficiary =
+            beneficiary;
+        self
+    }
+
+    /// Sets the secret key of the authority sealing mined blocks in the style of Clique, by
+    /// default they are not sealed.
+    pub fn authority(self, authority: B256) -> Self {
+        self.storage.inner.try_write().expect("storage is not shared before build").authority =
+            Some(authority);
+        self
+    }
+
+    /// Sets the source of the transactions mined blocks must start with.
+    ///
+    /// By default, the body of a block under Dawn is the shadow block it executes, as is, which is
+    /// only valid if the shadow block has no encrypted transactions.
+    pub fn mandatory_transactions<M>(
+        self,
+        mandatory_transactions: M,
+    ) -> AutoSealBuilder<Client, Pool, Engine, EvmConfig, M> {
+        let Self { client, consensus, pool, mode, storage, to_engine, evm_config, .. } = self;
+        AutoSealBuilder {
+            client,
+            consensus,
+            pool,
+            mode,
+            storage,
+            to_engine,
+            evm_config,
+            mandatory_transactions,
+        }
+    }
+
     /// Consumes the type and returns all components
     #[track_caller]
     pub fn build(
         self,
-    ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, evm_config } = self;
+    ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine, Mandatory>)
+    {
+        let Self {
+            client,
+            consensus,
+            pool,
+            mode,
+            storage,
+            to_engine,
+            evm_config,
+            mandatory_transactions,
+        } = self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -164,6 +235,7 @@ where
             client,
             pool,
             evm_config,
+            mandatory_transactions,
         );
         (consensus, auto_client, task)
     }
@@ -179,13 +251,15 @@ pub(crate) struct Storage {
 
 impl Storage {
     /// Initializes the [Storage] with the given best block. This should be initialized with the
-    /// highest block in the chain, if there is a chain already stored on-disk.
-    fn new(best_block: SealedHeader) -> Self {
+    /// highest block in the chain, if there is a chain already stored on-disk, and the shadow
+    /// blocks of its last [`DawnConfig::delay`](reth_chainspec::DawnConfig::delay) blocks.
+    fn new(best_block: SealedHeader, shadows: HashMap<BlockNumber, ShadowBlock>) -> Self {
         let (header, best_hash) = best_block.split();
         let mut storage = StorageInner {
             best_hash,
             total_difficulty: header.difficulty,
             best_block: header.number,
+            shadows,
             ..Default::default()
         };
         storage.headers.insert(header.number, header);
@@ -219,6 +293,12 @@ pub(crate) struct StorageInner {
     pub(crate) best_hash: B256,
     /// The total difficulty of the chain until this block
     pub(crate) total_difficulty: U256,
+    /// Shadow blocks whose transactions have not been executed yet
+    pub(crate) shadows: HashMap<BlockNumber, ShadowBlock>,
+    /// Beneficiary of new blocks and of their shadow blocks
+    pub(crate) beneficiary: Address,
+    /// Secret key of the authority sealing new blocks, if any
+    pub(crate) authority: Option<B256>,
 }
 
 // === impl StorageInner ===
@@ -251,6 +331,9 @@ impl StorageInner {
         self.total_difficulty += header.difficulty;
 
         trace!(target: "consensus::auto", num=self.best_block, hash=?self.best_hash, "inserting new block");
+        if let Some(shadow) = &body.shadow {
+            self.shadows.insert(header.number, shadow.clone());
+        }
         self.headers.insert(header.number, header);
         self.bodies.insert(self.best_hash, body);
         self.hash_to_number.insert(self.best_hash, self.best_block);
@@ -265,6 +348,7 @@ impl StorageInner {
         ommers: &[Header],
         withdrawals: Option<&Withdrawals>,
         requests: Option<&Requests>,
+        shadow: Option<&ShadowBlock>,
         chain_spec: &ChainSpec,
     ) -> Header {
         // check previous block for base fee
@@ -287,7 +371,7 @@ impl StorageInner {
         let mut header = Header {
             parent_hash: self.best_hash,
             ommers_hash: proofs::calculate_ommers_root(ommers),
-            beneficiary: Default::default(),
+            beneficiary: self.beneficiary,
             state_root: Default::default(),
             transactions_root: proofs::calculate_transaction_root(transactions),
             receipts_root: Default::default(),
@@ -306,6 +390,7 @@ impl StorageInner {
             extra_data: Default::default(),
             parent_beacon_block_root: None,
             requests_root: requests.map(|r| proofs::calculate_requests_root(&r.0)),
+            shadow_root: shadow.map(ShadowBlock::calculate_root),
         };
 
         if chain_spec.is_cancun_active_at_timestamp(timestamp) {
@@ -334,19 +419,26 @@ impl StorageInner {
 
     /// Builds and executes a new block with the given transactions, on the provided executor.
     ///
+    /// If Dawn is active, the transactions go into the shadow block of the new block, and its body
+    /// is made of the transactions of the shadow block of the block
+    /// [`DawnConfig::delay`](reth_chainspec::DawnConfig::delay) blocks back, as given by
+    /// `mandatory_transactions`. Fails if they are missing.
+    ///
     /// This returns the header of the executed block, as well as the poststate from execution.
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn build_and_execute<Provider, Executor>(
+    pub(crate) fn build_and_execute<Provider, Executor, Mandatory>(
         &mut self,
         transactions: Vec<TransactionSigned>,
         ommers: Vec<Header>,
         provider: &Provider,
         chain_spec: Arc<ChainSpec>,
         executor: &Executor,
+        mandatory_transactions: &Mandatory,
     ) -> Result<(SealedHeader, ExecutionOutcome), BlockExecutionError>
     where
         Executor: BlockExecutorProvider,
         Provider: StateProviderFactory,
+        Mandatory: MandatoryTransactions,
     {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
 
@@ -357,12 +449,53 @@ impl StorageInner {
         let requests =
             chain_spec.is_prague_active_at_timestamp(timestamp).then_some(Requests::default());
 
+        let mut db = StateProviderDatabase::new(
+            provider.latest().map_err(InternalBlockExecutionError::LatestBlock)?,
+        );
+
+        // if dawn is active, delay the execution of the transactions with a shadow block
+        let number = self.best_block + 1;
+        let dawn = chain_spec.dawn_at(number, timestamp).copied();
+        let (transactions, shadow) = if let Some(dawn) = dawn {
+            let parent = self
+                .headers
+                .get(&self.best_block)
+                .cloned()
+                .map(|header| SealedHeader::new(header, self.best_hash))
+                .ok_or_else(|| BlockExecutionError::msg("missing best block header"))?;
+            let delayed = match mandatory_transactions
+                .mandatory_transactions(provider, &chain_spec, &parent, timestamp)
+                .map_err(BlockExecutionError::other)?
+            {
+                Some(transactions) => transactions,
+                None => number
+                    .checked_sub(dawn.delay)
+                    .and_then(|number| self.shadows.get(&number))
+                    .map(|shadow| shadow.transactions.clone())
+                    .unwrap_or_default(),
+            };
+            // leave out the transactions that the executor would reject from the shadow block
+            let transactions = transactions
+                .into_iter()
+                .filter(|tx| {
+                    tx.recover_signer().is_some_and(|sender| {
+                        dawn::validate_shadow_transaction(&mut db, &tx.transaction, sender).is_ok()
+                    })
+                })
+                .collect();
+            let shadow = ShadowBlock { beneficiary: self.beneficiary, transactions };
+            (delayed, Some(shadow))
+        } else {
+            (transactions, None)
+        };
+
         let header = self.build_header_template(
             timestamp,
             &transactions,
             &ommers,
             withdrawals.as_ref(),
             requests.as_ref(),
+            shadow.as_ref(),
             &chain_spec,
         );
 
@@ -372,16 +505,13 @@ impl StorageInner {
             ommers: ommers.clone(),
             withdrawals: withdrawals.clone(),
             requests: requests.clone(),
+            shadow: shadow.clone(),
         }
         .with_recovered_senders()
         .ok_or(BlockExecutionError::Validation(BlockValidationError::SenderRecoveryError))?;
 
         trace!(target: "consensus::auto", transactions=?&block.body, "executing transactions");
 
-        let mut db = StateProviderDatabase::new(
-            provider.latest().map_err(InternalBlockExecutionError::LatestBlock)?,
-        );
-
         // execute the block
         let BlockExecutionOutput {
             state,
@@ -402,7 +532,7 @@ impl StorageInner {
         // root here
 
         let Block { mut header, body, .. } = block.block;
-        let body = BlockBody { transactions: body, ommers, withdrawals, requests };
+        let body = BlockBody { transactions: body, ommers, withdrawals, requests, shadow };
 
         trace!(target: "consensus::auto", ?execution_outcome, ?header, ?body, "executed block, calculating state root and completing header");
 
@@ -432,8 +562,22 @@ impl StorageInner {
         };
         trace!(target: "consensus::auto", root=?header.state_root, ?body, "calculated root");
 
+        // the extra data of a sealed header ends with the signature of the authority over the
+        // header without it, which is what the light clients of the Dawn enclaves check
+        if let Some(authority) = self.authority {
+            let signature =
+                sign_message(authority, header.hash_slow()).map_err(BlockExecutionError::other)?;
+            let mut seal = signature.to_bytes();
+            seal[64] = signature.odd_y_parity as u8;
+            header.extra_data = seal.to_vec().into();
+        }
+
         // finally insert into storage
         self.insert_new_block(header.clone(), body);
+        if let Some(dawn) = dawn {
+            // the shadow block of the block `delay` blocks back was just executed
+            self.shadows.retain(|number, _| number + dawn.delay > self.best_block);
+        }
 
         // set new header with hash that should have been updated by insert_new_block
         let new_header = header.seal(self.best_hash);
@@ -4,6 +4,7 @@ use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
 use reth_chainspec::ChainSpec;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::execute::BlockExecutorProvider;
+use reth_payload_primitives::MandatoryTransactions;
 use reth_primitives::IntoRecoveredTransaction;
 use reth_provider::{CanonChainTracker, StateProviderFactory};
 use reth_rpc_types::engine::ForkchoiceState;
@@ -21,7 +22,8 @@ use tokio::sync::{mpsc::UnboundedSender, oneshot};
 use tracing::{debug, error, warn};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
-pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTypes> {
+pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTypes, Mandatory = ()>
+{
     /// The configured chain spec
     chain_spec: Arc<ChainSpec>,
     /// The client used to interact with the state
@@ -42,12 +44,14 @@ pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTyp
     pipe_line_events: Option<EventStream<PipelineEvent>>,
     /// The type used for block execution
     block_executor: Executor,
+    /// The source of the transactions blocks must start with
+    mandatory_transactions: Mandatory,
 }
 
 // === impl MiningTask ===
 
-impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
-    MiningTask<Client, Pool, Executor, Engine>
+impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes, Mandatory>
+    MiningTask<Client, Pool, Executor, Engine, Mandatory>
 {
     /// Creates a new instance of the task
     #[allow(clippy::too_many_arguments)]
@@ -59,6 +63,7 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
         client: Client,
         pool: Pool,
         block_executor: Executor,
+        mandatory_transactions: Mandatory,
     ) -> Self {
         Self {
             chain_spec,
@@ -71,6 +76,7 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
             queued: Default::default(),
             pipe_line_events: None,
             block_executor,
+            mandatory_transactions,
         }
     }
 
@@ -80,12 +86,14 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
     }
 }
 
-impl<Executor, Client, Pool, Engine> Future for MiningTask<Client, Pool, Executor, Engine>
+impl<Executor, Client, Pool, Engine, Mandatory> Future
+    for MiningTask<Client, Pool, Executor, Engine, Mandatory>
 where
     Client: StateProviderFactory + CanonChainTracker + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     Engine: EngineTypes,
     Executor: BlockExecutorProvider,
+    Mandatory: MandatoryTransactions + Clone + Unpin + 'static,
 {
     type Output = ();
 
@@ -115,6 +123,7 @@ where
                 let pool = this.pool.clone();
                 let events = this.pipe_line_events.take();
                 let executor = this.block_executor.clone();
+                let mandatory_transactions = this.mandatory_transactions.clone();
 
                 // Create the mining future that creates a block, notifies the engine that drives
                 // the pipeline
@@ -136,6 +145,7 @@ where
                         &client,
                         chain_spec,
                         &executor,
+                        &mandatory_transactions,
                     ) {
                         Ok((new_header, _bundle_state)) => {
                             // clear all transactions from pool
@@ -216,8 +226,8 @@ where
     }
 }
 
-impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes> std::fmt::Debug
-    for MiningTask<Client, Pool, EvmConfig, Engine>
+impl<Client, Pool: TransactionPool, EvmConfig: std::fmt::Debug, Engine: EngineTypes, Mandatory>
+    std::fmt::Debug for MiningTask<Client, Pool, EvmConfig, Engine, Mandatory>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MiningTask").finish_non_exhaustive()
@@ -8,7 +8,7 @@ use reth_primitives::{
         MAXIMUM_EXTRA_DATA_SIZE,
     },
     eip4844::calculate_excess_blob_gas,
-    EthereumHardfork, GotExpected, Header, SealedBlock, SealedHeader,
+    EthereumHardfork, GotExpected, Header, SealedBlock, SealedHeader, TxType,
 };
 
 /// Gas used needs to be less than gas limit. Gas used is going to be checked after execution.
@@ -42,6 +42,7 @@ pub fn validate_header_base_fee(
 /// - Compares the ommer hash in the block header to the block body
 /// - Compares the transactions root in the block header to the block body
 /// - Pre-execution transaction validation
+/// - Compares the shadow root in the block header to the shadow block and checks its contents
 /// - (Optionally) Compares the receipts root in the block header to the block body
 pub fn validate_block_pre_execution(
     block: &SealedBlock,
@@ -101,6 +102,44 @@ pub fn validate_block_pre_execution(
         }
     }
 
+    // Dawn: shadow blocks
+    match (block.shadow_root, &block.shadow) {
+        (Some(header_shadow_root), Some(shadow)) => {
+            let shadow_root = shadow.calculate_root();
+            if shadow_root != header_shadow_root {
+                return Err(ConsensusError::BodyShadowRootDiff(
+                    GotExpected { got: shadow_root, expected: header_shadow_root }.into(),
+                ))
+            }
+
+            // the gas limits of the transactions are chosen by their senders, and may overflow
+            match shadow.gas_limit() {
+                Some(shadow_gas_limit) if shadow_gas_limit <= block.gas_limit => {}
+                shadow_gas_limit => {
+                    return Err(ConsensusError::ShadowBlockGasLimitExceeded {
+                        shadow_gas_limit: shadow_gas_limit.unwrap_or(u64::MAX),
+                        gas_limit: block.gas_limit,
+                    })
+                }
+            }
+
+            // Decrypted and undecrypted transactions are only created when executing the shadow
+            // block, so the block producer can not commit to them.
+            if let Some(tx) = shadow
+                .transactions
+                .iter()
+                .find(|tx| matches!(tx.tx_type(), TxType::DawnDecrypted | TxType::DawnUndecrypted))
+            {
+                return Err(ConsensusError::ShadowBlockTransactionTypeInvalid {
+                    tx_type: tx.tx_type() as u8,
+                })
+            }
+        }
+        (None, None) => {}
+        (Some(_), None) => return Err(ConsensusError::BodyShadowMissing),
+        (None, Some(_)) => return Err(ConsensusError::ShadowRootUnexpected),
+    }
+
     Ok(())
 }
 
@@ -275,8 +314,8 @@ mod tests {
     use reth_chainspec::ChainSpecBuilder;
     use reth_primitives::{
         hex_literal::hex, proofs, Account, Address, BlockBody, BlockHash, BlockHashOrNumber,
-        BlockNumber, Bytes, Signature, Transaction, TransactionSigned, TxEip4844, Withdrawal,
-        Withdrawals, U256,
+        BlockNumber, Bytes, ShadowBlock, Signature, Transaction, TransactionSigned, TxEip4844,
+        Withdrawal, Withdrawals, B256, U256,
     };
     use reth_storage_api::{
         errors::provider::ProviderResult, AccountReader, HeaderProvider, WithdrawalsProvider,
@@ -428,7 +467,8 @@ mod tests {
             blob_gas_used: None,
             excess_blob_gas: None,
             parent_beacon_block_root: None,
-            requests_root: None
+            requests_root: None,
+            shadow_root: None
         };
         // size: 0x9b5
 
@@ -449,6 +489,7 @@ mod tests {
                 ommers,
                 withdrawals: None,
                 requests: None,
+                shadow: None,
             },
             parent,
         )
@@ -516,6 +557,7 @@ mod tests {
             ommers: vec![],
             withdrawals: Some(Withdrawals::default()),
             requests: None,
+            shadow: None,
         };
 
         let block = SealedBlock::new(header, body);
@@ -532,4 +574,57 @@ mod tests {
             }))
         );
     }
+    #[test]
+    fn shadow_block_validation() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+
+        let create_block_with_shadow =
+            |shadow_root: Option<B256>, shadow: Option<ShadowBlock>| SealedBlock {
+                header: Header { gas_limit: 30_000_000, shadow_root, ..Default::default() }
+                    .seal_slow(),
+                shadow,
+                ..Default::default()
+            };
+
+        let shadow =
+            ShadowBlock { beneficiary: Address::random(), transactions: vec![mock_blob_tx(1, 0)] };
+
+        // Shadow root matches the shadow block
+        let block = create_block_with_shadow(Some(shadow.calculate_root()), Some(shadow.clone()));
+        assert_eq!(validate_block_pre_execution(&block, &chain_spec), Ok(()));
+
+        // Shadow root does not match the shadow block
+        let block = create_block_with_shadow(Some(B256::ZERO), Some(shadow.clone()));
+        assert_eq!(
+            validate_block_pre_execution(&block, &chain_spec),
+            Err(ConsensusError::BodyShadowRootDiff(
+                GotExpected { got: shadow.calculate_root(), expected: B256::ZERO }.into()
+            ))
+        );
+
+        // Shadow root without a shadow block and vice versa
+        let block = create_block_with_shadow(Some(shadow.calculate_root()), None);
+        assert_eq!(
+            validate_block_pre_execution(&block, &chain_spec),
+            Err(ConsensusError::BodyShadowMissing)
+        );
+        let block = create_block_with_shadow(None, Some(shadow));
+        assert_eq!(
+            validate_block_pre_execution(&block, &chain_spec),
+            Err(ConsensusError::ShadowRootUnexpected)
+        );
+
+        // Gas limits summing to more than 2^64
+        let mut tx = mock_blob_tx(1, 0);
+        tx.transaction.set_gas_limit(u64::MAX);
+        let shadow = ShadowBlock { beneficiary: Address::random(), transactions: vec![tx; 2] };
+        let block = create_block_with_shadow(Some(shadow.calculate_root()), Some(shadow));
+        assert_eq!(
+            validate_block_pre_execution(&block, &chain_spec),
+            Err(ConsensusError::ShadowBlockGasLimitExceeded {
+                shadow_gas_limit: u64::MAX,
+                gas_limit: 30_000_000
+            })
+        );
+    }
 }
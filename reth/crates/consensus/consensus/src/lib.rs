@@ -10,9 +10,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use reth_primitives::{
-    constants::MINIMUM_GAS_LIMIT, BlockHash, BlockNumber, BlockWithSenders, Bloom, GotExpected,
-    GotExpectedBoxed, Header, InvalidTransactionError, Receipt, Request, SealedBlock, SealedHeader,
-    B256, U256,
+    constants::MINIMUM_GAS_LIMIT, BlockHash, BlockNumber, BlockWithSenders, Bloom,
+    DawnTransactionError, GotExpected, GotExpectedBoxed, Header, InvalidTransactionError, Receipt,
+    Request, SealedBlock, SealedHeader, B256, U256,
 };
 
 #[cfg(feature = "std")]
@@ -179,6 +179,10 @@ pub enum ConsensusError {
     #[error("mismatched block requests root: {0}")]
     BodyRequestsRootDiff(GotExpectedBoxed<B256>),
 
+    /// Error when the shadow root in the block is different from the expected shadow root.
+    #[error("mismatched block shadow root: {0}")]
+    BodyShadowRootDiff(GotExpectedBoxed<B256>),
+
     /// Error when a block with a specific hash and number is already known.
     #[error("block with [hash={hash}, number={number}] is already known")]
     BlockKnown {
@@ -262,6 +266,14 @@ pub enum ConsensusError {
     #[error("unexpected requests root")]
     RequestsRootUnexpected,
 
+    /// Error when the shadow root is missing.
+    #[error("missing shadow root")]
+    ShadowRootMissing,
+
+    /// Error when an unexpected shadow root is encountered.
+    #[error("unexpected shadow root")]
+    ShadowRootUnexpected,
+
     /// Error when withdrawals are missing.
     #[error("missing withdrawals")]
     BodyWithdrawalsMissing,
@@ -270,6 +282,10 @@ pub enum ConsensusError {
     #[error("missing requests")]
     BodyRequestsMissing,
 
+    /// Error when the shadow block is missing.
+    #[error("missing shadow block")]
+    BodyShadowMissing,
+
     /// Error when blob gas used is missing.
     #[error("missing blob gas used")]
     BlobGasUsedMissing,
@@ -387,6 +403,46 @@ pub enum ConsensusError {
         /// The block's timestamp.
         timestamp: u64,
     },
+
+    /// Error when the gas limit of the shadow block exceeds the gas limit of the block.
+    #[error("shadow block gas limit {shadow_gas_limit} exceeds block gas limit {gas_limit}")]
+    ShadowBlockGasLimitExceeded {
+        /// The sum of the gas limits of the shadow block transactions, saturated at `u64::MAX`.
+        shadow_gas_limit: u64,
+        /// The gas limit in the block header.
+        gas_limit: u64,
+    },
+
+    /// Error when the shadow block contains a transaction that may only appear in a block body.
+    #[error("transaction type {tx_type} is not allowed in a shadow block")]
+    ShadowBlockTransactionTypeInvalid {
+        /// The type of the transaction.
+        tx_type: u8,
+    },
+
+    /// Error when the block contains a Dawn transaction before Dawn activates.
+    #[error("Dawn transaction {hash} present in pre-dawn block")]
+    DawnTransactionUnexpected {
+        /// The hash of the transaction.
+        hash: B256,
+    },
+
+    /// Error when the block contains an encrypted Dawn transaction once Dawn is active, instead of
+    /// its decrypted or undecrypted form.
+    #[error("encrypted Dawn transaction {hash} present in block body")]
+    DawnTransactionEncrypted {
+        /// The hash of the transaction.
+        hash: B256,
+    },
+
+    /// Error when a Dawn decrypted or undecrypted transaction fails verification.
+    #[error("invalid Dawn transaction {hash}: {error}")]
+    InvalidDawnTransaction {
+        /// The hash of the transaction.
+        hash: B256,
+        /// The verification error.
+        error: DawnTransactionError,
+    },
 }
 
 impl ConsensusError {
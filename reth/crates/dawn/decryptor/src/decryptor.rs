@@ -0,0 +1,660 @@
+//! Decryption of the shadow blocks of the canonical chain.
+
+use std::{collections::BTreeMap, future::Future};
+
+use dawn_enclave_protocol::ShadowProof;
+use reth_chainspec::{DawnConfig, DawnLabels};
+use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_node_api::FullNodeComponents;
+use reth_primitives::{
+    dawn_block_label, dawn_label, BlockHash, BlockNumHash, BlockNumber, ChainId, ShadowBlock,
+    Transaction, TransactionSigned, TxHash,
+};
+use reth_provider::{BlockReader, Chain, ProviderError};
+use reth_tracing::tracing::{debug, trace};
+
+use crate::{DecryptedTransactions, Enclave, EnclaveError};
+
+/// Errors that can occur when decrypting shadow blocks.
+#[derive(Debug, thiserror::Error)]
+pub enum DecryptorError {
+    /// The enclave failed to reveal a decryption key.
+    #[error(transparent)]
+    Enclave(#[from] EnclaveError),
+    /// The canonical chain could not be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The sender of an encrypted transaction could not be recovered.
+    #[error("failed to recover the sender of transaction {0}")]
+    SenderRecovery(TxHash),
+    /// The enclave revealed a decryption key that was not issued by the master key of the chain.
+    #[error("invalid decryption key revealed for transaction {0}")]
+    InvalidDecryptionKey(TxHash),
+    /// The enclave revealed a decryption key for the shadow block of a block that was not issued
+    /// by the master key of the chain.
+    #[error("invalid decryption key revealed for the shadow block of block {0}")]
+    InvalidBlockDecryptionKey(BlockNumber),
+    /// The block does not follow the checkpoint of the light client of the enclave, which can
+    /// thus not learn that its shadow block is canonical.
+    #[error("block {0} does not follow the checkpoint of the enclave")]
+    BeforeCheckpoint(BlockNumber),
+}
+
+/// A shadow block whose encrypted transactions are not decrypted yet.
+#[derive(Debug)]
+struct PendingShadow {
+    /// The hash of the block that committed to the shadow block.
+    hash: BlockHash,
+    /// The shadow block, empty if the block has none.
+    shadow: ShadowBlock,
+}
+
+/// Decrypts the encrypted transactions of the shadow blocks of the canonical chain.
+///
+/// The shadow block of block `N` makes up the body of block `N + d`, where `d` is the Dawn delay
+/// of the chain, so its encrypted transactions are decrypted once block `N` is `d` blocks deep,
+/// that is once block `N + d - 1` is canonical, right before block `N + d` is built.
+///
+/// Decryption keys are only ever revealed for shadow blocks that are canonical: shadow blocks are
+/// dropped as soon as their block is reverted, and the provider must agree that their block is
+/// canonical before their keys are requested from the [`Enclave`]. The enclave checks it on its
+/// own with a [`ShadowProof`] made of the canonical headers from the checkpoint of its light
+/// client on, and the decryptor then advances the checkpoint along the same headers, so that
+/// proofs stay `d + 1` headers long.
+///
+/// Blocks without a shadow block are recorded with an empty one, so that block builders can tell
+/// a block that committed to no transaction from a shadow block that is not decrypted yet.
+#[derive(Debug)]
+pub struct Decryptor<P, E> {
+    provider: P,
+    enclave: E,
+    dawn: DawnConfig,
+    /// The id of the chain, part of the labels of its blocks.
+    chain_id: ChainId,
+    /// The shadow blocks of the canonical chain that are not deep enough to be decrypted.
+    pending: BTreeMap<BlockNumber, PendingShadow>,
+    /// The decrypted shadow blocks.
+    decrypted: DecryptedTransactions,
+    /// The tip of the canonical chain.
+    tip: BlockNumber,
+}
+
+impl<P, E> Decryptor<P, E>
+where
+    P: BlockReader,
+    E: Enclave,
+{
+    /// Creates a new [`Decryptor`] for the chain of id `chain_id` with the given Dawn parameters,
+    /// writing the decrypted shadow blocks to `decrypted`.
+    pub const fn new(
+        provider: P,
+        enclave: E,
+        dawn: DawnConfig,
+        chain_id: ChainId,
+        decrypted: DecryptedTransactions,
+    ) -> Self {
+        Self { provider, enclave, dawn, chain_id, pending: BTreeMap::new(), decrypted, tip: 0 }
+    }
+
+    /// Loads the shadow blocks of the canonical chain up to `head` that are not executed yet, and
+    /// decrypts those that are deep enough.
+    pub async fn load(&mut self, head: BlockNumber) -> Result<(), DecryptorError> {
+        for number in (head + 1).saturating_sub(self.dawn.delay)..=head {
+            let (Some(hash), Some(block)) =
+                (self.provider.block_hash(number)?, self.provider.block(number.into())?)
+            else {
+                continue
+            };
+            let shadow = block.shadow.unwrap_or_default();
+            self.pending.insert(number, PendingShadow { hash, shadow });
+        }
+        self.tip = head;
+        self.decrypt_deep_shadows().await
+    }
+
+    /// Updates the shadow blocks with the chains of `notification`, and decrypts those that are
+    /// deep enough.
+    pub async fn on_notification(
+        &mut self,
+        notification: &ExExNotification,
+    ) -> Result<(), DecryptorError> {
+        if let Some(reverted) = notification.reverted_chain() {
+            self.revert(&reverted);
+        }
+        if let Some(committed) = notification.committed_chain() {
+            self.commit(&committed);
+            self.decrypt_deep_shadows().await?;
+        }
+        Ok(())
+    }
+
+    /// Runs the decryptor as an `ExEx`, until the node shuts down.
+    pub async fn run<Node: FullNodeComponents>(
+        mut self,
+        mut ctx: ExExContext<Node>,
+    ) -> eyre::Result<()> {
+        self.load(ctx.head.number).await?;
+        while let Some(notification) = ctx.notifications.recv().await {
+            self.on_notification(&notification).await?;
+            if let Some(committed) = notification.committed_chain() {
+                ctx.events.send(ExExEvent::FinishedHeight(committed.tip().number))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the shadow blocks of the reverted blocks.
+    fn revert(&mut self, chain: &Chain) {
+        let first = chain.first().number;
+        debug!(target: "dawn::decryptor", range = ?chain.range(), "Reverting shadow blocks");
+        self.pending.retain(|number, _| *number < first);
+        self.decrypted.revert(first);
+        self.tip = first.saturating_sub(1);
+    }
+
+    /// Records the shadow blocks of the committed blocks.
+    fn commit(&mut self, chain: &Chain) {
+        for block in chain.blocks_iter() {
+            let shadow = block.shadow.clone().unwrap_or_default();
+            self.pending.insert(block.number, PendingShadow { hash: block.hash(), shadow });
+        }
+        self.tip = chain.tip().number;
+    }
+
+    /// Decrypts the pending shadow blocks that are `d` blocks deep, and prunes the decrypted
+    /// shadow blocks whose execution block is itself `d` blocks deep.
+    async fn decrypt_deep_shadows(&mut self) -> Result<(), DecryptorError> {
+        let delay = self.dawn.delay;
+        let Some(deepest) = (self.tip + 1).checked_sub(delay) else { return Ok(()) };
+
+        let pending = self.pending.split_off(&(deepest + 1));
+        for (number, shadow) in std::mem::replace(&mut self.pending, pending) {
+            // the provider may already be ahead of the notifications, past a reorg of the block
+            if self.provider.block_hash(number)? != Some(shadow.hash) {
+                debug!(target: "dawn::decryptor", number, hash = ?shadow.hash, "Postponing the decryption of a shadow block that is not canonical");
+                self.pending.insert(number, shadow);
+                continue
+            }
+
+            let (block, headers) = self.deep_headers(number)?;
+            let transactions = self.decrypt(shadow.shadow, number, block, &headers).await?;
+            debug!(target: "dawn::decryptor", number, hash = ?shadow.hash, transactions = transactions.len(), "Decrypted shadow block");
+            self.decrypted.insert(BlockNumHash::new(number, shadow.hash), transactions);
+
+            // the checkpoint moves to the parent of the block, if it is not there already
+            if block > 0 {
+                self.enclave.advance(headers).await?;
+            }
+        }
+
+        self.decrypted.prune((self.tip + 2).saturating_sub(2 * delay));
+        Ok(())
+    }
+
+    /// Replaces the encrypted transactions of the shadow block of block `number` by their
+    /// decrypted or undecrypted form, proving to the enclave that the shadow block is deep enough
+    /// with the `headers` following its checkpoint, among which its block is at index `block`.
+    ///
+    /// If the chain labels ciphertexts by block, the single key of the block decrypts all of them.
+    async fn decrypt(
+        &self,
+        shadow: ShadowBlock,
+        number: BlockNumber,
+        block: u32,
+        headers: &[Vec<u8>],
+    ) -> Result<Vec<TransactionSigned>, DecryptorError> {
+        // the keys of all the encrypted transactions are revealed at once
+        let mut senders = Vec::new();
+        for (index, tx) in shadow.transactions.iter().enumerate() {
+            if let Transaction::DawnEncrypted(inner) = &tx.transaction {
+                let sender = tx.recover_signer().ok_or(DecryptorError::SenderRecovery(tx.hash))?;
+                senders.push((
+                    index as u32,
+                    sender,
+                    dawn_label(inner.chain_id, &sender, inner.nonce),
+                ));
+            }
+        }
+        let decryption_keys = if senders.is_empty() {
+            Vec::new()
+        } else {
+            let proof = ShadowProof {
+                headers: headers.to_vec(),
+                block,
+                shadow: alloy_rlp::encode(&shadow),
+            };
+            match self.dawn.labels {
+                DawnLabels::Transaction => {
+                    let indices = senders.iter().map(|(index, ..)| *index).collect();
+                    let keys = self.enclave.reveal_batch(proof, indices).await?;
+                    for ((index, _, label), key) in senders.iter().zip(&keys) {
+                        if !dawn_crypto::verify(label, &self.dawn.master_public_key, key) {
+                            let hash = shadow.transactions[*index as usize].hash;
+                            return Err(DecryptorError::InvalidDecryptionKey(hash))
+                        }
+                    }
+                    keys
+                }
+                DawnLabels::Block => {
+                    let key = self.enclave.reveal_block(proof, self.chain_id).await?;
+                    let label = dawn_block_label(self.chain_id, number);
+                    if !dawn_crypto::verify(&label, &self.dawn.master_public_key, &key) {
+                        return Err(DecryptorError::InvalidBlockDecryptionKey(number))
+                    }
+                    vec![key; senders.len()]
+                }
+            }
+        };
+        let mut revealed = senders.into_iter().zip(decryption_keys);
+
+        let transactions = shadow.transactions;
+        let mut decrypted = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            let Transaction::DawnEncrypted(encrypted) = &tx.transaction else {
+                decrypted.push(tx);
+                continue
+            };
+
+            let ((_, sender, _), decryption_key) =
+                revealed.next().expect("a key is revealed for every encrypted transaction");
+            let transaction = encrypted.decrypt(sender, decryption_key);
+            trace!(target: "dawn::decryptor", hash = ?tx.hash, tx_type = ?transaction.tx_type(), "Decrypted transaction");
+            decrypted
+                .push(TransactionSigned::from_transaction_and_signature(transaction, tx.signature));
+        }
+        Ok(decrypted)
+    }
+
+    /// Returns the RLP encoded canonical headers that prove that the shadow block of block
+    /// `number` is `d` blocks deep to the enclave, from the checkpoint of its light client to
+    /// block `number + d - 1`, and the index of block `number` among them.
+    fn deep_headers(&self, number: BlockNumber) -> Result<(u32, Vec<Vec<u8>>), DecryptorError> {
+        let checkpoint = self.enclave.checkpoint();
+        let block = number
+            .checked_sub(checkpoint.number + 1)
+            .ok_or(DecryptorError::BeforeCheckpoint(number))?;
+        let tip = (number + self.dawn.delay).saturating_sub(1).max(number);
+        let headers = self.provider.headers_range(checkpoint.number + 1..=tip)?;
+        if headers.len() as u64 != tip - checkpoint.number {
+            return Err(ProviderError::HeaderNotFound(tip.into()).into())
+        }
+        let block =
+            u32::try_from(block).expect("the block is less than 2^32 blocks after the checkpoint");
+        Ok((block, headers.iter().map(alloy_rlp::encode).collect()))
+    }
+}
+
+/// Returns the decryptor `ExEx` of a node, writing the decrypted shadow blocks to `decrypted`.
+///
+/// Fails if Dawn is not configured for the chain of the node.
+pub fn decryptor_exex<Node, E>(
+    ctx: ExExContext<Node>,
+    enclave: E,
+    decrypted: DecryptedTransactions,
+) -> eyre::Result<impl Future<Output = eyre::Result<()>>>
+where
+    Node: FullNodeComponents,
+    E: Enclave,
+{
+    let dawn = ctx.config.chain.dawn.ok_or_else(|| eyre::eyre!("Dawn is not configured"))?;
+    let anchor = enclave.anchor();
+    if anchor.delay != dawn.delay {
+        eyre::bail!(
+            "the enclave is anchored with a delay of {} instead of {}",
+            anchor.delay,
+            dawn.delay
+        )
+    }
+    let chain_id = ctx.config.chain.chain.id();
+    let decryptor = Decryptor::new(ctx.provider().clone(), enclave, dawn, chain_id, decrypted);
+    Ok(decryptor.run(ctx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dawn_crypto::{generate, reveal, Ciphertext, MasterPrivateKey, MasterPublicKey, Padding};
+    use dawn_enclave_protocol::{Anchor, Checkpoint, DecryptionKey, Label};
+    use dawn_light_client::{block_label, LightClient};
+    use parking_lot::Mutex;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_payload_primitives::{MandatoryTransactions, MandatoryTransactionsError};
+    use reth_primitives::{
+        bytes, public_key_to_address, sign_message, Address, Block, DawnPlaintext,
+        DawnTransactionError, Header, SealedBlockWithSenders, TxDawnEncrypted, TxKind, B256, U256,
+    };
+    use reth_provider::{test_utils::MockEthProvider, ExecutionOutcome};
+    use reth_testing_utils::generators::{self, generate_keys, sign_tx_with_key_pair};
+    use secp256k1::{SecretKey, SECP256K1};
+    use std::sync::Arc;
+
+    /// The secret key of the authority sealing the blocks after the genesis block.
+    const AUTHORITY: B256 = B256::repeat_byte(0x09);
+
+    /// An enclave that runs the light client of the SGX enclave, and records the labels it
+    /// reveals.
+    #[derive(Debug)]
+    struct MockEnclave {
+        msk: MasterPrivateKey,
+        light_client: Mutex<LightClient>,
+        revealed: Arc<Mutex<Vec<Label>>>,
+    }
+
+    impl Enclave for MockEnclave {
+        fn anchor(&self) -> Anchor {
+            *self.light_client.lock().anchor()
+        }
+
+        fn checkpoint(&self) -> Checkpoint {
+            self.light_client.lock().checkpoint()
+        }
+
+        async fn advance(&self, headers: Vec<Vec<u8>>) -> Result<(), EnclaveError> {
+            self.light_client
+                .lock()
+                .advance(&headers)
+                .map_err(|err| EnclaveError::Advance(err.to_string()))
+        }
+
+        async fn reveal(
+            &self,
+            proof: ShadowProof,
+            index: u32,
+        ) -> Result<DecryptionKey, EnclaveError> {
+            let label = self
+                .light_client
+                .lock()
+                .verify(&proof)
+                .and_then(|shadow| shadow.label(index))
+                .map_err(|err| EnclaveError::Reveal(err.to_string()))?;
+            self.revealed.lock().push(label.to_vec());
+            Ok(reveal(&label, &self.msk))
+        }
+
+        async fn reveal_block(
+            &self,
+            proof: ShadowProof,
+            chain_id: u64,
+        ) -> Result<DecryptionKey, EnclaveError> {
+            let label = self
+                .light_client
+                .lock()
+                .verify(&proof)
+                .map(|shadow| block_label(chain_id, shadow.number()))
+                .map_err(|err| EnclaveError::Reveal(err.to_string()))?;
+            self.revealed.lock().push(label.to_vec());
+            Ok(reveal(&label, &self.msk))
+        }
+    }
+
+    fn encrypted_transaction(mpk: &MasterPublicKey) -> TransactionSigned {
+        encrypted_transaction_for(mpk, None)
+    }
+
+    /// Returns an encrypted transaction of a new sender, encrypted for the label of `block` if
+    /// any, or else for its own label.
+    fn encrypted_transaction_for(
+        mpk: &MasterPublicKey,
+        block: Option<BlockNumber>,
+    ) -> TransactionSigned {
+        let key_pair = generate_keys(&mut generators::rng(), 1).remove(0);
+        let sender = public_key_to_address(key_pair.public_key());
+        let label = dawn_label(1, &sender, 0);
+        let identity = block.map_or(label, |number| dawn_block_label(1, number));
+        let payload = alloy_rlp::encode(DawnPlaintext::new(
+            Address::repeat_byte(0x22).into(),
+            bytes!("c0ffee"),
+        ));
+        let tx = TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(mpk, &identity, &payload, &label, Padding::None),
+        };
+        sign_tx_with_key_pair(key_pair, Transaction::DawnEncrypted(tx))
+    }
+
+    /// Returns the genesis block, which anchors the enclave.
+    fn genesis() -> SealedBlockWithSenders {
+        Block::default().seal_slow().seal_with_senders().unwrap()
+    }
+
+    /// Returns a post-Cancun child of `parent` sealed by [`AUTHORITY`], whose header commits to a
+    /// shadow block with `transactions`.
+    fn block(
+        parent: &SealedBlockWithSenders,
+        timestamp: u64,
+        transactions: Vec<TransactionSigned>,
+    ) -> SealedBlockWithSenders {
+        let shadow = ShadowBlock { beneficiary: Address::ZERO, transactions };
+        let mut header = Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            timestamp,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(B256::ZERO),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            shadow_root: Some(shadow.calculate_root()),
+            ..Default::default()
+        };
+        let signature = sign_message(AUTHORITY, header.hash_slow()).unwrap();
+        let mut seal = signature.to_bytes();
+        seal[64] = signature.odd_y_parity as u8;
+        header.extra_data = seal.to_vec().into();
+        Block { header, shadow: Some(shadow), ..Default::default() }
+            .seal_slow()
+            .seal_with_senders()
+            .unwrap()
+    }
+
+    fn chain(blocks: &[&SealedBlockWithSenders]) -> Arc<Chain> {
+        let blocks = blocks.iter().map(|block| (*block).clone());
+        Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None))
+    }
+
+    fn decryptor(
+        provider: &MockEthProvider,
+        genesis: &SealedBlockWithSenders,
+    ) -> (Decryptor<MockEthProvider, MockEnclave>, MasterPublicKey, Arc<Mutex<Vec<Label>>>) {
+        let (mpk, msk) = generate();
+        let dawn = DawnConfig::new(mpk);
+        let anchor = Anchor { number: genesis.number, hash: genesis.hash().0, delay: dawn.delay };
+        let authority = SecretKey::from_slice(AUTHORITY.as_slice()).unwrap().public_key(SECP256K1);
+        let light_client = Mutex::new(
+            LightClient::new(anchor, vec![public_key_to_address(authority).into()]).unwrap(),
+        );
+        let revealed = Arc::default();
+        let enclave = MockEnclave { msk, light_client, revealed: Arc::clone(&revealed) };
+        let decryptor =
+            Decryptor::new(provider.clone(), enclave, dawn, 1, DecryptedTransactions::default());
+        (decryptor, mpk, revealed)
+    }
+
+    #[tokio::test]
+    async fn decrypts_deep_shadow_blocks() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, revealed) = decryptor(&provider, &genesis);
+        let encrypted = encrypted_transaction(&mpk);
+        let block1 = block(&genesis, 1, vec![encrypted.clone()]);
+        let block2 = block(&block1, 2, vec![]);
+        for block in [&genesis, &block1, &block2] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+
+        // block 1 is only 1 block deep
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block1]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert!(revealed.lock().is_empty());
+        assert_eq!(decryptor.decrypted.get(block1.num_hash()), None);
+
+        // block 1 is 2 blocks deep, block 3 executes its shadow block
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block2]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert_eq!(revealed.lock().len(), 1);
+
+        let decrypted = decryptor.decrypted.get(block1.num_hash()).unwrap();
+        let [decrypted] = &decrypted[..] else { panic!("expected a single transaction") };
+        let Transaction::DawnDecrypted(tx) = &decrypted.transaction else {
+            panic!("expected a decrypted transaction")
+        };
+        assert_eq!(tx.to, TxKind::Call(Address::repeat_byte(0x22)));
+        assert_eq!(tx.input, bytes!("c0ffee"));
+        assert_eq!(decrypted.recover_signer(), encrypted.recover_signer());
+        assert_eq!(decrypted.verify_dawn(Some(&mpk), None), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn decrypts_shadow_blocks_in_order() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, revealed) = decryptor(&provider, &genesis);
+        let encrypted = vec![encrypted_transaction(&mpk), encrypted_transaction(&mpk)];
+        let block1 = block(&genesis, 1, encrypted.clone());
+        let block2 = block(&block1, 2, vec![]);
+        for block in [&genesis, &block1, &block2] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block1, &block2]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert_eq!(revealed.lock().len(), 2);
+
+        let decrypted = decryptor.decrypted.get(block1.num_hash()).unwrap();
+        assert_eq!(decrypted.len(), encrypted.len());
+        for (decrypted, encrypted) in decrypted.iter().zip(&encrypted) {
+            assert_eq!(decrypted.recover_signer(), encrypted.recover_signer());
+            assert_eq!(decrypted.verify_dawn(Some(&mpk), None), Ok(()));
+        }
+    }
+
+    #[tokio::test]
+    async fn decrypts_shadow_blocks_with_the_key_of_their_block() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, revealed) = decryptor(&provider, &genesis);
+        decryptor.dawn.labels = DawnLabels::Block;
+        let encrypted: Vec<_> = (0..3).map(|_| encrypted_transaction_for(&mpk, Some(1))).collect();
+        let block1 = block(&genesis, 1, encrypted.clone());
+        let block2 = block(&block1, 2, vec![]);
+        for block in [&genesis, &block1, &block2] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+
+        // a single key is revealed for all the transactions of the shadow block
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block1, &block2]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert_eq!(*revealed.lock(), vec![dawn_block_label(1, 1).to_vec()]);
+
+        let decrypted = decryptor.decrypted.get(block1.num_hash()).unwrap();
+        assert_eq!(decrypted.len(), encrypted.len());
+        for (decrypted, encrypted) in decrypted.iter().zip(&encrypted) {
+            let Transaction::DawnDecrypted(tx) = &decrypted.transaction else {
+                panic!("expected a decrypted transaction")
+            };
+            assert_eq!(tx.input, bytes!("c0ffee"));
+            assert_eq!(decrypted.recover_signer(), encrypted.recover_signer());
+            assert_eq!(decrypted.verify_dawn(Some(&mpk), Some(&dawn_block_label(1, 1))), Ok(()));
+            assert_eq!(
+                decrypted.verify_dawn(Some(&mpk), None),
+                Err(DawnTransactionError::InvalidDecryptionKey)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn advances_the_light_client_of_the_enclave() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, revealed) = decryptor(&provider, &genesis);
+        let block1 = block(&genesis, 1, vec![encrypted_transaction(&mpk)]);
+        let block2 = block(&block1, 2, vec![]);
+        let block3 = block(&block2, 3, vec![encrypted_transaction(&mpk)]);
+        let block4 = block(&block3, 4, vec![]);
+        for block in [&genesis, &block1, &block2, &block3, &block4] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block1, &block2]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert_eq!(decryptor.enclave.checkpoint().hash, genesis.hash().0);
+
+        // the checkpoint follows the decrypted shadow blocks, one block behind
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block3, &block4]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert_eq!(revealed.lock().len(), 2);
+        assert_eq!(decryptor.enclave.checkpoint(), Checkpoint { number: 2, hash: block2.hash().0 });
+        assert!(decryptor.decrypted.get(block3.num_hash()).is_some());
+    }
+
+    #[tokio::test]
+    async fn provides_decrypted_shadow_blocks_as_mandatory_transactions() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, _) = decryptor(&provider, &genesis);
+        let chain_spec = ChainSpecBuilder::mainnet().dawn(DawnConfig::new(mpk)).build();
+        let block1 = block(&genesis, 1, vec![encrypted_transaction(&mpk)]);
+        let block2 = block(&block1, 2, vec![]);
+        for block in [&genesis, &block1, &block2] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+        let decrypted = decryptor.decrypted.clone();
+        let mandatory_transactions = |parent: &SealedBlockWithSenders| {
+            decrypted.mandatory_transactions(&provider, &chain_spec, &parent.header, 3)
+        };
+
+        // block 3 must execute the shadow block of block 1, which is not decrypted yet
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block1]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        assert!(matches!(
+            mandatory_transactions(&block2),
+            Err(MandatoryTransactionsError::Missing(block)) if block == block1.num_hash()
+        ));
+
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&block2]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        let transactions = mandatory_transactions(&block2).unwrap().unwrap();
+        assert_eq!(Some(transactions), decrypted.get(block1.num_hash()));
+
+        // without Dawn, blocks are free to include any transaction
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let parent = &block2.header;
+        assert_eq!(
+            decrypted.mandatory_transactions(&provider, &chain_spec, parent, 3).unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn never_reveals_reverted_shadow_blocks() {
+        let provider = MockEthProvider::default();
+        let genesis = genesis();
+        let (mut decryptor, mpk, revealed) = decryptor(&provider, &genesis);
+        let old_block1 = block(&genesis, 1, vec![encrypted_transaction(&mpk)]);
+        let new_block1 = block(&genesis, 2, vec![]);
+        let new_block2 = block(&new_block1, 3, vec![]);
+        assert_ne!(old_block1.hash(), new_block1.hash());
+        // the provider is already past the reorg
+        for block in [&genesis, &new_block1, &new_block2] {
+            provider.add_block(block.hash(), block.clone().unseal().block);
+        }
+
+        let notification = ExExNotification::ChainCommitted { new: chain(&[&old_block1]) };
+        decryptor.on_notification(&notification).await.unwrap();
+        let notification = ExExNotification::ChainReorged {
+            old: chain(&[&old_block1]),
+            new: chain(&[&new_block1, &new_block2]),
+        };
+        decryptor.on_notification(&notification).await.unwrap();
+
+        assert!(revealed.lock().is_empty());
+        assert_eq!(decryptor.decrypted.get(old_block1.num_hash()), None);
+        assert_eq!(decryptor.decrypted.get(new_block1.num_hash()), Some(vec![]));
+    }
+}
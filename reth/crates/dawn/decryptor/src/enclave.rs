@@ -0,0 +1,484 @@
+//! Access to the SGX enclave of the Dawn secret management committee (SMC).
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process::{ExitStatus, Stdio},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use dawn_enclave_protocol::{
+    Anchor, Checkpoint, DecryptionKey, Request, Response, SealedLightClient,
+    SealedMasterPrivateKey, ShadowProof,
+};
+use parking_lot::Mutex;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    process::Command,
+};
+
+/// How long to wait for the response of an `app serve` daemon.
+const DAEMON_TIMEOUT: Duration = Duration::from_secs(30);
+/// Responses larger than this are rejected, like the requests of the daemon.
+const MAX_RESPONSE_LEN: usize = 16 << 20;
+
+/// Errors that can occur when talking to an [`Enclave`].
+#[derive(Debug, thiserror::Error)]
+pub enum EnclaveError {
+    /// The enclave could not be run or reached.
+    #[error("failed to run the enclave: {0}")]
+    Io(#[from] io::Error),
+    /// The enclave process failed.
+    #[error("enclave failed with {0}")]
+    Failed(ExitStatus),
+    /// The request or response could not be encoded.
+    #[error("malformed enclave message: {0}")]
+    Cbor(#[from] serde_cbor::Error),
+    /// The anchored master private key and the light client could not be read or written.
+    #[error("malformed sealed master private key: {0}")]
+    SealedKey(#[from] serde_json::Error),
+    /// The enclave refused to reveal a decryption key, for example because the proof that its
+    /// transaction is canonical is invalid.
+    #[error("enclave failed to reveal a decryption key: {0}")]
+    Reveal(String),
+    /// The enclave refused to advance its light client, for example because the headers are not
+    /// sealed by an authority of the chain.
+    #[error("enclave failed to advance its light client: {0}")]
+    Advance(String),
+    /// The enclave answered with a response that does not match the request.
+    #[error("unexpected enclave response {0:?}")]
+    UnexpectedResponse(Response),
+}
+
+/// An enclave holding the master private key of the SMC, bound to the anchor of the chain.
+///
+/// The enclave runs a light client of the chain, and only reveals the decryption key of an
+/// encrypted transaction given a [`ShadowProof`] that the shadow block including it is canonical
+/// and deep enough, since a revealed key can not be taken back.
+pub trait Enclave: Send + Sync + 'static {
+    /// Returns the anchor of the master private key, where the light client started.
+    fn anchor(&self) -> Anchor;
+
+    /// Returns the checkpoint of the light client: the block that the headers of the proofs
+    /// follow.
+    fn checkpoint(&self) -> Checkpoint;
+
+    /// Moves the checkpoint of the light client along `headers`, which follow it, to the parent
+    /// of the deepest block whose shadow block they prove.
+    fn advance(
+        &self,
+        headers: Vec<Vec<u8>>,
+    ) -> impl Future<Output = Result<(), EnclaveError>> + Send;
+
+    /// Reveals the decryption key of the encrypted transaction at `index` in the shadow block
+    /// proven by `proof`.
+    fn reveal(
+        &self,
+        proof: ShadowProof,
+        index: u32,
+    ) -> impl Future<Output = Result<DecryptionKey, EnclaveError>> + Send;
+
+    /// Reveals the decryption key of the block committing to the shadow block proven by `proof`,
+    /// for a chain of id `chain_id` whose ciphertexts are encrypted for the block including them.
+    fn reveal_block(
+        &self,
+        proof: ShadowProof,
+        chain_id: u64,
+    ) -> impl Future<Output = Result<DecryptionKey, EnclaveError>> + Send;
+
+    /// Reveals the decryption keys of the encrypted transactions at `indices` in the shadow block
+    /// proven by `proof`, one per index and in order.
+    ///
+    /// By default, keys are revealed one at a time.
+    fn reveal_batch(
+        &self,
+        proof: ShadowProof,
+        indices: Vec<u32>,
+    ) -> impl Future<Output = Result<Vec<DecryptionKey>, EnclaveError>> + Send {
+        async move {
+            let mut keys = Vec::with_capacity(indices.len());
+            for index in indices {
+                keys.push(self.reveal(proof.clone(), index).await?);
+            }
+            Ok(keys)
+        }
+    }
+}
+
+/// The enclave run by the SGX `app`.
+///
+/// The enclave is either loaded for each request by spawning the `app`, which reads a CBOR
+/// [`Request`] from its standard input and writes the CBOR [`Response`] to its standard output, or
+/// kept loaded by an `app serve` daemon, which reads requests from a socket, each preceded by its
+/// length as a 4-byte big-endian integer, and answers with responses framed the same way.
+///
+/// The light client of the enclave is sealed by it, and handed over with every request. Since the
+/// enclave only tells an outdated light client from the latest one while it stays loaded, the
+/// latest one is written back to the file it was loaded from whenever it advances.
+#[derive(Debug, Clone)]
+pub struct SgxEnclave {
+    /// How requests reach the enclave.
+    transport: Transport,
+    /// The anchor the master private key is sealed with.
+    anchor: Anchor,
+    /// The master private key of the SMC, sealed by the enclave.
+    sealed_key: Vec<u8>,
+    /// The latest light client of the enclave.
+    light_client: Arc<Mutex<SealedLightClient>>,
+    /// The JSON file the light client is written back to, if any.
+    path: Option<PathBuf>,
+}
+
+/// How an [`SgxEnclave`] reaches the enclave.
+#[derive(Debug, Clone)]
+enum Transport {
+    /// Spawns the `app` binary at this path, next to which the signed enclave is expected.
+    Process(PathBuf),
+    /// Connects to the `app serve` daemon at this address.
+    Daemon(DaemonAddress),
+}
+
+/// The address an `app serve` daemon listens on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonAddress {
+    /// A TCP socket address.
+    Tcp(SocketAddr),
+    /// The path of a Unix socket.
+    Unix(PathBuf),
+}
+
+impl FromStr for DaemonAddress {
+    type Err = Infallible;
+
+    /// Parses a TCP socket address, or else the path of a Unix socket, like `app serve` does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse::<SocketAddr>().map_or_else(|_| Self::Unix(s.into()), Self::Tcp))
+    }
+}
+
+impl SgxEnclave {
+    /// Creates a new [`SgxEnclave`] running `app` with the given master private key, sealed along
+    /// with `anchor`, and light client, which is only kept in memory.
+    pub fn new(
+        app: PathBuf,
+        anchor: Anchor,
+        sealed_key: SealedMasterPrivateKey,
+        light_client: SealedLightClient,
+    ) -> Self {
+        Self::with_transport(Transport::Process(app), anchor, sealed_key, light_client)
+    }
+
+    /// Creates a new [`SgxEnclave`] running `app`, reading the anchor, the anchored master
+    /// private key and the light client from the JSON file at `sealed_key`, as written by
+    /// `app anchor`.
+    pub fn load(app: PathBuf, sealed_key: &Path) -> Result<Self, EnclaveError> {
+        Self::load_with_transport(Transport::Process(app), sealed_key)
+    }
+
+    /// Creates a new [`SgxEnclave`] served by the `app serve` daemon at `address`, with the given
+    /// master private key, sealed along with `anchor`, and light client, which is only kept in
+    /// memory.
+    pub fn daemon(
+        address: DaemonAddress,
+        anchor: Anchor,
+        sealed_key: SealedMasterPrivateKey,
+        light_client: SealedLightClient,
+    ) -> Self {
+        Self::with_transport(Transport::Daemon(address), anchor, sealed_key, light_client)
+    }
+
+    /// Creates a new [`SgxEnclave`] served by the `app serve` daemon at `address`, reading the
+    /// anchor, the anchored master private key and the light client from the JSON file at
+    /// `sealed_key`, as written by `app anchor`.
+    pub fn load_daemon(address: DaemonAddress, sealed_key: &Path) -> Result<Self, EnclaveError> {
+        Self::load_with_transport(Transport::Daemon(address), sealed_key)
+    }
+
+    fn with_transport(
+        transport: Transport,
+        anchor: Anchor,
+        sealed_key: SealedMasterPrivateKey,
+        light_client: SealedLightClient,
+    ) -> Self {
+        Self {
+            transport,
+            anchor,
+            sealed_key: sealed_key.0,
+            light_client: Arc::new(Mutex::new(light_client)),
+            path: None,
+        }
+    }
+
+    fn load_with_transport(transport: Transport, path: &Path) -> Result<Self, EnclaveError> {
+        let (anchor, sealed_key, light_client) = serde_json::from_slice(&std::fs::read(path)?)?;
+        let enclave = Self::with_transport(transport, anchor, sealed_key, light_client);
+        Ok(Self { path: Some(path.to_path_buf()), ..enclave })
+    }
+
+    /// Writes the anchor, the anchored master private key and `light_client` to the JSON file the
+    /// enclave was loaded from, through a temporary file so that the file is never left half
+    /// written.
+    fn persist(&self, light_client: &SealedLightClient) -> Result<(), EnclaveError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let sealed_key = SealedMasterPrivateKey(self.sealed_key.clone());
+        let json = serde_json::to_vec(&(self.anchor, sealed_key, light_client))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Sends `request` to the enclave and waits for its response.
+    async fn request(&self, request: &Request) -> Result<Response, EnclaveError> {
+        let request = serde_cbor::to_vec(request)?;
+        let response = match &self.transport {
+            Transport::Process(app) => spawn(app, &request).await?,
+            Transport::Daemon(address) => {
+                tokio::time::timeout(DAEMON_TIMEOUT, connect(address, &request))
+                    .await
+                    .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))??
+            }
+        };
+        Ok(serde_cbor::from_slice(&response)?)
+    }
+}
+
+/// Sends `request` to a new enclave process and waits for its response.
+async fn spawn(app: &Path, request: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+    let mut command = Command::new(app);
+    // the `app` loads the signed enclave from its working directory
+    if let Some(dir) = app.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        command.current_dir(dir);
+    }
+    let mut child =
+        command.stdin(Stdio::piped()).stdout(Stdio::piped()).kill_on_drop(true).spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(request).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        return Err(EnclaveError::Failed(output.status))
+    }
+    Ok(output.stdout)
+}
+
+/// Sends `request` to the daemon at `address` over a new connection and waits for its response.
+async fn connect(address: &DaemonAddress, request: &[u8]) -> io::Result<Vec<u8>> {
+    match address {
+        DaemonAddress::Tcp(address) => exchange(TcpStream::connect(address).await?, request).await,
+        #[cfg(unix)]
+        DaemonAddress::Unix(path) => {
+            exchange(tokio::net::UnixStream::connect(path).await?, request).await
+        }
+        #[cfg(not(unix))]
+        DaemonAddress::Unix(_) => {
+            Err(io::Error::new(io::ErrorKind::Unsupported, "unix sockets are not supported"))
+        }
+    }
+}
+
+/// Writes the framed `request` to `stream` and reads the framed response.
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    request: &[u8],
+) -> io::Result<Vec<u8>> {
+    let len = u32::try_from(request.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "request too large"))?;
+    stream.write_u32(len).await?;
+    stream.write_all(request).await?;
+    stream.flush().await?;
+
+    // the daemon closes the connection if the enclave fails to handle the request
+    let len = stream.read_u32().await? as usize;
+    if len > MAX_RESPONSE_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("response of {len} bytes is too large"),
+        ))
+    }
+    let mut response = vec![0; len];
+    stream.read_exact(&mut response).await?;
+    Ok(response)
+}
+
+impl Enclave for SgxEnclave {
+    fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        self.light_client.lock().checkpoint
+    }
+
+    async fn advance(&self, headers: Vec<Vec<u8>>) -> Result<(), EnclaveError> {
+        let light_client = self.light_client.lock().clone();
+        match self.request(&Request::AdvanceHeaders(light_client, headers)).await? {
+            Response::AdvanceHeaders(light_client) => {
+                // the enclave now refuses the previous light client, so keep the new one even if
+                // it can not be written back
+                *self.light_client.lock() = light_client.clone();
+                self.persist(&light_client)
+            }
+            Response::Refused(reason) => Err(EnclaveError::Advance(reason)),
+            response => Err(EnclaveError::UnexpectedResponse(response)),
+        }
+    }
+
+    async fn reveal(&self, proof: ShadowProof, index: u32) -> Result<DecryptionKey, EnclaveError> {
+        let request = Request::Reveal(
+            proof,
+            index,
+            self.light_client.lock().clone(),
+            SealedMasterPrivateKey(self.sealed_key.clone()),
+        );
+        match self.request(&request).await? {
+            Response::Reveal(decryption_key) => Ok(decryption_key),
+            Response::Refused(reason) => Err(EnclaveError::Reveal(reason)),
+            response => Err(EnclaveError::UnexpectedResponse(response)),
+        }
+    }
+
+    async fn reveal_block(
+        &self,
+        proof: ShadowProof,
+        chain_id: u64,
+    ) -> Result<DecryptionKey, EnclaveError> {
+        let request = Request::RevealBlock(
+            proof,
+            chain_id,
+            self.light_client.lock().clone(),
+            SealedMasterPrivateKey(self.sealed_key.clone()),
+        );
+        match self.request(&request).await? {
+            Response::Reveal(decryption_key) => Ok(decryption_key),
+            Response::Refused(reason) => Err(EnclaveError::Reveal(reason)),
+            response => Err(EnclaveError::UnexpectedResponse(response)),
+        }
+    }
+
+    /// Reveals the decryption keys of the transactions at `indices` with a single request, so
+    /// that the enclave only unseals the master private key and verifies the proof once.
+    async fn reveal_batch(
+        &self,
+        proof: ShadowProof,
+        indices: Vec<u32>,
+    ) -> Result<Vec<DecryptionKey>, EnclaveError> {
+        let count = indices.len();
+        let request = Request::RevealBatch(
+            proof,
+            indices,
+            self.light_client.lock().clone(),
+            SealedMasterPrivateKey(self.sealed_key.clone()),
+        );
+        match self.request(&request).await? {
+            Response::RevealBatch(keys) if keys.len() == count => {
+                keys.into_iter().map(|key| key.map_err(EnclaveError::Reveal)).collect()
+            }
+            Response::Refused(reason) => Err(EnclaveError::Reveal(reason)),
+            response => Err(EnclaveError::UnexpectedResponse(response)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dawn_crypto::{generate, reveal, verify};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn parses_daemon_addresses() {
+        assert_eq!(
+            "127.0.0.1:7878".parse::<DaemonAddress>().unwrap(),
+            DaemonAddress::Tcp(([127, 0, 0, 1], 7878).into())
+        );
+        assert_eq!(
+            "/run/dawn/enclave.sock".parse::<DaemonAddress>().unwrap(),
+            DaemonAddress::Unix("/run/dawn/enclave.sock".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_responses() {
+        let (client, mut daemon) = tokio::io::duplex(64);
+        daemon.write_u32(u32::MAX).await.unwrap();
+        let err = exchange(client, b"request").await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn reveals_through_daemon() {
+        let (mpk, msk) = generate();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = DaemonAddress::Tcp(listener.local_addr().unwrap());
+        let proof = ShadowProof { headers: vec![vec![0xc0]], block: 0, shadow: vec![0xc0] };
+        let light_client = |number: u64, sealed| SealedLightClient {
+            checkpoint: Checkpoint { number, hash: [number as u8; 32] },
+            sealed,
+        };
+
+        // a daemon that serves one framed request per connection: it reveals a key, advances the
+        // light client, and refuses to reveal another key with the advanced light client
+        let expected = proof.clone();
+        let daemon = tokio::spawn(async move {
+            for step in 0..3 {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; stream.read_u32().await.unwrap() as usize];
+                stream.read_exact(&mut request).await.unwrap();
+                let response = match (step, serde_cbor::from_slice(&request).unwrap()) {
+                    (0, Request::Reveal(proof, 3, client, _)) => {
+                        assert_eq!((proof, client.sealed), (expected.clone(), vec![0]));
+                        Response::Reveal(reveal(b"label", &msk))
+                    }
+                    (1, Request::AdvanceHeaders(client, headers)) => {
+                        assert_eq!((headers, client.sealed), (expected.headers.clone(), vec![0]));
+                        Response::AdvanceHeaders(light_client(1, vec![1]))
+                    }
+                    (2, Request::Reveal(_, 3, client, _)) => {
+                        assert_eq!(client.sealed, vec![1]);
+                        Response::Refused("no".into())
+                    }
+                    (step, request) => panic!("unexpected request {request:?} at step {step}"),
+                };
+                let response = serde_cbor::to_vec(&response).unwrap();
+                stream.write_u32(response.len() as u32).await.unwrap();
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sealed_key.json");
+        let anchor = Anchor { number: 0, hash: [0; 32], delay: 2 };
+        let sealed_key = SealedMasterPrivateKey(vec![1, 2, 3]);
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&(anchor, sealed_key, light_client(0, vec![0]))).unwrap(),
+        )
+        .unwrap();
+        let enclave = SgxEnclave::load_daemon(address, &path).unwrap();
+        assert_eq!(enclave.checkpoint(), light_client(0, vec![]).checkpoint);
+
+        let decryption_key = enclave.reveal(proof.clone(), 3).await.unwrap();
+        assert!(verify(b"label", &mpk, &decryption_key));
+        enclave.advance(proof.headers.clone()).await.unwrap();
+        assert_eq!(enclave.checkpoint(), light_client(1, vec![]).checkpoint);
+        assert!(matches!(enclave.reveal(proof.clone(), 3).await, Err(EnclaveError::Reveal(_))));
+        daemon.await.unwrap();
+
+        // the advanced light client was written back for the next run
+        let (_, _, written): (Anchor, SealedMasterPrivateKey, SealedLightClient) =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written.sealed, vec![1]);
+
+        // the daemon is gone
+        assert!(matches!(enclave.reveal(proof, 3).await, Err(EnclaveError::Io(_))));
+    }
+}
@@ -0,0 +1,24 @@
+//! Decryption of the Dawn encrypted transactions of the canonical chain.
+//!
+//! Encrypted transactions are committed to by the shadow block of a block, and executed `d`
+//! blocks later, where `d` is the Dawn delay of the chain. The [`Decryptor`] runs as an `ExEx`
+//! that follows the canonical chain, requests the decryption keys of the encrypted transactions
+//! of a shadow block from the [`Enclave`] of the Dawn secret management committee once the block
+//! is deep enough, and writes the decrypted shadow block to [`DecryptedTransactions`], where block
+//! builders pick it up.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod decryptor;
+mod enclave;
+mod store;
+
+pub use decryptor::{decryptor_exex, Decryptor, DecryptorError};
+pub use enclave::{DaemonAddress, Enclave, EnclaveError, SgxEnclave};
+pub use store::DecryptedTransactions;
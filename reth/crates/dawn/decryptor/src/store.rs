@@ -0,0 +1,69 @@
+//! The decrypted shadow blocks shared with the payload builder.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use parking_lot::RwLock;
+use reth_chainspec::ChainSpec;
+use reth_payload_primitives::{MandatoryTransactions, MandatoryTransactionsError};
+use reth_primitives::{BlockHash, BlockNumHash, BlockNumber, SealedHeader, TransactionSigned};
+use reth_provider::{BlockHashReader, ProviderError};
+
+/// The transactions of decrypted shadow blocks, keyed by the block that committed to them.
+///
+/// The transactions of a decrypted shadow block are those of the shadow block, in order, with the
+/// encrypted transactions replaced by their decrypted or undecrypted form. They make up the body
+/// of the block [`DawnConfig::delay`](reth_chainspec::DawnConfig::delay) blocks later.
+///
+/// This is a cheaply cloneable handle: the [`Decryptor`](crate::Decryptor) fills it, and block
+/// builders read from it as their source of [`MandatoryTransactions`].
+#[derive(Debug, Clone, Default)]
+pub struct DecryptedTransactions {
+    inner: Arc<RwLock<BTreeMap<BlockNumber, (BlockHash, Vec<TransactionSigned>)>>>,
+}
+
+impl DecryptedTransactions {
+    /// Returns the decrypted transactions of the shadow block of `block`, if they are known.
+    pub fn get(&self, block: BlockNumHash) -> Option<Vec<TransactionSigned>> {
+        let inner = self.inner.read();
+        let (hash, transactions) = inner.get(&block.number)?;
+        (*hash == block.hash).then(|| transactions.clone())
+    }
+
+    /// Inserts the decrypted transactions of the shadow block of `block`.
+    pub(crate) fn insert(&self, block: BlockNumHash, transactions: Vec<TransactionSigned>) {
+        self.inner.write().insert(block.number, (block.hash, transactions));
+    }
+
+    /// Removes the shadow blocks of the blocks starting at `number`, which are no longer
+    /// canonical.
+    pub(crate) fn revert(&self, number: BlockNumber) {
+        self.inner.write().retain(|block, _| *block < number);
+    }
+
+    /// Removes the shadow blocks of the blocks before `number`.
+    pub(crate) fn prune(&self, number: BlockNumber) {
+        self.inner.write().retain(|block, _| *block >= number);
+    }
+}
+
+impl MandatoryTransactions for DecryptedTransactions {
+    fn mandatory_transactions<Client: BlockHashReader>(
+        &self,
+        client: &Client,
+        chain_spec: &ChainSpec,
+        parent: &SealedHeader,
+        timestamp: u64,
+    ) -> Result<Option<Vec<TransactionSigned>>, MandatoryTransactionsError> {
+        let number = parent.number + 1;
+        let Some(dawn) = chain_spec.dawn_at(number, timestamp) else { return Ok(None) };
+        let Some(committed) = number.checked_sub(dawn.delay) else { return Ok(None) };
+
+        let hash = if committed == parent.number {
+            parent.hash()
+        } else {
+            client.block_hash(committed)?.ok_or(ProviderError::HeaderNotFound(committed.into()))?
+        };
+        let block = BlockNumHash::new(committed, hash);
+        self.get(block).map(Some).ok_or(MandatoryTransactionsError::Missing(block))
+    }
+}
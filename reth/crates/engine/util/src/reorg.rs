@@ -386,6 +386,7 @@ where
             receipts_root: outcome.receipts_root_slow(reorg_target.header.number).unwrap(),
             logs_bloom: outcome.block_logs_bloom(reorg_target.header.number).unwrap(),
             requests_root: None, // TODO(prague)
+            shadow_root: None,
             gas_used: cumulative_gas_used,
             blob_gas_used,
             excess_blob_gas,
@@ -395,6 +396,7 @@ where
         ommers: reorg_target.ommers,
         withdrawals: reorg_target.withdrawals,
         requests: None, // TODO(prague)
+        shadow: None,
     }
     .seal_slow();
 
@@ -51,6 +51,8 @@ hardfork!(
         Cancun,
         /// Prague: <https://github.com/ethereum/execution-specs/blob/master/network-upgrades/mainnet-upgrades/prague.md>
         Prague,
+        /// Dawn: encrypted transactions, executed from shadow blocks.
+        Dawn,
     }
 );
 
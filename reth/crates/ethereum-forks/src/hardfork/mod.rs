@@ -71,6 +71,7 @@ mod tests {
             "ShAnGhAI",
             "CaNcUn",
             "PrAguE",
+            "dAwN",
         ];
         let expected_hardforks = [
             EthereumHardfork::Frontier,
@@ -91,6 +92,7 @@ mod tests {
             EthereumHardfork::Shanghai,
             EthereumHardfork::Cancun,
             EthereumHardfork::Prague,
+            EthereumHardfork::Dawn,
         ];
 
         let hardforks: Vec<EthereumHardfork> =
@@ -21,6 +21,15 @@ pub trait EthereumHardforks: Hardforks {
         self.is_fork_active_at_timestamp(EthereumHardfork::Prague, timestamp)
     }
 
+    /// Convenience method to check if [`EthereumHardfork::Dawn`] is active at a given block number
+    /// and timestamp.
+    ///
+    /// Dawn can be activated either by block number or by timestamp.
+    fn is_dawn_active_at_block_and_timestamp(&self, block_number: u64, timestamp: u64) -> bool {
+        let condition = self.fork(EthereumHardfork::Dawn);
+        condition.active_at_block(block_number) || condition.active_at_timestamp(timestamp)
+    }
+
     /// Convenience method to check if [`EthereumHardfork::Byzantium`] is active at a given block
     /// number.
     fn is_byzantium_active_at_block(&self, block_number: u64) -> bool {
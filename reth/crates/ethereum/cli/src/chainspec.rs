@@ -1,6 +1,6 @@
 use alloy_genesis::Genesis;
 use clap::{builder::TypedValueParser, error::Result, Arg, Command};
-use reth_chainspec::{ChainSpec, DEV, HOLESKY, MAINNET, SEPOLIA};
+use reth_chainspec::{ChainSpec, DawnConfig, DEV, HOLESKY, MAINNET, SEPOLIA};
 use reth_cli::chainspec::ChainSpecParser;
 use std::{ffi::OsStr, fs, path::PathBuf, sync::Arc};
 
@@ -30,6 +30,8 @@ fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
 
             // both serialized Genesis and ChainSpec structs supported
             let genesis: Genesis = serde_json::from_str(&raw)?;
+            // the chain spec ignores a malformed dawn section, so reject it here
+            DawnConfig::extract_from(&genesis).transpose()?;
 
             Arc::new(genesis.into())
         }
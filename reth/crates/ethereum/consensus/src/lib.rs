@@ -23,7 +23,7 @@ use reth_primitives::{
 use std::{sync::Arc, time::SystemTime};
 
 mod validation;
-pub use validation::validate_block_post_execution;
+pub use validation::{validate_block_post_execution, validate_dawn_transactions};
 
 /// Ethereum beacon consensus
 ///
@@ -120,6 +120,15 @@ impl Consensus for EthBeaconConsensus {
             return Err(ConsensusError::RequestsRootUnexpected)
         }
 
+        // Dawn: shadow blocks
+        if self.chain_spec.dawn_at(header.number, header.timestamp).is_some() {
+            if header.shadow_root.is_none() {
+                return Err(ConsensusError::ShadowRootMissing)
+            }
+        } else if header.shadow_root.is_some() {
+            return Err(ConsensusError::ShadowRootUnexpected)
+        }
+
         Ok(())
     }
 
@@ -205,7 +214,8 @@ impl Consensus for EthBeaconConsensus {
     }
 
     fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
-        validate_block_pre_execution(block, &self.chain_spec)
+        validate_block_pre_execution(block, &self.chain_spec)?;
+        validate_dawn_transactions(block, &self.chain_spec)
     }
 
     fn validate_block_post_execution(
@@ -220,8 +230,14 @@ impl Consensus for EthBeaconConsensus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chainspec::ChainSpecBuilder;
-    use reth_primitives::{proofs, B256};
+    use dawn_crypto::{
+        generate, reveal, Ciphertext, DecryptionKey, MasterPrivateKey, MasterPublicKey, Padding,
+    };
+    use reth_chainspec::{ChainSpecBuilder, DawnConfig, DawnLabels};
+    use reth_primitives::{
+        dawn_block_label, dawn_label, proofs, sign_message, Address, DawnTransactionError,
+        Signature, Transaction, TransactionSigned, TxDawnEncrypted, B256,
+    };
 
     fn header_with_gas_limit(gas_limit: u64) -> SealedHeader {
         let header = Header { gas_limit, ..Default::default() };
@@ -309,4 +325,186 @@ mod tests {
 
         assert_eq!(EthBeaconConsensus::new(chain_spec).validate_header(&header), Ok(()));
     }
+
+    #[test]
+    fn dawn_shadow_root() {
+        // ensures that blocks have a shadow root if and only if dawn is active
+        let dawn = DawnConfig { block: Some(1), ..reth_chainspec::DEV.dawn.unwrap() };
+        let chain_spec =
+            Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().dawn(dawn).build());
+        let consensus = EthBeaconConsensus::new(chain_spec);
+
+        let header = |number, shadow_root| {
+            Header {
+                number,
+                base_fee_per_gas: Some(1337u64),
+                withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+                shadow_root,
+                ..Default::default()
+            }
+            .seal_slow()
+        };
+
+        assert_eq!(consensus.validate_header(&header(0, None)), Ok(()));
+        assert_eq!(
+            consensus.validate_header(&header(0, Some(B256::ZERO))),
+            Err(ConsensusError::ShadowRootUnexpected)
+        );
+        assert_eq!(
+            consensus.validate_header(&header(1, None)),
+            Err(ConsensusError::ShadowRootMissing)
+        );
+        assert_eq!(consensus.validate_header(&header(1, Some(B256::ZERO))), Ok(()));
+    }
+
+    #[test]
+    fn dawn_encrypted_transactions() {
+        // ensures that blocks never contain encrypted transactions: they are not allowed before
+        // dawn, and go into shadow blocks once dawn is active
+        let dawn = DawnConfig { block: Some(1), ..reth_chainspec::DEV.dawn.unwrap() };
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().dawn(dawn).build();
+
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::DawnEncrypted(TxDawnEncrypted {
+                chain_id: 1,
+                nonce: 0,
+                gas_limit: 21_000,
+                max_fee_per_gas: 0,
+                max_priority_fee_per_gas: 0,
+                value: U256::ZERO,
+                access_list: Default::default(),
+                ciphertext: Ciphertext::encrypt(
+                    &dawn.master_public_key,
+                    &[0; 96],
+                    &[],
+                    &[],
+                    Padding::None,
+                ),
+            }),
+            Signature::default(),
+        );
+        let block = |number| SealedBlock {
+            header: Header { number, ..Default::default() }.seal_slow(),
+            body: vec![tx.clone()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            validate_dawn_transactions(&block(0), &chain_spec),
+            Err(ConsensusError::DawnTransactionUnexpected { hash: tx.hash() })
+        );
+        assert_eq!(
+            validate_dawn_transactions(&block(1), &chain_spec),
+            Err(ConsensusError::DawnTransactionEncrypted { hash: tx.hash() })
+        );
+    }
+
+    /// Returns a transaction sent with `nonce` that does not decrypt, since it was encrypted for
+    /// another label, carrying the decryption key returned by `decryption_key` for its sender.
+    fn undecrypted_transaction(
+        mpk: &MasterPublicKey,
+        nonce: u64,
+        decryption_key: impl FnOnce(&Address) -> DecryptionKey,
+    ) -> TransactionSigned {
+        let encrypted = Transaction::DawnEncrypted(TxDawnEncrypted {
+            chain_id: 1,
+            nonce,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(mpk, &[0; 96], &[], &[], Padding::None),
+        });
+        let signature = sign_message(B256::repeat_byte(0x11), encrypted.signature_hash()).unwrap();
+        let encrypted = TransactionSigned::from_transaction_and_signature(encrypted, signature);
+        let sender = encrypted.recover_signer().unwrap();
+        let Transaction::DawnEncrypted(tx) = &encrypted.transaction else { unreachable!() };
+        TransactionSigned::from_transaction_and_signature(
+            tx.decrypt(sender, decryption_key(&sender)),
+            signature,
+        )
+    }
+
+    #[test]
+    fn dawn_decryption_keys() {
+        // ensures that the decryption keys of a block are checked together, and that the invalid
+        // ones are still found
+        let (mpk, msk) = generate();
+        let chain_spec =
+            ChainSpecBuilder::mainnet().shanghai_activated().dawn(DawnConfig::new(mpk)).build();
+
+        let undecrypted = |nonce: u64, msk: &MasterPrivateKey| {
+            undecrypted_transaction(&mpk, nonce, |sender| {
+                reveal(&dawn_label(1, sender, nonce), msk)
+            })
+        };
+        let block = |body| SealedBlock {
+            header: Header { number: 1, ..Default::default() }.seal_slow(),
+            body,
+            ..Default::default()
+        };
+
+        let body: Vec<_> = (0..4).map(|nonce| undecrypted(nonce, &msk)).collect();
+        assert_eq!(validate_dawn_transactions(&block(body.clone()), &chain_spec), Ok(()));
+
+        // a key issued by another SMC
+        let (_, other_msk) = generate();
+        let mut invalid = body;
+        invalid[2] = undecrypted(2, &other_msk);
+        assert_eq!(
+            validate_dawn_transactions(&block(invalid.clone()), &chain_spec),
+            Err(ConsensusError::InvalidDawnTransaction {
+                hash: invalid[2].hash(),
+                error: DawnTransactionError::InvalidDecryptionKey,
+            })
+        );
+    }
+
+    #[test]
+    fn dawn_block_decryption_key() {
+        // ensures that the transactions of a block labelled chain all carry the key of the block
+        // whose shadow block they come from
+        let (mpk, msk) = generate();
+        let dawn = DawnConfig { labels: DawnLabels::Block, ..DawnConfig::new(mpk) };
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().dawn(dawn).build();
+        let number = 1 + dawn.delay;
+
+        let undecrypted = |nonce: u64, decryption_key: DecryptionKey| {
+            undecrypted_transaction(&mpk, nonce, |_| decryption_key)
+        };
+        let block = |body| SealedBlock {
+            header: Header { number, ..Default::default() }.seal_slow(),
+            body,
+            ..Default::default()
+        };
+
+        let key = reveal(&dawn_block_label(1, 1), &msk);
+        let body: Vec<_> = (0..4).map(|nonce| undecrypted(nonce, key.clone())).collect();
+        assert_eq!(validate_dawn_transactions(&block(body.clone()), &chain_spec), Ok(()));
+
+        // the key of another block
+        let other = reveal(&dawn_block_label(1, 2), &msk);
+        let mut invalid = body.clone();
+        invalid[2] = undecrypted(2, other);
+        assert_eq!(
+            validate_dawn_transactions(&block(invalid.clone()), &chain_spec),
+            Err(ConsensusError::InvalidDawnTransaction {
+                hash: invalid[2].hash(),
+                error: DawnTransactionError::InvalidDecryptionKey,
+            })
+        );
+
+        // a key valid for the transaction label rather than the block label
+        let mut invalid = body;
+        invalid[0] =
+            undecrypted_transaction(&mpk, 0, |sender| reveal(&dawn_label(1, sender, 0), &msk));
+        assert_eq!(
+            validate_dawn_transactions(&block(invalid.clone()), &chain_spec),
+            Err(ConsensusError::InvalidDawnTransaction {
+                hash: invalid[0].hash(),
+                error: DawnTransactionError::InvalidDecryptionKey,
+            })
+        );
+    }
 }
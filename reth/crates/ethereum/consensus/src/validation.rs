@@ -1,9 +1,82 @@
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::{ChainSpec, DawnLabels, EthereumHardforks};
 use reth_consensus::ConsensusError;
 use reth_primitives::{
-    gas_spent_by_transactions, BlockWithSenders, Bloom, GotExpected, Receipt, Request, B256,
+    dawn_block_label, gas_spent_by_transactions, BlockWithSenders, Bloom, GotExpected, Receipt,
+    Request, SealedBlock, TxType, B256,
 };
 
+/// Validate the Dawn decrypted and undecrypted transactions of a block:
+///
+/// - Checks that the block contains no Dawn transactions if Dawn is not active at the block
+/// - Checks that the block contains no encrypted transactions otherwise: they are included in
+///   shadow blocks, and only executed in decrypted or undecrypted form
+/// - Checks that their decryption key was issued by the SMC for the label derived from their chain
+///   id, sender and nonce, or, if the chain labels ciphertexts by block, for the label of the block
+///   [`DawnConfig::delay`](reth_chainspec::DawnConfig::delay) blocks earlier, see [`DawnLabels`]
+/// - Checks that the sender signed the re-encrypted transaction
+/// - Checks that undecrypted transactions fail to decrypt
+///
+/// The decryption keys are checked against the master public key of the chain, all at once, see
+/// [`dawn_crypto::verify_batch`], or, if the chain labels ciphertexts by block, by checking the
+/// single key of the block once. That the re-encrypted transactions were included in the shadow
+/// block [`DawnConfig::delay`](reth_chainspec::DawnConfig::delay) blocks earlier is checked
+/// against the shadow history when executing the block.
+pub fn validate_dawn_transactions(
+    block: &SealedBlock,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    let Some(dawn) = chain_spec.dawn_at(block.number, block.timestamp) else {
+        if let Some(tx) = block.body.iter().find(|tx| tx.is_dawn()) {
+            return Err(ConsensusError::DawnTransactionUnexpected { hash: tx.hash() })
+        }
+        return Ok(())
+    };
+    if let Some(tx) = block.body.iter().find(|tx| tx.tx_type() == TxType::DawnEncrypted) {
+        return Err(ConsensusError::DawnTransactionEncrypted { hash: tx.hash() })
+    }
+
+    let keys: Vec<_> = block
+        .body
+        .iter()
+        .enumerate()
+        .filter_map(|(index, tx)| tx.dawn_decryption_key().map(|key| (index, key)))
+        .collect();
+    let mut key_verified = vec![true; block.body.len()];
+    match dawn.labels {
+        // check all the decryption keys with a single pairing, and only locate the invalid ones if
+        // there are any
+        DawnLabels::Transaction => {
+            let items: Vec<_> = keys.iter().map(|(_, (label, key))| (&label[..], *key)).collect();
+            if let Err(invalid) = dawn_crypto::verify_batch(&dawn.master_public_key, &items) {
+                for item in invalid {
+                    key_verified[keys[item].0] = false;
+                }
+            }
+        }
+        // a block has a single valid key, which all the transactions must carry
+        DawnLabels::Block => {
+            if let Some((_, (_, block_key))) = keys.first() {
+                let label = dawn_block_label(
+                    chain_spec.chain.id(),
+                    block.number.saturating_sub(dawn.delay),
+                );
+                let valid = dawn_crypto::verify(&label, &dawn.master_public_key, block_key);
+                for (index, (_, key)) in &keys {
+                    key_verified[*index] = valid && key == block_key;
+                }
+            }
+        }
+    }
+
+    for (tx, key_verified) in block.body.iter().zip(key_verified) {
+        if let Err(error) = tx.verify_dawn_with_key(key_verified) {
+            return Err(ConsensusError::InvalidDawnTransaction { hash: tx.hash(), error })
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate a block with regard to execution results:
 ///
 /// - Compares the receipts root in the block header to the block body
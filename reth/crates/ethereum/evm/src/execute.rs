@@ -8,6 +8,7 @@ use core::fmt::Display;
 use reth_chainspec::{ChainSpec, EthereumHardforks, MAINNET};
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
+    dawn,
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
@@ -26,12 +27,15 @@ use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::BlockBatchRecord,
     db::states::bundle_state::BundleRetention,
-    state_change::{apply_blockhashes_update, post_block_balance_increments},
+    state_change::{
+        apply_blockhashes_update, apply_shadow_block_update, post_block_balance_increments,
+    },
     Evm, State,
 };
 use revm_primitives::{
     db::{Database, DatabaseCommit},
-    BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ResultAndState,
+    BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, InvalidTransaction,
+    ResultAndState,
 };
 
 #[cfg(not(feature = "std"))]
@@ -163,6 +167,40 @@ where
             block.number,
             block.parent_hash,
         )?;
+        // The fees of the transactions go to the producer of the delayed shadow block that
+        // committed to them.
+        let delayed_beneficiary = apply_shadow_block_update(
+            evm.db_mut(),
+            &self.chain_spec,
+            block.timestamp,
+            block.number,
+            &block.body,
+            block.shadow.as_ref(),
+        )?;
+        if let Some(beneficiary) = delayed_beneficiary {
+            evm.block_mut().coinbase = beneficiary;
+        }
+
+        // The transactions of the shadow block only execute `d` blocks later, but the ones that
+        // can not become valid by then are rejected now.
+        if let Some(shadow) = block.shadow.as_ref().filter(|_| delayed_beneficiary.is_some()) {
+            for transaction in &shadow.transactions {
+                let sender = transaction
+                    .recover_signer()
+                    .ok_or(BlockValidationError::SenderRecoveryError)?;
+                dawn::validate_shadow_transaction(evm.db_mut(), &transaction.transaction, sender)
+                    .map_err(|err| {
+                    let error = match err {
+                        EVMError::Database(e) => EVMError::Database(e.into()),
+                        EVMError::Transaction(e) => EVMError::Transaction(e),
+                        EVMError::Header(e) => EVMError::Header(e),
+                        EVMError::Custom(e) => EVMError::Custom(e),
+                        EVMError::Precompile(e) => EVMError::Precompile(e),
+                    };
+                    BlockValidationError::EVM { hash: transaction.hash, error: Box::new(error) }
+                })?;
+            }
+        }
 
         // execute transactions
         let mut cumulative_gas_used = 0;
@@ -171,7 +209,8 @@ where
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
             let block_available_gas = block.header.gas_limit - cumulative_gas_used;
-            if transaction.gas_limit() > block_available_gas {
+            let fits = transaction.gas_limit() <= block_available_gas;
+            if !fits && delayed_beneficiary.is_none() {
                 return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
                     transaction_gas_limit: transaction.gas_limit(),
                     block_available_gas,
@@ -182,7 +221,28 @@ where
             self.evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
 
             // Execute transaction.
-            let ResultAndState { result, state } = evm.transact().map_err(move |err| {
+            let result_and_state = if fits {
+                dawn::transact(&mut evm, &transaction.transaction, *sender)
+            } else {
+                Err(InvalidTransaction::CallerGasLimitMoreThanBlock.into())
+            };
+            // Delayed transactions that became invalid since their inclusion are skipped.
+            if delayed_beneficiary.is_some() &&
+                result_and_state.as_ref().is_err_and(dawn::is_invalid_transaction)
+            {
+                receipts.push(
+                    #[allow(clippy::needless_update)] // side-effect of optimism fields
+                    Receipt {
+                        tx_type: transaction.tx_type(),
+                        success: false,
+                        cumulative_gas_used,
+                        logs: vec![],
+                        ..Default::default()
+                    },
+                );
+                continue
+            }
+            let ResultAndState { result, state } = result_and_state.map_err(move |err| {
                 let new_err = match err {
                     EVMError::Transaction(e) => EVMError::Transaction(e),
                     EVMError::Header(e) => EVMError::Header(e),
@@ -471,15 +531,17 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_evm::dawn::encrypted_transaction_intrinsic_gas;
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256, public_key_to_address, Account, Address, Block, ShadowBlock, Transaction,
+        TxDawnEncrypted, TxKind, TxLegacy, B256, SHADOW_HISTORY_ADDRESS,
     };
     use reth_revm::{
         database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
     };
     use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
-    use revm_primitives::{b256, fixed_bytes, Bytes, BLOCKHASH_SERVE_WINDOW};
+    use revm_primitives::{b256, fixed_bytes, Bytes, SpecId, BLOCKHASH_SERVE_WINDOW};
     use secp256k1::{Keypair, Secp256k1};
     use std::collections::HashMap;
 
@@ -553,6 +615,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -584,6 +647,7 @@ mod tests {
                         ommers: vec![],
                         withdrawals: None,
                         requests: None,
+                        shadow: None,
                     },
                     senders: vec![],
                 },
@@ -650,6 +714,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -703,6 +768,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -747,6 +813,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -774,6 +841,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -833,6 +901,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -903,6 +972,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -953,6 +1023,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -994,6 +1065,7 @@ mod tests {
             timestamp: 1,
             number: fork_activation_block,
             requests_root: Some(EMPTY_ROOT_HASH),
+            shadow_root: None,
             ..Header::default()
         };
         let provider = executor_provider(chain_spec);
@@ -1010,6 +1082,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -1059,6 +1132,7 @@ mod tests {
             timestamp: 1,
             number: fork_activation_block,
             requests_root: Some(EMPTY_ROOT_HASH),
+            shadow_root: None,
             ..Header::default()
         };
 
@@ -1073,6 +1147,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -1127,6 +1202,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -1152,6 +1228,7 @@ mod tests {
             timestamp: 1,
             number: 1,
             requests_root: Some(EMPTY_ROOT_HASH),
+            shadow_root: None,
             ..Header::default()
         };
         let header_hash = header.hash_slow();
@@ -1166,6 +1243,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -1195,6 +1273,7 @@ mod tests {
             timestamp: 1,
             number: 2,
             requests_root: Some(EMPTY_ROOT_HASH),
+            shadow_root: None,
             ..Header::default()
         };
 
@@ -1208,6 +1287,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![],
                     },
@@ -1297,6 +1377,7 @@ mod tests {
                         ommers: vec![],
                         withdrawals: None,
                         requests: None,
+                        shadow: None,
                     }
                     .with_recovered_senders()
                     .unwrap(),
@@ -1385,6 +1466,7 @@ mod tests {
                     ommers: vec![],
                     withdrawals: None,
                     requests: None,
+                    shadow: None,
                 }
                 .with_recovered_senders()
                 .unwrap(),
@@ -1405,4 +1487,178 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn dawn_encrypted_transaction_inclusion() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let master_public_key = reth_chainspec::DEV.dawn.unwrap().master_public_key;
+
+        let mut db = StateProviderTest::default();
+        let sender_key_pair = Keypair::new(&Secp256k1::new(), &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let encrypted = TxDawnEncrypted {
+            chain_id: chain_spec.chain.id(),
+            nonce: 0,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 3,
+            value: U256::from(1),
+            access_list: Default::default(),
+            ciphertext: dawn_crypto::Ciphertext::encrypt(
+                &master_public_key,
+                &[0; 96],
+                &[1; 36],
+                &[],
+                dawn_crypto::Padding::None,
+            ),
+        };
+        let intrinsic_gas = encrypted_transaction_intrinsic_gas(SpecId::SHANGHAI, &encrypted);
+        let tx = sign_tx_with_key_pair(sender_key_pair, Transaction::DawnEncrypted(encrypted));
+
+        let beneficiary = Address::with_last_byte(0x42);
+        let header = Header {
+            timestamp: 1,
+            number: 1,
+            gas_limit: 10_000_000,
+            base_fee_per_gas: Some(7),
+            beneficiary,
+            ..Header::default()
+        };
+        let block = Block {
+            header,
+            body: vec![tx],
+            ommers: vec![],
+            withdrawals: None,
+            requests: None,
+            shadow: None,
+        }
+        .with_recovered_senders()
+        .unwrap();
+
+        let provider = executor_provider(chain_spec);
+        let mut executor = provider.executor(StateProviderDatabase::new(&db));
+        let output = executor.execute_without_verification(&block, U256::ZERO).unwrap();
+
+        // only the intrinsic gas is charged, without running any EVM code
+        assert_eq!(output.gas_used, intrinsic_gas);
+        let receipt = output.receipts.first().unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.cumulative_gas_used, intrinsic_gas);
+        assert!(receipt.logs.is_empty());
+
+        // the sender pays for the intrinsic gas and its nonce is bumped, no value is transferred
+        let sender = executor.state.basic(sender_address).unwrap().unwrap();
+        assert_eq!(sender.nonce, 1);
+        assert_eq!(sender.balance, U256::from(ETH_TO_WEI - 10 * intrinsic_gas as u128));
+        // the beneficiary gets the priority fee
+        let beneficiary = executor.state.basic(beneficiary).unwrap().unwrap();
+        assert_eq!(beneficiary.balance, U256::from(3 * intrinsic_gas));
+
+        // no contract was created
+        let created = sender_address.create(0);
+        assert!(executor.state.basic(created).unwrap().is_none());
+    }
+
+    #[test]
+    fn dawn_invalid_delayed_transaction() {
+        let dawn = reth_chainspec::DawnConfig {
+            delay: 2,
+            block: Some(0),
+            timestamp: None,
+            ..reth_chainspec::DEV.dawn.unwrap()
+        };
+        let chain_spec =
+            Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().dawn(dawn).build());
+
+        let sender_key_pair = Keypair::new(&Secp256k1::new(), &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        let transfer = |nonce| {
+            sign_tx_with_key_pair(
+                sender_key_pair,
+                Transaction::Legacy(TxLegacy {
+                    chain_id: Some(chain_spec.chain.id()),
+                    nonce,
+                    gas_price: 10,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::with_last_byte(1)),
+                    value: U256::from(1),
+                    input: Bytes::new(),
+                }),
+            )
+        };
+
+        // the sender spent nonce 0 since its transaction with nonce 1 was included
+        let delayed = transfer(1);
+        let delayed_beneficiary = Address::with_last_byte(0x42);
+        let delayed_root =
+            ShadowBlock { beneficiary: delayed_beneficiary, transactions: vec![delayed.clone()] }
+                .calculate_root();
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            sender_address,
+            Account { nonce: 2, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+        db.insert_account(
+            SHADOW_HISTORY_ADDRESS,
+            Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None },
+            None,
+            HashMap::from([
+                (B256::with_last_byte(2), delayed_root.into()),
+                (B256::with_last_byte(3), delayed_beneficiary.into_word().into()),
+            ]),
+        );
+
+        let block = |shadow: ShadowBlock| {
+            let header = Header {
+                timestamp: 1,
+                number: 3,
+                gas_limit: 10_000_000,
+                base_fee_per_gas: Some(7),
+                shadow_root: Some(shadow.calculate_root()),
+                ..Header::default()
+            };
+            Block {
+                header,
+                body: vec![delayed.clone()],
+                ommers: vec![],
+                withdrawals: None,
+                requests: None,
+                shadow: Some(shadow),
+            }
+            .with_recovered_senders()
+            .unwrap()
+        };
+
+        // the delayed transaction is skipped instead of invalidating the block
+        let provider = executor_provider(chain_spec);
+        let mut executor = provider.executor(StateProviderDatabase::new(&db));
+        let shadow = ShadowBlock { beneficiary: Address::ZERO, transactions: vec![transfer(2)] };
+        let output = executor.execute_without_verification(&block(shadow), U256::ZERO).unwrap();
+        assert_eq!(output.gas_used, 0);
+        let receipt = output.receipts.first().unwrap();
+        assert!(!receipt.success);
+        assert_eq!(receipt.cumulative_gas_used, 0);
+        let sender = executor.state.basic(sender_address).unwrap().unwrap();
+        assert_eq!(sender.nonce, 2);
+        assert_eq!(sender.balance, U256::from(ETH_TO_WEI));
+
+        // a shadow transaction whose nonce was already used is rejected at inclusion
+        let mut executor = provider.executor(StateProviderDatabase::new(&db));
+        let shadow = ShadowBlock { beneficiary: Address::ZERO, transactions: vec![transfer(1)] };
+        let err = executor.execute_without_verification(&block(shadow), U256::ZERO).unwrap_err();
+        assert!(matches!(
+            err,
+            BlockExecutionError::Validation(BlockValidationError::EVM { error, .. })
+                if matches!(*error, EVMError::Transaction(InvalidTransaction::NonceTooLow { .. }))
+        ));
+    }
 }
@@ -20,6 +20,7 @@ use reth_node_builder::{
     BuilderContext, ConfigureEvm, Node, PayloadBuilderConfig, PayloadTypes,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_primitives::MandatoryTransactions;
 use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
@@ -141,7 +142,7 @@ where
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
-            .with_head_timestamp(ctx.head().timestamp)
+            .with_head_block(ctx.head().number, ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
@@ -194,24 +195,44 @@ where
 }
 
 /// A basic ethereum payload service.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder<Evm = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<Evm = EthEvmConfig, Mandatory = ()> {
     /// The EVM configuration to use for the payload builder.
     pub evm_config: Evm,
+    /// The source of the transactions payloads must start with.
+    pub mandatory_transactions: Mandatory,
 }
 
 impl<EVM> EthereumPayloadBuilder<EVM> {
     /// Create a new instance with the given evm config.
     pub const fn new(evm_config: EVM) -> Self {
-        Self { evm_config }
+        Self { evm_config, mandatory_transactions: () }
+    }
+}
+
+impl<EVM: Default> Default for EthereumPayloadBuilder<EVM> {
+    fn default() -> Self {
+        Self::new(EVM::default())
+    }
+}
+
+impl<EVM, Mandatory> EthereumPayloadBuilder<EVM, Mandatory> {
+    /// Sets the source of the transactions payloads must start with.
+    pub fn with_mandatory_transactions<M>(
+        self,
+        mandatory_transactions: M,
+    ) -> EthereumPayloadBuilder<EVM, M> {
+        EthereumPayloadBuilder { evm_config: self.evm_config, mandatory_transactions }
     }
 }
 
-impl<Node, Evm, Pool> PayloadServiceBuilder<Node, Pool> for EthereumPayloadBuilder<Evm>
+impl<Node, Evm, Mandatory, Pool> PayloadServiceBuilder<Node, Pool>
+    for EthereumPayloadBuilder<Evm, Mandatory>
 where
     Node: FullNodeTypes,
     Evm: ConfigureEvm,
+    Mandatory: MandatoryTransactions + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
     <Node as NodeTypes>::Engine: PayloadTypes<
         BuiltPayload = EthBuiltPayload,
@@ -225,7 +246,8 @@ where
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
         let payload_builder =
-            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(self.evm_config);
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(self.evm_config)
+                .with_mandatory_transactions(self.mandatory_transactions);
         let conf = ctx.payload_builder_config();
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
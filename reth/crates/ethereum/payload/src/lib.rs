@@ -15,6 +15,7 @@ use reth_basic_payload_builder::{
 };
 use reth_errors::RethError;
 use reth_evm::{
+    dawn,
     system_calls::{
         post_block_withdrawal_requests_contract_call, pre_block_beacon_root_contract_call,
     },
@@ -25,18 +26,24 @@ use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{
     error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
 };
+use reth_payload_primitives::{MandatoryTransactions, MandatoryTransactionsError};
 use reth_primitives::{
     constants::{
         eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
     },
     eip4844::calculate_excess_blob_gas,
     proofs::{self, calculate_requests_root},
-    Block, EthereumHardforks, Header, IntoRecoveredTransaction, Receipt, EMPTY_OMMER_ROOT_HASH,
-    U256,
+    Block, EthereumHardforks, Header, IntoRecoveredTransaction, Receipt, ShadowBlock, TxType,
+    EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::StateProviderFactory;
-use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_revm::{
+    database::StateProviderDatabase,
+    state_change::{apply_blockhashes_update, apply_shadow_block_update},
+};
+use reth_transaction_pool::{
+    noop::NoopTransactionPool, BestTransactionsAttributes, TransactionPool,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
@@ -46,15 +53,28 @@ use tracing::{debug, trace, warn};
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig, Mandatory = ()> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// The source of the transactions payloads must start with.
+    mandatory_transactions: Mandatory,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, mandatory_transactions: () }
+    }
+}
+
+impl<EvmConfig, Mandatory> EthereumPayloadBuilder<EvmConfig, Mandatory> {
+    /// Sets the source of the transactions payloads must start with, see
+    /// [`MandatoryTransactions`].
+    pub fn with_mandatory_transactions<M>(
+        self,
+        mandatory_transactions: M,
+    ) -> EthereumPayloadBuilder<EvmConfig, M> {
+        EthereumPayloadBuilder { evm_config: self.evm_config, mandatory_transactions }
     }
 }
 
@@ -65,9 +85,11 @@ impl Default for EthereumPayloadBuilder {
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<EvmConfig, Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder<EvmConfig>
+impl<EvmConfig, Mandatory, Pool, Client> PayloadBuilder<Pool, Client>
+    for EthereumPayloadBuilder<EvmConfig, Mandatory>
 where
     EvmConfig: ConfigureEvm,
+    Mandatory: MandatoryTransactions + Clone,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -78,7 +100,11 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(self.evm_config.clone(), args)
+        default_ethereum_payload_builder(
+            self.evm_config.clone(),
+            &self.mandatory_transactions,
+            args,
+        )
     }
 
     fn build_empty_payload(
@@ -86,6 +112,27 @@ where
         client: &Client,
         config: PayloadConfig<Self::Attributes>,
     ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        // under dawn, even an empty payload must execute the shadow block `d` blocks back
+        let number = config.parent_block.number + 1;
+        if config.chain_spec.dawn_at(number, config.attributes.timestamp).is_some() {
+            let args = BuildArguments::new(
+                client,
+                NoopTransactionPool::default(),
+                Default::default(),
+                config,
+                Default::default(),
+                None,
+            );
+            return match default_ethereum_payload_builder(
+                self.evm_config.clone(),
+                &self.mandatory_transactions,
+                args,
+            )? {
+                BuildOutcome::Better { payload, .. } => Ok(payload),
+                _ => Err(PayloadBuilderError::MissingPayload),
+            }
+        }
+
         let extra_data = config.extra_data();
         let PayloadConfig {
             initialized_block_env,
@@ -237,9 +284,11 @@ where
             excess_blob_gas,
             parent_beacon_block_root: attributes.parent_beacon_block_root,
             requests_root,
+            shadow_root: None,
         };
 
-        let block = Block { header, body: vec![], ommers: vec![], withdrawals, requests };
+        let block =
+            Block { header, body: vec![], ommers: vec![], withdrawals, requests, shadow: None };
         let sealed_block = block.seal_slow();
 
         Ok(EthBuiltPayload::new(attributes.payload_id(), sealed_block, U256::ZERO))
@@ -251,13 +300,23 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The payload starts with the transactions of `mandatory_transactions`, in order, and fails if
+/// any of them is missing or can not be executed.
+///
+/// Under Dawn, the body of the payload is made of the mandatory transactions only, which are the
+/// transactions of the shadow block `d` blocks back, and the pool transactions go into the shadow
+/// block of the payload instead. Delayed transactions that became invalid since their inclusion are
+/// skipped, as when executing the block.
 #[inline]
-pub fn default_ethereum_payload_builder<EvmConfig, Pool, Client>(
+pub fn default_ethereum_payload_builder<EvmConfig, Mandatory, Pool, Client>(
     evm_config: EvmConfig,
+    mandatory_transactions: &Mandatory,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm,
+    Mandatory: MandatoryTransactions,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -326,6 +385,142 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
+
+    let mandatory_txs = mandatory_transactions
+        .mandatory_transactions(&client, &chain_spec, &parent_block.header, attributes.timestamp)?
+        .unwrap_or_default();
+
+    // under dawn, the pool transactions go into the shadow block, and the fees of the mandatory
+    // transactions go to the beneficiary of the delayed shadow block that committed to them
+    let is_dawn = chain_spec.dawn_at(block_number, attributes.timestamp).is_some();
+    let mut block_env = initialized_block_env.clone();
+    let mut shadow = None;
+    if is_dawn {
+        let mut shadow_txs = Vec::new();
+        let mut shadow_gas_limit = 0;
+        while let Some(pool_tx) = best_txs.next() {
+            // ensure the shadow block still has capacity for this transaction
+            if shadow_gas_limit + pool_tx.gas_limit() > block_gas_limit {
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
+            // check if the job was cancelled, if so we can exit early
+            if cancel.is_cancelled() {
+                return Ok(BuildOutcome::Cancelled)
+            }
+
+            let tx = pool_tx.to_recovered_transaction();
+
+            // Blob sidecars would have to be served with the block executing the transaction, and
+            // decrypted and undecrypted transactions only result from executing a shadow block.
+            // A ciphertext labelled for another block can't be decrypted with its key either.
+            if tx.is_eip4844() ||
+                matches!(tx.tx_type(), TxType::DawnDecrypted | TxType::DawnUndecrypted) ||
+                !pool_tx.can_be_included_in(block_number)
+            {
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
+            match dawn::validate_shadow_transaction(&mut db, &tx.transaction, tx.signer()) {
+                Ok(()) => {}
+                Err(EVMError::Transaction(err)) => {
+                    trace!(target: "payload_builder", %err, ?tx, "skipping invalid shadow transaction and its descendants");
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+                Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+            }
+            shadow_gas_limit += tx.gas_limit();
+
+            // the fees are only collected once the transaction executes, at most its gas limit
+            let miner_fee = tx.effective_tip_per_gas(Some(base_fee)).unwrap_or_default();
+            total_fees += U256::from(miner_fee) * U256::from(tx.gas_limit());
+
+            shadow_txs.push(tx.into_signed());
+        }
+
+        let shadow_block =
+            ShadowBlock { beneficiary: initialized_block_env.coinbase, transactions: shadow_txs };
+        if let Some(beneficiary) = apply_shadow_block_update(
+            &mut db,
+            &chain_spec,
+            attributes.timestamp,
+            block_number,
+            &mandatory_txs,
+            Some(&shadow_block),
+        )
+        .map_err(|err| PayloadBuilderError::Internal(err.into()))?
+        {
+            block_env.coinbase = beneficiary;
+        }
+        shadow = Some(shadow_block);
+    }
+
+    // include the mandatory transactions first: unlike pool transactions, they can't be skipped
+    for tx in mandatory_txs {
+        let hash = tx.hash;
+        let tx = tx.into_ecrecovered().ok_or(MandatoryTransactionsError::SignerRecovery(hash))?;
+
+        let result_and_state = if cumulative_gas_used + tx.gas_limit() > block_gas_limit {
+            Err(InvalidTransaction::CallerGasLimitMoreThanBlock.into())
+        } else {
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                initialized_cfg.clone(),
+                block_env.clone(),
+                evm_config.tx_env(&tx),
+            );
+            let mut evm = evm_config.evm_with_env(&mut db, env);
+            dawn::transact(&mut evm, &tx.transaction, tx.signer())
+        };
+        let ResultAndState { result, state } = match result_and_state {
+            Ok(res) => res,
+            // delayed transactions that became invalid since their inclusion are skipped
+            Err(err) if is_dawn && dawn::is_invalid_transaction(&err) => {
+                trace!(target: "payload_builder", %err, ?tx, "skipping invalid delayed transaction");
+                #[allow(clippy::needless_update)] // side-effect of optimism fields
+                receipts.push(Some(Receipt {
+                    tx_type: tx.tx_type(),
+                    success: false,
+                    cumulative_gas_used,
+                    logs: vec![],
+                    ..Default::default()
+                }));
+                executed_txs.push(tx.into_signed());
+                continue
+            }
+            Err(err) => return Err(PayloadBuilderError::MandatoryTransactionFailed(hash, err)),
+        };
+        db.commit(state);
+
+        if let Some(blob_tx) = tx.transaction.as_eip4844() {
+            sum_blob_gas_used += blob_tx.blob_gas();
+        }
+
+        let gas_used = result.gas_used();
+        cumulative_gas_used += gas_used;
+
+        #[allow(clippy::needless_update)] // side-effect of optimism fields
+        receipts.push(Some(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs().into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }));
+
+        if !is_dawn {
+            let miner_fee = tx
+                .effective_tip_per_gas(Some(base_fee))
+                .expect("fee is always valid; execution succeeded");
+            total_fees += U256::from(miner_fee) * U256::from(gas_used);
+        }
+
+        executed_txs.push(tx.into_signed());
+    }
+
+    // under dawn, the pool transactions were all considered for the shadow block already
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -368,7 +563,11 @@ where
         // Configure the environment for the block.
         let mut evm = evm_config.evm_with_env(&mut db, env);
 
-        let ResultAndState { result, state } = match evm.transact() {
+        let ResultAndState { result, state } = match dawn::transact(
+            &mut evm,
+            &tx.transaction,
+            tx.signer(),
+        ) {
             Ok(res) => res,
             Err(err) => {
                 match err {
@@ -533,10 +732,11 @@ where
         blob_gas_used,
         excess_blob_gas,
         requests_root,
+        shadow_root: shadow.as_ref().map(ShadowBlock::calculate_root),
     };
 
     // seal the block
-    let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
+    let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests, shadow };
 
     let sealed_block = block.seal_slow();
     debug!(target: "payload_builder", ?sealed_block, "sealed built block");
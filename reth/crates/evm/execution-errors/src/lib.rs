@@ -111,6 +111,21 @@ pub enum BlockValidationError {
     /// [EIP-6110]: https://eips.ethereum.org/EIPS/eip-6110
     #[error("failed to decode deposit requests from receipts: {0}")]
     DepositRequestDecode(String),
+    /// Provider error during the shadow history account loading.
+    #[error("failed to load shadow history account: {0}")]
+    ShadowHistoryAccountLoadingFailed(ProviderError),
+    /// Error when the transactions of the block do not match the delayed shadow block.
+    #[error("block transactions do not match shadow root: got {got}, expected {expected}")]
+    ShadowRootMismatch {
+        /// The shadow root of the block transactions
+        got: B256,
+        /// The shadow root committed to by the delayed block
+        expected: B256,
+    },
+    /// Error when the Dawn delay of the chain is zero, which leaves no slot to commit shadow
+    /// blocks to.
+    #[error("dawn delay must not be zero")]
+    ZeroDawnDelay,
 }
 
 /// `BlockExecutor` Errors
@@ -0,0 +1,249 @@
+//! Execution of Dawn transactions.
+//!
+//! Encrypted transactions are included in shadow blocks, and block bodies only carry them in
+//! decrypted or undecrypted form, which consensus enforces. Executing an encrypted transaction
+//! anyway, e.g. in a pending block, does not run any EVM code: its recipient and calldata are
+//! hidden. It only charges the sender for its intrinsic gas and bumps the sender nonce. The real
+//! call happens when the matching decrypted transaction executes.
+//!
+//! All Dawn transactions are charged [`DECRYPTION_GAS`] for the decryption work on top of their
+//! regular intrinsic gas.
+//!
+//! Shadow transactions are checked with [`validate_shadow_transaction`] when they are included, but
+//! they only execute `d` blocks later, by which time they may have become invalid. Such delayed
+//! transactions are skipped instead of invalidating the block, see [`is_invalid_transaction`].
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use reth_primitives::{constants::dawn::DECRYPTION_GAS, Transaction, TxDawnEncrypted};
+use revm::{Database, Evm};
+use revm_primitives::{
+    Account, Address, Bytes, EVMError, EvmState, ExecutionResult, InvalidTransaction, Output,
+    ResultAndState, SpecId, SuccessReason, U256,
+};
+
+/// Returns the intrinsic gas of the encrypted transaction, see [`TxDawnEncrypted::intrinsic_gas`].
+///
+/// This is the intrinsic gas of a call carrying the ciphertext as calldata together with the
+/// public access list, plus [`DECRYPTION_GAS`].
+pub fn encrypted_transaction_intrinsic_gas(spec_id: SpecId, tx: &TxDawnEncrypted) -> u64 {
+    tx.intrinsic_gas(spec_id)
+}
+
+/// Executes `transaction` sent by `sender`, once the transaction environment of the EVM has been
+/// filled from it.
+///
+/// Encrypted transactions are included with [`transact_encrypted`], decrypted and undecrypted
+/// transactions are executed with [`transact_decrypted`] and other transactions are executed as
+/// usual. A decrypted transaction whose hidden value exceeds its public bound is rejected.
+///
+/// The returned state changes are __not__ committed.
+pub fn transact<EXT, DB: Database>(
+    evm: &mut Evm<'_, EXT, DB>,
+    transaction: &Transaction,
+    sender: Address,
+) -> Result<ResultAndState, EVMError<DB::Error>> {
+    match transaction {
+        Transaction::DawnEncrypted(tx) => transact_encrypted(evm, tx, sender),
+        Transaction::DawnDecrypted(tx) if !tx.is_within_value_bound() => {
+            Err(EVMError::Custom("hidden value exceeds the public value bound".into()))
+        }
+        Transaction::DawnDecrypted(_) | Transaction::DawnUndecrypted(_) => transact_decrypted(evm),
+        _ => evm.transact(),
+    }
+}
+
+/// Returns whether `err` means that the executed transaction itself is invalid, as opposed to the
+/// block or the database.
+///
+/// A delayed transaction failing with such an error is skipped: it gets a failed receipt without
+/// using any gas or changing the state.
+pub const fn is_invalid_transaction<DBError>(err: &EVMError<DBError>) -> bool {
+    matches!(err, EVMError::Transaction(_) | EVMError::Custom(_))
+}
+
+/// Validates the shadow transaction sent by `sender` against the state of the block including it.
+///
+/// The transaction only executes `d` blocks later, so this only rejects transactions whose nonce
+/// was already used, or whose sender can not currently afford their fee budget and value.
+pub fn validate_shadow_transaction<DB: Database>(
+    db: &mut DB,
+    transaction: &Transaction,
+    sender: Address,
+) -> Result<(), EVMError<DB::Error>> {
+    let account = db.basic(sender).map_err(EVMError::Database)?.unwrap_or_default();
+    if transaction.nonce() < account.nonce {
+        return Err(InvalidTransaction::NonceTooLow {
+            tx: transaction.nonce(),
+            state: account.nonce,
+        }
+        .into())
+    }
+    ensure_budget(
+        account.balance,
+        transaction.gas_limit(),
+        U256::from(transaction.max_fee_per_gas()),
+        transaction.value(),
+    )?;
+    Ok(())
+}
+
+/// Includes the encrypted transaction sent by `sender` without running any EVM code.
+///
+/// The transaction is validated against the block and the sender account like a regular
+/// transaction: the sender must be able to afford the full fee budget and value of the
+/// transaction. Only the intrinsic gas is charged, see [`encrypted_transaction_intrinsic_gas`],
+/// and the sender nonce is incremented. The priority fee is credited to the block beneficiary.
+///
+/// The returned state changes are __not__ committed.
+pub fn transact_encrypted<EXT, DB: Database>(
+    evm: &mut Evm<'_, EXT, DB>,
+    tx: &TxDawnEncrypted,
+    sender: Address,
+) -> Result<ResultAndState, EVMError<DB::Error>> {
+    let gas_used = encrypted_transaction_intrinsic_gas(evm.spec_id(), tx);
+    if tx.gas_limit < gas_used {
+        return Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into())
+    }
+    if tx.chain_id != evm.cfg().chain_id {
+        return Err(InvalidTransaction::InvalidChainId.into())
+    }
+    if tx.max_priority_fee_per_gas > tx.max_fee_per_gas {
+        return Err(InvalidTransaction::PriorityFeeGreaterThanMaxFee.into())
+    }
+    let basefee = evm.block().basefee;
+    if U256::from(tx.max_fee_per_gas) < basefee {
+        return Err(InvalidTransaction::GasPriceLessThanBasefee.into())
+    }
+    if U256::from(tx.gas_limit) > evm.block().gas_limit {
+        return Err(InvalidTransaction::CallerGasLimitMoreThanBlock.into())
+    }
+
+    let mut caller = evm.db_mut().basic(sender).map_err(EVMError::Database)?.unwrap_or_default();
+    if caller.nonce != tx.nonce {
+        return Err(if caller.nonce > tx.nonce {
+            InvalidTransaction::NonceTooLow { tx: tx.nonce, state: caller.nonce }
+        } else {
+            InvalidTransaction::NonceTooHigh { tx: tx.nonce, state: caller.nonce }
+        }
+        .into())
+    }
+    caller.nonce =
+        caller.nonce.checked_add(1).ok_or(InvalidTransaction::NonceOverflowInTransaction)?;
+    ensure_budget(caller.balance, tx.gas_limit, U256::from(tx.max_fee_per_gas), tx.value)?;
+
+    let mut state = EvmState::default();
+    let mut account = Account::from(caller);
+    account.mark_touch();
+    state.insert(sender, account);
+
+    let gas_price = U256::from(tx.effective_gas_price(Some(basefee.saturating_to())));
+    charge_gas(evm, &mut state, sender, gas_price, gas_used)?;
+
+    let result = ExecutionResult::Success {
+        reason: SuccessReason::Stop,
+        gas_used,
+        gas_refunded: 0,
+        logs: Vec::new(),
+        output: Output::Call(Bytes::new()),
+    };
+    Ok(ResultAndState { result, state })
+}
+
+/// Executes the decrypted or undecrypted transaction the transaction environment of the EVM was
+/// filled from, charging [`DECRYPTION_GAS`] on top of the gas used by the EVM.
+///
+/// The EVM runs with the gas limit of the transaction minus [`DECRYPTION_GAS`], and the sender
+/// must be able to afford the full fee budget and value of the transaction.
+///
+/// The returned state changes are __not__ committed.
+pub fn transact_decrypted<EXT, DB: Database>(
+    evm: &mut Evm<'_, EXT, DB>,
+) -> Result<ResultAndState, EVMError<DB::Error>> {
+    let tx = evm.tx().clone();
+    let execution_gas_limit = tx
+        .gas_limit
+        .checked_sub(DECRYPTION_GAS)
+        .ok_or(InvalidTransaction::CallGasCostMoreThanGasLimit)?;
+    let balance = evm
+        .db_mut()
+        .basic(tx.caller)
+        .map_err(EVMError::Database)?
+        .map(|account| account.balance)
+        .unwrap_or_default();
+    ensure_budget(balance, tx.gas_limit, tx.gas_price, tx.value)?;
+
+    evm.tx_mut().gas_limit = execution_gas_limit;
+    let result = evm.transact();
+    evm.tx_mut().gas_limit = tx.gas_limit;
+    let ResultAndState { mut result, mut state } = result?;
+
+    let gas_price = evm.context.evm.env.effective_gas_price();
+    charge_gas(evm, &mut state, tx.caller, gas_price, DECRYPTION_GAS)?;
+    match &mut result {
+        ExecutionResult::Success { gas_used, .. } |
+        ExecutionResult::Revert { gas_used, .. } |
+        ExecutionResult::Halt { gas_used, .. } => *gas_used += DECRYPTION_GAS,
+    }
+    Ok(ResultAndState { result, state })
+}
+
+/// Ensures that `balance` covers the fee budget and value of a transaction.
+fn ensure_budget(
+    balance: U256,
+    gas_limit: u64,
+    max_fee_per_gas: U256,
+    value: U256,
+) -> Result<(), InvalidTransaction> {
+    let budget = U256::from(gas_limit)
+        .checked_mul(max_fee_per_gas)
+        .and_then(|fee| fee.checked_add(value))
+        .ok_or(InvalidTransaction::OverflowPaymentInTransaction)?;
+    if balance < budget {
+        return Err(InvalidTransaction::LackOfFundForMaxFee {
+            fee: Box::new(budget),
+            balance: Box::new(balance),
+        })
+    }
+    Ok(())
+}
+
+/// Charges `sender` for `gas` at `gas_price` in `state`.
+///
+/// The base fee is burnt, the priority fee is credited to the block beneficiary.
+fn charge_gas<EXT, DB: Database>(
+    evm: &mut Evm<'_, EXT, DB>,
+    state: &mut EvmState,
+    sender: Address,
+    gas_price: U256,
+    gas: u64,
+) -> Result<(), EVMError<DB::Error>> {
+    let basefee = evm.block().basefee;
+    let coinbase = evm.block().coinbase;
+
+    for address in [sender, coinbase] {
+        if !state.contains_key(&address) {
+            let info = evm.db_mut().basic(address).map_err(EVMError::Database)?.unwrap_or_default();
+            let mut account = Account::from(info);
+            account.mark_touch();
+            state.insert(address, account);
+        }
+    }
+
+    let fee = gas_price * U256::from(gas);
+    let caller = state.get_mut(&sender).expect("sender was loaded");
+    let Some(balance) = caller.info.balance.checked_sub(fee) else {
+        return Err(InvalidTransaction::LackOfFundForMaxFee {
+            fee: Box::new(fee),
+            balance: Box::new(caller.info.balance),
+        }
+        .into())
+    };
+    caller.info.balance = balance;
+
+    let reward = gas_price.saturating_sub(basefee) * U256::from(gas);
+    let beneficiary = state.get_mut(&coinbase).expect("beneficiary was loaded");
+    beneficiary.info.balance = beneficiary.info.balance.saturating_add(reward);
+    Ok(())
+}
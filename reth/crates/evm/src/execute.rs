@@ -217,6 +217,7 @@ mod tests {
             ommers: vec![],
             withdrawals: None,
             requests: None,
+            shadow: None,
         };
         let block = BlockWithSenders::new(block, Default::default()).unwrap();
         let _ = executor.execute(BlockExecutionInput::new(&block, U256::ZERO));
@@ -23,6 +23,7 @@ use revm_primitives::{
 };
 
 pub mod builder;
+pub mod dawn;
 pub mod either;
 pub mod execute;
 pub mod noop;
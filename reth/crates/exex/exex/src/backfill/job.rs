@@ -108,6 +108,7 @@ where
                 ommers: block.ommers,
                 withdrawals: block.withdrawals,
                 requests: block.requests,
+                shadow: block.shadow,
             }
             .with_senders_unchecked(senders);
 
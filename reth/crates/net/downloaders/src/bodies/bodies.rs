@@ -665,6 +665,7 @@ mod tests {
                         ommers: block.ommers,
                         withdrawals: None,
                         requests: None,
+                        shadow: None,
                     },
                 )
             })
@@ -25,6 +25,7 @@ pub(crate) fn zip_blocks<'a>(
                     ommers: body.ommers,
                     withdrawals: body.withdrawals,
                     requests: body.requests,
+                    shadow: body.shadow,
                 })
             }
         })
@@ -34,6 +34,7 @@ pub(crate) fn generate_bodies(
                     ommers: block.ommers,
                     withdrawals: block.withdrawals,
                     requests: block.requests,
+                    shadow: block.shadow,
                 },
             )
         })
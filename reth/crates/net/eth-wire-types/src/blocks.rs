@@ -273,7 +273,8 @@ mod tests {
                     blob_gas_used: None,
                     excess_blob_gas: None,
                     parent_beacon_block_root: None,
-                    requests_root: None
+                    requests_root: None,
+                    shadow_root: None
                 },
             ]),
         }.encode(&mut data);
@@ -308,7 +309,8 @@ mod tests {
                     blob_gas_used: None,
                     excess_blob_gas: None,
                     parent_beacon_block_root: None,
-                    requests_root: None
+                    requests_root: None,
+                    shadow_root: None
                 },
             ]),
         };
@@ -409,11 +411,13 @@ mod tests {
                             blob_gas_used: None,
                             excess_blob_gas: None,
                             parent_beacon_block_root: None,
-                            requests_root: None
+                            requests_root: None,
+                            shadow_root: None
                         },
                     ],
                     withdrawals: None,
-                    requests: None
+                    requests: None,
+                    shadow: None
                 }
             ]),
         };
@@ -485,11 +489,13 @@ mod tests {
                             blob_gas_used: None,
                             excess_blob_gas: None,
                             parent_beacon_block_root: None,
-                            requests_root: None
+                            requests_root: None,
+                            shadow_root: None
                         },
                     ],
                     withdrawals: None,
-                    requests: None
+                    requests: None,
+                    shadow: None
                 }
             ]),
         };
@@ -140,7 +140,8 @@ mod tests {
             blob_gas_used: None,
             excess_blob_gas: None,
             parent_beacon_block_root: None,
-            requests_root: None
+            requests_root: None,
+            shadow_root: None
         };
         assert_eq!(header.hash_slow(), expected_hash);
     }
@@ -266,6 +267,7 @@ mod tests {
             excess_blob_gas: Some(0),
             parent_beacon_block_root: None,
             requests_root: None,
+            shadow_root: None,
         };
 
         let header = Header::decode(&mut data.as_slice()).unwrap();
@@ -312,6 +314,7 @@ mod tests {
             blob_gas_used: Some(0),
             excess_blob_gas: Some(0x1600000),
             requests_root: None,
+            shadow_root: None,
         };
 
         let header = Header::decode(&mut data.as_slice()).unwrap();
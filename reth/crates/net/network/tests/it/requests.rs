@@ -80,6 +80,7 @@ async fn test_get_body() {
             ommers: block.ommers,
             withdrawals: None,
             requests: None,
+            shadow: None,
         };
         assert_eq!(blocks[0], expected);
     }
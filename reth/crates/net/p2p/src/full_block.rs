@@ -294,6 +294,7 @@ enum BodyResponse {
 ///   - ommer hash
 ///   - transaction root
 ///   - withdrawals root
+///   - shadow root
 fn ensure_valid_body_response(
     header: &SealedHeader,
     block: &BlockBody,
@@ -344,6 +345,21 @@ fn ensure_valid_body_response(
         _ => return Err(ConsensusError::RequestsRootUnexpected),
     }
 
+    match (header.shadow_root, &block.shadow) {
+        (Some(header_shadow_root), Some(shadow)) => {
+            let shadow_root = shadow.calculate_root();
+            if shadow_root != header_shadow_root {
+                return Err(ConsensusError::BodyShadowRootDiff(
+                    GotExpected { got: shadow_root, expected: header_shadow_root }.into(),
+                ))
+            }
+        }
+        (None, None) => {
+            // this is ok because we assume the fork is not active in this case
+        }
+        _ => return Err(ConsensusError::ShadowRootUnexpected),
+    }
+
     Ok(())
 }
 
@@ -26,6 +26,7 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
+use reth_payload_primitives::MandatoryTransactions;
 use reth_primitives::format_ether;
 use reth_provider::providers::BlockchainProvider;
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
@@ -86,20 +87,34 @@ where
 
 /// The default launcher for a node.
 #[derive(Debug)]
-pub struct DefaultNodeLauncher {
+pub struct DefaultNodeLauncher<Mandatory = ()> {
     /// The task executor for the node.
     pub ctx: LaunchContext,
+    /// The source of the transactions blocks mined in dev mode must start with.
+    pub mandatory_transactions: Mandatory,
 }
 
 impl DefaultNodeLauncher {
     /// Create a new instance of the default node launcher.
     pub const fn new(task_executor: TaskExecutor, data_dir: ChainPath<DataDirPath>) -> Self {
-        Self { ctx: LaunchContext::new(task_executor, data_dir) }
+        Self { ctx: LaunchContext::new(task_executor, data_dir), mandatory_transactions: () }
     }
 }
 
-impl<T, CB, AO> LaunchNode<NodeBuilderWithComponents<T, CB, AO>> for DefaultNodeLauncher
+impl<Mandatory> DefaultNodeLauncher<Mandatory> {
+    /// Sets the source of the transactions blocks mined in dev mode must start with.
+    pub fn with_mandatory_transactions<M>(
+        self,
+        mandatory_transactions: M,
+    ) -> DefaultNodeLauncher<M> {
+        DefaultNodeLauncher { ctx: self.ctx, mandatory_transactions }
+    }
+}
+
+impl<T, CB, AO, Mandatory> LaunchNode<NodeBuilderWithComponents<T, CB, AO>>
+    for DefaultNodeLauncher<Mandatory>
 where
+    Mandatory: MandatoryTransactions + Clone + Unpin + 'static,
     T: FullNodeTypes<Provider = BlockchainProvider<<T as FullNodeTypes>::DB>>,
     CB: NodeComponentsBuilder<T>,
     AO: NodeAddOns<NodeAdapter<T, CB::Components>>,
@@ -112,7 +127,7 @@ where
         self,
         target: NodeBuilderWithComponents<T, CB, AO>,
     ) -> eyre::Result<Self::Node> {
-        let Self { ctx } = self;
+        let Self { ctx, mandatory_transactions } = self;
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
@@ -229,6 +244,9 @@ where
                 mining_mode,
                 ctx.components().block_executor().clone(),
             )
+            .beneficiary(ctx.node_config().dev.beneficiary())
+            .authority(ctx.node_config().dev.authority_key())
+            .mandatory_transactions(mandatory_transactions)
             .build();
 
             let pipeline = crate::setup::build_networked_pipeline(
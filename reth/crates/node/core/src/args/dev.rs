@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use clap::Args;
 use humantime::parse_duration;
+use reth_primitives::{address, b256, Address, B256};
 
 /// Parameters for Dev testnet configuration
 #[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
@@ -39,6 +40,40 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// The beneficiary of mined blocks and of their shadow blocks.
+    ///
+    /// Defaults to the first prefunded account.
+    #[arg(long = "dev.beneficiary", help_heading = "Dev testnet")]
+    pub beneficiary: Option<Address>,
+
+    /// The secret key of the authority sealing mined blocks, which the light clients of the
+    /// enclaves check.
+    ///
+    /// Defaults to the key of the first prefunded account.
+    #[arg(long = "dev.authority-key", help_heading = "Dev testnet")]
+    pub authority_key: Option<B256>,
+}
+
+impl DevArgs {
+    /// The first account prefunded in dev mode, which receives the fees of mined blocks unless
+    /// `--dev.beneficiary` is set.
+    pub const DEFAULT_BENEFICIARY: Address = address!("f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+
+    /// Returns the beneficiary of mined blocks and of their shadow blocks.
+    pub fn beneficiary(&self) -> Address {
+        self.beneficiary.unwrap_or(Self::DEFAULT_BENEFICIARY)
+    }
+
+    /// The secret key of the first account prefunded in dev mode, which seals mined blocks unless
+    /// `--dev.authority-key` is set.
+    pub const DEFAULT_AUTHORITY_KEY: B256 =
+        b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+
+    /// Returns the secret key of the authority sealing mined blocks.
+    pub fn authority_key(&self) -> B256 {
+        self.authority_key.unwrap_or(Self::DEFAULT_AUTHORITY_KEY)
+    }
 }
 
 #[cfg(test)]
@@ -56,13 +91,40 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: false,
+                block_max_transactions: None,
+                block_time: None,
+                beneficiary: None,
+                authority_key: None,
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                beneficiary: None,
+                authority_key: None,
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                beneficiary: None,
+                authority_key: None,
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +133,16 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: Some(2),
+                block_time: None,
+                beneficiary: None,
+                authority_key: None,
+            }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -80,7 +151,9 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                beneficiary: None,
+                authority_key: None,
             }
         );
     }
@@ -1,7 +1,7 @@
 //! Clap parser utilities
 
 use alloy_genesis::Genesis;
-use reth_chainspec::ChainSpec;
+use reth_chainspec::{ChainSpec, DawnConfig};
 use reth_fs_util as fs;
 use std::{path::PathBuf, sync::Arc};
 
@@ -62,6 +62,8 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
 
             // both serialized Genesis and ChainSpec structs supported
             let genesis: Genesis = serde_json::from_str(&raw)?;
+            // the chain spec ignores a malformed dawn section, so reject it here
+            DawnConfig::extract_from(&genesis).transpose()?;
 
             Arc::new(genesis.into())
         }
@@ -95,6 +95,7 @@ where
         ommers: block.ommers,
         withdrawals: block.withdrawals,
         requests: block.requests,
+        shadow: block.shadow,
     };
 
     validate_block_pre_execution(&block, &chain_spec)?;
@@ -540,6 +540,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![addr, addr],
                     },
@@ -621,6 +622,7 @@ mod tests {
                             ommers: vec![],
                             withdrawals: None,
                             requests: None,
+                            shadow: None,
                         },
                         senders: vec![addr, addr],
                     },
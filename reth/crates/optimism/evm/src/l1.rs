@@ -288,6 +288,7 @@ mod tests {
             ommers: Vec::default(),
             withdrawals: None,
             requests: None,
+            shadow: None,
         };
 
         let l1_info: L1BlockInfo = extract_l1_info(&mock_block).unwrap();
@@ -310,6 +311,7 @@ mod tests {
             ommers: Vec::default(),
             withdrawals: None,
             requests: None,
+            shadow: None,
         };
 
         let l1_info: L1BlockInfo = extract_l1_info(&mock_block).unwrap();
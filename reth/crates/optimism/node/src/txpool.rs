@@ -108,6 +108,7 @@ where
             state_nonce,
             transaction: valid_tx,
             propagate,
+            dawn_target_block,
         } = outcome
         {
             let l1_block_info = self.block_info.l1_block_info.read().clone();
@@ -144,6 +145,7 @@ where
                 state_nonce,
                 transaction: valid_tx,
                 propagate,
+                dawn_target_block,
             }
         }
 
@@ -206,9 +206,17 @@ where
             excess_blob_gas,
             parent_beacon_block_root: attributes.payload_attributes.parent_beacon_block_root,
             requests_root: None,
+            shadow_root: None,
         };
 
-        let block = Block { header, body: vec![], ommers: vec![], withdrawals, requests: None };
+        let block = Block {
+            header,
+            body: vec![],
+            ommers: vec![],
+            withdrawals,
+            requests: None,
+            shadow: None,
+        };
         let sealed_block = block.seal_slow();
 
         Ok(OptimismBuiltPayload::new(
@@ -590,10 +598,18 @@ where
         blob_gas_used,
         excess_blob_gas,
         requests_root: None,
+        shadow_root: None,
     };
 
     // seal the block
-    let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests: None };
+    let block = Block {
+        header,
+        body: executed_txs,
+        ommers: vec![],
+        withdrawals,
+        requests: None,
+        shadow: None,
+    };
 
     let sealed_block = block.seal_slow();
     debug!(target: "payload_builder", ?sealed_block, "sealed built block");
@@ -1,7 +1,8 @@
 //! Error types emitted by types or implementations of this crate.
 
 use reth_errors::{ProviderError, RethError};
-use reth_primitives::{revm_primitives::EVMError, B256};
+use reth_payload_primitives::MandatoryTransactionsError;
+use reth_primitives::{revm_primitives::EVMError, TxHash, B256};
 use reth_transaction_pool::BlobStoreError;
 use tokio::sync::oneshot;
 
@@ -29,6 +30,12 @@ pub enum PayloadBuilderError {
     /// Thrown if the payload requests withdrawals before Shanghai activation.
     #[error("withdrawals set before Shanghai activation")]
     WithdrawalsBeforeShanghai,
+    /// The mandatory transactions of the payload could not be determined.
+    #[error(transparent)]
+    MandatoryTransactions(#[from] MandatoryTransactionsError),
+    /// A mandatory transaction of the payload could not be executed.
+    #[error("mandatory transaction {0} failed: {1}")]
+    MandatoryTransactionFailed(TxHash, EVMError<ProviderError>),
     /// Any other payload building errors.
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -1,7 +1,7 @@
 //! Error types emitted by types or implementations of this crate.
 
 use reth_errors::{ProviderError, RethError};
-use reth_primitives::{revm_primitives::EVMError, B256};
+use reth_primitives::{revm_primitives::EVMError, BlockNumHash, TxHash, B256};
 use reth_transaction_pool::BlobStoreError;
 use tokio::sync::oneshot;
 
@@ -53,6 +53,21 @@ impl From<oneshot::error::RecvError> for PayloadBuilderError {
     }
 }
 
+/// Errors that can occur when determining the mandatory transactions of a payload, see
+/// [`MandatoryTransactions`](crate::MandatoryTransactions).
+#[derive(Debug, thiserror::Error)]
+pub enum MandatoryTransactionsError {
+    /// The mandatory transactions committed to by a block are not available (yet).
+    #[error("missing mandatory transactions committed to by block {} ({})", .0.number, .0.hash)]
+    Missing(BlockNumHash),
+    /// The signer of a mandatory transaction could not be recovered.
+    #[error("failed to recover the signer of mandatory transaction {0}")]
+    SignerRecovery(TxHash),
+    /// The chain could not be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
 /// Thrown when the payload or attributes are known to be invalid before processing.
 ///
 /// This is used mainly for
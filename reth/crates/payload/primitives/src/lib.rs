@@ -10,7 +10,10 @@
 
 mod error;
 
-pub use error::{EngineObjectValidationError, PayloadBuilderError, VersionSpecificValidationError};
+pub use error::{
+    EngineObjectValidationError, MandatoryTransactionsError, PayloadBuilderError,
+    VersionSpecificValidationError,
+};
 
 /// Contains traits to abstract over payload attributes types and default implementations of the
 /// [`PayloadAttributes`] trait for ethereum mainnet and optimism types.
@@ -20,6 +23,9 @@ pub use traits::{BuiltPayload, PayloadAttributes, PayloadBuilderAttributes};
 mod payload;
 pub use payload::PayloadOrAttributes;
 
+mod mandatory;
+pub use mandatory::MandatoryTransactions;
+
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 /// The types that are used by the engine API.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone {
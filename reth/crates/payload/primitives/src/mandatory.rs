@@ -0,0 +1,56 @@
+//! Sources of the transactions a payload must start with.
+
+use crate::MandatoryTransactionsError;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{SealedHeader, TransactionSigned};
+use reth_storage_api::BlockHashReader;
+
+/// A source of the transactions a payload must start with, before any transaction of the pool.
+///
+/// Under Dawn, the body of block `N` is made of the shadow block of block `N - d`, with its
+/// encrypted transactions replaced by their decrypted or undecrypted form. Payload builders ask
+/// their source for these transactions, include all of them first, in order, and fail if they are
+/// not available rather than building a payload without them.
+pub trait MandatoryTransactions: Send + Sync {
+    /// Returns the transactions that the payload built on top of `parent` at `timestamp` must
+    /// start with, in order, or `None` if this source does not constrain the payload.
+    ///
+    /// Returns [`MandatoryTransactionsError::Missing`] if the payload must start with
+    /// transactions that are not available yet.
+    fn mandatory_transactions<Client: BlockHashReader>(
+        &self,
+        client: &Client,
+        chain_spec: &ChainSpec,
+        parent: &SealedHeader,
+        timestamp: u64,
+    ) -> Result<Option<Vec<TransactionSigned>>, MandatoryTransactionsError>;
+}
+
+/// No source: payloads are free to include any transaction.
+impl MandatoryTransactions for () {
+    fn mandatory_transactions<Client: BlockHashReader>(
+        &self,
+        _client: &Client,
+        _chain_spec: &ChainSpec,
+        _parent: &SealedHeader,
+        _timestamp: u64,
+    ) -> Result<Option<Vec<TransactionSigned>>, MandatoryTransactionsError> {
+        Ok(None)
+    }
+}
+
+/// An optional source: payloads are free to include any transaction if it is unset.
+impl<T: MandatoryTransactions> MandatoryTransactions for Option<T> {
+    fn mandatory_transactions<Client: BlockHashReader>(
+        &self,
+        client: &Client,
+        chain_spec: &ChainSpec,
+        parent: &SealedHeader,
+        timestamp: u64,
+    ) -> Result<Option<Vec<TransactionSigned>>, MandatoryTransactionsError> {
+        match self {
+            Some(source) => source.mandatory_transactions(client, chain_spec, parent, timestamp),
+            None => Ok(None),
+        }
+    }
+}
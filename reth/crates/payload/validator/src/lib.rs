@@ -51,6 +51,12 @@ impl ExecutionPayloadValidator {
         self.chain_spec().is_prague_active_at_timestamp(timestamp)
     }
 
+    /// Returns true if the Dawn hardfork is active at the given block number and timestamp.
+    #[inline]
+    fn is_dawn_active_at_block_and_timestamp(&self, block_number: u64, timestamp: u64) -> bool {
+        self.chain_spec().is_dawn_active_at_block_and_timestamp(block_number, timestamp)
+    }
+
     /// Cancun specific checks for EIP-4844 blob transactions.
     ///
     /// Ensures that the number of blob versioned hashes matches the number hashes included in the
@@ -171,6 +177,12 @@ impl ExecutionPayloadValidator {
             return Err(PayloadError::PrePragueBlockWithEip7702Transactions)
         }
 
+        if !self.is_dawn_active_at_block_and_timestamp(sealed_block.number, sealed_block.timestamp) &&
+            sealed_block.has_dawn_transactions()
+        {
+            return Err(PayloadError::PreDawnBlockWithDawnTransactions)
+        }
+
         // EIP-4844 checks
         self.ensure_matching_blob_versioned_hashes(&sealed_block, &cancun_fields)?;
 
@@ -42,6 +42,7 @@ impl TryFrom<RpcHeader> for Header {
             transactions_root: header.transactions_root,
             withdrawals_root: header.withdrawals_root,
             requests_root: header.requests_root,
+            shadow_root: header.shadow_root,
         })
     }
 }
@@ -0,0 +1,5 @@
+//! Gas schedule of the Dawn transactions.
+
+pub use alloy_consensus::dawn::{
+    DECRYPTION_GAS, EPHEMERAL_KEY_GAS, HASH_TO_G1_GAS, PAIRING_BASE_GAS, PAIRING_PER_PAIR_GAS,
+};
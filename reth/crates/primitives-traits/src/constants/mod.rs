@@ -7,6 +7,9 @@ use core::time::Duration;
 pub mod gas_units;
 pub use gas_units::{GIGAGAS, KILOGAS, MEGAGAS};
 
+/// Dawn gas schedule, for example [`dawn::DECRYPTION_GAS`].
+pub mod dawn;
+
 /// The client version: `reth/v{major}.{minor}.{patch}`
 pub const RETH_CLIENT_VERSION: &str = concat!("reth/v", env!("CARGO_PKG_VERSION"));
 
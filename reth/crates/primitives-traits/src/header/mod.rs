@@ -13,7 +13,7 @@ use alloy_eips::{
     BlockNumHash,
 };
 use alloy_primitives::{keccak256, Address, BlockNumber, Bloom, Bytes, B256, B64, U256};
-use alloy_rlp::{length_of_length, Decodable, Encodable};
+use alloy_rlp::{length_of_length, Decodable, Encodable, EMPTY_STRING_CODE};
 use bytes::BufMut;
 use core::mem;
 use reth_codecs::{add_arbitrary_tests, reth_codec, Compact};
@@ -97,6 +97,11 @@ pub struct Header {
     ///
     /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
     pub requests_root: Option<B256>,
+    /// The Keccak 256-bit hash of the RLP encoding of the shadow block carried in the block body.
+    ///
+    /// The shadow block holds the transactions that the producer of the d-th descendant of this
+    /// block must execute.
+    pub shadow_root: Option<B256>,
     /// An arbitrary byte array containing data relevant to this block. This must be 32 bytes or
     /// fewer; formally Hx.
     pub extra_data: Bytes,
@@ -132,6 +137,7 @@ impl Default for Header {
             excess_blob_gas: None,
             parent_beacon_block_root: None,
             requests_root: None,
+            shadow_root: None,
         }
     }
 }
@@ -304,6 +310,7 @@ impl Header {
         mem::size_of::<Option<u64>>() + // blob gas used
         mem::size_of::<Option<u64>>() + // excess blob gas
         mem::size_of::<Option<B256>>() + // parent beacon block root
+        mem::size_of::<Option<B256>>() + // shadow root
         self.extra_data.len() // extra data
     }
 
@@ -351,6 +358,13 @@ impl Header {
 
         if let Some(requests_root) = self.requests_root {
             length += requests_root.length();
+        } else if self.shadow_root.is_some() {
+            // Placeholder for the missing requests root.
+            length += 1;
+        }
+
+        if let Some(shadow_root) = self.shadow_root {
+            length += shadow_root.length();
         }
 
         length
@@ -410,6 +424,15 @@ impl Encodable for Header {
         // Encode EIP-7685 requests root
         if let Some(ref requests_root) = self.requests_root {
             requests_root.encode(out);
+        } else if self.shadow_root.is_some() {
+            // A shadow block can exist before EIP-7685, so the requests root is encoded as an
+            // empty string, which is never a valid encoding of a hash.
+            out.put_u8(EMPTY_STRING_CODE);
+        }
+
+        // Encode shadow root
+        if let Some(ref shadow_root) = self.shadow_root {
+            shadow_root.encode(out);
         }
     }
 
@@ -450,6 +473,7 @@ impl Decodable for Header {
             excess_blob_gas: None,
             parent_beacon_block_root: None,
             requests_root: None,
+            shadow_root: None,
         };
         if started_len - buf.len() < rlp_head.payload_length {
             this.base_fee_per_gas = Some(u64::decode(buf)?);
@@ -476,7 +500,16 @@ impl Decodable for Header {
 
         // Decode requests root.
         if started_len - buf.len() < rlp_head.payload_length {
-            this.requests_root = Some(B256::decode(buf)?);
+            if buf.first() == Some(&EMPTY_STRING_CODE) {
+                *buf = &buf[1..];
+            } else {
+                this.requests_root = Some(B256::decode(buf)?);
+            }
+        }
+
+        // Decode shadow root.
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.shadow_root = Some(B256::decode(buf)?);
         }
 
         let consumed = started_len - buf.len();
@@ -516,6 +549,7 @@ impl<'a> arbitrary::Arbitrary<'a> for Header {
             excess_blob_gas: u.arbitrary()?,
             parent_beacon_block_root: u.arbitrary()?,
             requests_root: u.arbitrary()?,
+            shadow_root: u.arbitrary()?,
             withdrawals_root: u.arbitrary()?,
         };
 
@@ -47,6 +47,11 @@ pub const fn generate_valid_header(
     // todo(onbjerg): adjust this for eip-7589
     header.requests_root = None;
 
+    // The shadow root is only unambiguous in RLP once the EIP-4844 fields are present.
+    if header.parent_beacon_block_root.is_none() {
+        header.shadow_root = None;
+    }
+
     header
 }
 
@@ -0,0 +1,31 @@
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dawn_crypto::{generate, reveal, DecryptionKey};
+use reth_primitives::{dawn_label, Address};
+
+/// Benchmarks checking the decryption keys of a block one by one against checking them in a
+/// single batch.
+fn verify_dawn_keys(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Dawn decryption key verification");
+    let (mpk, msk) = generate();
+
+    for num_keys in [1, 16, 128, 512] {
+        let labels: Vec<[u8; 96]> =
+            (0..num_keys).map(|nonce| dawn_label(1, &Address::repeat_byte(0x11), nonce)).collect();
+        let keys: Vec<DecryptionKey> = labels.iter().map(|label| reveal(label, &msk)).collect();
+        let items: Vec<(&[u8], &DecryptionKey)> =
+            labels.iter().map(|label| &label[..]).zip(&keys).collect();
+
+        group.bench_with_input(BenchmarkId::new("Each", num_keys), &items, |b, items| {
+            b.iter(|| items.iter().all(|(label, key)| dawn_crypto::verify(label, &mpk, key)))
+        });
+        group.bench_with_input(BenchmarkId::new("Batch", num_keys), &items, |b, items| {
+            b.iter(|| dawn_crypto::verify_batch(&mpk, items))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verify_dawn_keys);
+criterion_main!(benches);
@@ -60,6 +60,7 @@ impl TryFrom<alloy_rpc_types::Block> for Block {
             // todo(onbjerg): we don't know if this is added to rpc yet, so for now we leave it as
             // empty.
             requests: None,
+            shadow: None,
         })
     }
 }
@@ -209,7 +210,7 @@ impl TryFrom<alloy_rpc_types::Transaction> for Transaction {
                     input: tx.input,
                     }))*/
             }
-            Some(TxType::DawnEncrypted | TxType::DawnDecrypted) => {
+            Some(TxType::DawnEncrypted | TxType::DawnDecrypted | TxType::DawnUndecrypted) => {
                 todo!()
             }
             #[cfg(feature = "optimism")]
@@ -1,5 +1,5 @@
 use crate::{
-    Address, Bytes, GotExpected, Header, SealedHeader, TransactionSigned,
+    Address, Bytes, GotExpected, Header, SealedHeader, ShadowBlock, TransactionSigned,
     TransactionSignedEcRecovered, Withdrawals, B256,
 };
 pub use alloy_eips::eip1898::{
@@ -47,6 +47,8 @@ pub struct Block {
     pub withdrawals: Option<Withdrawals>,
     /// Block requests.
     pub requests: Option<Requests>,
+    /// Shadow block.
+    pub shadow: Option<ShadowBlock>,
 }
 
 impl Block {
@@ -58,6 +60,7 @@ impl Block {
             ommers: self.ommers,
             withdrawals: self.withdrawals,
             requests: self.requests,
+            shadow: self.shadow,
         }
     }
 
@@ -71,6 +74,7 @@ impl Block {
             ommers: self.ommers,
             withdrawals: self.withdrawals,
             requests: self.requests,
+            shadow: self.shadow,
         }
     }
 
@@ -139,6 +143,12 @@ impl Block {
         self.body.iter().any(|tx| tx.is_eip7702())
     }
 
+    /// Returns whether or not the block body contains any Dawn transactions.
+    #[inline]
+    pub fn has_dawn_transactions(&self) -> bool {
+        self.body.iter().any(|tx| tx.is_dawn())
+    }
+
     /// Returns an iterator over all blob transactions of the block
     #[inline]
     pub fn blob_transactions_iter(&self) -> impl Iterator<Item = &TransactionSigned> + '_ {
@@ -172,7 +182,8 @@ impl Block {
             // take into account capacity
             self.body.iter().map(TransactionSigned::size).sum::<usize>() + self.body.capacity() * core::mem::size_of::<TransactionSigned>() +
             self.ommers.iter().map(Header::size).sum::<usize>() + self.ommers.capacity() * core::mem::size_of::<Header>() +
-            self.withdrawals.as_ref().map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size)
+            self.withdrawals.as_ref().map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size) +
+            self.shadow.as_ref().map_or(core::mem::size_of::<Option<ShadowBlock>>(), ShadowBlock::size)
     }
 }
 
@@ -194,6 +205,7 @@ impl<'a> arbitrary::Arbitrary<'a> for Block {
             // for now just generate empty requests, see HACK above
             requests: u.arbitrary()?,
             withdrawals: u.arbitrary()?,
+            shadow: u.arbitrary()?,
         })
     }
 }
@@ -296,14 +308,16 @@ pub struct SealedBlock {
     pub withdrawals: Option<Withdrawals>,
     /// Block requests.
     pub requests: Option<Requests>,
+    /// Shadow block.
+    pub shadow: Option<ShadowBlock>,
 }
 
 impl SealedBlock {
     /// Create a new sealed block instance using the sealed header and block body.
     #[inline]
     pub fn new(header: SealedHeader, body: BlockBody) -> Self {
-        let BlockBody { transactions, ommers, withdrawals, requests } = body;
-        Self { header, body: transactions, ommers, withdrawals, requests }
+        let BlockBody { transactions, ommers, withdrawals, requests, shadow } = body;
+        Self { header, body: transactions, ommers, withdrawals, requests, shadow }
     }
 
     /// Header hash.
@@ -328,6 +342,7 @@ impl SealedBlock {
                 ommers: self.ommers,
                 withdrawals: self.withdrawals,
                 requests: self.requests,
+                shadow: self.shadow,
             },
         )
     }
@@ -421,6 +436,7 @@ impl SealedBlock {
             ommers: self.ommers,
             withdrawals: self.withdrawals,
             requests: self.requests,
+            shadow: self.shadow,
         }
     }
 
@@ -431,7 +447,8 @@ impl SealedBlock {
             // take into account capacity
             self.body.iter().map(TransactionSigned::size).sum::<usize>() + self.body.capacity() * core::mem::size_of::<TransactionSigned>() +
             self.ommers.iter().map(Header::size).sum::<usize>() + self.ommers.capacity() * core::mem::size_of::<Header>() +
-            self.withdrawals.as_ref().map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size)
+            self.withdrawals.as_ref().map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size) +
+            self.shadow.as_ref().map_or(core::mem::size_of::<Option<ShadowBlock>>(), ShadowBlock::size)
     }
 
     /// Calculates the total gas used by blob transactions in the sealed block.
@@ -451,6 +468,12 @@ impl SealedBlock {
         self.body.iter().any(|tx| tx.is_eip7702())
     }
 
+    /// Returns whether or not the block body contains any Dawn transactions.
+    #[inline]
+    pub fn has_dawn_transactions(&self) -> bool {
+        self.body.iter().any(|tx| tx.is_dawn())
+    }
+
     /// Ensures that the transaction root in the block header is valid.
     ///
     /// The transaction root is the Keccak 256-bit hash of the root node of the trie structure
@@ -564,6 +587,8 @@ pub struct BlockBody {
     pub withdrawals: Option<Withdrawals>,
     /// Requests in the block.
     pub requests: Option<Requests>,
+    /// Shadow block of the block.
+    pub shadow: Option<ShadowBlock>,
 }
 
 impl BlockBody {
@@ -576,6 +601,7 @@ impl BlockBody {
             ommers: self.ommers.clone(),
             withdrawals: self.withdrawals.clone(),
             requests: self.requests.clone(),
+            shadow: self.shadow.clone(),
         }
     }
 
@@ -601,6 +627,12 @@ impl BlockBody {
         self.requests.as_ref().map(|r| crate::proofs::calculate_requests_root(&r.0))
     }
 
+    /// Calculate the shadow root for the block body, if a shadow block exists. If there is no
+    /// shadow block, this will return `None`.
+    pub fn calculate_shadow_root(&self) -> Option<B256> {
+        self.shadow.as_ref().map(ShadowBlock::calculate_root)
+    }
+
     /// Calculates a heuristic for the in-memory size of the [`BlockBody`].
     #[inline]
     pub fn size(&self) -> usize {
@@ -610,7 +642,10 @@ impl BlockBody {
             self.ommers.capacity() * core::mem::size_of::<Header>() +
             self.withdrawals
                 .as_ref()
-                .map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size)
+                .map_or(core::mem::size_of::<Option<Withdrawals>>(), Withdrawals::total_size) +
+            self.shadow
+                .as_ref()
+                .map_or(core::mem::size_of::<Option<ShadowBlock>>(), ShadowBlock::size)
     }
 }
 
@@ -621,6 +656,7 @@ impl From<Block> for BlockBody {
             ommers: block.ommers,
             withdrawals: block.withdrawals,
             requests: block.requests,
+            shadow: block.shadow,
         }
     }
 }
@@ -637,7 +673,13 @@ impl<'a> arbitrary::Arbitrary<'a> for BlockBody {
         let ommers = (0..2).map(|_| Header::arbitrary(u)).collect::<arbitrary::Result<Vec<_>>>()?;
 
         // for now just generate empty requests, see HACK above
-        Ok(Self { transactions, ommers, requests: None, withdrawals: u.arbitrary()? })
+        Ok(Self {
+            transactions,
+            ommers,
+            requests: None,
+            withdrawals: u.arbitrary()?,
+            shadow: u.arbitrary()?,
+        })
     }
 }
 
@@ -34,6 +34,7 @@ pub mod eip7702;
 pub mod genesis;
 pub mod proofs;
 mod receipt;
+mod shadow;
 pub use reth_static_file_types as static_file;
 pub mod transaction;
 #[cfg(any(test, feature = "arbitrary"))]
@@ -56,6 +57,7 @@ pub use reth_primitives_traits::{
     logs_bloom, Account, Bytecode, GotExpected, GotExpectedBoxed, Header, HeaderError, Log,
     LogData, Request, Requests, SealedHeader, StorageEntry, Withdrawal, Withdrawals,
 };
+pub use shadow::{ShadowBlock, SHADOW_HISTORY_ADDRESS};
 pub use static_file::StaticFileSegment;
 
 pub use transaction::{
@@ -67,13 +69,15 @@ pub use transaction::{
 pub use transaction::BlobTransactionValidationError;
 
 pub use transaction::{
+    dawn_block_label, dawn_label,
     util::secp256k1::{public_key_to_address, recover_signer_unchecked, sign_message},
-    AccessList, AccessListItem, IntoRecoveredTransaction, InvalidTransactionError, Signature,
-    Transaction, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxHashOrNumber, TxLegacy,
-    TxDawnEncrypted, TxDawnDecrypted, 
-    TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID, DAWN_ENCRYPTED_TX_TYPE_ID, DAWN_DECRYPTED_TX_TYPE_ID,
+    AccessList, AccessListItem, DawnHiddenFields, DawnPlaintext, DawnTransactionError,
+    IntoRecoveredTransaction, InvalidTransactionError, Signature, Transaction, TransactionMeta,
+    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxDawnDecrypted,
+    TxDawnEncrypted, TxDawnUndecrypted, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxHashOrNumber,
+    TxLegacy, TxType, DAWN_DECRYPTED_TX_TYPE_ID, DAWN_ENCRYPTED_TX_TYPE_ID,
+    DAWN_UNDECRYPTED_TX_TYPE_ID, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
 };
 
 // Re-exports
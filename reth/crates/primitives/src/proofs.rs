@@ -2,7 +2,7 @@
 
 use crate::{
     constants::EMPTY_OMMER_ROOT_HASH, keccak256, Header, Receipt, ReceiptWithBloom,
-    ReceiptWithBloomRef, Request, TransactionSigned, Withdrawal, B256,
+    ReceiptWithBloomRef, Request, ShadowBlock, TransactionSigned, Withdrawal, B256,
 };
 use alloy_eips::eip7685::Encodable7685;
 use reth_trie_common::root::{ordered_trie_root, ordered_trie_root_with_encoder};
@@ -37,6 +37,13 @@ pub fn calculate_requests_root(requests: &[Request]) -> B256 {
     ordered_trie_root_with_encoder(requests, |item, buf| item.encode_7685(buf))
 }
 
+/// Calculates the shadow root for a header.
+///
+/// NOTE: This is the hash of the RLP encoding of the shadow block.
+pub fn calculate_shadow_root(shadow: &ShadowBlock) -> B256 {
+    keccak256(alloy_rlp::encode(shadow))
+}
+
 /// Calculates the receipt root for a header.
 #[cfg(feature = "optimism")]
 pub fn calculate_receipt_root_optimism(
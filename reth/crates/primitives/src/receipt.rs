@@ -484,6 +484,9 @@ impl<'a> ReceiptWithBloomEncoder<'a> {
             TxType::DawnDecrypted => {
                 out.put_u8(0x06);
             }
+            TxType::DawnUndecrypted => {
+                out.put_u8(0x07);
+            }
             #[cfg(feature = "optimism")]
             TxType::Deposit => {
                 out.put_u8(0x7E);
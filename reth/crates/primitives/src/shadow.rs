@@ -0,0 +1,51 @@
+//! Shadow blocks, which delay the execution of transactions by the Dawn delay of the chain.
+
+use crate::{address, Address, TransactionSigned, B256};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The system account that stores the shadow roots and beneficiaries of the last `d` blocks, where
+/// `d` is the Dawn delay of the chain.
+///
+/// The body of block `N` consists of the transactions of the shadow block of block `N - d`.
+pub const SHADOW_HISTORY_ADDRESS: Address = address!("000000000000000000000000000000000000da7e");
+
+/// The transactions that the producer of a block commits to, to be executed `d` blocks later, where
+/// `d` is the Dawn delay of the chain.
+///
+/// Encrypted transactions are included here as they were signed. By the time they are executed,
+/// they have been replaced in the block body by the matching decrypted or undecrypted
+/// transaction, see [`TransactionSigned::shadow_transaction`].
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::derive_arbitrary(rlp 10))]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, RlpEncodable, RlpDecodable,
+)]
+pub struct ShadowBlock {
+    /// The address that receives the fees of the transactions once they are executed.
+    pub beneficiary: Address,
+    /// The transactions to execute.
+    pub transactions: Vec<TransactionSigned>,
+}
+
+impl ShadowBlock {
+    /// Returns the sum of the gas limits of the transactions, or `None` if it overflows.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.transactions.iter().try_fold(0u64, |sum, tx| sum.checked_add(tx.gas_limit()))
+    }
+
+    /// Calculate the shadow root of the shadow block.
+    pub fn calculate_root(&self) -> B256 {
+        crate::proofs::calculate_shadow_root(self)
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [`ShadowBlock`].
+    #[inline]
+    pub fn size(&self) -> usize {
+        core::mem::size_of::<Address>() +
+            self.transactions.iter().map(TransactionSigned::size).sum::<usize>() +
+            self.transactions.capacity() * core::mem::size_of::<TransactionSigned>()
+    }
+}
@@ -93,12 +93,15 @@ impl FillTxEnv for TransactionSigned {
                     Some(AuthorizationList::Signed(tx.authorization_list.clone()));
             }
             Transaction::DawnEncrypted(tx) => {
+                // the recipient and calldata are hidden, so this is a value-less call to the
+                // sender charged for the ciphertext. Blocks include encrypted transactions
+                // without running the EVM, see `reth_evm::dawn::transact_encrypted`.
                 tx_env.gas_limit = tx.gas_limit;
                 tx_env.gas_price = U256::from(tx.max_fee_per_gas);
                 tx_env.gas_priority_fee = Some(U256::from(tx.max_priority_fee_per_gas));
-                tx_env.transact_to = TxKind::Create;
-                tx_env.value = tx.value;
-                tx_env.data.clear();
+                tx_env.transact_to = TxKind::Call(sender);
+                tx_env.value = U256::ZERO;
+                tx_env.data = tx.ciphertext_bytes();
                 tx_env.chain_id = Some(tx.chain_id);
                 tx_env.nonce = Some(tx.nonce);
                 tx_env.access_list.clone_from(&tx.access_list.0);
@@ -120,6 +123,21 @@ impl FillTxEnv for TransactionSigned {
                 tx_env.max_fee_per_blob_gas.take();
                 tx_env.authorization_list = None;
             }
+            Transaction::DawnUndecrypted(tx) => {
+                // a value-less call to the sender, charged for the ciphertext as calldata
+                tx_env.gas_limit = tx.gas_limit;
+                tx_env.gas_price = U256::from(tx.max_fee_per_gas);
+                tx_env.gas_priority_fee = Some(U256::from(tx.max_priority_fee_per_gas));
+                tx_env.transact_to = tx.kind();
+                tx_env.value = U256::ZERO;
+                tx_env.data = tx.ciphertext_bytes();
+                tx_env.chain_id = Some(tx.chain_id);
+                tx_env.nonce = Some(tx.nonce);
+                tx_env.access_list.clear();
+                tx_env.blob_hashes.clear();
+                tx_env.max_fee_per_blob_gas.take();
+                tx_env.authorization_list = None;
+            }
             #[cfg(feature = "optimism")]
             Transaction::Deposit(tx) => {
                 tx_env.access_list.clear();
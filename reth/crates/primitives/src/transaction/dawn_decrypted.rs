@@ -1,5 +1,13 @@
-use super::access_list::AccessList;
-use crate::{keccak256, Address, Bytes, ChainId, Signature, TxKind, TxType, B256, U256};
+use super::{
+    access_list::AccessList,
+    dawn_encrypted::{
+        dawn_label, decode_decryption_key, decode_ephemeral_public_key,
+        encode_ephemeral_public_key, ephemeral_public_key_length,
+    },
+};
+use crate::{
+    keccak256, Address, Bytes, ChainId, Signature, TxDawnEncrypted, TxKind, TxType, B256, U256,
+};
 use alloy_rlp::{length_of_length, Decodable, Encodable, Header};
 use core::mem;
 
@@ -10,7 +18,9 @@ use reth_codecs::Compact;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
-use dawn_crypto::{DecryptionKey, EphemeralPublicKey};
+use dawn_crypto::{
+    CiphertextProof, DecryptionKey, EphemeralPublicKey, MasterPublicKey, MAX_PADDING,
+};
 
 /// A transaction with a priority fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::reth_codec)]
@@ -77,10 +87,65 @@ pub struct TxDawnDecrypted {
     /// Input as data, or if `to` is [`TxKind::Call`]: An unlimited size byte array specifying the
     /// input data of the message call, formally Td.
     pub ephemeral_public_key: EphemeralPublicKey,
+    /// The proof of the ciphertext if it is of version 2, which re-encrypting cannot recompute.
+    pub ciphertext_proof: Option<CiphertextProof>,
     pub decryption_key: DecryptionKey,
     pub sender: Address,
+    /// The public value of the encrypted transaction if its plaintext hides `value` and
+    /// `access_list`, in which case it bounds `value` and the public access list is empty. `None`
+    /// if both are public.
+    pub value_bound: Option<U256>,
+    /// The number of zero bytes padding the plaintext in the ciphertext, at most
+    /// [`MAX_PADDING`], which re-encrypting needs to rebuild the ciphertext.
+    pub padding: u64,
     // keep last for compact
-    pub input: Bytes
+    pub input: Bytes,
+}
+
+impl From<TxDawnDecrypted> for alloy_consensus::TxDawnDecrypted {
+    fn from(tx: TxDawnDecrypted) -> Self {
+        Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit as u128,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            to: tx.to,
+            value: tx.value,
+            access_list: tx.access_list,
+            input: tx.input,
+            ephemeral_public_key: tx.ephemeral_public_key,
+            ciphertext_proof: tx.ciphertext_proof,
+            decryption_key: tx.decryption_key,
+            sender: tx.sender,
+            value_bound: tx.value_bound,
+            padding: tx.padding,
+        }
+    }
+}
+
+impl TryFrom<alloy_consensus::TxDawnDecrypted> for TxDawnDecrypted {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(tx: alloy_consensus::TxDawnDecrypted) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit.try_into().map_err(|_| alloy_rlp::Error::Overflow)?,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            to: tx.to,
+            value: tx.value,
+            access_list: tx.access_list,
+            ephemeral_public_key: tx.ephemeral_public_key,
+            ciphertext_proof: tx.ciphertext_proof,
+            decryption_key: tx.decryption_key,
+            sender: tx.sender,
+            value_bound: tx.value_bound,
+            padding: tx.padding,
+            input: tx.input,
+        })
+    }
 }
 
 impl TxDawnDecrypted {
@@ -102,6 +167,36 @@ impl TxDawnDecrypted {
         }
     }
 
+    /// Returns the identity label of the transaction, see [`dawn_label`].
+    pub fn label(&self) -> [u8; 96] {
+        dawn_label(self.chain_id, &self.sender, self.nonce)
+    }
+
+    /// Returns `true` if the decryption key was issued by the owner of `mpk` for the label of the
+    /// transaction.
+    pub fn verify_decryption_key(&self, mpk: &MasterPublicKey) -> bool {
+        dawn_crypto::verify(&self.label(), mpk, &self.decryption_key)
+    }
+
+    /// Returns `true` if the value does not exceed [`TxDawnDecrypted::value_bound`], if any.
+    pub fn is_within_value_bound(&self) -> bool {
+        self.value_bound.map_or(true, |value_bound| self.value <= value_bound)
+    }
+
+    /// Re-encrypts the transaction, returning the encrypted transaction the sender signed.
+    ///
+    /// The ciphertext is rebuilt from the ephemeral public key and the decryption key, so it is
+    /// only equal to the original one if `to`, `input`, the hidden fields, if any, and the
+    /// padding are what the sender encrypted.
+    ///
+    /// Panics if the ephemeral public key or the decryption key is not a valid point, which
+    /// decoding the transaction rules out.
+    pub fn reencrypt(&self) -> TxDawnEncrypted {
+        alloy_consensus::dawn::reencrypt_unsigned(&self.clone().into())
+            .try_into()
+            .expect("the gas limit is that of the decrypted transaction")
+    }
+
     /// Decodes the inner [`TxDawnDecrypted`] fields from RLP bytes.
     ///
     /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
@@ -116,20 +211,52 @@ impl TxDawnDecrypted {
     /// - `value`
     /// - `data` (`input`)
     /// - `access_list`
+    /// - `ephemeral_public_key`, followed by `ciphertext_proof` if any
+    /// - `decryption_key`
+    /// - `sender`
+    /// - `value_bound`, as a list holding the bound, empty if the value is public
+    /// - `padding`
+    ///
+    /// The ephemeral public key and the decryption key must be valid points, and the padding must
+    /// not exceed [`MAX_PADDING`].
     pub(crate) fn decode_inner(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let chain_id = Decodable::decode(buf)?;
+        let nonce = Decodable::decode(buf)?;
+        let max_priority_fee_per_gas = Decodable::decode(buf)?;
+        let max_fee_per_gas = Decodable::decode(buf)?;
+        let gas_limit = Decodable::decode(buf)?;
+        let to = Decodable::decode(buf)?;
+        let value = Decodable::decode(buf)?;
+        let input = Decodable::decode(buf)?;
+        let access_list = Decodable::decode(buf)?;
+        let (ephemeral_public_key, ciphertext_proof) = decode_ephemeral_public_key(buf)?;
+        let decryption_key = decode_decryption_key(buf)?;
+        let sender = Decodable::decode(buf)?;
+        let value_bound = match Vec::<U256>::decode(buf)?[..] {
+            [] => None,
+            [value_bound] => Some(value_bound),
+            _ => return Err(alloy_rlp::Error::Custom("more than one value bound")),
+        };
+        let padding = Decodable::decode(buf)?;
+        if padding > MAX_PADDING as u64 {
+            return Err(alloy_rlp::Error::Custom("padding too long"))
+        }
         Ok(Self {
-            chain_id: Decodable::decode(buf)?,
-            nonce: Decodable::decode(buf)?,
-            max_priority_fee_per_gas: Decodable::decode(buf)?,
-            max_fee_per_gas: Decodable::decode(buf)?,
-            gas_limit: Decodable::decode(buf)?,
-            to: Decodable::decode(buf)?,
-            value: Decodable::decode(buf)?,
-            input: Decodable::decode(buf)?,
-            access_list: Decodable::decode(buf)?,
-            ephemeral_public_key: EphemeralPublicKey(Decodable::decode(buf)?),
-            decryption_key: DecryptionKey(Decodable::decode(buf)?),
-            sender: Decodable::decode(buf)?,
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to,
+            value,
+            input,
+            access_list,
+            ephemeral_public_key,
+            ciphertext_proof,
+            decryption_key,
+            sender,
+            value_bound,
+            padding,
         })
     }
 
@@ -144,9 +271,14 @@ impl TxDawnDecrypted {
             self.value.length() +
             self.input.0.length() +
             self.access_list.length() +
-            self.ephemeral_public_key.0.length() +
+            ephemeral_public_key_length(
+                &self.ephemeral_public_key,
+                self.ciphertext_proof.as_ref(),
+            ) +
             self.decryption_key.0.length() +
-            self.sender.length()
+            self.sender.length() +
+            alloy_rlp::list_length::<_, U256>(self.value_bound.as_slice()) +
+            self.padding.length()
     }
 
     /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
@@ -160,9 +292,15 @@ impl TxDawnDecrypted {
         self.value.encode(out);
         self.input.0.encode(out);
         self.access_list.encode(out);
-        out.put_slice(&self.ephemeral_public_key.0);
-        out.put_slice(&self.decryption_key.0);
+        encode_ephemeral_public_key(
+            &self.ephemeral_public_key,
+            self.ciphertext_proof.as_ref(),
+            out,
+        );
+        self.decryption_key.0.encode(out);
         self.sender.encode(out);
+        alloy_rlp::encode_list::<_, U256>(self.value_bound.as_slice(), out);
+        self.padding.encode(out);
     }
 
     /// Inner encoding function that is used for both rlp [`Encodable`] trait and for calculating
@@ -221,33 +359,31 @@ impl TxDawnDecrypted {
         self.to.size() + // to
         mem::size_of::<U256>() + // value
         self.access_list.size() + // access_list
-        self.input.len() // input
+        self.input.len() + // input
+        mem::size_of::<Option<CiphertextProof>>() + // ciphertext_proof
+        mem::size_of::<Option<U256>>() + // value_bound
+        mem::size_of::<u64>() // padding
     }
 
-    /// Encodes the EIP-1559 transaction in RLP for signing.
+    /// Encodes the transaction in RLP for signing.
     ///
-    /// This encodes the transaction as:
-    /// `tx_type || rlp(chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to,
-    /// value, input, access_list)`
-    ///
-    /// Note that there is no rlp header before the transaction type byte.
+    /// The sender signed the encrypted transaction, so this is the signing encoding of
+    /// [`TxDawnDecrypted::reencrypt`].
     pub(crate) fn encode_for_signing(&self, out: &mut dyn bytes::BufMut) {
-        out.put_u8(self.tx_type() as u8);
-        Header { list: true, payload_length: self.fields_len() }.encode(out);
-        self.encode_fields(out);
+        self.reencrypt().encode_for_signing(out)
     }
 
     /// Outputs the length of the signature RLP encoding for the transaction.
     pub(crate) fn payload_len_for_signature(&self) -> usize {
-        let payload_length = self.fields_len();
-        // 'transaction type byte length' + 'header length' + 'payload length'
-        1 + length_of_length(payload_length) + payload_length
+        self.reencrypt().payload_len_for_signature()
     }
 
     /// Outputs the signature hash of the transaction by first encoding without a signature, then
     /// hashing.
+    ///
+    /// This re-encrypts the transaction, which is expensive.
     pub(crate) fn signature_hash(&self) -> B256 {
-        let mut buf = Vec::with_capacity(self.payload_len_for_signature());
+        let mut buf = Vec::new();
         self.encode_for_signing(&mut buf);
         keccak256(&buf)
     }
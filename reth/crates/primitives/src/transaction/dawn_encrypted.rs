@@ -1,7 +1,9 @@
 use super::access_list::AccessList;
-use crate::{keccak256, Bytes, ChainId, Signature, TxKind, TxType, B256, U256};
+use crate::{keccak256, Address, Bytes, ChainId, Signature, Transaction, TxType, B256, U256};
+use alloy_consensus::dawn::GasSpec;
 use alloy_rlp::{length_of_length, Decodable, Encodable, Header};
 use core::mem;
+use revm_primitives::SpecId;
 
 #[cfg(any(test, feature = "reth-codec"))]
 use reth_codecs::Compact;
@@ -10,8 +12,9 @@ use reth_codecs::Compact;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
-use dawn_crypto::Ciphertext;
-use dawn_crypto::EphemeralPublicKey;
+use dawn_crypto::{Ciphertext, CiphertextProof, DecryptionKey, EphemeralPublicKey};
+
+pub use alloy_consensus::dawn::{block_label as dawn_block_label, label as dawn_label};
 
 /// A transaction with a priority fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::reth_codec)]
@@ -56,6 +59,9 @@ pub struct TxDawnEncrypted {
     /// be transferred to the message call’s recipient or,
     /// in the case of contract creation, as an endowment
     /// to the newly created account; formally Tv.
+    ///
+    /// If the plaintext hides the value, this is a public upper bound on it instead, which the
+    /// sender must be able to afford.
     pub value: U256,
 
     /// The accessList specifies a list of addresses and storage keys;
@@ -63,11 +69,95 @@ pub struct TxDawnEncrypted {
     /// and `accessed_storage_keys` global sets (introduced in EIP-2929).
     /// A gas cost is charged, though at a discount relative to the cost of
     /// accessing outside the list.
+    ///
+    /// Empty if the plaintext hides the access list.
     pub access_list: AccessList,
 
     pub ciphertext: Ciphertext,
 }
 
+/// Decodes an ephemeral public key, rejecting it unless it is a valid point, so that invalid
+/// ciphertexts are turned away when transactions are decoded rather than when they are decrypted.
+///
+/// The proof of a version 2 ciphertext follows the key in the same string, so that version 1
+/// ciphertexts keep their encoding.
+pub(crate) fn decode_ephemeral_public_key(
+    buf: &mut &[u8],
+) -> alloy_rlp::Result<(EphemeralPublicKey, Option<CiphertextProof>)> {
+    let bytes = Header::decode_bytes(buf, false)?;
+    let (u, proof) = match bytes.len() {
+        96 => (bytes, None),
+        160 => (&bytes[..96], Some(CiphertextProof(bytes[96..].try_into().unwrap()))),
+        _ => return Err(alloy_rlp::Error::UnexpectedLength),
+    };
+    let u = EphemeralPublicKey::try_from(<[u8; 96]>::try_from(u).unwrap())
+        .map_err(|_| alloy_rlp::Error::Custom("invalid ephemeral public key"))?;
+    Ok((u, proof))
+}
+
+/// Returns the length of an ephemeral public key and proof encoded by
+/// [`encode_ephemeral_public_key`].
+pub(crate) fn ephemeral_public_key_length(
+    u: &EphemeralPublicKey,
+    proof: Option<&CiphertextProof>,
+) -> usize {
+    let payload_length = u.0.len() + proof.map_or(0, |proof| proof.0.len());
+    Header { list: false, payload_length }.length() + payload_length
+}
+
+/// Encodes an ephemeral public key followed by the proof of a version 2 ciphertext, if any, see
+/// [`decode_ephemeral_public_key`].
+pub(crate) fn encode_ephemeral_public_key(
+    u: &EphemeralPublicKey,
+    proof: Option<&CiphertextProof>,
+    out: &mut dyn bytes::BufMut,
+) {
+    let payload_length = u.0.len() + proof.map_or(0, |proof| proof.0.len());
+    Header { list: false, payload_length }.encode(out);
+    out.put_slice(&u.0);
+    if let Some(proof) = proof {
+        out.put_slice(&proof.0);
+    }
+}
+
+/// Decodes a decryption key, rejecting it unless it is a valid point.
+pub(crate) fn decode_decryption_key(buf: &mut &[u8]) -> alloy_rlp::Result<DecryptionKey> {
+    DecryptionKey::try_from(<[u8; 48]>::decode(buf)?)
+        .map_err(|_| alloy_rlp::Error::Custom("invalid decryption key"))
+}
+
+impl From<TxDawnEncrypted> for alloy_consensus::TxDawnEncrypted {
+    fn from(tx: TxDawnEncrypted) -> Self {
+        Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit as u128,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            value: tx.value,
+            access_list: tx.access_list,
+            ciphertext: tx.ciphertext,
+        }
+    }
+}
+
+impl TryFrom<alloy_consensus::TxDawnEncrypted> for TxDawnEncrypted {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(tx: alloy_consensus::TxDawnEncrypted) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit.try_into().map_err(|_| alloy_rlp::Error::Overflow)?,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            value: tx.value,
+            access_list: tx.access_list,
+            ciphertext: tx.ciphertext,
+        })
+    }
+}
+
 impl TxDawnEncrypted {
     /// Returns the effective gas price for the given `base_fee`.
     pub const fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
@@ -87,6 +177,81 @@ impl TxDawnEncrypted {
         }
     }
 
+    /// Returns the number of bytes of ciphertext the sender is charged for, proof and padding
+    /// included.
+    pub fn ciphertext_len(&self) -> usize {
+        self.ciphertext.u.0.len() +
+            self.ciphertext.proof.map_or(0, |proof| proof.0.len()) +
+            self.ciphertext.payload.len() +
+            self.ciphertext.tag.len()
+    }
+
+    /// Returns the ciphertext as the calldata the sender is charged for.
+    pub fn ciphertext_bytes(&self) -> Bytes {
+        let proof = self.ciphertext.proof.as_ref().map_or(&[][..], |proof| &proof.0[..]);
+        [&self.ciphertext.u.0[..], proof, &self.ciphertext.payload, &self.ciphertext.tag]
+            .concat()
+            .into()
+    }
+
+    /// Returns the intrinsic gas of the transaction when executed under `spec_id`, the lowest
+    /// `gas_limit` it is accepted with, see [`alloy_consensus::dawn::intrinsic_gas`].
+    ///
+    /// This is the intrinsic gas of a call carrying the ciphertext as calldata together with the
+    /// public access list, plus [`DECRYPTION_GAS`](crate::constants::dawn::DECRYPTION_GAS).
+    pub fn intrinsic_gas(&self, spec_id: SpecId) -> u64 {
+        let spec = if spec_id.is_enabled_in(SpecId::BERLIN) {
+            GasSpec::Berlin
+        } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
+            GasSpec::Istanbul
+        } else {
+            GasSpec::Frontier
+        };
+        alloy_consensus::dawn::intrinsic_gas(&self.clone().into(), spec) as u64
+    }
+
+    /// Returns `true` if the ciphertext is of version 2 and its proof holds for the label of the
+    /// transaction sent by `sender`, see [`Ciphertext::verify_proof`], or for `block_label` with
+    /// the label of the transaction as associated data on a chain that labels ciphertexts by
+    /// block, see [`dawn_block_label`].
+    ///
+    /// This shows that the sender encrypted the ciphertext for this transaction, rather than
+    /// copying the ephemeral public key of someone else's, without decrypting it.
+    pub fn verify_ciphertext_proof(
+        &self,
+        sender: &Address,
+        block_label: Option<&[u8; 96]>,
+    ) -> bool {
+        let label = dawn_label(self.chain_id, sender, self.nonce);
+        self.ciphertext.verify_proof(block_label.unwrap_or(&label), &label)
+    }
+
+    /// Decrypts the transaction sent by `sender` with the decryption key issued for its label.
+    ///
+    /// Returns a [`Transaction::DawnDecrypted`] if the ciphertext decrypts to a well-formed
+    /// plaintext, see [`DawnPlaintext`](crate::DawnPlaintext), and a
+    /// [`Transaction::DawnUndecrypted`] otherwise. Either way the result is signed by the
+    /// signature of the encrypted transaction.
+    ///
+    /// If the plaintext hides the value and access list, the decrypted transaction carries them
+    /// and keeps the public value as its
+    /// [`TxDawnDecrypted::value_bound`](crate::TxDawnDecrypted::value_bound).
+    pub fn decrypt(&self, sender: Address, decryption_key: DecryptionKey) -> Transaction {
+        let tx = alloy_consensus::TxDawnEncrypted::from(self.clone());
+        if let Ok(decrypted) =
+            alloy_consensus::dawn::decrypt_unsigned(&tx, &decryption_key, &sender)
+        {
+            return Transaction::DawnDecrypted(
+                decrypted.try_into().expect("the gas limit is that of the encrypted transaction"),
+            )
+        }
+        let undecrypted = alloy_consensus::dawn::undecrypt_unsigned(&tx, &decryption_key, &sender)
+            .expect("the ciphertext fails to decrypt");
+        Transaction::DawnUndecrypted(
+            undecrypted.try_into().expect("the gas limit is that of the encrypted transaction"),
+        )
+    }
+
     /// Decodes the inner [`TxDawnEncrypted`] fields from RLP bytes.
     ///
     /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
@@ -101,6 +266,8 @@ impl TxDawnEncrypted {
     /// - `value`
     /// - `data` (`input`)
     /// - `access_list`
+    /// - `ciphertext`, whose ephemeral public key must be a valid point, with the proof of a
+    ///   version 2 ciphertext appended to it
     pub(crate) fn decode_inner(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
         Ok(Self {
             chain_id: Decodable::decode(buf)?,
@@ -110,10 +277,14 @@ impl TxDawnEncrypted {
             gas_limit: Decodable::decode(buf)?,
             value: Decodable::decode(buf)?,
             access_list: Decodable::decode(buf)?,
-            ciphertext: Ciphertext {
-                u: EphemeralPublicKey(Decodable::decode(buf)?),
-                payload: Decodable::decode(buf)?,
-                tag: Decodable::decode(buf)?,
+            ciphertext: {
+                let (u, proof) = decode_ephemeral_public_key(buf)?;
+                Ciphertext {
+                    u,
+                    payload: Decodable::decode(buf)?,
+                    tag: Decodable::decode(buf)?,
+                    proof,
+                }
             },
         })
     }
@@ -127,9 +298,9 @@ impl TxDawnEncrypted {
             self.gas_limit.length() +
             self.value.length() +
             self.access_list.length() +
-         self.ciphertext.u.0.length() +
-         self.ciphertext.payload.length() +
-         self.ciphertext.tag.length()
+            ephemeral_public_key_length(&self.ciphertext.u, self.ciphertext.proof.as_ref()) +
+            self.ciphertext.payload.length() +
+            self.ciphertext.tag.length()
     }
 
     /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
@@ -141,7 +312,7 @@ impl TxDawnEncrypted {
         self.gas_limit.encode(out);
         self.value.encode(out);
         self.access_list.encode(out);
-        self.ciphertext.u.0.encode(out);
+        encode_ephemeral_public_key(&self.ciphertext.u, self.ciphertext.proof.as_ref(), out);
         self.ciphertext.payload.encode(out);
         self.ciphertext.tag.encode(out);
     }
@@ -201,9 +372,7 @@ impl TxDawnEncrypted {
         mem::size_of::<u128>() + // max_priority_fee_per_gas
         mem::size_of::<U256>() + // value
         self.access_list.size() + // access_list
-        self.ciphertext.u.0.len() + // ciphertext
-        self.ciphertext.payload.len() +
-        self.ciphertext.tag.len()
+        self.ciphertext_len() // ciphertext
     }
 
     /// Encodes the EIP-1559 transaction in RLP for signing.
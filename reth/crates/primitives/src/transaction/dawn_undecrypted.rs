@@ -0,0 +1,326 @@
+use super::{
+    access_list::AccessList,
+    dawn_encrypted::{
+        dawn_label, decode_decryption_key, decode_ephemeral_public_key,
+        encode_ephemeral_public_key, ephemeral_public_key_length,
+    },
+};
+use crate::{
+    keccak256, Address, Bytes, ChainId, Signature, TxDawnEncrypted, TxKind, TxType, B256, U256,
+};
+use alloy_rlp::{length_of_length, Decodable, Encodable, Header};
+use core::mem;
+
+#[cfg(any(test, feature = "reth-codec"))]
+use reth_codecs::Compact;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use dawn_crypto::{Ciphertext, DecryptionKey, MasterPublicKey};
+
+/// An encrypted transaction whose ciphertext failed to decrypt.
+///
+/// It is only valid if decrypting `ciphertext` with `decryption_key` fails. Executing it does not
+/// transfer value or run code; the sender only pays for the ciphertext as calldata.
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::reth_codec)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TxDawnUndecrypted {
+    /// Added as EIP-155: Simple replay attack protection
+    pub chain_id: ChainId,
+
+    /// A scalar value equal to the number of transactions sent by the sender; formally Tn.
+    pub nonce: u64,
+
+    /// A scalar value equal to the maximum
+    /// amount of gas that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    pub gas_limit: u64,
+
+    /// A scalar value equal to the maximum
+    /// amount of gas that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    ///
+    /// This is also known as `GasFeeCap`
+    pub max_fee_per_gas: u128,
+
+    /// Max Priority fee that transaction is paying
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    ///
+    /// This is also known as `GasTipCap`
+    pub max_priority_fee_per_gas: u128,
+
+    /// The value of the original encrypted transaction. It is never transferred.
+    pub value: U256,
+
+    /// The access list of the original encrypted transaction.
+    pub access_list: AccessList,
+
+    /// The decryption key for the label of the original encrypted transaction.
+    pub decryption_key: DecryptionKey,
+    /// The sender of the original encrypted transaction.
+    pub sender: Address,
+    // keep last for compact
+    /// The ciphertext of the original encrypted transaction.
+    pub ciphertext: Ciphertext,
+}
+
+impl TryFrom<alloy_consensus::TxDawnUndecrypted> for TxDawnUndecrypted {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(tx: alloy_consensus::TxDawnUndecrypted) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit.try_into().map_err(|_| alloy_rlp::Error::Overflow)?,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            value: tx.value,
+            access_list: tx.access_list,
+            decryption_key: tx.decryption_key,
+            sender: tx.sender,
+            ciphertext: tx.ciphertext,
+        })
+    }
+}
+
+impl TxDawnUndecrypted {
+    /// Returns the effective gas price for the given `base_fee`.
+    pub const fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        match base_fee {
+            None => self.max_fee_per_gas,
+            Some(base_fee) => {
+                // if the tip is greater than the max priority fee per gas, set it to the max
+                // priority fee per gas + base fee
+                let tip = self.max_fee_per_gas.saturating_sub(base_fee as u128);
+                if tip > self.max_priority_fee_per_gas {
+                    self.max_priority_fee_per_gas + base_fee as u128
+                } else {
+                    // otherwise return the max fee per gas
+                    self.max_fee_per_gas
+                }
+            }
+        }
+    }
+
+    /// Returns the original encrypted transaction, which is what the sender signed.
+    pub fn encrypted(&self) -> TxDawnEncrypted {
+        TxDawnEncrypted {
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            gas_limit: self.gas_limit,
+            max_fee_per_gas: self.max_fee_per_gas,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            value: self.value,
+            access_list: self.access_list.clone(),
+            ciphertext: self.ciphertext.clone(),
+        }
+    }
+
+    /// Returns the identity label of the transaction, see [`dawn_label`].
+    pub fn label(&self) -> [u8; 96] {
+        dawn_label(self.chain_id, &self.sender, self.nonce)
+    }
+
+    /// Returns `true` if the decryption key was issued by the owner of `mpk` for the label of the
+    /// transaction.
+    pub fn verify_decryption_key(&self, mpk: &MasterPublicKey) -> bool {
+        dawn_crypto::verify(&self.label(), mpk, &self.decryption_key)
+    }
+
+    /// Returns `true` if the ciphertext fails to decrypt to a well-formed plaintext, see
+    /// [`DawnPlaintext`](super::DawnPlaintext), or to hidden fields that the public value and
+    /// access list do not allow, which is what makes the transaction valid.
+    pub fn is_undecryptable(&self) -> bool {
+        let tx = alloy_consensus::TxDawnEncrypted::from(self.encrypted());
+        alloy_consensus::dawn::decrypt_unsigned(&tx, &self.decryption_key, &self.sender).is_err()
+    }
+
+    /// Returns the target of the transaction, which is always the sender.
+    pub const fn kind(&self) -> TxKind {
+        TxKind::Call(self.sender)
+    }
+
+    /// Returns the number of bytes of ciphertext the sender is charged for, proof and padding
+    /// included.
+    pub fn ciphertext_len(&self) -> usize {
+        self.ciphertext.u.0.len() +
+            self.ciphertext.proof.map_or(0, |proof| proof.0.len()) +
+            self.ciphertext.payload.len() +
+            self.ciphertext.tag.len()
+    }
+
+    /// Returns the ciphertext as the calldata the sender is charged for.
+    pub fn ciphertext_bytes(&self) -> Bytes {
+        let proof = self.ciphertext.proof.as_ref().map_or(&[][..], |proof| &proof.0[..]);
+        [&self.ciphertext.u.0[..], proof, &self.ciphertext.payload, &self.ciphertext.tag]
+            .concat()
+            .into()
+    }
+
+    /// Decodes the inner [`TxDawnUndecrypted`] fields from RLP bytes.
+    ///
+    /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
+    /// RLP fields in the following order:
+    ///
+    /// - `chain_id`
+    /// - `nonce`
+    /// - `max_priority_fee_per_gas`
+    /// - `max_fee_per_gas`
+    /// - `gas_limit`
+    /// - `value`
+    /// - `access_list`
+    /// - `ciphertext`, with the proof of a version 2 ciphertext appended to its ephemeral public
+    ///   key
+    /// - `decryption_key`
+    /// - `sender`
+    ///
+    /// The ephemeral public key and the decryption key must be valid points.
+    pub(crate) fn decode_inner(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            chain_id: Decodable::decode(buf)?,
+            nonce: Decodable::decode(buf)?,
+            max_priority_fee_per_gas: Decodable::decode(buf)?,
+            max_fee_per_gas: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            access_list: Decodable::decode(buf)?,
+            ciphertext: {
+                let (u, proof) = decode_ephemeral_public_key(buf)?;
+                Ciphertext {
+                    u,
+                    payload: Decodable::decode(buf)?,
+                    tag: Decodable::decode(buf)?,
+                    proof,
+                }
+            },
+            decryption_key: decode_decryption_key(buf)?,
+            sender: Decodable::decode(buf)?,
+        })
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn fields_len(&self) -> usize {
+        self.chain_id.length() +
+            self.nonce.length() +
+            self.max_priority_fee_per_gas.length() +
+            self.max_fee_per_gas.length() +
+            self.gas_limit.length() +
+            self.value.length() +
+            self.access_list.length() +
+            ephemeral_public_key_length(&self.ciphertext.u, self.ciphertext.proof.as_ref()) +
+            self.ciphertext.payload.length() +
+            self.ciphertext.tag.length() +
+            self.decryption_key.0.length() +
+            self.sender.length()
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn bytes::BufMut) {
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.max_priority_fee_per_gas.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.gas_limit.encode(out);
+        self.value.encode(out);
+        self.access_list.encode(out);
+        encode_ephemeral_public_key(&self.ciphertext.u, self.ciphertext.proof.as_ref(), out);
+        self.ciphertext.payload.encode(out);
+        self.ciphertext.tag.encode(out);
+        self.decryption_key.0.encode(out);
+        self.sender.encode(out);
+    }
+
+    /// Inner encoding function that is used for both rlp [`Encodable`] trait and for calculating
+    /// hash that for eip2718 does not require rlp header
+    ///
+    /// This encodes the transaction as:
+    /// `rlp(chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, value,
+    /// access_list, ciphertext, decryption_key, sender, y_parity, r, s)`
+    pub(crate) fn encode_with_signature(
+        &self,
+        signature: &Signature,
+        out: &mut dyn bytes::BufMut,
+        with_header: bool,
+    ) {
+        let payload_length = self.fields_len() + signature.payload_len();
+        if with_header {
+            Header {
+                list: false,
+                payload_length: 1 + length_of_length(payload_length) + payload_length,
+            }
+            .encode(out);
+        }
+        out.put_u8(self.tx_type() as u8);
+        let header = Header { list: true, payload_length };
+        header.encode(out);
+        self.encode_fields(out);
+        signature.encode(out);
+    }
+
+    /// Output the length of the RLP signed transaction encoding, _without_ a RLP string header.
+    pub(crate) fn payload_len_with_signature_without_header(&self, signature: &Signature) -> usize {
+        let payload_length = self.fields_len() + signature.payload_len();
+        // 'transaction type byte length' + 'header length' + 'payload length'
+        1 + length_of_length(payload_length) + payload_length
+    }
+
+    /// Output the length of the RLP signed transaction encoding. This encodes with a RLP header.
+    pub(crate) fn payload_len_with_signature(&self, signature: &Signature) -> usize {
+        let len = self.payload_len_with_signature_without_header(signature);
+        length_of_length(len) + len
+    }
+
+    /// Get transaction type
+    pub(crate) const fn tx_type(&self) -> TxType {
+        TxType::DawnUndecrypted
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [`TxDawnUndecrypted`] transaction.
+    #[inline]
+    pub fn size(&self) -> usize {
+        mem::size_of::<ChainId>() + // chain_id
+        mem::size_of::<u64>() + // nonce
+        mem::size_of::<u64>() + // gas_limit
+        mem::size_of::<u128>() + // max_fee_per_gas
+        mem::size_of::<u128>() + // max_priority_fee_per_gas
+        mem::size_of::<U256>() + // value
+        self.access_list.size() + // access_list
+        self.ciphertext_len() + // ciphertext
+        mem::size_of::<DecryptionKey>() + // decryption_key
+        mem::size_of::<Address>() // sender
+    }
+
+    /// Encodes the transaction in RLP for signing.
+    ///
+    /// The sender signed the original encrypted transaction, so this is the signing encoding of
+    /// [`TxDawnUndecrypted::encrypted`].
+    pub(crate) fn encode_for_signing(&self, out: &mut dyn bytes::BufMut) {
+        self.encrypted().encode_for_signing(out)
+    }
+
+    /// Outputs the length of the signature RLP encoding for the transaction.
+    pub(crate) fn payload_len_for_signature(&self) -> usize {
+        self.encrypted().payload_len_for_signature()
+    }
+
+    /// Outputs the signature hash of the transaction by first encoding without a signature, then
+    /// hashing.
+    pub(crate) fn signature_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(self.payload_len_for_signature());
+        self.encode_for_signing(&mut buf);
+        keccak256(&buf)
+    }
+}
@@ -32,6 +32,9 @@ pub enum InvalidTransactionError {
     /// The transaction requires EIP-7702 which is not enabled currently.
     #[display(fmt = "EIP-7702 transactions are disabled")]
     Eip7702Disabled,
+    /// The transaction requires Dawn which is not enabled currently.
+    #[display(fmt = "Dawn transactions are disabled")]
+    DawnDisabled,
     /// Thrown if a transaction is not supported in the current network configuration.
     #[display(fmt = "transaction type not supported")]
     TxTypeNotSupported,
@@ -54,6 +57,13 @@ pub enum InvalidTransactionError {
     /// Thrown if the sender of a transaction is a contract.
     #[display(fmt = "transaction signer has bytecode set")]
     SignerAccountHasBytecode,
+    /// Thrown if the hidden value of a decrypted Dawn transaction exceeds its public bound.
+    #[display(fmt = "hidden value exceeds the public value bound")]
+    DawnValueExceedsBound,
+    /// Thrown if the ciphertext of an encrypted Dawn transaction does not carry a valid proof of
+    /// well-formedness, which version 1 ciphertexts lack.
+    #[display(fmt = "ciphertext lacks a valid proof of well-formedness")]
+    DawnInvalidCiphertextProof,
 }
 
 #[cfg(feature = "std")]
@@ -73,6 +83,31 @@ pub enum TransactionConversionError {
 #[cfg(feature = "std")]
 impl std::error::Error for TransactionConversionError {}
 
+/// Represents error variants that can happen when verifying a Dawn decrypted or undecrypted
+/// transaction, see [`TransactionSigned::verify_dawn`](crate::TransactionSigned::verify_dawn).
+#[derive(Debug, Clone, Eq, PartialEq, derive_more::Display)]
+pub enum DawnTransactionError {
+    /// There is no master public key to verify the decryption key against.
+    #[display(fmt = "Dawn master public key is not configured")]
+    MasterKeyMissing,
+    /// The decryption key was not issued by the SMC for the label of the transaction.
+    #[display(fmt = "decryption key does not match the transaction label")]
+    InvalidDecryptionKey,
+    /// The signature of the encrypted transaction does not recover to the sender.
+    #[display(fmt = "encrypted transaction was not signed by the sender")]
+    InvalidSender,
+    /// The ciphertext of an undecrypted transaction decrypts successfully.
+    #[display(fmt = "ciphertext of undecrypted transaction decrypts")]
+    DecryptionSucceeded,
+    /// The hidden value of a decrypted transaction exceeds the public bound of the encrypted
+    /// transaction.
+    #[display(fmt = "hidden value exceeds the public value bound")]
+    ValueExceedsBound,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DawnTransactionError {}
+
 /// Represents error variants than can happen when trying to convert a
 /// [`TransactionSignedEcRecovered`](crate::TransactionSignedEcRecovered) transaction.
 #[derive(Debug, Clone, Eq, PartialEq, derive_more::Display)]
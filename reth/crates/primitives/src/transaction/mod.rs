@@ -10,21 +10,25 @@ use alloy_rlp::{
 };
 use bytes::Buf;
 use core::mem;
+use dawn_crypto::{DecryptionKey, MasterPublicKey};
 use derive_more::{AsRef, Deref};
 use once_cell::sync::Lazy;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
 pub use access_list::{AccessList, AccessListItem, AccessListResult};
+pub use alloy_consensus::dawn::{HiddenFields as DawnHiddenFields, Plaintext as DawnPlaintext};
+pub use dawn_decrypted::TxDawnDecrypted;
+pub use dawn_encrypted::{dawn_block_label, dawn_label, TxDawnEncrypted};
+pub use dawn_undecrypted::TxDawnUndecrypted;
 pub use eip1559::TxEip1559;
 pub use eip2930::TxEip2930;
 pub use eip4844::TxEip4844;
 pub use eip7702::TxEip7702;
-pub use dawn_encrypted::TxDawnEncrypted;
-pub use dawn_decrypted::TxDawnDecrypted;
 
 pub use error::{
-    InvalidTransactionError, TransactionConversionError, TryFromRecoveredTransactionError,
+    DawnTransactionError, InvalidTransactionError, TransactionConversionError,
+    TryFromRecoveredTransactionError,
 };
 pub use legacy::TxLegacy;
 pub use meta::TransactionMeta;
@@ -38,19 +42,21 @@ pub use sidecar::{BlobTransaction, BlobTransactionSidecar};
 pub use compat::FillTxEnv;
 pub use signature::{extract_chain_id, Signature};
 pub use tx_type::{
-    TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID, DAWN_ENCRYPTED_TX_TYPE_ID, DAWN_DECRYPTED_TX_TYPE_ID,
+    TxType, DAWN_DECRYPTED_TX_TYPE_ID, DAWN_ENCRYPTED_TX_TYPE_ID, DAWN_UNDECRYPTED_TX_TYPE_ID,
+    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
+    LEGACY_TX_TYPE_ID,
 };
 pub use variant::TransactionSignedVariant;
 
 pub(crate) mod access_list;
 mod compat;
+mod dawn_decrypted;
+mod dawn_encrypted;
+mod dawn_undecrypted;
 mod eip1559;
 mod eip2930;
 mod eip4844;
 mod eip7702;
-mod dawn_encrypted;
-mod dawn_decrypted;
 mod error;
 mod legacy;
 mod meta;
@@ -140,6 +146,7 @@ pub enum Transaction {
     Eip7702(TxEip7702),
     DawnEncrypted(TxDawnEncrypted),
     DawnDecrypted(TxDawnDecrypted),
+    DawnUndecrypted(TxDawnUndecrypted),
     /// Optimism deposit transaction.
     #[cfg(feature = "optimism")]
     Deposit(TxDeposit),
@@ -159,11 +166,25 @@ impl Transaction {
             Self::Eip7702(tx) => tx.signature_hash(),
             Self::DawnEncrypted(tx) => tx.signature_hash(),
             Self::DawnDecrypted(tx) => tx.signature_hash(),
+            Self::DawnUndecrypted(tx) => tx.signature_hash(),
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => B256::ZERO,
         }
     }
 
+    /// Returns the signer recovered from the signature hash if it is the sender of the
+    /// transaction.
+    ///
+    /// Dawn decrypted and undecrypted transactions embed the sender of the encrypted transaction,
+    /// so a signer that does not match it is rejected.
+    pub(crate) fn check_recovered_signer(&self, signer: Address) -> Option<Address> {
+        match self {
+            Self::DawnDecrypted(tx) if tx.sender != signer => None,
+            Self::DawnUndecrypted(tx) if tx.sender != signer => None,
+            _ => Some(signer),
+        }
+    }
+
     /// Get `chain_id`.
     pub const fn chain_id(&self) -> Option<u64> {
         match self {
@@ -174,6 +195,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { chain_id, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { chain_id, .. }) => Some(*chain_id),
             Self::DawnDecrypted(TxDawnDecrypted { chain_id, .. }) => Some(*chain_id),
+            Self::DawnUndecrypted(TxDawnUndecrypted { chain_id, .. }) => Some(*chain_id),
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => None,
         }
@@ -189,6 +211,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { chain_id: ref mut c, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { chain_id: ref mut c, .. }) => *c = chain_id,
             Self::DawnDecrypted(TxDawnDecrypted { chain_id: ref mut c, .. }) => *c = chain_id,
+            Self::DawnUndecrypted(TxDawnUndecrypted { chain_id: ref mut c, .. }) => *c = chain_id,
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => { /* noop */ }
         }
@@ -196,26 +219,30 @@ impl Transaction {
 
     /// Gets the transaction's [`TxKind`], which is the address of the recipient or
     /// [`TxKind::Create`] if the transaction is a contract creation.
-    pub const fn kind(&self) -> TxKind {
-        match self {
+    ///
+    /// Returns `None` for encrypted Dawn transactions, whose recipient is hidden until they are
+    /// decrypted. They are executed as a call to their sender, like undecrypted ones.
+    pub const fn kind(&self) -> Option<TxKind> {
+        Some(match self {
             Self::Legacy(TxLegacy { to, .. }) |
             Self::Eip2930(TxEip2930 { to, .. }) |
             Self::Eip1559(TxEip1559 { to, .. }) |
             Self::Eip7702(TxEip7702 { to, .. }) => *to,
-            Self::DawnEncrypted(_) => TxKind::Call(Address::ZERO), // FIXME: not really true
-            Self::DawnDecrypted(TxDawnDecrypted{ to, .. }) => *to,
+            Self::DawnEncrypted(_) => return None,
+            Self::DawnDecrypted(TxDawnDecrypted { to, .. }) => *to,
+            Self::DawnUndecrypted(TxDawnUndecrypted { sender, .. }) => TxKind::Call(*sender),
             Self::Eip4844(TxEip4844 { to, .. }) => TxKind::Call(*to),
             #[cfg(feature = "optimism")]
             Self::Deposit(TxDeposit { to, .. }) => *to,
-        }
+        })
     }
 
     /// Get the transaction's address of the contract that will be called, or the address that will
     /// receive the transfer.
     ///
-    /// Returns `None` if this is a `CREATE` transaction.
+    /// Returns `None` if this is a `CREATE` transaction, or an encrypted Dawn transaction.
     pub fn to(&self) -> Option<Address> {
-        self.kind().to().copied()
+        self.kind().and_then(|kind| kind.to().copied())
     }
 
     /// Get the transaction's type
@@ -228,6 +255,7 @@ impl Transaction {
             Self::Eip7702(set_code_tx) => set_code_tx.tx_type(),
             Self::DawnEncrypted(encrypted_tx) => encrypted_tx.tx_type(),
             Self::DawnDecrypted(encrypted_tx) => encrypted_tx.tx_type(),
+            Self::DawnUndecrypted(encrypted_tx) => encrypted_tx.tx_type(),
             #[cfg(feature = "optimism")]
             Self::Deposit(deposit_tx) => deposit_tx.tx_type(),
         }
@@ -243,6 +271,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { value, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { value, .. }) => value,
             Self::DawnDecrypted(TxDawnDecrypted { value, .. }) => value,
+            Self::DawnUndecrypted(TxDawnUndecrypted { value, .. }) => value,
             #[cfg(feature = "optimism")]
             Self::Deposit(TxDeposit { value, .. }) => value,
         }
@@ -258,6 +287,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { nonce, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { nonce, .. }) => *nonce,
             Self::DawnDecrypted(TxDawnDecrypted { nonce, .. }) => *nonce,
+            Self::DawnUndecrypted(TxDawnUndecrypted { nonce, .. }) => *nonce,
             // Deposit transactions do not have nonces.
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => 0,
@@ -276,6 +306,7 @@ impl Transaction {
             Self::Eip7702(tx) => Some(&tx.access_list),
             Self::DawnEncrypted(tx) => Some(&tx.access_list),
             Self::DawnDecrypted(tx) => Some(&tx.access_list),
+            Self::DawnUndecrypted(tx) => Some(&tx.access_list),
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => None,
         }
@@ -301,6 +332,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { gas_limit, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { gas_limit, .. }) => *gas_limit,
             Self::DawnDecrypted(TxDawnDecrypted { gas_limit, .. }) => *gas_limit,
+            Self::DawnUndecrypted(TxDawnUndecrypted { gas_limit, .. }) => *gas_limit,
             #[cfg(feature = "optimism")]
             Self::Deposit(TxDeposit { gas_limit, .. }) => *gas_limit,
         }
@@ -310,8 +342,12 @@ impl Transaction {
     pub const fn is_dynamic_fee(&self) -> bool {
         match self {
             Self::Legacy(_) | Self::Eip2930(_) => false,
-            Self::Eip1559(_) | Self::Eip4844(_) | Self::Eip7702(_) |
-                Self::DawnEncrypted(_) | Self::DawnDecrypted(_) => true,
+            Self::Eip1559(_) |
+            Self::Eip4844(_) |
+            Self::Eip7702(_) |
+            Self::DawnEncrypted(_) |
+            Self::DawnDecrypted(_) |
+            Self::DawnUndecrypted(_) => true,
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => false,
         }
@@ -329,6 +365,7 @@ impl Transaction {
             Self::Eip7702(TxEip7702 { max_fee_per_gas, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { max_fee_per_gas, .. }) => *max_fee_per_gas,
             Self::DawnDecrypted(TxDawnDecrypted { max_fee_per_gas, .. }) => *max_fee_per_gas,
+            Self::DawnUndecrypted(TxDawnUndecrypted { max_fee_per_gas, .. }) => *max_fee_per_gas,
             // Deposit transactions buy their L2 gas on L1 and, as such, the L2 gas is not
             // refundable.
             #[cfg(feature = "optimism")]
@@ -347,7 +384,8 @@ impl Transaction {
             Self::Eip4844(TxEip4844 { max_priority_fee_per_gas, .. }) |
             Self::Eip7702(TxEip7702 { max_priority_fee_per_gas, .. }) |
             Self::DawnEncrypted(TxDawnEncrypted { max_priority_fee_per_gas, .. }) |
-            Self::DawnDecrypted(TxDawnDecrypted { max_priority_fee_per_gas, .. }) => {
+            Self::DawnDecrypted(TxDawnDecrypted { max_priority_fee_per_gas, .. }) |
+            Self::DawnUndecrypted(TxDawnUndecrypted { max_priority_fee_per_gas, .. }) => {
                 Some(*max_priority_fee_per_gas)
             }
             #[cfg(feature = "optimism")]
@@ -361,8 +399,13 @@ impl Transaction {
     /// This is also commonly referred to as the "blob versioned hashes" (`BlobVersionedHashes`).
     pub fn blob_versioned_hashes(&self) -> Option<Vec<B256>> {
         match self {
-            Self::Legacy(_) | Self::Eip2930(_) | Self::Eip1559(_) | Self::Eip7702(_)
-                | Self::DawnEncrypted(_) | Self::DawnDecrypted(_) => None,
+            Self::Legacy(_) |
+            Self::Eip2930(_) |
+            Self::Eip1559(_) |
+            Self::Eip7702(_) |
+            Self::DawnEncrypted(_) |
+            Self::DawnDecrypted(_) |
+            Self::DawnUndecrypted(_) => None,
             Self::Eip4844(TxEip4844 { blob_versioned_hashes, .. }) => {
                 Some(blob_versioned_hashes.to_vec())
             }
@@ -406,8 +449,15 @@ impl Transaction {
             Self::Eip1559(TxEip1559 { max_priority_fee_per_gas, .. }) |
             Self::Eip4844(TxEip4844 { max_priority_fee_per_gas, .. }) |
             Self::Eip7702(TxEip7702 { max_priority_fee_per_gas, .. }) => *max_priority_fee_per_gas,
-            Self::DawnEncrypted(TxDawnEncrypted { max_priority_fee_per_gas, .. }) => *max_priority_fee_per_gas,
-            Self::DawnDecrypted(TxDawnDecrypted { max_priority_fee_per_gas, .. }) => *max_priority_fee_per_gas,
+            Self::DawnEncrypted(TxDawnEncrypted { max_priority_fee_per_gas, .. }) => {
+                *max_priority_fee_per_gas
+            }
+            Self::DawnDecrypted(TxDawnDecrypted { max_priority_fee_per_gas, .. }) => {
+                *max_priority_fee_per_gas
+            }
+            Self::DawnUndecrypted(TxDawnUndecrypted { max_priority_fee_per_gas, .. }) => {
+                *max_priority_fee_per_gas
+            }
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => 0,
         }
@@ -425,6 +475,7 @@ impl Transaction {
             Self::Eip7702(dynamic_tx) => dynamic_tx.effective_gas_price(base_fee),
             Self::DawnEncrypted(dynamic_tx) => dynamic_tx.effective_gas_price(base_fee),
             Self::DawnDecrypted(dynamic_tx) => dynamic_tx.effective_gas_price(base_fee),
+            Self::DawnUndecrypted(dynamic_tx) => dynamic_tx.effective_gas_price(base_fee),
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => 0,
         }
@@ -462,15 +513,17 @@ impl Transaction {
     }
 
     /// Get the transaction's input field.
-    pub const fn input(&self) -> &Bytes {
+    ///
+    /// Encrypted and undecrypted Dawn transactions have no plaintext input, and return empty bytes.
+    pub fn input(&self) -> &Bytes {
         match self {
             Self::Legacy(TxLegacy { input, .. }) |
             Self::Eip2930(TxEip2930 { input, .. }) |
             Self::Eip1559(TxEip1559 { input, .. }) |
             Self::Eip4844(TxEip4844 { input, .. }) |
-            Self::Eip7702(TxEip7702 { input, .. }) => input,
-            Self::DawnEncrypted(_) => panic!("encrypted transaction has no input"),
+            Self::Eip7702(TxEip7702 { input, .. }) |
             Self::DawnDecrypted(TxDawnDecrypted { input, .. }) => input,
+            Self::DawnEncrypted(_) | Self::DawnUndecrypted(_) => Default::default(),
             #[cfg(feature = "optimism")]
             Self::Deposit(TxDeposit { input, .. }) => input,
         }
@@ -547,6 +600,9 @@ impl Transaction {
             Self::DawnDecrypted(encrypted_tx) => {
                 encrypted_tx.encode_with_signature(signature, out, with_header)
             }
+            Self::DawnUndecrypted(encrypted_tx) => {
+                encrypted_tx.encode_with_signature(signature, out, with_header)
+            }
             #[cfg(feature = "optimism")]
             Self::Deposit(deposit_tx) => deposit_tx.encode(out, with_header),
         }
@@ -562,6 +618,7 @@ impl Transaction {
             Self::Eip7702(tx) => tx.gas_limit = gas_limit,
             Self::DawnEncrypted(tx) => tx.gas_limit = gas_limit,
             Self::DawnDecrypted(tx) => tx.gas_limit = gas_limit,
+            Self::DawnUndecrypted(tx) => tx.gas_limit = gas_limit,
             #[cfg(feature = "optimism")]
             Self::Deposit(tx) => tx.gas_limit = gas_limit,
         }
@@ -577,6 +634,7 @@ impl Transaction {
             Self::Eip7702(tx) => tx.nonce = nonce,
             Self::DawnEncrypted(tx) => tx.nonce = nonce,
             Self::DawnDecrypted(tx) => tx.nonce = nonce,
+            Self::DawnUndecrypted(tx) => tx.nonce = nonce,
             #[cfg(feature = "optimism")]
             Self::Deposit(_) => { /* noop */ }
         }
@@ -592,12 +650,15 @@ impl Transaction {
             Self::Eip7702(tx) => tx.value = value,
             Self::DawnEncrypted(tx) => tx.value = value,
             Self::DawnDecrypted(tx) => tx.value = value,
+            Self::DawnUndecrypted(tx) => tx.value = value,
             #[cfg(feature = "optimism")]
             Self::Deposit(tx) => tx.value = value,
         }
     }
 
     /// This sets the transaction's input field.
+    ///
+    /// Does nothing for encrypted and undecrypted Dawn transactions, whose input is hidden.
     pub fn set_input(&mut self, input: Bytes) {
         match self {
             Self::Legacy(tx) => tx.input = input,
@@ -605,8 +666,8 @@ impl Transaction {
             Self::Eip1559(tx) => tx.input = input,
             Self::Eip4844(tx) => tx.input = input,
             Self::Eip7702(tx) => tx.input = input,
-            Self::DawnEncrypted(tx) => panic!("cannot set input on encrypted transaction"),
             Self::DawnDecrypted(tx) => tx.input = input,
+            Self::DawnEncrypted(_) | Self::DawnUndecrypted(_) => {}
             #[cfg(feature = "optimism")]
             Self::Deposit(tx) => tx.input = input,
         }
@@ -623,6 +684,7 @@ impl Transaction {
             Self::Eip7702(tx) => tx.size(),
             Self::DawnEncrypted(tx) => tx.size(),
             Self::DawnDecrypted(tx) => tx.size(),
+            Self::DawnUndecrypted(tx) => tx.size(),
             #[cfg(feature = "optimism")]
             Self::Deposit(tx) => tx.size(),
         }
@@ -670,6 +732,18 @@ impl Transaction {
         matches!(self, Self::DawnDecrypted(_))
     }
 
+    /// Returns true if the transaction is an undecrypted transaction.
+    #[inline]
+    pub const fn is_undecrypted(&self) -> bool {
+        matches!(self, Self::DawnUndecrypted(_))
+    }
+
+    /// Returns true if the transaction is an encrypted, decrypted or undecrypted transaction.
+    #[inline]
+    pub const fn is_dawn(&self) -> bool {
+        matches!(self, Self::DawnEncrypted(_) | Self::DawnDecrypted(_) | Self::DawnUndecrypted(_))
+    }
+
     /// Returns the [`TxLegacy`] variant if the transaction is a legacy transaction.
     pub const fn as_legacy(&self) -> Option<&TxLegacy> {
         match self {
@@ -772,6 +846,9 @@ impl reth_codecs::Compact for Transaction {
             Self::DawnDecrypted(tx) => {
                 tx.to_compact(buf);
             }
+            Self::DawnUndecrypted(tx) => {
+                tx.to_compact(buf);
+            }
             #[cfg(feature = "optimism")]
             Self::Deposit(tx) => {
                 tx.to_compact(buf);
@@ -826,6 +903,10 @@ impl reth_codecs::Compact for Transaction {
                         let (tx, buf) = TxDawnDecrypted::from_compact(buf, buf.len());
                         (Self::DawnDecrypted(tx), buf)
                     }
+                    7 => {
+                        let (tx, buf) = TxDawnUndecrypted::from_compact(buf, buf.len());
+                        (Self::DawnUndecrypted(tx), buf)
+                    }
                     #[cfg(feature = "optimism")]
                     126 => {
                         let (tx, buf) = TxDeposit::from_compact(buf, buf.len());
@@ -871,6 +952,9 @@ impl Encodable for Transaction {
             Self::DawnDecrypted(encrypted_tx) => {
                 encrypted_tx.encode_for_signing(out);
             }
+            Self::DawnUndecrypted(encrypted_tx) => {
+                encrypted_tx.encode_for_signing(out);
+            }
             #[cfg(feature = "optimism")]
             Self::Deposit(deposit_tx) => {
                 deposit_tx.encode(out, true);
@@ -887,6 +971,7 @@ impl Encodable for Transaction {
             Self::Eip7702(set_code_tx) => set_code_tx.payload_len_for_signature(),
             Self::DawnEncrypted(encrypted_tx) => encrypted_tx.payload_len_for_signature(),
             Self::DawnDecrypted(encrypted_tx) => encrypted_tx.payload_len_for_signature(),
+            Self::DawnUndecrypted(encrypted_tx) => encrypted_tx.payload_len_for_signature(),
             #[cfg(feature = "optimism")]
             Self::Deposit(deposit_tx) => deposit_tx.payload_len(),
         }
@@ -929,7 +1014,9 @@ impl TransactionSignedNoHash {
         }
 
         let signature_hash = self.signature_hash();
-        self.signature.recover_signer(signature_hash)
+        self.signature
+            .recover_signer(signature_hash)
+            .and_then(|signer| self.transaction.check_recovered_signer(signer))
     }
 
     /// Recover signer from signature and hash _without ensuring that the signature has a low `s`
@@ -966,7 +1053,9 @@ impl TransactionSignedNoHash {
             }
         }
 
-        self.signature.recover_signer_unchecked(keccak256(buffer))
+        self.signature
+            .recover_signer_unchecked(keccak256(buffer))
+            .and_then(|signer| self.transaction.check_recovered_signer(signer))
     }
 
     /// Converts into a transaction type with its hash: [`TransactionSigned`].
@@ -1007,7 +1096,9 @@ impl reth_codecs::Compact for TransactionSignedNoHash {
         buf.put_u8(0);
 
         let sig_bit = self.signature.to_compact(buf) as u8;
-        let zstd_bit = !self.transaction.is_encrypted() && self.transaction.input().len() >= 32;
+        let zstd_bit = !self.transaction.is_encrypted() &&
+            !self.transaction.is_undecrypted() &&
+            self.transaction.input().len() >= 32;
 
         let tx_bits = if zstd_bit {
             crate::compression::TRANSACTION_COMPRESSOR.with(|compressor| {
@@ -1125,7 +1216,9 @@ impl TransactionSigned {
             return Some(from)
         }
         let signature_hash = self.signature_hash();
-        self.signature.recover_signer(signature_hash)
+        self.signature
+            .recover_signer(signature_hash)
+            .and_then(|signer| self.transaction.check_recovered_signer(signer))
     }
 
     /// Recover signer from signature and hash _without ensuring that the signature has a low `s`
@@ -1141,7 +1234,9 @@ impl TransactionSigned {
             return Some(from)
         }
         let signature_hash = self.signature_hash();
-        self.signature.recover_signer_unchecked(signature_hash)
+        self.signature
+            .recover_signer_unchecked(signature_hash)
+            .and_then(|signer| self.transaction.check_recovered_signer(signer))
     }
 
     /// Recovers a list of signers from a transaction list iterator.
@@ -1275,6 +1370,9 @@ impl TransactionSigned {
             Transaction::DawnDecrypted(encrypted_tx) => {
                 encrypted_tx.payload_len_with_signature(&self.signature)
             }
+            Transaction::DawnUndecrypted(encrypted_tx) => {
+                encrypted_tx.payload_len_with_signature(&self.signature)
+            }
             #[cfg(feature = "optimism")]
             Transaction::Deposit(deposit_tx) => deposit_tx.payload_len(),
         }
@@ -1297,6 +1395,80 @@ impl TransactionSigned {
         initial_tx
     }
 
+    /// Returns the transaction as it was included in a shadow block.
+    ///
+    /// Decrypted and undecrypted transactions are turned back into the encrypted transaction that
+    /// the sender signed, other transactions are returned as is.
+    pub fn shadow_transaction(&self) -> Self {
+        let transaction = match &self.transaction {
+            Transaction::DawnDecrypted(tx) => Transaction::DawnEncrypted(tx.reencrypt()),
+            Transaction::DawnUndecrypted(tx) => Transaction::DawnEncrypted(tx.encrypted()),
+            _ => return self.clone(),
+        };
+        Self::from_transaction_and_signature(transaction, self.signature)
+    }
+
+    /// Verifies a Dawn decrypted or undecrypted transaction against the master public key of the
+    /// SMC.
+    ///
+    /// This checks that the decryption key was issued for the label derived from the chain id,
+    /// sender and nonce, or for `block_label` on a chain that labels ciphertexts by block, see
+    /// [`dawn_block_label`](crate::dawn_block_label), that the sender signed the encrypted
+    /// transaction it comes from, that the hidden value of decrypted transactions fits within
+    /// their public bound, and, for undecrypted transactions, that the ciphertext fails to
+    /// decrypt. Other transactions are always valid.
+    pub fn verify_dawn(
+        &self,
+        mpk: Option<&MasterPublicKey>,
+        block_label: Option<&[u8; 96]>,
+    ) -> Result<(), DawnTransactionError> {
+        let Some((label, decryption_key)) = self.dawn_decryption_key() else { return Ok(()) };
+        let mpk = mpk.ok_or(DawnTransactionError::MasterKeyMissing)?;
+        let label = block_label.unwrap_or(&label);
+        self.verify_dawn_with_key(dawn_crypto::verify(label, mpk, decryption_key))
+    }
+
+    /// Returns the label and the decryption key of a Dawn decrypted or undecrypted transaction,
+    /// which [`TransactionSigned::verify_dawn`] checks against the master public key unless the
+    /// chain labels ciphertexts by block.
+    pub fn dawn_decryption_key(&self) -> Option<([u8; 96], &DecryptionKey)> {
+        match &self.transaction {
+            Transaction::DawnDecrypted(tx) => Some((tx.label(), &tx.decryption_key)),
+            Transaction::DawnUndecrypted(tx) => Some((tx.label(), &tx.decryption_key)),
+            _ => None,
+        }
+    }
+
+    /// Verifies a Dawn decrypted or undecrypted transaction like
+    /// [`TransactionSigned::verify_dawn`], given whether its decryption key was already found to
+    /// be valid, for example by checking the keys of a whole block with
+    /// [`dawn_crypto::verify_batch`].
+    pub fn verify_dawn_with_key(&self, key_verified: bool) -> Result<(), DawnTransactionError> {
+        let sender = match &self.transaction {
+            Transaction::DawnDecrypted(tx) => {
+                if !tx.is_within_value_bound() {
+                    return Err(DawnTransactionError::ValueExceedsBound)
+                }
+                tx.sender
+            }
+            Transaction::DawnUndecrypted(tx) => {
+                if !tx.is_undecryptable() {
+                    return Err(DawnTransactionError::DecryptionSucceeded)
+                }
+                tx.sender
+            }
+            _ => return Ok(()),
+        };
+        if !key_verified {
+            return Err(DawnTransactionError::InvalidDecryptionKey)
+        }
+
+        if self.shadow_transaction().recover_signer() != Some(sender) {
+            return Err(DawnTransactionError::InvalidSender)
+        }
+        Ok(())
+    }
+
     /// Calculate a heuristic for the in-memory size of the [`TransactionSigned`].
     #[inline]
     pub fn size(&self) -> usize {
@@ -1402,8 +1574,15 @@ impl TransactionSigned {
             TxType::Eip1559 => Transaction::Eip1559(TxEip1559::decode_inner(data)?),
             TxType::Eip4844 => Transaction::Eip4844(TxEip4844::decode_inner(data)?),
             TxType::Eip7702 => Transaction::Eip7702(TxEip7702::decode_inner(data)?),
-            TxType::DawnEncrypted => Transaction::DawnEncrypted(TxDawnEncrypted::decode_inner(data)?),
-            TxType::DawnDecrypted => Transaction::DawnDecrypted(TxDawnDecrypted::decode_inner(data)?),
+            TxType::DawnEncrypted => {
+                Transaction::DawnEncrypted(TxDawnEncrypted::decode_inner(data)?)
+            }
+            TxType::DawnDecrypted => {
+                Transaction::DawnDecrypted(TxDawnDecrypted::decode_inner(data)?)
+            }
+            TxType::DawnUndecrypted => {
+                Transaction::DawnUndecrypted(TxDawnUndecrypted::decode_inner(data)?)
+            }
             #[cfg(feature = "optimism")]
             TxType::Deposit => Transaction::Deposit(TxDeposit::decode_inner(data)?),
             TxType::Legacy => return Err(RlpError::Custom("unexpected legacy tx type")),
@@ -1489,6 +1668,9 @@ impl TransactionSigned {
             Transaction::DawnDecrypted(encrypted_tx) => {
                 encrypted_tx.payload_len_with_signature_without_header(&self.signature)
             }
+            Transaction::DawnUndecrypted(encrypted_tx) => {
+                encrypted_tx.payload_len_with_signature_without_header(&self.signature)
+            }
             #[cfg(feature = "optimism")]
             Transaction::Deposit(deposit_tx) => deposit_tx.payload_len_without_header(),
         }
@@ -2036,7 +2218,11 @@ mod tests {
 
         #[test]
         fn test_parallel_recovery_order(txes in proptest::collection::vec(
-            proptest_arbitrary_interop::arb::<Transaction>(),
+            proptest::strategy::Strategy::prop_filter(
+                proptest_arbitrary_interop::arb::<Transaction>(),
+                "dawn decrypted and undecrypted transactions must embed their signer",
+                |tx| !matches!(tx, Transaction::DawnDecrypted(_) | Transaction::DawnUndecrypted(_)),
+            ),
             *crate::transaction::PARALLEL_SENDER_RECOVERY_THRESHOLD * 5
         )) {
             let mut rng =rand::thread_rng();
@@ -2147,6 +2333,422 @@ mod tests {
         assert_eq!(tx_signed_no_hash, decoded);
     }
 
+    /// Returns a master key, an encrypted transaction to `to` with a public value of 1 and the
+    /// `hidden` fields, signed by a random sender, and its decryption.
+    #[cfg(feature = "secp256k1")]
+    fn dawn_decrypted_transaction(
+        to: TxKind,
+        hidden: Option<crate::DawnHiddenFields>,
+    ) -> (dawn_crypto::MasterPublicKey, TransactionSigned, crate::TxDawnDecrypted) {
+        dawn_padded_transaction(to, hidden, dawn_crypto::Padding::None)
+    }
+
+    /// Returns the same as [`dawn_decrypted_transaction`], with a plaintext padded according to
+    /// `padding`.
+    #[cfg(feature = "secp256k1")]
+    fn dawn_padded_transaction(
+        to: TxKind,
+        hidden: Option<crate::DawnHiddenFields>,
+        padding: dawn_crypto::Padding,
+    ) -> (dawn_crypto::MasterPublicKey, TransactionSigned, crate::TxDawnDecrypted) {
+        use crate::{
+            dawn_label, transaction::util::secp256k1::public_key_to_address, DawnPlaintext,
+            TxDawnDecrypted, TxDawnEncrypted,
+        };
+        use dawn_crypto::{generate, reveal, Ciphertext};
+
+        let (mpk, msk) = generate();
+        let key_pair =
+            secp256k1::Keypair::new(&secp256k1::Secp256k1::new(), &mut rand::thread_rng());
+        let sender = public_key_to_address(key_pair.public_key());
+        let input = bytes!("c0ffee");
+
+        let label = dawn_label(1, &sender, 0);
+        let payload =
+            alloy_rlp::encode(DawnPlaintext { to, input: input.clone(), hidden: hidden.clone() });
+        let encrypted = Transaction::DawnEncrypted(TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 100_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000,
+            value: U256::from(1),
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(&mpk, &label, &payload, &label, padding),
+        });
+        let signature = crate::sign_message(
+            B256::from_slice(&key_pair.secret_bytes()[..]),
+            encrypted.signature_hash(),
+        )
+        .unwrap();
+        let encrypted = TransactionSigned::from_transaction_and_signature(encrypted, signature);
+        let Transaction::DawnEncrypted(tx) = &encrypted.transaction else { unreachable!() };
+
+        let value_bound = hidden.is_some().then_some(tx.value);
+        let decrypted = TxDawnDecrypted {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            to,
+            value: hidden.as_ref().map_or(tx.value, |hidden| hidden.value),
+            access_list: hidden.map_or_else(|| tx.access_list.clone(), |hidden| hidden.access_list),
+            ephemeral_public_key: tx.ciphertext.u.clone(),
+            ciphertext_proof: tx.ciphertext.proof,
+            decryption_key: reveal(&label, &msk),
+            sender,
+            value_bound,
+            padding: padding.padding(payload.len()) as u64,
+            input,
+        };
+        (mpk, encrypted, decrypted)
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn verify_dawn_decrypted_transaction() {
+        use crate::{DawnTransactionError, TxDawnDecrypted};
+        use dawn_crypto::generate;
+
+        let (mpk, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), None);
+        let signature = encrypted.signature;
+        let Transaction::DawnEncrypted(encrypted) = encrypted.transaction else { unreachable!() };
+        let signed = |tx: TxDawnDecrypted| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnDecrypted(tx),
+                signature,
+            )
+        };
+
+        assert_eq!(decrypted.reencrypt(), encrypted);
+        // the recipient is only known once decrypted
+        assert_eq!(Transaction::DawnEncrypted(encrypted.clone()).kind(), None);
+        assert_eq!(Transaction::DawnEncrypted(encrypted.clone()).to(), None);
+        assert_eq!(
+            Transaction::DawnDecrypted(decrypted.clone()).to(),
+            Some(Address::repeat_byte(0x22))
+        );
+        assert_eq!(signed(decrypted.clone()).verify_dawn(Some(&mpk), None), Ok(()));
+        assert_eq!(
+            signed(decrypted.clone()).verify_dawn(None, None),
+            Err(DawnTransactionError::MasterKeyMissing)
+        );
+
+        // key issued by another SMC
+        let (other_mpk, _) = generate();
+        assert_eq!(
+            signed(decrypted.clone()).verify_dawn(Some(&other_mpk), None),
+            Err(DawnTransactionError::InvalidDecryptionKey)
+        );
+
+        // plaintext replaced by the block producer
+        let tampered = TxDawnDecrypted { input: bytes!("deadbeef"), ..decrypted.clone() };
+        assert_eq!(
+            signed(tampered).verify_dawn(Some(&mpk), None),
+            Err(DawnTransactionError::InvalidSender)
+        );
+
+        // call turned into a contract creation by the block producer
+        let create = TxDawnDecrypted { to: TxKind::Create, ..decrypted };
+        assert_eq!(
+            signed(create).verify_dawn(Some(&mpk), None),
+            Err(DawnTransactionError::InvalidSender)
+        );
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn verify_dawn_decrypted_contract_creation() {
+        let (mpk, encrypted, decrypted) = dawn_decrypted_transaction(TxKind::Create, None);
+        let sender = decrypted.sender;
+        let Transaction::DawnEncrypted(tx) = &encrypted.transaction else { unreachable!() };
+
+        assert_eq!(
+            tx.decrypt(sender, decrypted.decryption_key.clone()),
+            Transaction::DawnDecrypted(decrypted.clone())
+        );
+        assert_eq!(&decrypted.reencrypt(), tx);
+
+        let signed = TransactionSigned::from_transaction_and_signature(
+            Transaction::DawnDecrypted(decrypted),
+            encrypted.signature,
+        );
+        assert_eq!(signed.verify_dawn(Some(&mpk), None), Ok(()));
+        assert_eq!(signed.recover_signer(), Some(sender));
+        assert_eq!(signed.shadow_transaction(), encrypted);
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn verify_dawn_decrypted_hidden_fields() {
+        use crate::{
+            AccessList, AccessListItem, DawnHiddenFields, DawnTransactionError, TxDawnDecrypted,
+        };
+
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(0x22),
+            storage_keys: vec![B256::with_last_byte(1)],
+        }]);
+        let hidden = DawnHiddenFields { value: U256::from(1), access_list: access_list.clone() };
+        let (mpk, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), Some(hidden));
+        let sender = decrypted.sender;
+        let signature = encrypted.signature;
+        let Transaction::DawnEncrypted(tx) = &encrypted.transaction else { unreachable!() };
+        let signed = |tx: TxDawnDecrypted| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnDecrypted(tx),
+                signature,
+            )
+        };
+
+        assert_eq!(decrypted.access_list, access_list);
+        assert_eq!(decrypted.value_bound, Some(U256::from(1)));
+        assert_eq!(
+            tx.decrypt(sender, decrypted.decryption_key.clone()),
+            Transaction::DawnDecrypted(decrypted.clone())
+        );
+        assert_eq!(&decrypted.reencrypt(), tx);
+        assert_eq!(signed(decrypted.clone()).verify_dawn(Some(&mpk), None), Ok(()));
+
+        let mut encoded = Vec::new();
+        signed(decrypted.clone()).encode_enveloped(&mut encoded);
+        assert_eq!(
+            TransactionSigned::decode_enveloped(&mut &encoded[..]).unwrap(),
+            signed(decrypted.clone())
+        );
+        let mut compact = Vec::new();
+        let len = decrypted.to_compact(&mut compact);
+        assert_eq!(TxDawnDecrypted::from_compact(&compact, len).0, decrypted);
+
+        // hidden value above the public bound
+        let tampered = TxDawnDecrypted { value: U256::from(2), ..decrypted.clone() };
+        assert_eq!(
+            signed(tampered).verify_dawn(Some(&mpk), None),
+            Err(DawnTransactionError::ValueExceedsBound)
+        );
+
+        // hidden fields that the public fields do not allow decrypt to an undecrypted transaction
+        let mut tx = tx.clone();
+        tx.value = U256::ZERO;
+        let Transaction::DawnUndecrypted(undecrypted) =
+            tx.decrypt(sender, decrypted.decryption_key.clone())
+        else {
+            panic!("expected an undecrypted transaction")
+        };
+        assert!(undecrypted.is_undecryptable());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn verify_dawn_decrypted_padding() {
+        use crate::{DawnTransactionError, TxDawnDecrypted};
+        use dawn_crypto::Padding;
+
+        let (mpk, encrypted, decrypted) = dawn_padded_transaction(
+            TxKind::Call(Address::repeat_byte(0x22)),
+            None,
+            Padding::Multiple(128),
+        );
+        let signature = encrypted.signature;
+        let Transaction::DawnEncrypted(tx) = encrypted.transaction else { unreachable!() };
+        assert_eq!(tx.ciphertext.payload.len(), 128);
+        assert!(decrypted.padding > 0);
+        assert_eq!(
+            tx.decrypt(decrypted.sender, decrypted.decryption_key.clone()),
+            Transaction::DawnDecrypted(decrypted.clone())
+        );
+        assert_eq!(decrypted.reencrypt(), tx);
+
+        let signed = |tx: TxDawnDecrypted| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnDecrypted(tx),
+                signature,
+            )
+        };
+        assert_eq!(signed(decrypted.clone()).verify_dawn(Some(&mpk), None), Ok(()));
+        let mut compact = Vec::new();
+        let len = decrypted.to_compact(&mut compact);
+        assert_eq!(TxDawnDecrypted::from_compact(&compact, len).0, decrypted);
+
+        // padding changed by the block producer
+        let tampered = TxDawnDecrypted { padding: decrypted.padding - 1, ..decrypted };
+        assert_eq!(
+            signed(tampered).verify_dawn(Some(&mpk), None),
+            Err(DawnTransactionError::InvalidSender)
+        );
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn decrypt_dawn_encrypted_transaction() {
+        use dawn_crypto::{generate, reveal};
+
+        let (_, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), None);
+        let Transaction::DawnEncrypted(encrypted) = encrypted.transaction else { unreachable!() };
+        let sender = decrypted.sender;
+
+        assert_eq!(
+            encrypted.decrypt(sender, decrypted.decryption_key.clone()),
+            Transaction::DawnDecrypted(decrypted.clone())
+        );
+
+        // key issued by another SMC
+        let (_, other_msk) = generate();
+        let other_key = reveal(&decrypted.label(), &other_msk);
+        let Transaction::DawnUndecrypted(undecrypted) = encrypted.decrypt(sender, other_key) else {
+            panic!("expected an undecrypted transaction")
+        };
+        assert!(undecrypted.is_undecryptable());
+        assert_eq!(undecrypted.encrypted(), encrypted);
+
+        // their input is hidden, and stays empty
+        for mut tx in
+            [Transaction::DawnEncrypted(encrypted), Transaction::DawnUndecrypted(undecrypted)]
+        {
+            tx.set_input(bytes!("c0ffee"));
+            assert!(tx.input().is_empty());
+        }
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn recover_dawn_decrypted_signer() {
+        use crate::{PooledTransactionsElement, TxDawnDecrypted};
+
+        let (_, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), None);
+        let sender = decrypted.sender;
+        let signed = |tx: TxDawnDecrypted| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnDecrypted(tx),
+                encrypted.signature,
+            )
+        };
+
+        let tx = signed(decrypted.clone());
+        assert_eq!(tx.signature_hash(), encrypted.signature_hash());
+        assert_eq!(tx.recover_signer(), Some(sender));
+        assert_eq!(tx.recover_signer_unchecked(), Some(sender));
+        assert_eq!(
+            TransactionSignedNoHash::from(tx.clone()).encode_and_recover_unchecked(&mut Vec::new()),
+            Some(sender)
+        );
+        assert_eq!(
+            PooledTransactionsElement::try_from_broadcast(tx.clone()).unwrap().recover_signer(),
+            Some(sender)
+        );
+        let txs = vec![encrypted.clone(), tx];
+        assert_eq!(TransactionSigned::recover_signers(&txs, txs.len()), Some(vec![sender; 2]));
+        assert_eq!(
+            TransactionSigned::recover_signers_unchecked(&txs, txs.len()),
+            Some(vec![sender; 2])
+        );
+
+        // plaintext replaced by the block producer
+        let tampered = signed(TxDawnDecrypted { input: bytes!("deadbeef"), ..decrypted.clone() });
+        assert_eq!(tampered.recover_signer(), None);
+        assert_eq!(
+            TransactionSignedNoHash::from(tampered.clone())
+                .encode_and_recover_unchecked(&mut Vec::new()),
+            None
+        );
+        let txs = vec![encrypted, tampered];
+        assert_eq!(TransactionSigned::recover_signers(&txs, txs.len()), None);
+
+        // call turned into a contract creation by the block producer
+        let create = signed(TxDawnDecrypted { to: TxKind::Create, ..decrypted });
+        assert_eq!(create.recover_signer(), None);
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn decode_dawn_invalid_points() {
+        use crate::TxDawnDecrypted;
+        use dawn_crypto::{DecryptionKey, EphemeralPublicKey};
+
+        let (_, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), None);
+        let signature = encrypted.signature;
+        let decode = |tx: Transaction| {
+            let mut encoded = Vec::new();
+            TransactionSigned::from_transaction_and_signature(tx, signature)
+                .encode_enveloped(&mut encoded);
+            TransactionSigned::decode_enveloped(&mut &encoded[..]).map(|tx| tx.transaction)
+        };
+        let Transaction::DawnEncrypted(tx) = encrypted.transaction else { unreachable!() };
+        assert!(decode(Transaction::DawnEncrypted(tx.clone())).is_ok());
+        assert!(decode(Transaction::DawnDecrypted(decrypted.clone())).is_ok());
+
+        // the ephemeral public key of an encrypted transaction is not on the curve
+        let mut invalid = tx.clone();
+        invalid.ciphertext.u = EphemeralPublicKey([0xff; 96]);
+        assert_eq!(
+            decode(Transaction::DawnEncrypted(invalid)),
+            Err(alloy_rlp::Error::Custom("invalid ephemeral public key"))
+        );
+
+        // decrypting with an invalid decryption key does not panic, but the result does not decode
+        let invalid_key = DecryptionKey([0xff; 48]);
+        let undecrypted = tx.decrypt(decrypted.sender, invalid_key.clone());
+        assert!(matches!(undecrypted, Transaction::DawnUndecrypted(_)));
+        assert_eq!(decode(undecrypted), Err(alloy_rlp::Error::Custom("invalid decryption key")));
+        let invalid = TxDawnDecrypted { decryption_key: invalid_key, ..decrypted };
+        assert_eq!(
+            decode(Transaction::DawnDecrypted(invalid)),
+            Err(alloy_rlp::Error::Custom("invalid decryption key"))
+        );
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn verify_dawn_ciphertext_proof() {
+        use crate::{dawn_block_label, dawn_label, TxDawnDecrypted, TxDawnEncrypted};
+        use dawn_crypto::{Ciphertext, Padding};
+
+        let (mpk, encrypted, decrypted) =
+            dawn_decrypted_transaction(TxKind::Call(Address::repeat_byte(0x22)), None);
+        let Transaction::DawnEncrypted(tx) = encrypted.transaction else { unreachable!() };
+        assert_eq!(tx.ciphertext.version(), 2);
+        assert!(tx.verify_ciphertext_proof(&decrypted.sender, None));
+        // the ciphertext of another sender, or of another nonce
+        assert!(!tx.verify_ciphertext_proof(&Address::repeat_byte(0x33), None));
+        assert!(!TxDawnEncrypted { nonce: 1, ..tx.clone() }
+            .verify_ciphertext_proof(&decrypted.sender, None));
+
+        // a ciphertext encrypted for the label of a block, and its transaction
+        let label = dawn_label(1, &decrypted.sender, 0);
+        let block_label = dawn_block_label(1, 7);
+        let ciphertext = Ciphertext::encrypt(&mpk, &block_label, &[1; 36], &label, Padding::None);
+        let for_block = TxDawnEncrypted { ciphertext, ..tx.clone() };
+        assert!(for_block.verify_ciphertext_proof(&decrypted.sender, Some(&block_label)));
+        assert!(!for_block.verify_ciphertext_proof(&decrypted.sender, None));
+        assert!(
+            !for_block.verify_ciphertext_proof(&decrypted.sender, Some(&dawn_block_label(1, 8)))
+        );
+        assert!(!for_block.verify_ciphertext_proof(&Address::repeat_byte(0x33), Some(&block_label)));
+        assert!(!tx.verify_ciphertext_proof(&decrypted.sender, Some(&block_label)));
+
+        // both versions survive encoding and storage
+        let mut v1 = tx.clone();
+        v1.ciphertext.proof = None;
+        assert!(!v1.verify_ciphertext_proof(&decrypted.sender, None));
+        for tx in [tx, v1] {
+            let mut encoded = Vec::new();
+            tx.encode_fields(&mut encoded);
+            assert_eq!(TxDawnEncrypted::decode_inner(&mut &encoded[..]).unwrap(), tx);
+            let mut compact = Vec::new();
+            let len = tx.to_compact(&mut compact);
+            assert_eq!(TxDawnEncrypted::from_compact(&compact, len).0, tx);
+        }
+        let mut compact = Vec::new();
+        let len = decrypted.to_compact(&mut compact);
+        assert_eq!(TxDawnDecrypted::from_compact(&compact, len).0, decrypted);
+    }
+
     #[test]
     fn create_txs_disallowed_for_eip4844() {
         let data =
@@ -4,9 +4,8 @@
 use super::{error::TransactionConversionError, TxEip7702};
 use crate::{
     Address, BlobTransaction, BlobTransactionSidecar, Bytes, Signature, Transaction,
-    TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844, TxHash,
-    TxDawnEncrypted, TxDawnDecrypted,
-    TxLegacy, B256, EIP4844_TX_TYPE_ID,
+    TransactionSigned, TransactionSignedEcRecovered, TxDawnDecrypted, TxDawnEncrypted, TxEip1559,
+    TxEip2930, TxEip4844, TxHash, TxLegacy, B256, EIP4844_TX_TYPE_ID,
 };
 use alloy_rlp::{Decodable, Encodable, Error as RlpError, Header, EMPTY_LIST_CODE};
 use bytes::Buf;
@@ -106,6 +105,8 @@ impl PooledTransactionsElement {
             }
             // Not supported because missing blob sidecar
             tx @ TransactionSigned { transaction: Transaction::Eip4844(_), .. } => Err(tx),
+            // Not supported because undecrypted transactions are never pooled
+            tx @ TransactionSigned { transaction: Transaction::DawnUndecrypted(_), .. } => Err(tx),
             #[cfg(feature = "optimism")]
             // Not supported because deposit transactions are never pooled
             tx @ TransactionSigned { transaction: Transaction::Deposit(_), .. } => Err(tx),
@@ -190,7 +191,11 @@ impl PooledTransactionsElement {
     ///
     /// Returns `None` if the transaction's signature is invalid, see also [`Self::recover_signer`].
     pub fn recover_signer(&self) -> Option<Address> {
-        self.signature().recover_signer(self.signature_hash())
+        let signer = self.signature().recover_signer(self.signature_hash())?;
+        match self {
+            Self::DawnDecrypted { transaction, .. } if transaction.sender != signer => None,
+            _ => Some(signer),
+        }
     }
 
     /// Tries to recover signer and return [`PooledTransactionsElementEcRecovered`].
@@ -300,6 +305,9 @@ impl PooledTransactionsElement {
                         signature: typed_tx.signature,
                         hash: typed_tx.hash,
                     })},
+                    Transaction::DawnUndecrypted(_) => Err(RlpError::Custom(
+                        "Dawn undecrypted transactions cannot be decoded to PooledTransactionsElement",
+                    )),
                     #[cfg(feature = "optimism")]
                     Transaction::Deposit(_) => Err(RlpError::Custom("Optimism deposit transaction cannot be decoded to PooledTransactionsElement"))
                 }
@@ -726,6 +734,9 @@ impl Decodable for PooledTransactionsElement {
                         signature: typed_tx.signature,
                         hash: typed_tx.hash,
                     }),
+                    Transaction::DawnUndecrypted(_) => Err(RlpError::Custom(
+                        "Dawn undecrypted transactions cannot be decoded to PooledTransactionsElement",
+                    )),
                     #[cfg(feature = "optimism")]
                     Transaction::Deposit(_) => Err(RlpError::Custom("Optimism deposit transaction cannot be decoded to PooledTransactionsElement"))
                 }
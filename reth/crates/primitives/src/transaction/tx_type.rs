@@ -29,6 +29,7 @@ pub const EIP7702_TX_TYPE_ID: u8 = 4;
 
 pub const DAWN_ENCRYPTED_TX_TYPE_ID: u8 = 5;
 pub const DAWN_DECRYPTED_TX_TYPE_ID: u8 = 6;
+pub const DAWN_UNDECRYPTED_TX_TYPE_ID: u8 = 7;
 
 /// Identifier for [`TxDeposit`](crate::TxDeposit) transaction.
 #[cfg(feature = "optimism")]
@@ -61,6 +62,8 @@ pub enum TxType {
     DawnEncrypted = 5_isize,
     /// Dawn Decrypted transaction type.
     DawnDecrypted = 6_isize,
+    /// Dawn Undecrypted transaction type.
+    DawnUndecrypted = 7_isize,
     /// Optimism Deposit transaction.
     #[cfg(feature = "optimism")]
     Deposit = 126_isize,
@@ -75,7 +78,7 @@ impl TxType {
         match self {
             Self::Legacy => false,
             Self::Eip2930 | Self::Eip1559 | Self::Eip4844 | Self::Eip7702 => true,
-            Self::DawnEncrypted | Self::DawnDecrypted => true,
+            Self::DawnEncrypted | Self::DawnDecrypted | Self::DawnUndecrypted => true,
             #[cfg(feature = "optimism")]
             Self::Deposit => false,
         }
@@ -92,6 +95,7 @@ impl From<TxType> for u8 {
             TxType::Eip7702 => EIP7702_TX_TYPE_ID,
             TxType::DawnEncrypted => 5,
             TxType::DawnDecrypted => 6,
+            TxType::DawnUndecrypted => 7,
             #[cfg(feature = "optimism")]
             TxType::Deposit => DEPOSIT_TX_TYPE_ID,
         }
@@ -127,6 +131,8 @@ impl TryFrom<u8> for TxType {
             return Ok(Self::DawnEncrypted)
         } else if value == Self::DawnDecrypted {
             return Ok(Self::DawnDecrypted)
+        } else if value == Self::DawnUndecrypted {
+            return Ok(Self::DawnUndecrypted)
         }
 
         Err("invalid tx type")
@@ -176,6 +182,10 @@ impl reth_codecs::Compact for TxType {
                 buf.put_u8(*self as u8);
                 COMPACT_EXTENDED_IDENTIFIER_FLAG
             }
+            Self::DawnUndecrypted => {
+                buf.put_u8(*self as u8);
+                COMPACT_EXTENDED_IDENTIFIER_FLAG
+            }
             #[cfg(feature = "optimism")]
             Self::Deposit => {
                 buf.put_u8(*self as u8);
@@ -201,6 +211,7 @@ impl reth_codecs::Compact for TxType {
                         EIP7702_TX_TYPE_ID => Self::Eip7702,
                         DAWN_ENCRYPTED_TX_TYPE_ID => Self::DawnEncrypted,
                         DAWN_DECRYPTED_TX_TYPE_ID => Self::DawnDecrypted,
+                        DAWN_UNDECRYPTED_TX_TYPE_ID => Self::DawnUndecrypted,
                         #[cfg(feature = "optimism")]
                         DEPOSIT_TX_TYPE_ID => Self::Deposit,
                         _ => panic!("Unsupported TxType identifier: {extended_identifier}"),
@@ -284,6 +295,11 @@ mod tests {
             (TxType::Eip1559, 2, vec![]),
             (TxType::Eip4844, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![EIP4844_TX_TYPE_ID]),
             (TxType::Eip7702, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![EIP7702_TX_TYPE_ID]),
+            (
+                TxType::DawnUndecrypted,
+                COMPACT_EXTENDED_IDENTIFIER_FLAG,
+                vec![DAWN_UNDECRYPTED_TX_TYPE_ID],
+            ),
             #[cfg(feature = "optimism")]
             (TxType::Deposit, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![DEPOSIT_TX_TYPE_ID]),
         ];
@@ -307,6 +323,11 @@ mod tests {
             (TxType::Eip1559, 2, vec![]),
             (TxType::Eip4844, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![EIP4844_TX_TYPE_ID]),
             (TxType::Eip7702, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![EIP7702_TX_TYPE_ID]),
+            (
+                TxType::DawnUndecrypted,
+                COMPACT_EXTENDED_IDENTIFIER_FLAG,
+                vec![DAWN_UNDECRYPTED_TX_TYPE_ID],
+            ),
             #[cfg(feature = "optimism")]
             (TxType::Deposit, COMPACT_EXTENDED_IDENTIFIER_FLAG, vec![DEPOSIT_TX_TYPE_ID]),
         ];
@@ -3,7 +3,10 @@ use alloy_eips::eip2935::{HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_CODE};
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus_common::calc;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
-use reth_primitives::{Address, Block, Withdrawal, Withdrawals, B256, U256};
+use reth_primitives::{
+    Address, Block, ShadowBlock, TransactionSigned, Withdrawal, Withdrawals, B256,
+    SHADOW_HISTORY_ADDRESS, U256,
+};
 use reth_storage_errors::provider::ProviderError;
 use revm::{
     primitives::{Account, AccountInfo, Bytecode, EvmStorageSlot, BLOCKHASH_SERVE_WINDOW},
@@ -117,6 +120,84 @@ fn eip2935_block_hash_slot<DB: Database<Error: Into<ProviderError>>>(
     Ok((slot, EvmStorageSlot::new_changed(current_hash, block_hash.into())))
 }
 
+/// Applies the pre-block state change of Dawn shadow blocks.
+///
+/// The transactions of block `N` must be the transactions committed to by the shadow block of block
+/// `N - d`, where `d` is the Dawn delay of the chain, whose root and beneficiary are read from a
+/// ring buffer in the storage of [`SHADOW_HISTORY_ADDRESS`]. They are then replaced by the root and
+/// beneficiary of the shadow block of block `N`.
+///
+/// Returns the beneficiary of the delayed shadow block, which receives the fees of the transactions
+/// of the block, or `None` if Dawn is not active or the block has no shadow block, in which case
+/// this is a no-op.
+#[inline]
+pub fn apply_shadow_block_update<DB: Database<Error: Into<ProviderError>> + DatabaseCommit>(
+    db: &mut DB,
+    chain_spec: &ChainSpec,
+    block_timestamp: u64,
+    block_number: u64,
+    body: &[TransactionSigned],
+    shadow: Option<&ShadowBlock>,
+) -> Result<Option<Address>, BlockExecutionError>
+where
+    DB::Error: core::fmt::Display,
+{
+    let (Some(dawn), Some(shadow)) = (chain_spec.dawn_at(block_number, block_timestamp), shadow)
+    else {
+        return Ok(None)
+    };
+
+    // If the account does not exist yet, we create it with a nonce of 1, so it does not get
+    // deleted.
+    let mut account: Account = db
+        .basic(SHADOW_HISTORY_ADDRESS)
+        .map_err(|err| BlockValidationError::ShadowHistoryAccountLoadingFailed(err.into()))?
+        .unwrap_or_else(|| AccountInfo { nonce: 1, ..Default::default() })
+        .into();
+
+    let slot = block_number.checked_rem(dawn.delay).ok_or(BlockValidationError::ZeroDawnDelay)?;
+    let root_slot = U256::from(2 * slot);
+    let beneficiary_slot = root_slot + U256::from(1);
+    let delayed_root = db
+        .storage(SHADOW_HISTORY_ADDRESS, root_slot)
+        .map_err(|err| BlockValidationError::ShadowHistoryAccountLoadingFailed(err.into()))?;
+    let delayed_beneficiary = db
+        .storage(SHADOW_HISTORY_ADDRESS, beneficiary_slot)
+        .map_err(|err| BlockValidationError::ShadowHistoryAccountLoadingFailed(err.into()))?;
+
+    let transactions = body.iter().map(TransactionSigned::shadow_transaction).collect();
+    let delayed_shadow =
+        ShadowBlock { beneficiary: Address::from_word(delayed_beneficiary.into()), transactions };
+
+    // A zero root means that no shadow block was committed to, which is the case for the first
+    // `d` blocks with a shadow block.
+    let expected = B256::from(delayed_root);
+    let got = if delayed_root.is_zero() && delayed_shadow.transactions.is_empty() {
+        B256::ZERO
+    } else {
+        delayed_shadow.calculate_root()
+    };
+    if got != expected {
+        return Err(BlockValidationError::ShadowRootMismatch { got, expected }.into())
+    }
+
+    // Insert the state changes for the slots
+    account.storage.insert(
+        root_slot,
+        EvmStorageSlot::new_changed(delayed_root, shadow.calculate_root().into()),
+    );
+    account.storage.insert(
+        beneficiary_slot,
+        EvmStorageSlot::new_changed(delayed_beneficiary, shadow.beneficiary.into_word().into()),
+    );
+
+    // Mark the account as touched and commit the state change
+    account.mark_touch();
+    db.commit(HashMap::from([(SHADOW_HISTORY_ADDRESS, account)]));
+
+    Ok(Some(delayed_shadow.beneficiary))
+}
+
 /// Returns a map of addresses to their balance increments if the Shanghai hardfork is active at the
 /// given timestamp.
 ///
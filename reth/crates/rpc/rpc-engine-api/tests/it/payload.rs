@@ -26,6 +26,7 @@ fn transform_block<F: FnOnce(Block) -> Block>(src: SealedBlock, f: F) -> Executi
         ommers: transformed.ommers,
         withdrawals: transformed.withdrawals,
         requests: transformed.requests,
+        shadow: transformed.shadow,
     })
 }
 
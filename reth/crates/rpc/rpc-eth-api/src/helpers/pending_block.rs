@@ -5,7 +5,9 @@ use std::time::{Duration, Instant};
 
 use futures::Future;
 use reth_chainspec::EthereumHardforks;
-use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{
+    dawn, system_calls::pre_block_beacon_root_contract_call, ConfigureEvm, ConfigureEvmEnv,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
     constants::{eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_ROOT_HASH},
@@ -313,7 +315,8 @@ pub trait LoadPendingBlock: EthApiTypes {
 
             let mut evm = revm::Evm::builder().with_env(env).with_db(&mut db).build();
 
-            let ResultAndState { result, state } = match evm.transact() {
+            let result_and_state = dawn::transact(&mut evm, &tx.transaction, tx.signer());
+            let ResultAndState { result, state } = match result_and_state {
                 Ok(res) => res,
                 Err(err) => {
                     match err {
@@ -433,10 +436,18 @@ pub trait LoadPendingBlock: EthApiTypes {
             extra_data: Default::default(),
             parent_beacon_block_root,
             requests_root,
+            shadow_root: None,
         };
 
         // seal the block
-        let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
+        let block = Block {
+            header,
+            body: executed_txs,
+            ommers: vec![],
+            withdrawals,
+            requests,
+            shadow: None,
+        };
         Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
     }
 }
@@ -311,6 +311,13 @@ pub enum RpcInvalidTransactionError {
     /// Thrown if the sender of a transaction is a contract.
     #[error("sender is not an EOA")]
     SenderNoEOA,
+    /// Thrown if the hidden value of a decrypted Dawn transaction exceeds its public bound.
+    #[error("hidden value exceeds the public value bound")]
+    DawnValueExceedsBound,
+    /// Thrown if the ciphertext of an encrypted Dawn transaction does not carry a valid proof of
+    /// well-formedness.
+    #[error("ciphertext lacks a valid proof of well-formedness")]
+    DawnInvalidCiphertextProof,
     /// Gas limit was exceeded during execution.
     /// Contains the gas limit.
     #[error("out of gas: gas required exceeds allowance: {0}")]
@@ -504,6 +511,7 @@ impl From<reth_primitives::InvalidTransactionError> for RpcInvalidTransactionErr
             InvalidTransactionError::Eip1559Disabled |
             InvalidTransactionError::Eip4844Disabled |
             InvalidTransactionError::Eip7702Disabled |
+            InvalidTransactionError::DawnDisabled |
             InvalidTransactionError::TxTypeNotSupported => Self::TxTypeNotSupported,
             InvalidTransactionError::GasUintOverflow => Self::GasUintOverflow,
             InvalidTransactionError::GasTooLow => Self::GasTooLow,
@@ -511,6 +519,8 @@ impl From<reth_primitives::InvalidTransactionError> for RpcInvalidTransactionErr
             InvalidTransactionError::TipAboveFeeCap => Self::TipAboveFeeCap,
             InvalidTransactionError::FeeCapTooLow => Self::FeeCapTooLow,
             InvalidTransactionError::SignerAccountHasBytecode => Self::SenderNoEOA,
+            InvalidTransactionError::DawnValueExceedsBound => Self::DawnValueExceedsBound,
+            InvalidTransactionError::DawnInvalidCiphertextProof => Self::DawnInvalidCiphertextProof,
         }
     }
 }
@@ -80,7 +80,10 @@ impl ReceiptBuilder {
             logs,
         };
 
-        let (contract_address, to) = match transaction.transaction.kind() {
+        // encrypted Dawn transactions hide their recipient, and are executed as a call to their
+        // sender
+        let kind = transaction.transaction.kind().unwrap_or(TxKind::Call(from));
+        let (contract_address, to) = match kind {
             TxKind::Create => (Some(from.create(transaction.transaction.nonce())), None),
             TxKind::Call(addr) => (None, Some(Address(*addr))),
         };
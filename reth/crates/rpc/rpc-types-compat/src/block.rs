@@ -120,6 +120,7 @@ pub fn from_primitive_with_hash(primitive_header: reth_primitives::SealedHeader)
         excess_blob_gas,
         parent_beacon_block_root,
         requests_root,
+        shadow_root,
     } = header;
 
     Header {
@@ -146,6 +147,7 @@ pub fn from_primitive_with_hash(primitive_header: reth_primitives::SealedHeader)
         parent_beacon_block_root,
         total_difficulty: None,
         requests_root,
+        shadow_root,
     }
 }
 
@@ -56,6 +56,7 @@ pub fn try_payload_v1_to_block(payload: ExecutionPayloadV1) -> Result<Block, Pay
         excess_blob_gas: None,
         parent_beacon_block_root: None,
         requests_root: None,
+        shadow_root: None,
         extra_data: payload.extra_data,
         // Defaults
         ommers_hash: EMPTY_OMMER_ROOT_HASH,
@@ -69,6 +70,7 @@ pub fn try_payload_v1_to_block(payload: ExecutionPayloadV1) -> Result<Block, Pay
         ommers: Default::default(),
         withdrawals: None,
         requests: None,
+        shadow: None,
     })
 }
 
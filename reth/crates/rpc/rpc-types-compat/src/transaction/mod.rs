@@ -42,7 +42,8 @@ fn fill(
     let signer = tx.signer();
     let signed_tx = tx.into_signed();
 
-    let to: Option<Address> = match signed_tx.kind() {
+    // encrypted Dawn transactions hide their recipient, and are executed as a call to their sender
+    let to: Option<Address> = match signed_tx.kind().unwrap_or(TxKind::Call(signer)) {
         TxKind::Create => None,
         TxKind::Call(to) => Some(Address(*to)),
     };
@@ -118,7 +119,7 @@ fn fill(
 /// Convert [`TransactionSignedEcRecovered`] to [`TransactionRequest`]
 pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> TransactionRequest {
     let from = tx.signer();
-    let to = Some(tx.transaction.to().into());
+    let to = Some(tx.transaction.kind().unwrap_or(TxKind::Call(from)));
     let gas = tx.transaction.gas_limit();
     let value = tx.transaction.value();
     let input = tx.transaction.input().clone();
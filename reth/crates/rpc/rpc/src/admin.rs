@@ -153,6 +153,11 @@ where
             prague_time => Prague,
         ]);
 
+        // the dawn parameters may not come from the genesis config, e.g. for specs built in code
+        if let Some(dawn) = self.chain_spec.dawn.and_then(|dawn| serde_json::to_value(dawn).ok()) {
+            config.extra_fields.insert("dawn".to_string(), dawn);
+        }
+
         Ok(NodeInfo {
             id: id2pk(enode.id)
                 .map(|pk| pk.to_string())
@@ -219,7 +219,8 @@ where
                         gas_fees,
                         gas_price: U256::from(gas_price),
                         gas_used,
-                        to_address: tx.to(),
+                        // encrypted Dawn transactions are executed as a call to their sender
+                        to_address: tx.kind().map_or(Some(signer), |kind| kind.to().copied()),
                         tx_hash: tx.hash(),
                         value,
                         revert,
@@ -664,6 +664,7 @@ mod tests {
                     ommers: block.ommers.clone(),
                     withdrawals: block.withdrawals.clone(),
                     requests: block.requests.clone(),
+                    shadow: block.shadow.clone(),
                 },
             )
         }
@@ -943,6 +944,7 @@ mod tests {
                             ommers: body.ommers,
                             withdrawals: body.withdrawals,
                             requests: body.requests,
+                            shadow: body.shadow,
                         }));
                     }
 
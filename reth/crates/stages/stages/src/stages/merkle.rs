@@ -514,7 +514,7 @@ mod tests {
                 accounts.iter().map(|(addr, acc)| (*addr, (*acc, std::iter::empty()))),
             )?;
 
-            let SealedBlock { header, body, ommers, withdrawals, requests } = random_block(
+            let SealedBlock { header, body, ommers, withdrawals, requests, shadow } = random_block(
                 &mut rng,
                 stage_progress,
                 preblocks.last().map(|b| b.hash()),
@@ -529,8 +529,14 @@ mod tests {
                     .into_iter()
                     .map(|(address, account)| (address, (account, std::iter::empty()))),
             );
-            let sealed_head =
-                SealedBlock { header: header.seal_slow(), body, ommers, withdrawals, requests };
+            let sealed_head = SealedBlock {
+                header: header.seal_slow(),
+                body,
+                ommers,
+                withdrawals,
+                requests,
+                shadow,
+            };
 
             let head_hash = sealed_head.hash();
             let mut blocks = vec![sealed_head];
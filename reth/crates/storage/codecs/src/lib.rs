@@ -22,7 +22,7 @@ pub use reth_codecs_derive::*;
 use alloy_primitives::{Address, Bloom, Bytes, FixedBytes, U256};
 use bytes::{Buf, BufMut};
 
-use dawn_crypto::{Ciphertext, DecryptionKey, EphemeralPublicKey};
+use dawn_crypto::{Ciphertext, CiphertextProof, DecryptionKey, EphemeralPublicKey};
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
@@ -742,26 +742,67 @@ mod tests {
     }
 }
 
+/// Version 1 ciphertexts are stored as `u || payload || tag`, and version 2 ciphertexts as
+/// `2 || u || proof || payload || tag`. The first byte of a compressed point always has its top bit
+/// set, so it tells the two apart.
 impl Compact for Ciphertext {
         fn to_compact<B>(&self, buf: &mut B) -> usize
     where
         B: bytes::BufMut + AsMut<[u8]>,
     {
+        let mut len = 0;
+        if let Some(proof) = &self.proof {
+            buf.put_u8(2);
+            len += 1 + proof.0.len();
+        }
         buf.put_slice(&self.u.0);
+        if let Some(proof) = &self.proof {
+            buf.put_slice(&proof.0);
+        }
         buf.put_slice(&self.payload);
         buf.put_slice(&self.tag);
-        self.u.0.len() + self.payload.len() + self.tag.len()
+        len + self.u.0.len() + self.payload.len() + self.tag.len()
     }
 
 
-    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8])
+    fn from_compact(mut buf: &[u8], mut len: usize) -> (Self, &[u8])
     {
+        let versioned = buf[0] & 0x80 == 0;
+        if versioned {
+            buf.advance(1);
+            len -= 1;
+        }
         let mut u = EphemeralPublicKey([0; 96]);
         let mut tag = [0; 16];
         u.0.copy_from_slice(&buf[..96]);
-        tag.copy_from_slice(&buf[len-16..]);
-        let payload = buf[96..len-16].to_vec();
-        (Self { u, payload, tag }, &buf[len..])
+        let (proof, start) = if versioned {
+            let mut proof = CiphertextProof([0; 64]);
+            proof.0.copy_from_slice(&buf[96..160]);
+            (Some(proof), 160)
+        } else {
+            (None, 96)
+        };
+        tag.copy_from_slice(&buf[len-16..len]);
+        let payload = buf[start..len-16].to_vec();
+        (Self { u, payload, tag, proof }, &buf[len..])
+    }
+}
+
+impl Compact for CiphertextProof {
+        fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.0);
+        self.0.len()
+    }
+
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8])
+    {
+        let mut proof = [0; 64];
+        proof.copy_from_slice(&buf[..64]);
+        (Self(proof), &buf[64..])
     }
 }
 
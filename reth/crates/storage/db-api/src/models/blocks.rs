@@ -1,7 +1,9 @@
 //! Block related models and types.
 
 use reth_codecs::{reth_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawals, B256};
+use reth_primitives::{
+    Address, Header, ShadowBlock, TransactionSignedNoHash, TxNumber, Withdrawals, B256,
+};
 use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
@@ -84,6 +86,38 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Withdrawals,
 }
 
+/// The storage representation of a block's shadow block.
+#[reth_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StoredBlockShadow {
+    /// The beneficiary of the shadow block.
+    pub beneficiary: Address,
+    /// The transactions of the shadow block.
+    pub transactions: Vec<TransactionSignedNoHash>,
+}
+
+impl From<ShadowBlock> for StoredBlockShadow {
+    fn from(shadow: ShadowBlock) -> Self {
+        Self {
+            beneficiary: shadow.beneficiary,
+            transactions: shadow.transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<StoredBlockShadow> for ShadowBlock {
+    fn from(shadow: StoredBlockShadow) -> Self {
+        Self {
+            beneficiary: shadow.beneficiary,
+            transactions: shadow
+                .transactions
+                .into_iter()
+                .map(TransactionSignedNoHash::with_hash)
+                .collect(),
+        }
+    }
+}
+
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
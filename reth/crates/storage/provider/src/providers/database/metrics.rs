@@ -62,6 +62,7 @@ pub(crate) enum Action {
     InsertTransactionHashNumbers,
     InsertBlockWithdrawals,
     InsertBlockRequests,
+    InsertBlockShadow,
     InsertBlockBodyIndices,
     InsertTransactionBlocks,
     GetNextTxNum,
@@ -108,6 +109,8 @@ struct DatabaseProviderMetrics {
     insert_block_withdrawals: Histogram,
     /// Duration of insert block requests
     insert_block_requests: Histogram,
+    /// Duration of insert block shadow
+    insert_block_shadow: Histogram,
     /// Duration of insert block body indices
     insert_block_body_indices: Histogram,
     /// Duration of insert transaction blocks
@@ -140,6 +143,7 @@ impl DatabaseProviderMetrics {
             Action::InsertTransactionHashNumbers => self.insert_tx_hash_numbers.record(duration),
             Action::InsertBlockWithdrawals => self.insert_block_withdrawals.record(duration),
             Action::InsertBlockRequests => self.insert_block_requests.record(duration),
+            Action::InsertBlockShadow => self.insert_block_shadow.record(duration),
             Action::InsertBlockBodyIndices => self.insert_block_body_indices.record(duration),
             Action::InsertTransactionBlocks => self.insert_tx_blocks.record(duration),
             Action::GetNextTxNum => self.get_next_tx_num.record(duration),
@@ -27,7 +27,8 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockShadow,
+        StoredBlockWithdrawals,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
@@ -39,9 +40,9 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
     BlockWithSenders, Bytecode, GotExpected, Header, Receipt, Requests, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    SealedBlockWithSenders, SealedHeader, ShadowBlock, StaticFileSegment, StorageEntry,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -384,6 +385,21 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         )
     }
 
+    /// Returns the shadow block of the block with the given number and header.
+    ///
+    /// Returns `None` if the header does not commit to a shadow block.
+    fn shadow_by_block(
+        &self,
+        number: BlockNumber,
+        header: &Header,
+    ) -> ProviderResult<Option<ShadowBlock>> {
+        if header.shadow_root.is_none() {
+            return Ok(None)
+        }
+        let shadow = self.tx.get::<tables::BlockShadows>(number)?.unwrap_or_default();
+        Ok(Some(shadow.into()))
+    }
+
     fn block_with_senders<H, HF, B, BF>(
         &self,
         id: BlockHashOrNumber,
@@ -401,6 +417,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             Vec<Header>,
             Option<Withdrawals>,
             Option<Requests>,
+            Option<ShadowBlock>,
         ) -> ProviderResult<Option<B>>,
     {
         let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
@@ -410,6 +427,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         let withdrawals =
             self.withdrawals_by_block(block_number.into(), header.as_ref().timestamp)?;
         let requests = self.requests_by_block(block_number.into(), header.as_ref().timestamp)?;
+        let shadow = self.shadow_by_block(block_number, header.as_ref())?;
 
         // Get the block body
         //
@@ -440,7 +458,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             })
             .collect();
 
-        construct_block(header, body, senders, ommers, withdrawals, requests)
+        construct_block(header, body, senders, ommers, withdrawals, requests, shadow)
     }
 
     /// Returns a range of blocks from the database.
@@ -452,6 +470,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
     ///     – Ommers
     ///     – Withdrawals
     ///     – Requests
+    ///     – Shadow block
     ///     – Senders
     fn block_range<F, H, HF, R>(
         &self,
@@ -468,6 +487,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             Vec<Header>,
             Option<Withdrawals>,
             Option<Requests>,
+            Option<ShadowBlock>,
         ) -> ProviderResult<R>,
     {
         if range.is_empty() {
@@ -481,6 +501,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         let mut ommers_cursor = self.tx.cursor_read::<tables::BlockOmmers>()?;
         let mut withdrawals_cursor = self.tx.cursor_read::<tables::BlockWithdrawals>()?;
         let mut requests_cursor = self.tx.cursor_read::<tables::BlockRequests>()?;
+        let mut shadows_cursor = self.tx.cursor_read::<tables::BlockShadows>()?;
         let mut block_body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
 
         for header in headers {
@@ -514,6 +535,16 @@ impl<TX: DbTx> DatabaseProvider<TX> {
                     } else {
                         None
                     };
+                let shadow = if header_ref.shadow_root.is_some() {
+                    Some(
+                        shadows_cursor
+                            .seek_exact(header_ref.number)?
+                            .map(|(_, s)| s.into())
+                            .unwrap_or_default(),
+                    )
+                } else {
+                    None
+                };
                 let ommers =
                     if self.chain_spec.final_paris_total_difficulty(header_ref.number).is_some() {
                         Vec::new()
@@ -524,7 +555,9 @@ impl<TX: DbTx> DatabaseProvider<TX> {
                             .unwrap_or_default()
                     };
 
-                if let Ok(b) = assemble_block(header, tx_range, ommers, withdrawals, requests) {
+                if let Ok(b) =
+                    assemble_block(header, tx_range, ommers, withdrawals, requests, shadow)
+                {
                     blocks.push(b);
                 }
             }
@@ -543,6 +576,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
     ///     – Ommers
     ///     – Withdrawals
     ///     – Requests
+    ///     – Shadow block
     ///     – Senders
     fn block_with_senders_range<H, HF, B, BF>(
         &self,
@@ -559,46 +593,50 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             Vec<Header>,
             Option<Withdrawals>,
             Option<Requests>,
+            Option<ShadowBlock>,
             Vec<Address>,
         ) -> ProviderResult<B>,
     {
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
         let mut senders_cursor = self.tx.cursor_read::<tables::TransactionSenders>()?;
 
-        self.block_range(range, headers_range, |header, tx_range, ommers, withdrawals, requests| {
-            let (body, senders) = if tx_range.is_empty() {
-                (Vec::new(), Vec::new())
-            } else {
-                let body = self
-                    .transactions_by_tx_range_with_cursor(tx_range.clone(), &mut tx_cursor)?
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<TransactionSigned>>();
-                // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
+        self.block_range(
+            range,
+            headers_range,
+            |header, tx_range, ommers, withdrawals, requests, shadow| {
+                let (body, senders) = if tx_range.is_empty() {
+                    (Vec::new(), Vec::new())
+                } else {
+                    let body = self
+                        .transactions_by_tx_range_with_cursor(tx_range.clone(), &mut tx_cursor)?
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<TransactionSigned>>();
+                    // fetch senders from the senders table
+                    let known_senders = senders_cursor
                         .walk_range(tx_range.clone())?
                         .collect::<Result<HashMap<_, _>, _>>()?;
 
-                let mut senders = Vec::with_capacity(body.len());
-                for (tx_num, tx) in tx_range.zip(body.iter()) {
-                    match known_senders.get(&tx_num) {
-                        None => {
-                            // recover the sender from the transaction if not found
-                            let sender = tx
-                                .recover_signer_unchecked()
-                                .ok_or_else(|| ProviderError::SenderRecoveryError)?;
-                            senders.push(sender);
+                    let mut senders = Vec::with_capacity(body.len());
+                    for (tx_num, tx) in tx_range.zip(body.iter()) {
+                        match known_senders.get(&tx_num) {
+                            None => {
+                                // recover the sender from the transaction if not found
+                                let sender = tx
+                                    .recover_signer_unchecked()
+                                    .ok_or_else(|| ProviderError::SenderRecoveryError)?;
+                                senders.push(sender);
+                            }
+                            Some(sender) => senders.push(*sender),
                         }
-                        Some(sender) => senders.push(*sender),
                     }
-                }
 
-                (body, senders)
-            };
+                    (body, senders)
+                };
 
-            assemble_block(header, body, ommers, withdrawals, requests, senders)
-        })
+                assemble_block(header, body, ommers, withdrawals, requests, shadow, senders)
+            },
+        )
     }
 
     /// Get requested blocks transaction with senders
@@ -677,6 +715,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         // - Uncles/ommers
         // - Withdrawals
         // - Requests
+        // - Shadow blocks
         // - Signers
 
         let block_headers = self.get::<tables::Headers>(range.clone())?;
@@ -688,6 +727,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         let block_ommers = self.get::<tables::BlockOmmers>(range.clone())?;
         let block_withdrawals = self.get::<tables::BlockWithdrawals>(range.clone())?;
         let block_requests = self.get::<tables::BlockRequests>(range.clone())?;
+        let block_shadows = self.get::<tables::BlockShadows>(range.clone())?;
 
         let block_tx = self.get_block_transaction_range(range)?;
 
@@ -700,9 +740,11 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         let mut block_ommers_iter = block_ommers.into_iter();
         let mut block_withdrawals_iter = block_withdrawals.into_iter();
         let mut block_requests_iter = block_requests.into_iter();
+        let mut block_shadows_iter = block_shadows.into_iter();
         let mut block_ommers = block_ommers_iter.next();
         let mut block_withdrawals = block_withdrawals_iter.next();
         let mut block_requests = block_requests_iter.next();
+        let mut block_shadows = block_shadows_iter.next();
 
         let mut blocks = Vec::new();
         for ((main_block_number, header), (_, header_hash), (_, tx)) in
@@ -750,8 +792,20 @@ impl<TX: DbTx> DatabaseProvider<TX> {
                 requests = None;
             }
 
+            // shadow block can be missing
+            let mut shadow = None;
+            if header.shadow_root.is_some() {
+                shadow = Some(ShadowBlock::default());
+                if let Some((block_number, _)) = block_shadows.as_ref() {
+                    if *block_number == main_block_number {
+                        shadow = Some(block_shadows.take().unwrap().1.into());
+                        block_shadows = block_shadows_iter.next();
+                    }
+                }
+            }
+
             blocks.push(SealedBlockWithSenders {
-                block: SealedBlock { header, body, ommers, withdrawals, requests },
+                block: SealedBlock { header, body, ommers, withdrawals, requests, shadow },
                 senders,
             })
         }
@@ -1319,6 +1373,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// * [`BlockOmmers`](tables::BlockOmmers)
     /// * [`BlockWithdrawals`](tables::BlockWithdrawals)
     /// * [`BlockRequests`](tables::BlockRequests)
+    /// * [`BlockShadows`](tables::BlockShadows)
     /// * [`HeaderTerminalDifficulties`](tables::HeaderTerminalDifficulties)
     ///
     /// This will also remove transaction data according to
@@ -1339,6 +1394,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         self.remove::<tables::BlockOmmers>(range.clone())?;
         self.remove::<tables::BlockWithdrawals>(range.clone())?;
         self.remove::<tables::BlockRequests>(range.clone())?;
+        self.remove::<tables::BlockShadows>(range.clone())?;
         self.remove_block_transaction_range(range.clone())?;
         self.remove::<tables::HeaderTerminalDifficulties>(range)?;
 
@@ -1353,6 +1409,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// * [`BlockOmmers`](tables::BlockOmmers)
     /// * [`BlockWithdrawals`](tables::BlockWithdrawals)
     /// * [`BlockRequests`](tables::BlockRequests)
+    /// * [`BlockShadows`](tables::BlockShadows)
     /// * [`HeaderTerminalDifficulties`](tables::HeaderTerminalDifficulties)
     ///
     /// This will also remove transaction data according to
@@ -1368,6 +1425,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         // - Uncles/ommers
         // - Withdrawals
         // - Requests
+        // - Shadow blocks
         // - Signers
 
         let block_headers = self.take::<tables::Headers>(range.clone())?;
@@ -1382,6 +1440,7 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let block_ommers = self.take::<tables::BlockOmmers>(range.clone())?;
         let block_withdrawals = self.take::<tables::BlockWithdrawals>(range.clone())?;
         let block_requests = self.take::<tables::BlockRequests>(range.clone())?;
+        let block_shadows = self.take::<tables::BlockShadows>(range.clone())?;
         let block_tx = self.take_block_transaction_range(range.clone())?;
 
         // rm HeaderTerminalDifficulties
@@ -1396,9 +1455,11 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let mut block_ommers_iter = block_ommers.into_iter();
         let mut block_withdrawals_iter = block_withdrawals.into_iter();
         let mut block_requests_iter = block_requests.into_iter();
+        let mut block_shadows_iter = block_shadows.into_iter();
         let mut block_ommers = block_ommers_iter.next();
         let mut block_withdrawals = block_withdrawals_iter.next();
         let mut block_requests = block_requests_iter.next();
+        let mut block_shadows = block_shadows_iter.next();
 
         let mut blocks = Vec::new();
         for ((main_block_number, header), (_, header_hash), (_, tx)) in
@@ -1446,8 +1507,20 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
                 requests = None;
             }
 
+            // shadow block can be missing
+            let mut shadow = None;
+            if header.shadow_root.is_some() {
+                shadow = Some(ShadowBlock::default());
+                if let Some((block_number, _)) = block_shadows.as_ref() {
+                    if *block_number == main_block_number {
+                        shadow = Some(block_shadows.take().unwrap().1.into());
+                        block_shadows = block_shadows_iter.next();
+                    }
+                }
+            }
+
             blocks.push(SealedBlockWithSenders {
-                block: SealedBlock { header, body, ommers, withdrawals, requests },
+                block: SealedBlock { header, body, ommers, withdrawals, requests, shadow },
                 senders,
             })
         }
@@ -1974,6 +2047,7 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
                 let withdrawals = self.withdrawals_by_block(number.into(), header.timestamp)?;
                 let ommers = self.ommers(number.into())?.unwrap_or_default();
                 let requests = self.requests_by_block(number.into(), header.timestamp)?;
+                let shadow = self.shadow_by_block(number, &header)?;
                 // If the body indices are not found, this means that the transactions either do not
                 // exist in the database yet, or they do exit but are not indexed.
                 // If they exist but are not indexed, we don't have enough
@@ -1983,7 +2057,14 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
                     None => return Ok(None),
                 };
 
-                return Ok(Some(Block { header, body: transactions, ommers, withdrawals, requests }))
+                return Ok(Some(Block {
+                    header,
+                    body: transactions,
+                    ommers,
+                    withdrawals,
+                    requests,
+                    shadow,
+                }))
             }
         }
 
@@ -2038,8 +2119,8 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
             id,
             transaction_kind,
             |block_number| self.header_by_number(block_number),
-            |header, body, senders, ommers, withdrawals, requests| {
-                Block { header, body, ommers, withdrawals, requests }
+            |header, body, senders, ommers, withdrawals, requests, shadow| {
+                Block { header, body, ommers, withdrawals, requests, shadow }
                     // Note: we're using unchecked here because we know the block contains valid txs
                     // wrt to its height and can ignore the s value check so pre
                     // EIP-2 txs are allowed
@@ -2059,8 +2140,8 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
             id,
             transaction_kind,
             |block_number| self.sealed_header(block_number),
-            |header, body, senders, ommers, withdrawals, requests| {
-                SealedBlock { header, body, ommers, withdrawals, requests }
+            |header, body, senders, ommers, withdrawals, requests, shadow| {
+                SealedBlock { header, body, ommers, withdrawals, requests, shadow }
                     // Note: we're using unchecked here because we know the block contains valid txs
                     // wrt to its height and can ignore the s value check so pre
                     // EIP-2 txs are allowed
@@ -2076,7 +2157,7 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         self.block_range(
             range,
             |range| self.headers_range(range),
-            |header, tx_range, ommers, withdrawals, requests| {
+            |header, tx_range, ommers, withdrawals, requests, shadow| {
                 let body = if tx_range.is_empty() {
                     Vec::new()
                 } else {
@@ -2085,7 +2166,7 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
                         .map(Into::into)
                         .collect()
                 };
-                Ok(Block { header, body, ommers, withdrawals, requests })
+                Ok(Block { header, body, ommers, withdrawals, requests, shadow })
             },
         )
     }
@@ -2097,8 +2178,8 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         self.block_with_senders_range(
             range,
             |range| self.headers_range(range),
-            |header, body, ommers, withdrawals, requests, senders| {
-                Block { header, body, ommers, withdrawals, requests }
+            |header, body, ommers, withdrawals, requests, shadow, senders| {
+                Block { header, body, ommers, withdrawals, requests, shadow }
                     .try_with_senders_unchecked(senders)
                     .map_err(|_| ProviderError::SenderRecoveryError)
             },
@@ -2112,9 +2193,9 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
         self.block_with_senders_range(
             range,
             |range| self.sealed_headers_range(range),
-            |header, body, ommers, withdrawals, requests, senders| {
+            |header, body, ommers, withdrawals, requests, shadow, senders| {
                 SealedBlockWithSenders::new(
-                    SealedBlock { header, body, ommers, withdrawals, requests },
+                    SealedBlock { header, body, ommers, withdrawals, requests, shadow },
                     senders,
                 )
                 .ok_or(ProviderError::SenderRecoveryError)
@@ -3421,6 +3502,7 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
     /// If withdrawals are not empty, this will modify
     /// [`BlockWithdrawals`](tables::BlockWithdrawals).
     /// If requests are not empty, this will modify [`BlockRequests`](tables::BlockRequests).
+    /// If the block has a shadow block, this will modify [`BlockShadows`](tables::BlockShadows).
     ///
     /// If the provider has __not__ configured full sender pruning, this will modify
     /// [`TransactionSenders`](tables::TransactionSenders).
@@ -3551,6 +3633,11 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             }
         }
 
+        if let Some(shadow) = block.block.shadow {
+            self.tx.put::<tables::BlockShadows>(block_number, StoredBlockShadow::from(shadow))?;
+            durations_recorder.record_relative(metrics::Action::InsertBlockShadow);
+        }
+
         let block_indices = StoredBlockBodyIndices { first_tx_num, tx_count };
         self.tx.put::<tables::BlockBodyIndices>(block_number, block_indices.clone())?;
         durations_recorder.record_relative(metrics::Action::InsertBlockBodyIndices);
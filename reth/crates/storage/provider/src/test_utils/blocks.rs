@@ -109,6 +109,7 @@ pub fn genesis() -> SealedBlock {
         ommers: vec![],
         withdrawals: Some(Withdrawals::default()),
         requests: Some(Requests::default()),
+        shadow: None,
     }
 }
 
@@ -242,7 +242,8 @@ impl InvalidPoolTransactionError {
                     InvalidTransactionError::Eip2930Disabled |
                     InvalidTransactionError::Eip1559Disabled |
                     InvalidTransactionError::Eip4844Disabled |
-                    InvalidTransactionError::Eip7702Disabled => {
+                    InvalidTransactionError::Eip7702Disabled |
+                    InvalidTransactionError::DawnDisabled => {
                         // settings
                         false
                     }
@@ -250,7 +251,9 @@ impl InvalidPoolTransactionError {
                     InvalidTransactionError::ChainIdMismatch |
                     InvalidTransactionError::GasUintOverflow |
                     InvalidTransactionError::TxTypeNotSupported |
-                    InvalidTransactionError::SignerAccountHasBytecode => true,
+                    InvalidTransactionError::SignerAccountHasBytecode |
+                    InvalidTransactionError::DawnValueExceedsBound |
+                    InvalidTransactionError::DawnInvalidCiphertextProof => true,
                 }
             }
             Self::ExceedsGasLimit(_, _) => true,
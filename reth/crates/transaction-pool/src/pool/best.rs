@@ -3,7 +3,7 @@ use crate::{
     TransactionOrdering, ValidPoolTransaction,
 };
 use core::fmt;
-use reth_primitives::B256 as TxHash;
+use reth_primitives::{BlockNumber, B256 as TxHash};
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
     sync::Arc,
@@ -24,6 +24,15 @@ pub(crate) struct BestTransactionsWithFees<T: TransactionOrdering> {
     pub(crate) base_fee_per_blob_gas: u64,
 }
 
+impl<T: TransactionOrdering> BestTransactionsWithFees<T> {
+    /// Only yields transactions that can be included in block `number`, see
+    /// [`BestTransactions::for_block`].
+    pub(crate) fn for_block(mut self, number: BlockNumber) -> Self {
+        self.best = self.best.for_block(number);
+        self
+    }
+}
+
 impl<T: TransactionOrdering> crate::traits::BestTransactions for BestTransactionsWithFees<T> {
     fn mark_invalid(&mut self, tx: &Self::Item) {
         BestTransactions::mark_invalid(&mut self.best, tx)
@@ -90,9 +99,18 @@ pub(crate) struct BestTransactions<T: TransactionOrdering> {
     pub(crate) new_transaction_receiver: Option<Receiver<PendingTransaction<T>>>,
     /// Flag to control whether to skip blob transactions (EIP4844).
     pub(crate) skip_blobs: bool,
+    /// The block the transactions are for, if known, to skip encrypted Dawn transactions labelled
+    /// for another block.
+    pub(crate) block_number: Option<BlockNumber>,
 }
 
 impl<T: TransactionOrdering> BestTransactions<T> {
+    /// Only yields transactions that can be included in block `number`.
+    pub(crate) fn for_block(mut self, number: BlockNumber) -> Self {
+        self.block_number = Some(number);
+        self
+    }
+
     /// Mark the transaction and it's descendants as invalid.
     pub(crate) fn mark_invalid(&mut self, tx: &Arc<ValidPoolTransaction<T::Transaction>>) {
         self.invalid.insert(*tx.hash());
@@ -182,6 +200,16 @@ impl<T: TransactionOrdering> Iterator for BestTransactions<T> {
                 continue
             }
 
+            // skip encrypted transactions labelled for another block, which can't be decrypted in
+            // this one, without unlocking their descendants
+            if self
+                .block_number
+                .map_or(false, |number| !best.transaction.can_be_included_in(number))
+            {
+                self.mark_invalid(&best.transaction);
+                continue
+            }
+
             // Insert transactions that just got unlocked.
             if let Some(unlocked) = self.all.get(&best.unlocks()) {
                 self.independent.insert(unlocked.clone());
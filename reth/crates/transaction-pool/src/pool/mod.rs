@@ -422,6 +422,7 @@ where
                 state_nonce,
                 transaction,
                 propagate,
+                dawn_target_block,
             } => {
                 let sender_id = self.get_sender_id(transaction.sender());
                 let transaction_id = TransactionId::new(sender_id, transaction.nonce());
@@ -444,6 +445,7 @@ where
                     propagate,
                     timestamp: Instant::now(),
                     origin,
+                    dawn_target_block,
                 };
 
                 let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
@@ -1200,6 +1202,7 @@ mod tests {
                             sidecar: sidecar.clone(),
                         },
                         propagate: true,
+                        dawn_target_block: None,
                     },
                 )
                 .unwrap();
@@ -22,7 +22,7 @@ use reth_primitives::{
     constants::{
         eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
     },
-    Address, TxHash, B256,
+    Address, BlockNumber, TxHash, B256,
 };
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
@@ -255,7 +255,12 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Returns an iterator that yields transactions that are ready to be included in the block with
     /// the tracked fees.
     pub(crate) fn best_transactions(&self) -> BestTransactions<T> {
-        self.pending_pool.best()
+        self.pending_pool.best().for_block(self.next_block_number())
+    }
+
+    /// The number of the block after the last seen one, which the best transactions are for.
+    fn next_block_number(&self) -> BlockNumber {
+        self.all_transactions.last_seen_block_number + 1
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -284,20 +289,28 @@ impl<T: TransactionOrdering> TxPool<T> {
                     let unlocked_by_blob_fee =
                         self.blob_pool.satisfy_attributes(best_transactions_attributes);
 
-                    Box::new(self.pending_pool.best_with_unlocked(
-                        unlocked_by_blob_fee,
-                        self.all_transactions.pending_fees.base_fee,
-                    ))
+                    Box::new(
+                        self.pending_pool
+                            .best_with_unlocked(
+                                unlocked_by_blob_fee,
+                                self.all_transactions.pending_fees.base_fee,
+                            )
+                            .for_block(self.next_block_number()),
+                    )
                 } else {
-                    Box::new(self.pending_pool.best())
+                    Box::new(self.best_transactions())
                 }
             }
             Ordering::Greater => {
                 // base fee increased, we only need to enforce this on the pending pool
-                Box::new(self.pending_pool.best_with_basefee_and_blobfee(
-                    best_transactions_attributes.basefee,
-                    best_transactions_attributes.blob_fee.unwrap_or_default(),
-                ))
+                Box::new(
+                    self.pending_pool
+                        .best_with_basefee_and_blobfee(
+                            best_transactions_attributes.basefee,
+                            best_transactions_attributes.blob_fee.unwrap_or_default(),
+                        )
+                        .for_block(self.next_block_number()),
+                )
             }
             Ordering::Less => {
                 // base fee decreased, we need to move transactions from the basefee + blob pool to
@@ -311,7 +324,8 @@ impl<T: TransactionOrdering> TxPool<T> {
 
                 Box::new(
                     self.pending_pool
-                        .best_with_unlocked(unlocked, self.all_transactions.pending_fees.base_fee),
+                        .best_with_unlocked(unlocked, self.all_transactions.pending_fees.base_fee)
+                        .for_block(self.next_block_number()),
                 )
             }
         }
@@ -419,7 +433,12 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        // Remove the encrypted transactions that missed the block they were labelled for
+        let stale = self.remove_stale_dawn_transactions();
+        self.metrics.removed_transactions.increment(stale.len() as u64);
+
+        let UpdateOutcome { promoted, mut discarded } = self.update_accounts(changed_senders);
+        discarded.extend(stale);
 
         self.metrics.performed_state_updates.increment(1);
 
@@ -705,6 +724,31 @@ impl<T: TransactionOrdering> TxPool<T> {
         tx
     }
 
+    /// Removes the encrypted Dawn transactions labelled for a block that is not ahead of the last
+    /// seen block anymore, along with their descendants, which can't be executed without them.
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    fn remove_stale_dawn_transactions(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let next_block_number = self.next_block_number();
+        let stale: Vec<_> = self
+            .all_transactions
+            .by_hash
+            .values()
+            .filter(|tx| tx.dawn_target_block.map_or(false, |target| target < next_block_number))
+            .map(|tx| *tx.id())
+            .collect();
+
+        let mut removed = Vec::new();
+        for id in stale {
+            // a stale transaction may already be removed as the descendant of another one
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+        removed
+    }
+
     /// Removes _only_ the descendants of the given transaction from the __entire__ pool.
     ///
     /// All removed transactions are added to the `removed` vec.
@@ -2975,4 +3019,48 @@ mod tests {
             vec![1, 2, 3]
         );
     }
+
+    #[test]
+    fn dawn_transactions_labelled_by_block() {
+        let on_chain_balance = U256::from(10_000);
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let advance = |pool: &mut TxPool<MockOrdering>| {
+            let mut block_info = pool.block_info();
+            block_info.last_seen_block_number += 1;
+            pool.on_canonical_state_change(block_info, vec![], HashMap::new())
+        };
+
+        // an encrypted transaction labelled for block 2 and its descendant, and a transaction of
+        // another sender
+        let tx = MockTransaction::eip1559();
+        let mut encrypted = f.validated(tx.clone());
+        encrypted.dawn_target_block = Some(2);
+        let descendant = f.validated(tx.next());
+        let other = f.validated(MockTransaction::eip1559());
+        for tx in [encrypted.clone(), descendant.clone(), other.clone()] {
+            pool.add_transaction(tx, on_chain_balance, 0).unwrap();
+        }
+
+        // block 1 is next
+        assert_eq!(
+            pool.best_transactions().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![*other.hash()]
+        );
+
+        // block 2 is next
+        let outcome = advance(&mut pool);
+        assert!(outcome.discarded.is_empty());
+        assert_eq!(pool.best_transactions().count(), 3);
+
+        // block 2 was mined without the encrypted transaction
+        let outcome = advance(&mut pool);
+        let mut discarded: Vec<_> = outcome.discarded.iter().map(|tx| *tx.hash()).collect();
+        discarded.sort();
+        let mut stale = vec![*encrypted.hash(), *descendant.hash()];
+        stale.sort();
+        assert_eq!(discarded, stale);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(other.hash()));
+    }
 }
@@ -21,6 +21,7 @@ use reth_primitives::{
     TxType, B256, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
     U256,
 };
+use revm::primitives::SpecId;
 use std::{ops::Range, sync::Arc, time::Instant, vec::IntoIter};
 
 /// A transaction pool implementation using [`MockOrdering`] for transaction ordering.
@@ -761,6 +762,18 @@ impl EthPoolTransaction for MockTransaction {
     fn authorization_count(&self) -> usize {
         0
     }
+
+    fn dawn_intrinsic_gas(&self, _spec_id: SpecId) -> Option<u64> {
+        None
+    }
+
+    fn exceeds_dawn_value_bound(&self) -> bool {
+        false
+    }
+
+    fn lacks_dawn_ciphertext_proof(&self, _block_label: Option<&[u8; 96]>) -> bool {
+        false
+    }
 }
 
 impl TryFrom<TransactionSignedEcRecovered> for MockTransaction {
@@ -1144,6 +1157,7 @@ impl MockTransactionFactory {
             transaction,
             timestamp: Instant::now(),
             origin,
+            dawn_target_block: None,
         }
     }
 
@@ -15,6 +15,7 @@ use reth_primitives::{
     PooledTransactionsElementEcRecovered, SealedBlock, Transaction, TransactionSignedEcRecovered,
     TxHash, TxKind, TxType, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, U256,
 };
+use revm::primitives::SpecId;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::{
@@ -885,6 +886,19 @@ pub trait EthPoolTransaction:
 
     /// Returns the number of authorizations this transaction has.
     fn authorization_count(&self) -> usize;
+
+    /// Returns the intrinsic gas of an encrypted Dawn transaction under `spec_id`, which charges
+    /// its ciphertext as calldata.
+    fn dawn_intrinsic_gas(&self, spec_id: SpecId) -> Option<u64>;
+
+    /// Returns `true` if this is a decrypted Dawn transaction whose hidden value exceeds the public
+    /// bound of its encrypted transaction.
+    fn exceeds_dawn_value_bound(&self) -> bool;
+
+    /// Returns `true` if this is an encrypted Dawn transaction whose ciphertext does not carry a
+    /// valid proof of well-formedness for its sender and nonce, and for the given block label if
+    /// the chain labels ciphertexts by block.
+    fn lacks_dawn_ciphertext_proof(&self, block_label: Option<&[u8; 96]>) -> bool;
 }
 
 /// The default [`PoolTransaction`] for the [Pool](crate::Pool) for Ethereum.
@@ -966,6 +980,9 @@ impl EthPooledTransaction {
             Transaction::DawnDecrypted(t) => {
                 U256::from(t.max_fee_per_gas).saturating_mul(U256::from(t.gas_limit))
             }
+            Transaction::DawnUndecrypted(t) => {
+                U256::from(t.max_fee_per_gas).saturating_mul(U256::from(t.gas_limit))
+            }
             _ => U256::ZERO,
         };
         let mut cost = transaction.value();
@@ -1063,6 +1080,7 @@ impl PoolTransaction for EthPooledTransaction {
             Transaction::Eip7702(tx) => tx.max_fee_per_gas,
             Transaction::DawnEncrypted(tx) => tx.max_fee_per_gas,
             Transaction::DawnDecrypted(tx) => tx.max_fee_per_gas,
+            Transaction::DawnUndecrypted(tx) => tx.max_fee_per_gas,
             _ => 0,
         }
     }
@@ -1083,6 +1101,7 @@ impl PoolTransaction for EthPooledTransaction {
             Transaction::Eip7702(tx) => Some(tx.max_priority_fee_per_gas),
             Transaction::DawnEncrypted(tx) => Some(tx.max_priority_fee_per_gas),
             Transaction::DawnDecrypted(tx) => Some(tx.max_priority_fee_per_gas),
+            Transaction::DawnUndecrypted(tx) => Some(tx.max_priority_fee_per_gas),
             _ => None,
         }
     }
@@ -1107,8 +1126,11 @@ impl PoolTransaction for EthPooledTransaction {
 
     /// Returns the transaction's [`TxKind`], which is the address of the recipient or
     /// [`TxKind::Create`] if the transaction is a contract creation.
+    ///
+    /// Encrypted Dawn transactions hide their recipient, and are executed as a call to their
+    /// sender.
     fn kind(&self) -> TxKind {
-        self.transaction.kind()
+        self.transaction.kind().unwrap_or(TxKind::Call(self.transaction.signer()))
     }
 
     fn input(&self) -> &[u8] {
@@ -1119,7 +1141,8 @@ impl PoolTransaction for EthPooledTransaction {
     fn size(&self) -> usize {
         match &self.transaction.transaction {
             Transaction::DawnEncrypted(tx) => tx.ciphertext.payload.len(),
-            tx => tx.input().len()
+            Transaction::DawnUndecrypted(tx) => tx.ciphertext_len(),
+            tx => tx.input().len(),
         }
     }
 
@@ -1172,6 +1195,29 @@ impl EthPoolTransaction for EthPooledTransaction {
             _ => 0,
         }
     }
+
+    fn dawn_intrinsic_gas(&self, spec_id: SpecId) -> Option<u64> {
+        match &self.transaction.transaction {
+            Transaction::DawnEncrypted(tx) => Some(tx.intrinsic_gas(spec_id)),
+            _ => None,
+        }
+    }
+
+    fn exceeds_dawn_value_bound(&self) -> bool {
+        match &self.transaction.transaction {
+            Transaction::DawnDecrypted(tx) => !tx.is_within_value_bound(),
+            _ => false,
+        }
+    }
+
+    fn lacks_dawn_ciphertext_proof(&self, block_label: Option<&[u8; 96]>) -> bool {
+        match &self.transaction.transaction {
+            Transaction::DawnEncrypted(tx) => {
+                !tx.verify_ciphertext_proof(&self.transaction.signer(), block_label)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl TryFrom<TransactionSignedEcRecovered> for EthPooledTransaction {
@@ -9,14 +9,14 @@ use crate::{
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::{ChainSpec, DawnLabels, EthereumHardforks};
 use reth_primitives::{
-    constants::eip4844::MAX_BLOBS_PER_BLOCK, GotExpected, InvalidTransactionError, SealedBlock,
-    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
-    DAWN_ENCRYPTED_TX_TYPE_ID, DAWN_DECRYPTED_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID,
+    constants::{dawn::DECRYPTION_GAS, eip4844::MAX_BLOBS_PER_BLOCK},
+    dawn_block_label, GotExpected, InvalidTransactionError, SealedBlock, DAWN_DECRYPTED_TX_TYPE_ID,
+    DAWN_ENCRYPTED_TX_TYPE_ID, DAWN_UNDECRYPTED_TX_TYPE_ID, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
+    EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
 };
-use reth_storage_api::{AccountReader, BlockReaderIdExt, StateProviderFactory};
+use reth_storage_api::{AccountReader, BlockNumReader, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use revm::{
     interpreter::gas::validate_initial_tx_gas,
@@ -156,6 +156,9 @@ where
         origin: TransactionOrigin,
         mut transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
+        // The block an encrypted transaction must be included in, if any
+        let mut dawn_target_block = None;
+
         // Checks for tx_type
         match transaction.tx_type() {
             LEGACY_TX_TYPE_ID => {
@@ -199,7 +202,49 @@ where
             }
 
             DAWN_ENCRYPTED_TX_TYPE_ID | DAWN_DECRYPTED_TX_TYPE_ID => {
-                // ok
+                // Reject Dawn transactions until Dawn activates.
+                if !self.fork_tracker.is_dawn_activated() {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::DawnDisabled.into(),
+                    )
+                }
+                // Reject decrypted transactions whose hidden value exceeds its public bound.
+                if transaction.exceeds_dawn_value_bound() {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::DawnValueExceedsBound.into(),
+                    )
+                }
+                // Reject encrypted transactions whose ciphertext could be mauled, before it is
+                // included in a shadow block. If the chain labels ciphertexts by block, they must
+                // be encrypted for the next block.
+                let block_label = match self.chain_spec.dawn {
+                    Some(dawn)
+                        if dawn.labels == DawnLabels::Block &&
+                            transaction.tx_type() == DAWN_ENCRYPTED_TX_TYPE_ID =>
+                    {
+                        match self.client.best_block_number() {
+                            Ok(number) => {
+                                dawn_target_block = Some(number + 1);
+                                Some(dawn_block_label(self.chain_id(), number + 1))
+                            }
+                            Err(err) => {
+                                return TransactionValidationOutcome::Error(
+                                    *transaction.hash(),
+                                    Box::new(err),
+                                )
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+                if transaction.lacks_dawn_ciphertext_proof(block_label.as_ref()) {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::DawnInvalidCiphertextProof.into(),
+                    )
+                }
             }
             _ => {
                 return TransactionValidationOutcome::Invalid(
@@ -418,6 +463,7 @@ where
                 }
                 TransactionOrigin::Private => false,
             },
+            dawn_target_block,
         }
     }
 
@@ -434,6 +480,10 @@ where
         if self.chain_spec.is_prague_active_at_timestamp(new_tip_block.timestamp) {
             self.fork_tracker.prague.store(true, std::sync::atomic::Ordering::Relaxed);
         }
+
+        if self.chain_spec.dawn_at(new_tip_block.number, new_tip_block.timestamp).is_some() {
+            self.fork_tracker.dawn.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
     }
 }
 
@@ -447,6 +497,8 @@ pub struct EthTransactionValidatorBuilder {
     cancun: bool,
     /// Fork indicator whether we are in the Cancun hardfork.
     prague: bool,
+    /// Fork indicator whether we are in the Dawn hardfork.
+    dawn: bool,
     /// Whether using EIP-2718 type transactions is allowed
     eip2718: bool,
     /// Whether using EIP-1559 type transactions is allowed
@@ -505,6 +557,9 @@ impl EthTransactionValidatorBuilder {
 
             // prague not yet activated
             prague: false,
+
+            // dawn not yet activated
+            dawn: false,
         }
     }
 
@@ -550,6 +605,17 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Disables the Dawn fork.
+    pub const fn no_dawn(self) -> Self {
+        self.set_dawn(false)
+    }
+
+    /// Set the Dawn fork.
+    pub const fn set_dawn(mut self, dawn: bool) -> Self {
+        self.dawn = dawn;
+        self
+    }
+
     /// Disables the support for EIP-2718 transactions.
     pub const fn no_eip2718(self) -> Self {
         self.set_eip2718(false)
@@ -610,6 +676,15 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Configures validation rules based on the head block's number and timestamp.
+    ///
+    /// Like [`Self::with_head_timestamp`], but also configures whether the Dawn hardfork, which may
+    /// activate at a block, is activated at launch.
+    pub fn with_head_block(mut self, number: u64, timestamp: u64) -> Self {
+        self.dawn = self.chain_spec.dawn_at(number, timestamp).is_some();
+        self.with_head_timestamp(timestamp)
+    }
+
     /// Sets a max size in bytes of a single transaction allowed into the pool
     pub const fn with_max_tx_input_bytes(mut self, max_tx_input_bytes: usize) -> Self {
         self.max_tx_input_bytes = max_tx_input_bytes;
@@ -638,6 +713,7 @@ impl EthTransactionValidatorBuilder {
             shanghai,
             cancun,
             prague,
+            dawn,
             eip2718,
             eip1559,
             eip4844,
@@ -654,6 +730,7 @@ impl EthTransactionValidatorBuilder {
             shanghai: AtomicBool::new(shanghai),
             cancun: AtomicBool::new(cancun),
             prague: AtomicBool::new(prague),
+            dawn: AtomicBool::new(dawn),
         };
 
         let inner = EthTransactionValidatorInner {
@@ -729,6 +806,8 @@ pub struct ForkTracker {
     pub cancun: AtomicBool,
     /// Tracks if prague is activated at the block's timestamp.
     pub prague: AtomicBool,
+    /// Tracks if dawn is activated at the block's number or timestamp.
+    pub dawn: AtomicBool,
 }
 
 impl ForkTracker {
@@ -746,6 +825,11 @@ impl ForkTracker {
     pub fn is_prague_activated(&self) -> bool {
         self.prague.load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Returns `true` if Dawn fork is activated.
+    pub fn is_dawn_activated(&self) -> bool {
+        self.dawn.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 /// Ensure that the code size is not greater than `max_init_code_size`.
@@ -766,15 +850,15 @@ pub fn ensure_max_init_code_size<T: PoolTransaction>(
 
 /// Ensures that gas limit of the transaction exceeds the intrinsic gas of the transaction.
 ///
+/// Encrypted Dawn transactions are charged their own intrinsic gas, see
+/// [`TxDawnEncrypted::intrinsic_gas`](reth_primitives::TxDawnEncrypted::intrinsic_gas), and
+/// decrypted and undecrypted ones are charged [`DECRYPTION_GAS`] on top of their intrinsic gas.
+///
 /// Caution: This only checks past the Merge hardfork.
 pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
     transaction: &T,
     fork_tracker: &ForkTracker,
 ) -> Result<(), InvalidPoolTransactionError> {
-    if transaction.tx_type() == DAWN_ENCRYPTED_TX_TYPE_ID {
-        // FIXME: ensure there's enough gas for decryption and ciphertext
-        return Ok(())
-    }
     let spec_id = if fork_tracker.is_prague_activated() {
         SpecId::PRAGUE
     } else if fork_tracker.is_shanghai_activated() {
@@ -783,13 +867,22 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
         SpecId::MERGE
     };
 
-    let gas_after_merge = validate_initial_tx_gas(
-        spec_id,
-        transaction.input(),
-        transaction.kind().is_create(),
-        transaction.access_list().map(|list| list.0.as_slice()).unwrap_or(&[]),
-        transaction.authorization_count() as u64,
-    );
+    let gas_after_merge = match transaction.dawn_intrinsic_gas(spec_id) {
+        Some(gas) => gas,
+        None => {
+            let gas = validate_initial_tx_gas(
+                spec_id,
+                transaction.input(),
+                transaction.kind().is_create(),
+                transaction.access_list().map(|list| list.0.as_slice()).unwrap_or(&[]),
+                transaction.authorization_count() as u64,
+            );
+            match transaction.tx_type() {
+                DAWN_DECRYPTED_TX_TYPE_ID | DAWN_UNDECRYPTED_TX_TYPE_ID => gas + DECRYPTION_GAS,
+                _ => gas,
+            }
+        }
+    };
 
     if transaction.gas_limit() < gas_after_merge {
         Err(InvalidPoolTransactionError::IntrinsicGasTooLow)
@@ -805,8 +898,12 @@ mod tests {
         blobstore::InMemoryBlobStore, error::PoolErrorKind, CoinbaseTipOrdering,
         EthPooledTransaction, Pool, TransactionPool,
     };
-    use reth_chainspec::MAINNET;
-    use reth_primitives::{hex, PooledTransactionsElement, U256};
+    use dawn_crypto::{Ciphertext, Padding};
+    use reth_chainspec::{DawnConfig, DEV, MAINNET};
+    use reth_primitives::{
+        dawn_label, hex, Address, Header, PooledTransactionsElement, Signature, Transaction,
+        TransactionSigned, TxDawnEncrypted, B256, U256,
+    };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 
     fn get_transaction() -> EthPooledTransaction {
@@ -822,8 +919,12 @@ mod tests {
     #[tokio::test]
     async fn validate_transaction() {
         let transaction = get_transaction();
-        let mut fork_tracker =
-            ForkTracker { shanghai: false.into(), cancun: false.into(), prague: false.into() };
+        let mut fork_tracker = ForkTracker {
+            shanghai: false.into(),
+            cancun: false.into(),
+            prague: false.into(),
+            dawn: false.into(),
+        };
 
         let res = ensure_intrinsic_gas(&transaction, &fork_tracker);
         assert!(res.is_ok());
@@ -888,4 +989,168 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn dawn_intrinsic_gas() {
+        let fork_tracker = ForkTracker {
+            shanghai: true.into(),
+            cancun: true.into(),
+            prague: false.into(),
+            dawn: true.into(),
+        };
+        let pooled = |tx: &TxDawnEncrypted| {
+            let tx = TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnEncrypted(tx.clone()),
+                Signature::default(),
+            );
+            let encoded_length = tx.length_without_header();
+            EthPooledTransaction::new(tx.with_signer(Address::ZERO), encoded_length)
+        };
+
+        let mut tx = TxDawnEncrypted {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 0,
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(
+                &DEV.dawn.unwrap().master_public_key,
+                &[0; 96],
+                &[1; 36],
+                &[],
+                Padding::None,
+            ),
+        };
+        let res = ensure_intrinsic_gas(&pooled(&tx), &fork_tracker);
+        assert!(matches!(res, Err(InvalidPoolTransactionError::IntrinsicGasTooLow)));
+
+        // the ciphertext is charged for as calldata, on top of the decryption gas
+        let ciphertext_gas: u64 =
+            tx.ciphertext_bytes().iter().map(|&byte| if byte == 0 { 4 } else { 16 }).sum();
+        tx.gas_limit = 21_000 + ciphertext_gas + DECRYPTION_GAS - 1;
+        let res = ensure_intrinsic_gas(&pooled(&tx), &fork_tracker);
+        assert!(matches!(res, Err(InvalidPoolTransactionError::IntrinsicGasTooLow)));
+
+        tx.gas_limit += 1;
+        assert!(ensure_intrinsic_gas(&pooled(&tx), &fork_tracker).is_ok());
+
+        // which is what block execution charges
+        assert_eq!(tx.gas_limit, tx.intrinsic_gas(SpecId::SHANGHAI));
+    }
+
+    #[test]
+    fn dawn_ciphertext_proof() {
+        let pooled = |tx: &TxDawnEncrypted, sender: Address| {
+            let tx = TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnEncrypted(tx.clone()),
+                Signature::default(),
+            );
+            let encoded_length = tx.length_without_header();
+            EthPooledTransaction::new(tx.with_signer(sender), encoded_length)
+        };
+        let sender = Address::repeat_byte(0x11);
+        let label = dawn_label(DEV.chain.id(), &sender, 0);
+        let tx = TxDawnEncrypted {
+            chain_id: DEV.chain.id(),
+            nonce: 0,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 0,
+            value: U256::ZERO,
+            access_list: Default::default(),
+            ciphertext: Ciphertext::encrypt(
+                &DEV.dawn.unwrap().master_public_key,
+                &label,
+                &[1; 36],
+                &label,
+                Padding::None,
+            ),
+        };
+        assert!(!pooled(&tx, sender).lacks_dawn_ciphertext_proof(None));
+
+        let validator = EthTransactionValidatorBuilder::new(DEV.clone())
+            .set_dawn(true)
+            .build(MockEthProvider::default(), InMemoryBlobStore::default());
+        let is_rejected = |transaction| {
+            matches!(
+                validator.validate_one(TransactionOrigin::External, transaction),
+                TransactionValidationOutcome::Invalid(
+                    _,
+                    InvalidPoolTransactionError::Consensus(
+                        InvalidTransactionError::DawnInvalidCiphertextProof
+                    )
+                )
+            )
+        };
+
+        // the ciphertext copied by another sender
+        assert!(is_rejected(pooled(&tx, Address::repeat_byte(0x22))));
+        // a version 1 ciphertext, which could have been copied
+        let mut v1 = tx.clone();
+        v1.ciphertext.proof = None;
+        assert!(is_rejected(pooled(&v1, sender)));
+        assert!(!is_rejected(pooled(&tx, sender)));
+    }
+
+    #[test]
+    fn dawn_block_ciphertext_proof() {
+        let pooled = |tx: &TxDawnEncrypted| {
+            let tx = TransactionSigned::from_transaction_and_signature(
+                Transaction::DawnEncrypted(tx.clone()),
+                Signature::default(),
+            );
+            let encoded_length = tx.length_without_header();
+            EthPooledTransaction::new(tx.with_signer(Address::repeat_byte(0x11)), encoded_length)
+        };
+        let dawn = DawnConfig { labels: DawnLabels::Block, ..DEV.dawn.unwrap() };
+        let chain_spec = Arc::new(ChainSpec { dawn: Some(dawn), ..(**DEV).clone() });
+        let chain_id = chain_spec.chain.id();
+        let encrypted = |block_label: &[u8]| {
+            let label = dawn_label(chain_id, &Address::repeat_byte(0x11), 0);
+            TxDawnEncrypted {
+                chain_id,
+                nonce: 0,
+                gas_limit: 1_000_000,
+                max_fee_per_gas: 1_000_000_000,
+                max_priority_fee_per_gas: 0,
+                value: U256::ZERO,
+                access_list: Default::default(),
+                ciphertext: Ciphertext::encrypt(
+                    &dawn.master_public_key,
+                    block_label,
+                    &[1; 36],
+                    &label,
+                    Padding::None,
+                ),
+            }
+        };
+
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::ZERO, Header { number: 7, ..Default::default() });
+        let validator = EthTransactionValidatorBuilder::new(chain_spec)
+            .set_dawn(true)
+            .build(provider, InMemoryBlobStore::default());
+        let is_rejected = |transaction| {
+            matches!(
+                validator.validate_one(TransactionOrigin::External, transaction),
+                TransactionValidationOutcome::Invalid(
+                    _,
+                    InvalidPoolTransactionError::Consensus(
+                        InvalidTransactionError::DawnInvalidCiphertextProof
+                    )
+                )
+            )
+        };
+
+        // only ciphertexts encrypted for the next block are accepted
+        assert!(!is_rejected(pooled(&encrypted(&dawn_block_label(chain_id, 8)))));
+        assert!(is_rejected(pooled(&encrypted(&dawn_block_label(chain_id, 9)))));
+        assert!(is_rejected(pooled(&encrypted(&dawn_label(
+            chain_id,
+            &Address::repeat_byte(0x11),
+            0
+        )))));
+    }
 }
@@ -6,7 +6,7 @@ use crate::{
     traits::{PoolTransaction, TransactionOrigin},
 };
 use reth_primitives::{
-    Address, BlobTransactionSidecar, IntoRecoveredTransaction,
+    Address, BlobTransactionSidecar, BlockNumber, IntoRecoveredTransaction,
     PooledTransactionsElementEcRecovered, SealedBlock, TransactionSignedEcRecovered, TxHash, B256,
     U256,
 };
@@ -45,6 +45,9 @@ pub enum TransactionValidationOutcome<T: PoolTransaction> {
         transaction: ValidTransaction<T>,
         /// Whether to propagate the transaction to the network.
         propagate: bool,
+        /// The block the ciphertext of an encrypted Dawn transaction is labelled for, on a chain
+        /// that labels ciphertexts by block. The transaction can't be included in any other block.
+        dawn_target_block: Option<BlockNumber>,
     },
     /// The transaction is considered invalid indefinitely: It violates constraints that prevent
     /// this transaction from ever becoming valid.
@@ -226,6 +229,9 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub timestamp: Instant,
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
+    /// The only block this transaction can be included in, if it is an encrypted Dawn
+    /// transaction labelled by block.
+    pub dawn_target_block: Option<BlockNumber>,
 }
 
 // === impl ValidPoolTransaction ===
@@ -324,6 +330,12 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.is_eip4844()
     }
 
+    /// Returns `false` if this is an encrypted Dawn transaction labelled for another block than
+    /// `number`.
+    pub fn can_be_included_in(&self, number: BlockNumber) -> bool {
+        self.dawn_target_block.map_or(true, |target| target == number)
+    }
+
     /// The heap allocated size of this transaction.
     pub(crate) fn size(&self) -> usize {
         self.transaction.size()
@@ -355,6 +367,7 @@ impl<T: PoolTransaction + Clone> Clone for ValidPoolTransaction<T> {
             propagate: self.propagate,
             timestamp: self.timestamp,
             origin: self.origin,
+            dawn_target_block: self.dawn_target_block,
         }
     }
 }
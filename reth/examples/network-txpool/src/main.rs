@@ -86,6 +86,7 @@ impl TransactionValidator for OkValidator {
             state_nonce: transaction.nonce(),
             transaction: ValidTransaction::Valid(transaction),
             propagate: false,
+            dawn_target_block: None,
         }
     }
 }
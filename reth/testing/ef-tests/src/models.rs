@@ -114,6 +114,7 @@ impl From<Header> for SealedHeader {
             excess_blob_gas: value.excess_blob_gas.map(|v| v.to::<u64>()),
             parent_beacon_block_root: value.parent_beacon_block_root,
             requests_root: value.requests_root,
+            shadow_root: None,
         };
         header.seal(value.hash)
     }
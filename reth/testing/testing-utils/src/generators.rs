@@ -165,6 +165,7 @@ pub fn random_block<R: Rng>(
         ommers,
         withdrawals: None,
         requests: None,
+        shadow: None,
     }
 }
 
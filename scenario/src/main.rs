@@ -11,7 +11,7 @@ use eyre::Result;
 
 use futures_util::StreamExt;
 
-use dawn_enclave_protocol::{MasterPublicKey, SealedMasterPrivateKey};
+use dawn_enclave_protocol::MasterPublicKey;
 
 sol!(
     #[allow(missing_docs)]
@@ -60,13 +60,13 @@ struct Scenario {
 
 impl Scenario {
     async fn new() -> Result<Self> {
-        let (mpk, _) = load_master_key();
         let (deployer_wallet, deployer_address) = derive_key(0)?;
         let provider = &ProviderBuilder::new()
             .with_recommended_fillers()
             .wallet(deployer_wallet)
             .on_ws(WsConnect::new("ws://localhost:8546"))
             .await?;
+        let mpk = load_master_public_key(provider).await?;
 
         // Deploy the contract.
         let block_delay = 2;
@@ -186,7 +186,16 @@ impl Scenario {
             gas: Some(1_000_000),
             ..auctions.bid(auction_id, amount).into_transaction_request()}.build_typed_tx().unwrap();
         
-        let tx = dawn::encrypt(&self.mpk, tx.legacy().unwrap(), &bidder_address);
+        // pad so that bids cannot be told apart from other calls by their length
+        let padding = dawn::Padding::Multiple(256);
+        let tx = dawn::encrypt(
+            &self.mpk,
+            tx.legacy().unwrap(),
+            &bidder_address,
+            dawn::Identity::Transaction,
+            padding,
+        )
+        .map_err(|err| eyre::eyre!("failed to encrypt bid: {err:?}"))?;
         let tx = TypedTransaction::DawnEncrypted(tx);
         let tx = <EthereumWallet as NetworkWallet<Ethereum>>::sign_transaction(&bidder_wallet, tx).await?;
         let r = provider.send_tx_envelope(tx).await?.get_receipt().await?;
@@ -236,7 +245,11 @@ async fn wait_for_block<T: Transport + Clone>(
     Ok(())
 }
 
-fn load_master_key() -> (MasterPublicKey, SealedMasterPrivateKey) {
-    serde_json::from_str(&std::env::var("DAWN_MASTER_KEY").expect("DAWN_MASTER_KEY not set"))
-        .unwrap()
+/// Reads the Dawn master public key from the chain config of the node.
+async fn load_master_public_key<T: Transport + Clone>(
+    provider: &impl Provider<T, Ethereum>,
+) -> Result<MasterPublicKey> {
+    let node_info: serde_json::Value = provider.raw_request("admin_nodeInfo".into(), ()).await?;
+    let mpk = &node_info["protocols"]["eth"]["config"]["dawn"]["masterPublicKey"];
+    Ok(serde_json::from_value(mpk.clone())?)
 }
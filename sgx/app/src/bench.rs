@@ -0,0 +1,81 @@
+//! Compares revealing decryption keys one request at a time with revealing them in a batch.
+
+use dawn_crypto::verify;
+use dawn_enclave_protocol::{Request, Response, SealedMasterPrivateKey};
+use dawn_light_client::testing::{encrypted_transaction, Chain};
+use sgx_types::*;
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use daemon::enclave_request;
+
+/// Reveals the decryption keys of the `transactions` encrypted transactions of a shadow block
+/// through the enclave `eid`, as the daemon does, first with one `Reveal` request per
+/// transaction, then with a single `RevealBatch` request.
+pub fn bench(eid: sgx_enclave_id_t, transactions: usize) -> io::Result<()> {
+    let mut chain = Chain::sealed(2, &[9; 32]);
+    let (transactions, labels): (Vec<_>, Vec<_>) = (0..transactions as u64)
+        .map(|nonce| encrypted_transaction(&[1; 32], 1337, nonce))
+        .unzip();
+    chain.push(transactions);
+    chain.push(vec![]);
+    let proof = chain.proof(0, 1);
+    let generate = Request::Generate(chain.anchor, vec![chain.authority().unwrap()]);
+    let Response::Generate(mpk, smpk, client) = request(eid, &generate)? else {
+        panic!("Expected Generate response")
+    };
+
+    let start = Instant::now();
+    let mut single = Vec::with_capacity(labels.len());
+    for index in 0..labels.len() as u32 {
+        let reveal = Request::Reveal(
+            proof.clone(),
+            index,
+            client.clone(),
+            SealedMasterPrivateKey(smpk.0.clone()),
+        );
+        let Response::Reveal(dk) = request(eid, &reveal)? else {
+            panic!("Expected Reveal response")
+        };
+        single.push(dk);
+    }
+    report("single", labels.len(), start.elapsed());
+
+    let start = Instant::now();
+    let indices = (0..labels.len() as u32).collect();
+    let reveal = Request::RevealBatch(proof, indices, client, smpk);
+    let Response::RevealBatch(batch) = request(eid, &reveal)? else {
+        panic!("Expected RevealBatch response")
+    };
+    report("batch", labels.len(), start.elapsed());
+
+    assert_eq!(batch.len(), labels.len());
+    for ((label, single), batch) in labels.iter().zip(single).zip(batch) {
+        let batch = batch.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        assert!(batch == single && verify(label, &mpk, &batch));
+    }
+    Ok(())
+}
+
+fn request(eid: sgx_enclave_id_t, request: &Request) -> io::Result<Response> {
+    let request =
+        serde_cbor::to_vec(request).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    let response = enclave_request(eid, &request).map_err(|status| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("ECALL failed: {}", status.as_str()),
+        )
+    })?;
+    serde_cbor::from_slice(&response).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+fn report(mode: &str, transactions: usize, elapsed: Duration) {
+    println!(
+        "{}: {} transactions in {:?} ({:.0} transactions/s)",
+        mode,
+        transactions,
+        elapsed,
+        transactions as f64 / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,337 @@
+//! A long-running host process that keeps the enclave loaded and serves requests over a socket.
+//!
+//! Requests and responses are CBOR `Request`s and `Response`s, each preceded by its length as a
+//! 4-byte big-endian integer. A client may send any number of requests over a connection, and
+//! clients may connect concurrently; each request is one `handle_request` ECALL. A request that the
+//! enclave fails to handle closes its connection. SIGINT and SIGTERM stop accepting connections,
+//! let the connections finish their current request and return.
+
+use libc;
+use sgx_types::*;
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+extern "C" {
+    fn handle_request(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        request: *const u8,
+        request_len: usize,
+        response: *mut u8,
+        response_capacity: usize,
+        response_len: *mut usize,
+    ) -> sgx_status_t;
+}
+
+/// Requests larger than this close the connection.
+const MAX_REQUEST_LEN: usize = 16 << 20;
+/// The initial size of the buffer the enclave writes a response to.
+const RESPONSE_CAPACITY: usize = 64 << 10;
+/// The number of concurrent ECALLs, which must not exceed the `TCSNum` of `Enclave.config.xml`.
+const ECALL_SLOTS: usize = 8;
+/// Connections beyond this are closed right away.
+const MAX_CONNECTIONS: usize = 64;
+/// How long a connection may wait between two requests.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long a peer may stall in the middle of a request or response.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often waiting threads check for a shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_shutdown(_signal: libc::c_int) {
+    SHUTDOWN.store(true, Ordering::SeqCst);
+}
+
+/// Serves the enclave `eid` on `address` until SIGINT or SIGTERM.
+///
+/// `address` is a TCP socket address such as `127.0.0.1:7878`, or else the path of a Unix socket,
+/// which is removed on shutdown.
+pub fn serve(address: &str, eid: sgx_enclave_id_t) -> io::Result<()> {
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            request_shutdown as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+        libc::signal(
+            libc::SIGTERM,
+            request_shutdown as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    let listener = Listener::bind(address)?;
+    eprintln!("[+] Serving the enclave on {}", address);
+
+    let slots = Arc::new(Slots::new(ECALL_SLOTS));
+    let mut connections: Vec<thread::JoinHandle<()>> = Vec::new();
+    while !SHUTDOWN.load(Ordering::SeqCst) {
+        let stream = match listener.accept() {
+            Ok(stream) => stream,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                eprintln!("[-] Accept failed: {}", err);
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+        connections.retain(|connection| !connection.is_finished());
+        if connections.len() >= MAX_CONNECTIONS {
+            eprintln!("[-] Too many connections, closing a new one");
+            continue;
+        }
+        let slots = Arc::clone(&slots);
+        connections.push(thread::spawn(move || {
+            if let Err(err) = serve_connection(stream, eid, &slots) {
+                eprintln!("[-] Connection closed: {}", err);
+            }
+        }));
+    }
+
+    eprintln!("[+] Shutting down");
+    drop(listener);
+    for connection in connections {
+        let _ = connection.join();
+    }
+    Ok(())
+}
+
+/// Serves the requests of one client until it disconnects, idles or misbehaves.
+fn serve_connection(mut stream: Stream, eid: sgx_enclave_id_t, slots: &Slots) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    // short reads let the connection notice a shutdown while waiting for the next request
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    loop {
+        let mut len = [0; 4];
+        if !read_full(&mut stream, &mut len, IDLE_TIMEOUT)? {
+            return Ok(());
+        }
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_REQUEST_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("request of {} bytes is too large", len),
+            ));
+        }
+        let mut request = vec![0; len];
+        if !read_full(&mut stream, &mut request, IO_TIMEOUT)? {
+            return Ok(());
+        }
+
+        let response = slots
+            .call(|| enclave_request(eid, &request))
+            .map_err(|status| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("enclave failed to handle the request: {}", status.as_str()),
+                )
+            })?;
+        stream.write_all(&(response.len() as u32).to_be_bytes())?;
+        stream.write_all(&response)?;
+        stream.flush()?;
+    }
+}
+
+/// Fills `buf`, failing if the peer sends nothing for `timeout`.
+///
+/// Returns `false` if the peer closed the connection, or the daemon is shutting down, before the
+/// first byte.
+fn read_full(stream: &mut Stream, buf: &mut [u8], timeout: Duration) -> io::Result<bool> {
+    let mut read = 0;
+    let mut progress = Instant::now();
+    while read < buf.len() {
+        if read == 0 && SHUTDOWN.load(Ordering::SeqCst) {
+            return Ok(false);
+        }
+        match stream.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                read += n;
+                progress = Instant::now();
+            }
+            Err(ref err)
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                if progress.elapsed() > timeout {
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+/// Hands the CBOR `request` to the enclave and returns its CBOR response.
+pub fn enclave_request(eid: sgx_enclave_id_t, request: &[u8]) -> Result<Vec<u8>, sgx_status_t> {
+    let mut response = vec![0; RESPONSE_CAPACITY];
+    loop {
+        let mut retval = sgx_status_t::SGX_SUCCESS;
+        let mut response_len = 0;
+        let result = unsafe {
+            handle_request(
+                eid,
+                &mut retval,
+                request.as_ptr(),
+                request.len(),
+                response.as_mut_ptr(),
+                response.len(),
+                &mut response_len,
+            )
+        };
+        if result != sgx_status_t::SGX_SUCCESS {
+            return Err(result);
+        }
+        match retval {
+            sgx_status_t::SGX_SUCCESS => {
+                response.truncate(response_len);
+                return Ok(response);
+            }
+            // the response did not fit, retry with a large enough buffer
+            sgx_status_t::SGX_ERROR_OUT_OF_MEMORY if response_len > response.len() => {
+                response.resize(response_len, 0)
+            }
+            retval => return Err(retval),
+        }
+    }
+}
+
+/// Bounds the number of concurrent ECALLs, since an ECALL fails when the enclave has no thread
+/// control structure left.
+struct Slots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Slots {
+    fn new(slots: usize) -> Slots {
+        Slots {
+            free: Mutex::new(slots),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Runs `f` once a slot is free.
+    fn call<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let mut free = self.free.lock().unwrap();
+        while *free == 0 {
+            free = self.released.wait(free).unwrap();
+        }
+        *free -= 1;
+        drop(free);
+
+        let result = f();
+
+        *self.free.lock().unwrap() += 1;
+        self.released.notify_one();
+        result
+    }
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    Unix(UnixListener, PathBuf),
+}
+
+impl Listener {
+    /// Binds to `address` if it is a TCP socket address, and to the Unix socket at the path
+    /// `address` otherwise.
+    fn bind(address: &str) -> io::Result<Listener> {
+        let listener = match address.parse::<SocketAddr>() {
+            Ok(address) => Listener::Tcp(TcpListener::bind(address)?),
+            Err(_) => Listener::Unix(UnixListener::bind(address)?, PathBuf::from(address)),
+        };
+        // accepting does not block, so that the daemon notices a shutdown
+        match listener {
+            Listener::Tcp(ref listener) => listener.set_nonblocking(true)?,
+            Listener::Unix(ref listener, _) => listener.set_nonblocking(true)?,
+        }
+        Ok(listener)
+    }
+
+    fn accept(&self) -> io::Result<Stream> {
+        match *self {
+            Listener::Tcp(ref listener) => listener.accept().map(|(stream, _)| Stream::Tcp(stream)),
+            Listener::Unix(ref listener, _) => {
+                listener.accept().map(|(stream, _)| Stream::Unix(stream))
+            }
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Listener::Unix(_, ref path) = *self {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+enum Stream {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.set_nonblocking(nonblocking),
+            Stream::Unix(ref stream) => stream.set_nonblocking(nonblocking),
+        }
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.set_read_timeout(timeout),
+            Stream::Unix(ref stream) => stream.set_read_timeout(timeout),
+        }
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.set_write_timeout(timeout),
+            Stream::Unix(ref stream) => stream.set_write_timeout(timeout),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.read(buf),
+            Stream::Unix(ref mut stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.write(buf),
+            Stream::Unix(ref mut stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.flush(),
+            Stream::Unix(ref mut stream) => stream.flush(),
+        }
+    }
+}
@@ -28,6 +28,8 @@
 
 extern crate dawn_crypto;
 extern crate dawn_enclave_protocol;
+extern crate dawn_light_client;
+extern crate libc;
 extern crate sgx_types;
 extern crate sgx_urts;
 use sgx_types::*;
@@ -36,8 +38,13 @@ use sgx_urts::SgxEnclave;
 use std::io;
 use std::process::{Command, Stdio};
 
+mod bench;
+mod daemon;
+
 use dawn_crypto::verify;
-use dawn_enclave_protocol::{Request, Response};
+use dawn_enclave_protocol::{Anchor, Request, Response, SealedMasterPrivateKey};
+use dawn_light_client::block_label;
+use dawn_light_client::testing::{encrypted_transaction, Chain};
 
 static ENCLAVE_FILE: &'static str = "enclave.signed.so";
 
@@ -83,22 +90,129 @@ fn enclave_handle(request: Request) -> io::Result<Response> {
 }
 
 fn selfcheck() -> io::Result<()> {
-    let request = Request::Generate();
-    let Response::Generate(mpk, emsk) = enclave_handle(request)? else { panic!("Expected Generate response") };
+    // a chain sealed by its authority, on which the shadow blocks of blocks 1 and 2 are deep enough
+    let mut chain = Chain::sealed(2, &[9; 32]);
+    let (first, first_label) = encrypted_transaction(&[1; 32], 1337, 0);
+    let (second, second_label) = encrypted_transaction(&[1; 32], 1337, 1);
+    chain.push(vec![first]);
+    chain.push(vec![second]);
+    chain.push(vec![]);
+
+    // without authorities, the enclave refuses to generate a key
+    let request = Request::Generate(chain.anchor, vec![]);
+    let Response::Refused(_) = enclave_handle(request)? else {
+        panic!("Expected Refused response")
+    };
 
-    let request = Request::Reveal(b"label".to_vec(), emsk);
-    let Response::Reveal(dk) = enclave_handle(request)? else { panic!("Expected Reveal response") };
+    let authorities = vec![chain.authority().unwrap()];
+    let request = Request::Generate(chain.anchor, authorities);
+    let Response::Generate(mpk, emsk, client) = enclave_handle(request)? else {
+        panic!("Expected Generate response")
+    };
+
+    // a key can not be anchored again: the light client of another key with the same anchor but
+    // another authority does not unlock it
+    let impostor = vec![Chain::sealed(2, &[8; 32]).authority().unwrap()];
+    let request = Request::Generate(chain.anchor, impostor);
+    let Response::Generate(_, _, impostor_client) = enclave_handle(request)? else {
+        panic!("Expected Generate response")
+    };
+    let request = Request::Reveal(
+        chain.proof(0, 1),
+        0,
+        impostor_client,
+        SealedMasterPrivateKey(emsk.0.clone()),
+    );
+    let Response::Refused(_) = enclave_handle(request)? else {
+        panic!("Expected Refused response")
+    };
+
+    let mut shallow = chain.proof(0, 1);
+    shallow.headers.truncate(1);
+    let request = Request::Reveal(
+        shallow,
+        0,
+        client.clone(),
+        SealedMasterPrivateKey(emsk.0.clone()),
+    );
+    let Response::Refused(_) = enclave_handle(request)? else {
+        panic!("Expected Refused response")
+    };
+
+    let request = Request::Reveal(
+        chain.proof(0, 1),
+        0,
+        client.clone(),
+        SealedMasterPrivateKey(emsk.0.clone()),
+    );
+    let Response::Reveal(dk) = enclave_handle(request)? else {
+        panic!("Expected Reveal response")
+    };
+    assert!(verify(&first_label, &mpk, &dk));
+
+    // the light client moves on to block 1, from which the shadow block of block 2 is proven
+    let request = Request::AdvanceHeaders(client, chain.headers(0, 3));
+    let Response::AdvanceHeaders(client) = enclave_handle(request)? else {
+        panic!("Expected AdvanceHeaders response")
+    };
+    assert_eq!(client.checkpoint, chain.checkpoint(1));
 
-    assert!(verify(b"label", &mpk, &dk));
+    // the key of block 2, for a chain whose ciphertexts are encrypted for their block
+    let request = Request::RevealBlock(
+        chain.proof(1, 2),
+        1337,
+        client.clone(),
+        SealedMasterPrivateKey(emsk.0.clone()),
+    );
+    let Response::Reveal(dk) = enclave_handle(request)? else {
+        panic!("Expected Reveal response")
+    };
+    assert!(verify(&block_label(1337, 2), &mpk, &dk));
+
+    let request = Request::Reveal(chain.proof(1, 2), 0, client, emsk);
+    let Response::Reveal(dk) = enclave_handle(request)? else {
+        panic!("Expected Reveal response")
+    };
+    assert!(verify(&second_label, &mpk, &dk));
     Ok(())
 }
 
-fn generate() -> io::Result<()> {
-    let request = Request::Generate();
-    let Response::Generate(mpk, emsk) = enclave_handle(request)? else { panic!("Expected Generate response") };
+/// Generates a master key bound to an anchor, and writes the master public key, the anchor, the
+/// sealed master private key and the light client of the enclave, which only accepts headers
+/// sealed by one of `authorities`.
+fn generate(number: &str, hash: &str, delay: &str, authorities: &[String]) -> io::Result<()> {
+    let invalid = |what| io::Error::new(io::ErrorKind::InvalidInput, format!("invalid {}", what));
+    let anchor = Anchor {
+        number: number.parse().map_err(|_| invalid("block number"))?,
+        hash: parse_hex(hash).ok_or_else(|| invalid("block hash"))?,
+        delay: delay.parse().map_err(|_| invalid("delay"))?,
+    };
+    let authorities = authorities
+        .iter()
+        .map(|authority| parse_hex(authority).ok_or_else(|| invalid("authority")))
+        .collect::<io::Result<_>>()?;
 
-    serde_json::to_writer(std::io::stdout(), &(mpk, emsk))?;
-    Ok(())
+    match enclave_handle(Request::Generate(anchor, authorities))? {
+        Response::Generate(mpk, emsk, client) => {
+            serde_json::to_writer(std::io::stdout(), &(mpk, anchor, emsk, client))?;
+            Ok(())
+        }
+        Response::Refused(reason) => Err(io::Error::new(io::ErrorKind::Other, reason)),
+        _ => panic!("Expected Generate response"),
+    }
+}
+
+/// Parses `N` bytes written in hexadecimal, with or without the `0x` prefix.
+fn parse_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() != 2 * N {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (byte, digits) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some(bytes)
 }
 
 fn main() -> io::Result<()> {
@@ -106,7 +220,30 @@ fn main() -> io::Result<()> {
     args.next(); // skip program name
     match args.next().as_deref() {
         Some("selfcheck") => selfcheck(),
-        Some("generate") => generate(),
+        Some("generate") => match (args.next(), args.next(), args.next()) {
+            (Some(number), Some(hash), Some(delay)) => {
+                generate(&number, &hash, &delay, &args.collect::<Vec<_>>())
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Usage: app generate <block number> <block hash> <delay> <authority>...",
+            )),
+        },
+        Some("bench") => match args.next().map(|transactions| transactions.parse()) {
+            None => bench(256),
+            Some(Ok(transactions)) => bench(transactions),
+            Some(Err(_)) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Usage: app bench [number of transactions]",
+            )),
+        },
+        Some("serve") => match args.next() {
+            Some(address) => serve(&address),
+            None => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Usage: app serve <socket path or address>",
+            )),
+        },
         Some(cmd) => Err(io::Error::new(
             io::ErrorKind::Other,
             format!("Unknown command: {}", cmd),
@@ -115,6 +252,38 @@ fn main() -> io::Result<()> {
     }
 }
 
+fn bench(transactions: usize) -> io::Result<()> {
+    let enclave = match init_enclave() {
+        Ok(r) => r,
+        Err(x) => {
+            eprintln!("[-] Init Enclave Failed {}!", x.as_str());
+            std::process::exit(x as i32);
+        }
+    };
+
+    let result = bench::bench(enclave.geteid(), transactions);
+
+    enclave.destroy();
+
+    result
+}
+
+fn serve(address: &str) -> io::Result<()> {
+    let enclave = match init_enclave() {
+        Ok(r) => r,
+        Err(x) => {
+            eprintln!("[-] Init Enclave Failed {}!", x.as_str());
+            std::process::exit(x as i32);
+        }
+    };
+
+    let result = daemon::serve(address, enclave.geteid());
+
+    enclave.destroy();
+
+    result
+}
+
 fn run_enclave() -> io::Result<()> {
     let enclave = match init_enclave() {
         Ok(r) => r,
@@ -22,6 +22,7 @@ extern "C" {
 #endif
 
 sgx_status_t handle(void);
+sgx_status_t handle_request(const uint8_t* request, size_t request_len, uint8_t* response, size_t response_capacity, size_t* response_len);
 void t_global_init_ecall(uint64_t id, const uint8_t* path, size_t len);
 void t_global_exit_ecall(void);
 
@@ -25,43 +25,468 @@ extern crate sgx_types;
 #[cfg(not(target_env = "sgx"))]
 #[macro_use]
 extern crate sgx_tstd as std;
+extern crate dawn_crypto;
+extern crate dawn_dkg;
 extern crate dawn_enclave_protocol;
-use dawn_enclave_protocol::{Request, Response};
+extern crate dawn_light_client;
+#[macro_use]
+extern crate lazy_static;
+use dawn_crypto::channel::ChannelPrivateKey;
+use dawn_crypto::threshold::PrivateKeyShare;
+use dawn_crypto::MasterPrivateKey;
+use dawn_dkg::{Dkg, Step};
+use dawn_enclave_protocol::{
+    Anchor, DkgMessage, Request, Response, SealedChannelKey, SealedDkgState, SealedKeyShare,
+    SealedLightClient, SealedMasterPrivateKey,
+};
+use dawn_light_client::{block_label, LightClient, Versions};
 use sgx_tseal::SgxSealedData;
+use sgx_types::marker::ContiguousMemory;
 use sgx_types::*;
+use std::convert::TryInto;
 use std::io::{self, Write};
+use std::panic;
+use std::slice;
+use std::string::{String, ToString};
+use std::sync::SgxMutex;
 use std::vec::Vec;
 
+lazy_static! {
+    /// The latest versions of the light clients handed over since the enclave was loaded, which
+    /// only protects against rollbacks for as long as the host daemon keeps it loaded.
+    static ref VERSIONS: SgxMutex<Versions> = SgxMutex::new(Versions::default());
+    /// The latest epoch of the committees whose key shares were handed over or formed since the
+    /// enclave was loaded, to refuse the shares of retired committees while it stays loaded.
+    static ref EPOCH: SgxMutex<u64> = SgxMutex::new(0);
+}
+
 #[no_mangle]
 pub extern "C" fn handle() -> sgx_status_t {
     let request: Request = serde_cbor::from_reader(io::stdin()).unwrap();
-    let response = match request {
-        Request::Generate() => {
+    let response = respond(request);
+    let mut stdout = io::stdout().lock();
+    serde_cbor::to_writer(&mut stdout, &response).unwrap();
+    stdout.flush().unwrap();
+
+    sgx_status_t::SGX_SUCCESS
+}
+
+/// Handles one CBOR request of the host daemon, which keeps the enclave loaded across requests.
+///
+/// If the CBOR response does not fit in `response_capacity` bytes, its length is written to
+/// `response_len` and `SGX_ERROR_OUT_OF_MEMORY` is returned, so that the host can retry with a
+/// large enough buffer. The request is then handled again, which is harmless since the first
+/// response never left the enclave.
+#[no_mangle]
+pub extern "C" fn handle_request(
+    request: *const u8,
+    request_len: usize,
+    response: *mut u8,
+    response_capacity: usize,
+    response_len: *mut usize,
+) -> sgx_status_t {
+    if request.is_null() || response_len.is_null() {
+        return sgx_status_t::SGX_ERROR_INVALID_PARAMETER;
+    }
+    let request = unsafe { slice::from_raw_parts(request, request_len) };
+    let Ok(request) = serde_cbor::from_slice::<Request>(request) else {
+        return sgx_status_t::SGX_ERROR_INVALID_PARAMETER;
+    };
+    // a panicking request must not take the enclave down with it, the daemon keeps serving
+    let Ok(bytes) = panic::catch_unwind(move || serde_cbor::to_vec(&respond(request)).unwrap())
+    else {
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    };
+    unsafe { *response_len = bytes.len() };
+    if response.is_null() || bytes.len() > response_capacity {
+        return sgx_status_t::SGX_ERROR_OUT_OF_MEMORY;
+    }
+    let response = unsafe { slice::from_raw_parts_mut(response, bytes.len()) };
+    response.copy_from_slice(&bytes);
+
+    sgx_status_t::SGX_SUCCESS
+}
+
+fn respond(request: Request) -> Response {
+    match request {
+        // the key is bound to its anchor and authorities from the start, so that the host can not
+        // bind it to a chain of its own afterwards
+        Request::Generate(anchor, authorities) => {
+            let client = match LightClient::new(anchor, authorities) {
+                Ok(client) => client,
+                Err(err) => return Response::Refused(err.to_string()),
+            };
             let (mpk, msk) = dawn_crypto::generate();
-            let aad = b"";
             let data = msk.to_bytes();
-            let sealed_data = SgxSealedData::<[u8; 32]>::seal_data(aad, &data).unwrap();
-            let raw = to_raw_sealed_data(&sealed_data);
-            Response::Generate(mpk, dawn_enclave_protocol::SealedMasterPrivateKey(raw))
+            let sealed_data =
+                SgxSealedData::<[u8; 32]>::seal_data(&binding_of(&client), &data).unwrap();
+            Response::Generate(
+                mpk,
+                SealedMasterPrivateKey(to_raw_sealed_data(&sealed_data)),
+                seal_light_client(&client),
+            )
+        }
+        Request::AdvanceHeaders(mut sealed_client, headers) => {
+            let advanced = unseal_light_client(&mut sealed_client).and_then(|mut client| {
+                client.advance(&headers).map_err(|err| err.to_string())?;
+                record_version(&client)?;
+                Ok(client)
+            });
+            match advanced {
+                Ok(client) => Response::AdvanceHeaders(seal_light_client(&client)),
+                Err(err) => Response::Refused(err),
+            }
+        }
+        Request::Reveal(proof, index, mut sealed_client, mut smpk) => {
+            let revealed = unseal_master_key(&mut smpk).and_then(|(msk, binding)| {
+                let client = light_client_of(&binding, &mut sealed_client)?;
+                let label = client
+                    .verify(&proof)
+                    .and_then(|shadow| shadow.label(index))
+                    .map_err(|err| err.to_string())?;
+                Ok(dawn_crypto::reveal(&label, &msk))
+            });
+            match revealed {
+                Ok(dk) => Response::Reveal(dk),
+                Err(err) => Response::Refused(err),
+            }
+        }
+        Request::RevealBatch(proof, indices, mut sealed_client, mut smpk) => {
+            // the key is unsealed and the proof verified once for the whole batch
+            let (msk, binding) = match unseal_master_key(&mut smpk) {
+                Ok(key) => key,
+                Err(err) => return Response::Refused(err),
+            };
+            let client = match light_client_of(&binding, &mut sealed_client) {
+                Ok(client) => client,
+                Err(err) => return Response::Refused(err),
+            };
+            let shadow = match client.verify(&proof) {
+                Ok(shadow) => shadow,
+                Err(err) => return Response::Refused(err.to_string()),
+            };
+            let keys = indices
+                .into_iter()
+                .map(|index| match shadow.label(index) {
+                    Ok(label) => Ok(dawn_crypto::reveal(&label, &msk)),
+                    Err(err) => Err(err.to_string()),
+                })
+                .collect();
+            Response::RevealBatch(keys)
+        }
+        Request::ChannelKey() => {
+            let channel = ChannelPrivateKey::generate();
+            let data = channel.to_bytes();
+            let sealed_data = SgxSealedData::<[u8; 32]>::seal_data(CHANNEL_KEY_AAD, &data).unwrap();
+            Response::ChannelKey(
+                channel.public_key(),
+                SealedChannelKey(to_raw_sealed_data(&sealed_data)),
+            )
+        }
+        Request::DkgStart {
+            me,
+            threshold,
+            members,
+            mut channel,
+            keys,
+            anchor,
+            authorities,
+        } => {
+            let client = match LightClient::new(anchor, authorities) {
+                Ok(client) => client,
+                Err(err) => return Response::Refused(err.to_string()),
+            };
+            let channel = match unseal_channel_key(&mut channel) {
+                Ok(channel) => channel,
+                Err(err) => return Response::Refused(err),
+            };
+            match Dkg::new(me, threshold, members, &channel, keys) {
+                Ok((dkg, messages)) => Response::DkgRound(seal_dkg_state(&dkg, &client), messages),
+                Err(err) => Response::DkgFailed(err.to_string()),
+            }
+        }
+        Request::ReshareStart {
+            old,
+            share,
+            me,
+            threshold,
+            members,
+            mut channel,
+            keys,
+            anchor,
+            authorities,
+        } => {
+            let client = match LightClient::new(anchor, authorities) {
+                Ok(client) => client,
+                Err(err) => return Response::Refused(err.to_string()),
+            };
+            let channel = match unseal_channel_key(&mut channel) {
+                Ok(channel) => channel,
+                Err(err) => return Response::Refused(err),
+            };
+            let share = match share
+                .map(|mut share| unseal_key_share(&mut share))
+                .transpose()
+            {
+                Ok(Some((_, epoch, _))) if epoch != old.epoch => {
+                    return Response::Refused(
+                        "the key share is not of the old committee".to_string(),
+                    )
+                }
+                Ok(share) => share.map(|(sk, _, _)| sk),
+                Err(err) => return Response::Refused(err),
+            };
+            match Dkg::reshare(old, share.as_ref(), me, threshold, members, &channel, keys) {
+                Ok((dkg, messages)) => Response::DkgRound(seal_dkg_state(&dkg, &client), messages),
+                Err(err) => Response::DkgFailed(err.to_string()),
+            }
+        }
+        Request::DkgRound(mut state, messages) => dkg_round(&mut state, messages),
+        Request::RevealShare(proof, index, mut sealed_client, mut sealed_share) => {
+            let (sk, _, binding) = match unseal_key_share(&mut sealed_share) {
+                Ok(share) => share,
+                Err(err) => return Response::Refused(err),
+            };
+            let revealed = light_client_of(&binding, &mut sealed_client).and_then(|client| {
+                client
+                    .verify(&proof)
+                    .and_then(|shadow| shadow.label(index))
+                    .map_err(|err| err.to_string())
+            });
+            match revealed {
+                Ok(label) => {
+                    Response::RevealShare(dawn_crypto::threshold::reveal_share(&label, &sk))
+                }
+                Err(err) => Response::Refused(err),
+            }
+        }
+        // the chain id comes from the host, but the key is bound to a single chain by its anchor,
+        // and no ciphertext of that chain is encrypted for the label of another chain id
+        Request::RevealBlock(proof, chain_id, mut sealed_client, mut smpk) => {
+            let revealed = unseal_master_key(&mut smpk).and_then(|(msk, binding)| {
+                let client = light_client_of(&binding, &mut sealed_client)?;
+                let label = client
+                    .verify(&proof)
+                    .map(|shadow| block_label(chain_id, shadow.number()))
+                    .map_err(|err| err.to_string())?;
+                Ok(dawn_crypto::reveal(&label, &msk))
+            });
+            match revealed {
+                Ok(dk) => Response::Reveal(dk),
+                Err(err) => Response::Refused(err),
+            }
         }
-        Request::Reveal(label, mut smpk) => {
-            let sealed_data = from_raw_sealed_data(&mut smpk.0).unwrap();
-            let msk = dawn_crypto::MasterPrivateKey::from_bytes(
-                *sealed_data.unseal_data().unwrap().decrypt,
-            );
-            let dk = dawn_crypto::reveal(&label, &msk);
-            Response::Reveal(dk)
+        Request::RevealBlockShare(proof, chain_id, mut sealed_client, mut sealed_share) => {
+            let (sk, _, binding) = match unseal_key_share(&mut sealed_share) {
+                Ok(share) => share,
+                Err(err) => return Response::Refused(err),
+            };
+            let revealed = light_client_of(&binding, &mut sealed_client).and_then(|client| {
+                client
+                    .verify(&proof)
+                    .map(|shadow| block_label(chain_id, shadow.number()))
+                    .map_err(|err| err.to_string())
+            });
+            match revealed {
+                Ok(label) => {
+                    Response::RevealShare(dawn_crypto::threshold::reveal_share(&label, &sk))
+                }
+                Err(err) => Response::Refused(err),
+            }
         }
+    }
+}
+
+// delivers the messages of the current round to the sealed key generation and ends the round
+fn dkg_round(state: &mut SealedDkgState, messages: Vec<DkgMessage>) -> Response {
+    let (mut dkg, client) = match unseal_dkg_state(state) {
+        Ok(state) => state,
+        Err(err) => return Response::Refused(err),
     };
-    let mut stdout = io::stdout().lock();
-    serde_cbor::to_writer(&mut stdout, &response).unwrap();
-    stdout.flush().unwrap();
+    for message in messages {
+        // invalid messages are dropped, misbehaving members are dealt with by the protocol
+        let _ = dkg.handle(message);
+    }
+    match dkg.end_round() {
+        Ok(Step::Continue(messages)) => Response::DkgRound(seal_dkg_state(&dkg, &client), messages),
+        Ok(Step::Done(output)) => {
+            if let Err(err) = record_epoch(output.committee.epoch) {
+                return Response::Refused(err);
+            }
+            let sealed_share = seal_key_share(&output.share, output.committee.epoch, &client);
+            Response::DkgDone(
+                output.mpk,
+                output.committee,
+                sealed_share,
+                seal_light_client(&client),
+            )
+        }
+        Ok(Step::Retired) => match record_epoch(dkg.epoch()) {
+            Ok(()) => Response::DkgRetired,
+            Err(err) => Response::Refused(err),
+        },
+        Err(err) => Response::DkgFailed(err.to_string()),
+    }
+}
 
-    sgx_status_t::SGX_SUCCESS
+/// The anchor and the authorities a key is bound to when it is created.
+type Binding = (Anchor, Vec<[u8; 20]>);
+
+/// Encodes the anchor and the authorities of `client`, which keys are sealed with.
+fn binding_of(client: &LightClient) -> Vec<u8> {
+    client
+        .anchor()
+        .to_bytes_with_authorities(client.authorities())
+}
+
+fn decode_binding(bytes: &[u8]) -> Result<Binding, String> {
+    Anchor::from_bytes_with_authorities(bytes).ok_or_else(|| "malformed anchor".to_string())
+}
+
+/// Unseals a master private key, along with the anchor and authorities it is bound to.
+fn unseal_master_key(
+    smpk: &mut SealedMasterPrivateKey,
+) -> Result<(MasterPrivateKey, Binding), String> {
+    let sealed_data = from_raw_sealed_data::<[u8; 32]>(&mut smpk.0)
+        .ok_or_else(|| "malformed sealed master private key".to_string())?;
+    let unsealed_data = sealed_data
+        .unseal_data()
+        .map_err(|err| format!("failed to unseal the master private key: {}", err.as_str()))?;
+    let msk = MasterPrivateKey::try_from_bytes(*unsealed_data.decrypt)
+        .map_err(|err| format!("malformed master private key: {}", err))?;
+    Ok((msk, decode_binding(unsealed_data.get_additional_txt())?))
+}
+
+/// Seals a key share, binding its member index, the epoch of its committee, and the anchor and
+/// authorities of `client` as additional data.
+fn seal_key_share(share: &PrivateKeyShare, epoch: u64, client: &LightClient) -> SealedKeyShare {
+    let mut aad = share.index.to_le_bytes().to_vec();
+    aad.extend_from_slice(&epoch.to_le_bytes());
+    aad.extend_from_slice(&binding_of(client));
+    let data = share.to_bytes();
+    let sealed_data = SgxSealedData::<[u8; 32]>::seal_data(&aad, &data).unwrap();
+    SealedKeyShare(to_raw_sealed_data(&sealed_data))
+}
+
+/// Unseals a key share, along with the epoch of its committee and the anchor and authorities it
+/// is bound to. Shares of committees older than the latest the enclave saw are refused.
+fn unseal_key_share(
+    sealed_share: &mut SealedKeyShare,
+) -> Result<(PrivateKeyShare, u64, Binding), String> {
+    let sealed_data = from_raw_sealed_data::<[u8; 32]>(&mut sealed_share.0)
+        .ok_or_else(|| "malformed sealed key share".to_string())?;
+    let unsealed_data = sealed_data
+        .unseal_data()
+        .map_err(|err| format!("failed to unseal the key share: {}", err.as_str()))?;
+    let aad = unsealed_data.get_additional_txt();
+    if aad.len() < 12 {
+        return Err("malformed key share index or epoch".to_string());
+    }
+    let (index, aad) = aad.split_at(4);
+    let (epoch, binding) = aad.split_at(8);
+    let index = u32::from_le_bytes(index.try_into().unwrap());
+    let epoch = u64::from_le_bytes(epoch.try_into().unwrap());
+    record_epoch(epoch)?;
+    let sk = PrivateKeyShare::try_from_bytes(index, *unsealed_data.decrypt)
+        .map_err(|err| format!("malformed key share: {}", err))?;
+    Ok((sk, epoch, decode_binding(binding)?))
+}
+
+fn seal_light_client(client: &LightClient) -> SealedLightClient {
+    let data = serde_cbor::to_vec(client).unwrap();
+    let sealed_data = SgxSealedData::<[u8]>::seal_data(b"", &data).unwrap();
+    SealedLightClient {
+        checkpoint: client.checkpoint(),
+        sealed: to_raw_sealed_data(&sealed_data),
+    }
+}
+
+/// Unseals a light client, refusing it if the host rolled it back.
+fn unseal_light_client(sealed_client: &mut SealedLightClient) -> Result<LightClient, String> {
+    let sealed_data = from_raw_sealed_data::<[u8]>(&mut sealed_client.sealed)
+        .ok_or_else(|| "malformed sealed light client".to_string())?;
+    let unsealed_data = sealed_data
+        .unseal_data()
+        .map_err(|err| format!("failed to unseal the light client: {}", err.as_str()))?;
+    let client: LightClient = serde_cbor::from_slice(unsealed_data.get_decrypt_txt())
+        .map_err(|_| "malformed light client".to_string())?;
+    record_version(&client)?;
+    Ok(client)
+}
+
+/// Unseals the light client of a key, which must start at the anchor the key is bound to and
+/// accept the seals of the same authorities.
+fn light_client_of(
+    binding: &Binding,
+    sealed_client: &mut SealedLightClient,
+) -> Result<LightClient, String> {
+    let client = unseal_light_client(sealed_client)?;
+    if client.anchor() != &binding.0 || client.authorities() != &binding.1[..] {
+        return Err("the light client is not the one of the key".to_string());
+    }
+    Ok(client)
+}
+
+fn record_version(client: &LightClient) -> Result<(), String> {
+    VERSIONS
+        .lock()
+        .unwrap()
+        .check(client)
+        .map_err(|err| err.to_string())
+}
+
+/// Records that the enclave saw a committee of `epoch`, failing if it saw a later one.
+fn record_epoch(epoch: u64) -> Result<(), String> {
+    let mut latest = EPOCH.lock().unwrap();
+    if epoch < *latest {
+        return Err(format!(
+            "the committee of epoch {} is older than the committee of epoch {}",
+            epoch, *latest
+        ));
+    }
+    *latest = epoch;
+    Ok(())
+}
+
+/// The additional data of sealed channel keys, which tells them apart from the other sealed keys.
+const CHANNEL_KEY_AAD: &[u8] = b"channel key";
+
+fn unseal_channel_key(sealed_channel: &mut SealedChannelKey) -> Result<ChannelPrivateKey, String> {
+    let sealed_data = from_raw_sealed_data::<[u8; 32]>(&mut sealed_channel.0)
+        .ok_or_else(|| "malformed sealed channel key".to_string())?;
+    let unsealed_data = sealed_data
+        .unseal_data()
+        .map_err(|err| format!("failed to unseal the channel key: {}", err.as_str()))?;
+    if unsealed_data.get_additional_txt() != CHANNEL_KEY_AAD {
+        return Err("the sealed key is not a channel key".to_string());
+    }
+    ChannelPrivateKey::try_from_bytes(*unsealed_data.decrypt)
+        .map_err(|err| format!("malformed channel key: {}", err))
+}
+
+/// Seals the state of a key generation along with the light client its key share will be bound
+/// to.
+fn seal_dkg_state(dkg: &Dkg, client: &LightClient) -> SealedDkgState {
+    let data = serde_cbor::to_vec(&(dkg, client)).unwrap();
+    let sealed_data = SgxSealedData::<[u8]>::seal_data(b"", &data).unwrap();
+    SealedDkgState(to_raw_sealed_data(&sealed_data))
+}
+
+fn unseal_dkg_state(state: &mut SealedDkgState) -> Result<(Dkg, LightClient), String> {
+    let sealed_data = from_raw_sealed_data::<[u8]>(&mut state.0)
+        .ok_or_else(|| "malformed sealed key generation state".to_string())?;
+    let unsealed_data = sealed_data.unseal_data().map_err(|err| {
+        format!(
+            "failed to unseal the key generation state: {}",
+            err.as_str()
+        )
+    })?;
+    serde_cbor::from_slice(unsealed_data.get_decrypt_txt())
+        .map_err(|_| "malformed key generation state".to_string())
 }
 
-fn to_raw_sealed_data(sealed_data: &SgxSealedData<[u8; 32]>) -> Vec<u8> {
-    let len = SgxSealedData::<[u8; 32]>::calc_raw_sealed_data_size(
+fn to_raw_sealed_data<T: ?Sized + ContiguousMemory>(sealed_data: &SgxSealedData<T>) -> Vec<u8> {
+    let len = SgxSealedData::<T>::calc_raw_sealed_data_size(
         sealed_data.get_add_mac_txt_len(),
         sealed_data.get_encrypt_txt_len(),
     );
@@ -74,9 +499,9 @@ fn to_raw_sealed_data(sealed_data: &SgxSealedData<[u8; 32]>) -> Vec<u8> {
     buf
 }
 
-fn from_raw_sealed_data(raw: &mut [u8]) -> Option<SgxSealedData<[u8; 32]>> {
+fn from_raw_sealed_data<T: ?Sized + ContiguousMemory>(raw: &mut [u8]) -> Option<SgxSealedData<T>> {
     unsafe {
-        SgxSealedData::<[u8; 32]>::from_raw_sealed_data_t(
+        SgxSealedData::<T>::from_raw_sealed_data_t(
             raw.as_mut_ptr() as *mut sgx_sealed_data_t,
             raw.len() as u32,
         )